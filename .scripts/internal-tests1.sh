@@ -13,6 +13,7 @@ $rt test-with-features wtx 32-tuple-impls
 $rt test-with-features wtx arbitrary
 $rt test-with-features wtx argon2
 $rt test-with-features wtx asn1
+$rt test-with-features wtx cbor
 $rt test-with-features wtx client-api-framework
 $rt test-with-features wtx crossbeam-channel
 $rt test-with-features wtx crypto
@@ -22,6 +23,7 @@ $rt test-with-features wtx crypto-ring
 $rt test-with-features wtx database
 $rt test-with-features wtx database-tests
 $rt test-with-features wtx default
+$rt test-with-features wtx dns
 $rt test-with-features wtx embassy-net,_hack
 $rt test-with-features wtx embassy-time,_hack
 $rt test-with-features wtx executor
@@ -8,15 +8,22 @@ $rt test-with-features wtx grpc-server,crypto-ring
 $rt test-with-features wtx hashbrown
 $rt test-with-features wtx http
 $rt test-with-features wtx http2-client-pool,crypto-ring
+$rt test-with-features wtx http2-h2c
+$rt test-with-features wtx http-client-framework
 $rt test-with-features wtx http-cookie
 $rt test-with-features wtx http-cookie-secure
+$rt test-with-features wtx http-jwt,crypto-ring
+$rt test-with-features wtx http-oauth2,crypto-ring
 $rt test-with-features wtx http-session,crypto-ring
+$rt test-with-features wtx http-web-authn,crypto-ring
 $rt test-with-features wtx http2,crypto-ring
 $rt test-with-features wtx http2-server-framework,crypto-ring
+$rt test-with-features wtx http2-server-framework,http2-h2c,tokio
 $rt test-with-features wtx httparse
 $rt test-with-features wtx libc
 $rt test-with-features wtx macros
 $rt test-with-features wtx memchr
+$rt test-with-features wtx metrics
 $rt test-with-features wtx nightly
 $rt test-with-features wtx optimizations
 $rt test-with-features wtx parking_lot
@@ -33,7 +33,7 @@ async fn main() -> wtx::Result<()> {
 
   let bridge_fut = async {
     loop {
-      let data = stream_bridge.listen().await;
+      let data = stream_bridge.listen().await?;
       if stream_writer_bridge.lock().await.manage_bridge_data(data).await? {
         break;
       }
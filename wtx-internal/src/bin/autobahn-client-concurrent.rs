@@ -29,7 +29,7 @@ async fn main() {
           Ok(elem) => elem,
         };
       if let Some(el) = PollOnce::new(&mut bridge_frame).await {
-        if stream_writer.manage_bridge_data(el).await.unwrap() {
+        if stream_writer.manage_bridge_data(el.unwrap()).await.unwrap() {
           break;
         }
         bridge_frame.set(stream_bridge.listen());
@@ -29,9 +29,11 @@ async fn echo(
   let (mut common, mut reader, mut writer) = ws.split_mut();
   loop {
     let origin = WebSocketPayloadOrigin::Adaptive;
-    let mut frame = reader.read_frame(&mut buffer, &mut common, origin).await?;
+    let mut frame = reader.read_msg_fragment(&mut buffer, &mut common, origin).await?;
     match frame.op_code() {
-      OpCode::Binary | OpCode::Text => writer.write_frame(&mut common, &mut frame).await?,
+      OpCode::Binary | OpCode::Continuation | OpCode::Text => {
+        writer.write_frame(&mut common, &mut frame).await?;
+      }
       OpCode::Close => break,
       _ => {}
     }
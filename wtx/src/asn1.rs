@@ -63,7 +63,7 @@ pub(crate) const GENERALIZED_TIME_TAG: u8 = 24;
 pub(crate) const INTEGER_TAG: u8 = 2;
 pub(crate) const OCTET_STRING_TAG: u8 = 4;
 pub(crate) const OID_TAG: u8 = 6;
-#[cfg(feature = "x509")]
+#[cfg(any(feature = "http-jwt", feature = "x509"))]
 pub(crate) const SEQUENCE_TAG: u8 = 48;
 pub(crate) const SET_TAG: u8 = 49;
 pub(crate) const UTC_TIME_TAG: u8 = 23;
@@ -157,6 +157,32 @@ pub(crate) fn asn1_writer(
   Ok(())
 }
 
+// Encodes big-endian unsigned integers as a sequence of DER integers, which is the format of
+// PKCS#1 public keys and ECDSA signatures.
+#[cfg(any(feature = "http-jwt", feature = "http-web-authn"))]
+#[inline]
+pub(crate) fn asn1_unsigned_integers(
+  integers: &[&[u8]],
+) -> crate::Result<crate::collections::Vector<u8>> {
+  use crate::codec::Encode as _;
+  let mut rslt = crate::collections::Vector::new();
+  let mut ew = EncodeWrapper::new(&mut rslt, Asn1EncodeWrapperAux::default());
+  asn1_writer(&mut ew, Len::MAX_THREE_BYTES, SEQUENCE_TAG, |local_ew| {
+    for unsigned in integers {
+      let idx = unsigned.iter().position(|el| *el != 0).unwrap_or(unsigned.len());
+      let trimmed = unsigned.get(idx..).unwrap_or_default();
+      let mut bytes = crate::collections::Vector::with_capacity(trimmed.len().wrapping_add(1))?;
+      if trimmed.first().is_none_or(|el| el & 0b1000_0000 != 0) {
+        bytes.push(0)?;
+      }
+      bytes.extend_from_copyable_slice(trimmed)?;
+      Integer::new(bytes.as_slice())?.encode(local_ew)?;
+    }
+    Ok(())
+  })?;
+  Ok(rslt)
+}
+
 #[inline]
 pub(crate) fn decode_asn1_tlv(bytes: &[u8]) -> crate::Result<(u8, Len, &[u8], &[u8])> {
   let [tag, maybe_len, maybe_after_len @ ..] = bytes else {
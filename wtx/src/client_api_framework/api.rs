@@ -14,6 +14,15 @@ pub trait Api {
     async { Ok(()) }
   }
 
+  /// Token automatically sent in the `Authorization: Bearer` header of HTTP requests, if any.
+  ///
+  /// Tokens that expire can be renewed in [`Api::before_sending`], which is what `OAuth2Api` of
+  /// the `http-oauth2` feature does with OAuth 2.0 access tokens.
+  #[inline]
+  fn auth_bearer(&self) -> Option<&str> {
+    None
+  }
+
   /// Fallible hook that is automatically called before sending any related request.
   #[inline]
   fn before_sending(&mut self) -> impl Future<Output = Result<(), Self::Error>> {
@@ -38,6 +47,11 @@ where
     (**self).after_sending().await
   }
 
+  #[inline]
+  fn auth_bearer(&self) -> Option<&str> {
+    (**self).auth_bearer()
+  }
+
   #[inline]
   async fn before_sending(&mut self) -> Result<(), Self::Error> {
     (**self).before_sending().await
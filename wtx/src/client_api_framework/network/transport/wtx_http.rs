@@ -109,6 +109,9 @@ where
   {
     let _ = rb.content_type(*elem)?;
   }
+  if let Some(elem) = pkgs_aux.api.auth_bearer() {
+    let _ = rb.auth_bearer(&[elem])?;
+  }
   Ok(())
 }
 
@@ -252,7 +255,8 @@ mod http_client_pool {
         &mut self
           .lock(&pkgs_aux.tp.lease_mut().ext_req_params_mut().msg_buffer.uri.to_ref())
           .await?
-          .client,
+          .client
+          .clone(),
         pkgs_aux,
         req_id,
       )
@@ -286,7 +290,8 @@ mod http_client_pool {
         &mut self
           .lock(&pkgs_aux.tp.lease_mut().ext_req_params_mut().msg_buffer.uri.to_ref())
           .await?
-          .client,
+          .client
+          .clone(),
         pkgs_aux,
       )
       .await
@@ -306,7 +311,8 @@ mod http_client_pool {
         &mut self
           .lock(&pkgs_aux.tp.lease_mut().ext_req_params_mut().msg_buffer.uri.to_ref())
           .await?
-          .client,
+          .client
+          .clone(),
         pkg,
         pkgs_aux,
       )
@@ -353,7 +359,8 @@ mod http_client_pool {
         &mut self
           .lock(&pkgs_aux.tp.lease_mut().ext_req_params_mut().msg_buffer.uri.to_ref())
           .await?
-          .client,
+          .client
+          .clone(),
         pkgs_aux,
         req_id,
       )
@@ -387,7 +394,8 @@ mod http_client_pool {
         &mut self
           .lock(&pkgs_aux.tp.lease_mut().ext_req_params_mut().msg_buffer.uri.to_ref())
           .await?
-          .client,
+          .client
+          .clone(),
         pkgs_aux,
       )
       .await
@@ -407,7 +415,8 @@ mod http_client_pool {
         &mut self
           .lock(&pkgs_aux.tp.lease_mut().ext_req_params_mut().msg_buffer.uri.to_ref())
           .await?
-          .client,
+          .client
+          .clone(),
         pkg,
         pkgs_aux,
       )
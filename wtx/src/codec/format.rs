@@ -4,11 +4,15 @@
 #[macro_use]
 mod tests;
 
+#[cfg(feature = "cbor")]
+mod cbor;
 #[cfg(feature = "quick-protobuf")]
 mod quick_protobuf;
 #[cfg(feature = "serde_json")]
 mod serde_json;
 
+#[cfg(feature = "cbor")]
+pub use self::cbor::*;
 #[cfg(feature = "quick-protobuf")]
 pub use self::quick_protobuf::*;
 #[cfg(feature = "serde_json")]
@@ -0,0 +1,332 @@
+mod cbor_decoder;
+#[cfg(feature = "serde")]
+mod cbor_deserializer;
+mod cbor_encoder;
+mod cbor_error;
+#[cfg(feature = "serde")]
+mod cbor_serializer;
+mod cbor_ty;
+
+use crate::misc::{Lease, LeaseMut};
+pub use cbor_decoder::CborDecoder;
+#[cfg(feature = "serde")]
+pub use cbor_deserializer::CborDeserializer;
+pub use cbor_encoder::CborEncoder;
+pub use cbor_error::CborError;
+#[cfg(feature = "serde")]
+pub use cbor_serializer::{CborSerializeCompound, CborSerializer};
+pub use cbor_ty::CborTy;
+
+pub(crate) const BREAK: u8 = 0xff;
+pub(crate) const MAX_DEPTH: u8 = 64;
+
+/// Type that indicates the usage of the CBOR format (RFC 8949).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Cbor {
+  is_deterministic: bool,
+}
+
+impl Cbor {
+  /// If `is_deterministic` is `true`, the entries of serialized maps are sorted according to the
+  /// core deterministic encoding requirements, which is useful for signatures or hashes.
+  #[inline]
+  pub const fn new(is_deterministic: bool) -> Self {
+    Self { is_deterministic }
+  }
+
+  /// See [`Self::new`].
+  #[inline]
+  pub const fn is_deterministic(&self) -> bool {
+    self.is_deterministic
+  }
+}
+
+impl Lease<Cbor> for Cbor {
+  #[inline]
+  fn lease(&self) -> &Cbor {
+    self
+  }
+}
+
+impl LeaseMut<Cbor> for Cbor {
+  #[inline]
+  fn lease_mut(&mut self) -> &mut Cbor {
+    self
+  }
+}
+
+/// Deserializes `T` from `bytes`, which must contain exactly one CBOR item.
+#[cfg(feature = "serde")]
+#[inline]
+pub fn cbor_from_slice<'de, T>(bytes: &'de [u8]) -> crate::Result<T>
+where
+  T: serde::Deserialize<'de>,
+{
+  let mut deserializer = CborDeserializer::new(bytes);
+  let value = T::deserialize(&mut deserializer)?;
+  if !deserializer.remaining().is_empty() {
+    return Err(CborError::TrailingBytes.into());
+  }
+  Ok(value)
+}
+
+/// Serializes `value` as a CBOR item that is appended to `buffer`. See [`Cbor::new`] for more
+/// information about `is_deterministic`.
+#[cfg(feature = "serde")]
+#[inline]
+pub fn cbor_to_vector<T>(
+  buffer: &mut crate::collections::Vector<u8>,
+  is_deterministic: bool,
+  value: &T,
+) -> crate::Result<()>
+where
+  T: serde::Serialize + ?Sized,
+{
+  value.serialize(&mut CborSerializer::new(buffer, is_deterministic))
+}
+
+#[cfg(feature = "serde")]
+_impl_se_collections!(
+  (Cbor, serde::Serialize),
+  array: |this, bytes, drsr| { cbor_to_vector(bytes, drsr.is_deterministic, &this[..])?; }
+  arrayvector: |this, bytes, drsr| { cbor_to_vector(bytes, drsr.is_deterministic, this)?; }
+  slice_ref: |this, bytes, drsr| { cbor_to_vector(bytes, drsr.is_deterministic, this)?; }
+  vec: |this, bytes, drsr| { cbor_to_vector(bytes, drsr.is_deterministic, this)?; }
+);
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    codec::format::{CborDecoder, CborEncoder, CborError, CborTy},
+    collections::Vector,
+  };
+
+  const FLOATS: &[(f64, &[u8])] = &[
+    (0.0, &[0xf9, 0, 0]),
+    (-0.0, &[0xf9, 0x80, 0]),
+    (1.0, &[0xf9, 0x3c, 0]),
+    (1.1, &[0xfb, 0x3f, 0xf1, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a]),
+    (1.5, &[0xf9, 0x3e, 0]),
+    (65504.0, &[0xf9, 0x7b, 0xff]),
+    (100000.0, &[0xfa, 0x47, 0xc3, 0x50, 0]),
+    (3.4028234663852886e38, &[0xfa, 0x7f, 0x7f, 0xff, 0xff]),
+    (1.0e300, &[0xfb, 0x7e, 0x37, 0xe4, 0x3c, 0x88, 0, 0x75, 0x9c]),
+    (5.960464477539063e-8, &[0xf9, 0, 1]),
+    (0.00006103515625, &[0xf9, 0x04, 0]),
+    (-4.0, &[0xf9, 0xc4, 0]),
+    (-4.1, &[0xfb, 0xc0, 0x10, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66]),
+    (f64::INFINITY, &[0xf9, 0x7c, 0]),
+    (f64::NEG_INFINITY, &[0xf9, 0xfc, 0]),
+  ];
+  const INTEGERS: &[(i64, &[u8])] = &[
+    (0, &[0]),
+    (23, &[0x17]),
+    (24, &[0x18, 0x18]),
+    (100, &[0x18, 0x64]),
+    (1000, &[0x19, 0x03, 0xe8]),
+    (1000000, &[0x1a, 0, 0x0f, 0x42, 0x40]),
+    (1000000000000, &[0x1b, 0, 0, 0, 0xe8, 0xd4, 0xa5, 0x10, 0]),
+    (-1, &[0x20]),
+    (-10, &[0x29]),
+    (-100, &[0x38, 0x63]),
+    (-1000, &[0x39, 0x03, 0xe7]),
+    (i64::MIN, &[0x3b, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+  ];
+
+  #[test]
+  fn deterministic_maps() {
+    let mut buffer = Vector::new();
+    let mut encoder = CborEncoder::new(&mut buffer);
+    encoder
+      .map_sorted(3, |local_encoder| {
+        local_encoder.text("b")?;
+        local_encoder.u64(1)?;
+        local_encoder.text("a")?;
+        local_encoder.u64(2)?;
+        local_encoder.u64(10)?;
+        local_encoder.u64(3)
+      })
+      .unwrap();
+    assert_eq!(&*buffer, &[0xa3, 0x0a, 0x03, 0x61, 0x61, 0x02, 0x61, 0x62, 0x01]);
+  }
+
+  #[test]
+  fn floats() {
+    for (value, bytes) in FLOATS {
+      let mut buffer = Vector::new();
+      CborEncoder::new(&mut buffer).f64(*value).unwrap();
+      assert_eq!(&*buffer, *bytes);
+      let decoded = CborDecoder::new(bytes).f64().unwrap();
+      assert_eq!(decoded.to_bits(), value.to_bits());
+    }
+    let mut buffer = Vector::new();
+    CborEncoder::new(&mut buffer).f64(f64::NAN).unwrap();
+    assert_eq!(&*buffer, &[0xf9, 0x7e, 0]);
+    assert!(CborDecoder::new(&buffer).f64().unwrap().is_nan());
+  }
+
+  #[test]
+  fn indefinite_lengths() {
+    let bytes = [0x9f, 0x01, 0x82, 0x02, 0x03, 0x9f, 0x04, 0x05, 0xff, 0xff];
+    let mut decoder = CborDecoder::new(&bytes);
+    assert_eq!(decoder.array_len().unwrap(), None);
+    assert_eq!(decoder.u64().unwrap(), 1);
+    assert_eq!(decoder.array_len().unwrap(), Some(2));
+    decoder.skip().unwrap();
+    decoder.skip().unwrap();
+    decoder.skip().unwrap();
+    assert!(decoder.consume_break());
+    assert!(decoder.remaining().is_empty());
+    assert!(matches!(
+      CborDecoder::new(&[0x5f, 0x41, 0x01, 0xff]).bytes(),
+      Err(crate::Error::CborError(CborError::IndefiniteLengthString))
+    ));
+    assert!(matches!(
+      CborDecoder::new(&[0xff]).skip(),
+      Err(crate::Error::CborError(CborError::UnexpectedBreak))
+    ));
+  }
+
+  #[test]
+  fn integers() {
+    for (value, bytes) in INTEGERS {
+      let mut buffer = Vector::new();
+      CborEncoder::new(&mut buffer).i64(*value).unwrap();
+      assert_eq!(&*buffer, *bytes);
+      assert_eq!(CborDecoder::new(bytes).i64().unwrap(), *value);
+    }
+    let max = [0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+    assert_eq!(CborDecoder::new(&max).u64().unwrap(), u64::MAX);
+    assert!(matches!(
+      CborDecoder::new(&max).i64(),
+      Err(crate::Error::CborError(CborError::IntegerOverflow))
+    ));
+  }
+
+  #[test]
+  fn max_depth() {
+    let bytes = [0x81; 128];
+    assert!(matches!(
+      CborDecoder::new(&bytes).skip(),
+      Err(crate::Error::CborError(CborError::MaxDepthReached))
+    ));
+  }
+
+  #[test]
+  fn strings_and_simple_values() {
+    let mut buffer = Vector::new();
+    let mut encoder = CborEncoder::new(&mut buffer);
+    encoder.tag(1).unwrap();
+    encoder.u64(1363896240).unwrap();
+    encoder.text("\u{00fc}").unwrap();
+    encoder.bytes(&[1, 2, 3, 4]).unwrap();
+    encoder.bool(false).unwrap();
+    encoder.null().unwrap();
+    assert_eq!(
+      &*buffer,
+      &[0xc1, 0x1a, 0x51, 0x4b, 0x67, 0xb0, 0x62, 0xc3, 0xbc, 0x44, 1, 2, 3, 4, 0xf4, 0xf6]
+    );
+    let mut decoder = CborDecoder::new(&buffer);
+    assert_eq!(decoder.peek().unwrap(), CborTy::Tag);
+    assert_eq!(decoder.tag().unwrap(), 1);
+    assert_eq!(decoder.u64().unwrap(), 1363896240);
+    assert_eq!(decoder.text().unwrap(), "\u{00fc}");
+    assert_eq!(decoder.bytes().unwrap(), &[1, 2, 3, 4]);
+    assert!(!decoder.bool().unwrap());
+    assert_eq!(decoder.peek().unwrap(), CborTy::Null);
+    decoder.null().unwrap();
+    assert!(decoder.remaining().is_empty());
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde_round_trip() {
+    use crate::codec::format::{cbor_from_slice, cbor_to_vector};
+    use alloc::collections::BTreeMap;
+
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    enum Enum {
+      Newtype(u8),
+      Struct { value: i32 },
+      Tuple(bool, bool),
+      Unit,
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct Struct<'any> {
+      #[serde(borrow, with = "serde_bytes_borrowed")]
+      bytes: &'any [u8],
+      enums: [Enum; 4],
+      map: BTreeMap<i8, f32>,
+      none: Option<u8>,
+      text: &'any str,
+    }
+
+    mod serde_bytes_borrowed {
+      pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<&'de [u8], D::Error>
+      where
+        D: serde::Deserializer<'de>,
+      {
+        serde::Deserialize::deserialize(deserializer)
+      }
+
+      pub(crate) fn serialize<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+      where
+        S: serde::Serializer,
+      {
+        serializer.serialize_bytes(bytes)
+      }
+    }
+
+    let value = Struct {
+      bytes: &[1, 2],
+      enums: [Enum::Newtype(1), Enum::Struct { value: -1 }, Enum::Tuple(true, false), Enum::Unit],
+      map: BTreeMap::from([(-1, 1.5), (1, 2.5)]),
+      none: None,
+      text: "text",
+    };
+    let mut buffer = Vector::new();
+    cbor_to_vector(&mut buffer, true, &value).unwrap();
+    assert_eq!(cbor_from_slice::<Struct<'_>>(&buffer).unwrap(), value);
+    buffer.push(0).unwrap();
+    assert!(matches!(
+      cbor_from_slice::<Struct<'_>>(&buffer),
+      Err(crate::Error::CborError(CborError::TrailingBytes))
+    ));
+  }
+
+  #[cfg(feature = "serde_json")]
+  #[test]
+  fn serde_unknown_lengths() {
+    use crate::codec::format::{CborDeserializer, CborSerializer, cbor_from_slice, cbor_to_vector};
+    use serde::Serializer as _;
+
+    let mut buffer = Vector::new();
+    (&mut CborSerializer::new(&mut buffer, true))
+      .collect_map([("b", 1u8), ("a", 2u8)].into_iter().filter(|_| true))
+      .unwrap();
+    assert_eq!(&*buffer, &[0xa2, 0x61, 0x61, 0x02, 0x61, 0x62, 0x01]);
+    let bytes = [0xbf, 0x61, 0x61, 0x01, 0x61, 0x62, 0x9f, 0x02, 0x03, 0xff, 0xff];
+    let mut deserializer = CborDeserializer::new(&bytes);
+    let value: serde_json::Value = serde::Deserialize::deserialize(&mut deserializer).unwrap();
+    assert_eq!(value, serde_json::json!({ "a": 1, "b": [2, 3] }));
+    buffer.clear();
+    cbor_to_vector(&mut buffer, false, &value).unwrap();
+    assert_eq!(cbor_from_slice::<serde_json::Value>(&buffer).unwrap(), value);
+  }
+
+  #[cfg(all(feature = "client-api-framework", feature = "serde"))]
+  _create_dnsn_test!(
+    cbor,
+    (VerbatimEncoder, VerbatimDecoder),
+    Cbor as Cbor::new(false),
+    (
+      &[0xa1, 0x63, 0x66, 0x6f, 0x6f, 0x63, 0x66, 0x6f, 0x6f][..],
+      (&[0xa1, 0x63, 0x62, 0x61, 0x72, 0x63, 0x62, 0x61, 0x72][..]).into()
+    ),
+    (
+      VerbatimEncoder { data: _Foo { foo: "foo" } },
+      VerbatimDecoder { data: _Bar { bar: "bar".into() } }
+    ),
+  );
+}
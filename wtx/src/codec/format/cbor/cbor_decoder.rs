@@ -0,0 +1,305 @@
+use crate::{
+  codec::format::{
+    CborError, CborTy,
+    cbor::{BREAK, MAX_DEPTH},
+  },
+  misc::from_utf8_basic,
+};
+
+/// Zero-copy decoder of CBOR items (RFC 8949).
+///
+/// Indefinite-length arrays and maps are supported but indefinite-length strings are rejected
+/// because their chunks can't be referenced as a single slice.
+#[derive(Debug)]
+pub struct CborDecoder<'de> {
+  bytes: &'de [u8],
+}
+
+impl<'de> CborDecoder<'de> {
+  /// New instance
+  #[inline]
+  pub const fn new(bytes: &'de [u8]) -> Self {
+    Self { bytes }
+  }
+
+  /// Decodes the header of an array and returns its number of elements. `None` indicates an
+  /// indefinite-length array that ends when [`Self::consume_break`] returns `true`.
+  #[inline]
+  pub fn array_len(&mut self) -> crate::Result<Option<usize>> {
+    self.container_len(4)
+  }
+
+  /// Decodes a boolean.
+  #[inline]
+  pub fn bool(&mut self) -> crate::Result<bool> {
+    match self.simple()? {
+      20 => Ok(false),
+      21 => Ok(true),
+      _ => Err(CborError::UnexpectedTy.into()),
+    }
+  }
+
+  /// Decodes a byte string.
+  #[inline]
+  pub fn bytes(&mut self) -> crate::Result<&'de [u8]> {
+    let len = self.string_len(2)?;
+    self.take(len)
+  }
+
+  /// Advances and returns `true` if the next byte is the "break" stop code of indefinite-length
+  /// items.
+  #[inline]
+  pub fn consume_break(&mut self) -> bool {
+    if let [BREAK, rest @ ..] = self.bytes {
+      self.bytes = rest;
+      return true;
+    }
+    false
+  }
+
+  /// Decodes a half, single or double-precision float.
+  #[inline]
+  pub fn f64(&mut self) -> crate::Result<f64> {
+    let [first, rest @ ..] = self.bytes else {
+      return Err(CborError::UnexpectedEof.into());
+    };
+    let value = match *first {
+      0xf9 => {
+        self.bytes = rest;
+        f16_to_f64(u16::from_be_bytes(self.array()?))
+      }
+      0xfa => {
+        self.bytes = rest;
+        f64::from(f32::from_be_bytes(self.array()?))
+      }
+      0xfb => {
+        self.bytes = rest;
+        f64::from_be_bytes(self.array()?)
+      }
+      _ => return Err(CborError::UnexpectedTy.into()),
+    };
+    Ok(value)
+  }
+
+  /// Decodes an unsigned or a negative integer.
+  #[inline]
+  pub fn i64(&mut self) -> crate::Result<i64> {
+    let (major, arg) = self.header()?;
+    let value = i64::try_from(arg).map_err(|_err| CborError::IntegerOverflow)?;
+    match major {
+      0 => Ok(value),
+      1 => Ok(value.wrapping_neg().wrapping_sub(1)),
+      _ => Err(CborError::UnexpectedTy.into()),
+    }
+  }
+
+  /// Decodes the header of a map and returns its number of entries. `None` indicates an
+  /// indefinite-length map that ends when [`Self::consume_break`] returns `true`.
+  #[inline]
+  pub fn map_len(&mut self) -> crate::Result<Option<usize>> {
+    self.container_len(5)
+  }
+
+  /// Decodes a `null` simple value.
+  #[inline]
+  pub fn null(&mut self) -> crate::Result<()> {
+    if self.simple()? != 22 {
+      return Err(CborError::UnexpectedTy.into());
+    }
+    Ok(())
+  }
+
+  /// Type of the next item without advancing.
+  #[inline]
+  pub fn peek(&self) -> crate::Result<CborTy> {
+    let Some(first) = self.bytes.first() else {
+      return Err(CborError::UnexpectedEof.into());
+    };
+    Ok(match (first >> 5, first & 0b0001_1111) {
+      (0, _) => CborTy::UnsignedInt,
+      (1, _) => CborTy::NegativeInt,
+      (2, _) => CborTy::Bytes,
+      (3, _) => CborTy::Text,
+      (4, _) => CborTy::Array,
+      (5, _) => CborTy::Map,
+      (6, _) => CborTy::Tag,
+      (_, 20 | 21) => CborTy::Bool,
+      (_, 22) => CborTy::Null,
+      (_, 23) => CborTy::Undefined,
+      (_, 25..=27) => CborTy::Float,
+      (_, 31) => return Err(CborError::UnexpectedBreak.into()),
+      _ => CborTy::Simple,
+    })
+  }
+
+  /// Bytes that weren't decoded yet.
+  #[inline]
+  pub const fn remaining(&self) -> &'de [u8] {
+    self.bytes
+  }
+
+  /// Skips the next item, including all of its nested items.
+  #[inline]
+  pub fn skip(&mut self) -> crate::Result<()> {
+    self.skip_with_depth(0)
+  }
+
+  /// Decodes the number of a tag. The tagged item is the next item.
+  #[inline]
+  pub fn tag(&mut self) -> crate::Result<u64> {
+    let (major, arg) = self.header()?;
+    if major != 6 {
+      return Err(CborError::UnexpectedTy.into());
+    }
+    Ok(arg)
+  }
+
+  /// Decodes a text string.
+  #[inline]
+  pub fn text(&mut self) -> crate::Result<&'de str> {
+    let len = self.string_len(3)?;
+    Ok(from_utf8_basic(self.take(len)?).map_err(|_err| CborError::InvalidUtf8)?)
+  }
+
+  /// Decodes an unsigned integer.
+  #[inline]
+  pub fn u64(&mut self) -> crate::Result<u64> {
+    let (major, arg) = self.header()?;
+    if major != 0 {
+      return Err(CborError::UnexpectedTy.into());
+    }
+    Ok(arg)
+  }
+
+  fn array<const N: usize>(&mut self) -> crate::Result<[u8; N]> {
+    let Some((data, rest)) = self.bytes.split_first_chunk() else {
+      return Err(CborError::UnexpectedEof.into());
+    };
+    self.bytes = rest;
+    Ok(*data)
+  }
+
+  fn container_len(&mut self, expected_major: u8) -> crate::Result<Option<usize>> {
+    if let [first, rest @ ..] = self.bytes
+      && *first == ((expected_major << 5) | 31)
+    {
+      self.bytes = rest;
+      return Ok(None);
+    }
+    let (major, arg) = self.header()?;
+    if major != expected_major {
+      return Err(CborError::UnexpectedTy.into());
+    }
+    Ok(Some(usize::try_from(arg).map_err(|_err| CborError::IntegerOverflow)?))
+  }
+
+  // Returns the major type and the argument of the next item. Indefinite lengths and the "break"
+  // stop code are handled by the callers.
+  fn header(&mut self) -> crate::Result<(u8, u64)> {
+    let [first, rest @ ..] = self.bytes else {
+      return Err(CborError::UnexpectedEof.into());
+    };
+    let (major, info) = (first >> 5, first & 0b0001_1111);
+    self.bytes = rest;
+    let arg = match info {
+      0..=23 => info.into(),
+      24 => u8::from_be_bytes(self.array()?).into(),
+      25 => u16::from_be_bytes(self.array()?).into(),
+      26 => u32::from_be_bytes(self.array()?).into(),
+      27 => u64::from_be_bytes(self.array()?),
+      31 if major == 7 => return Err(CborError::UnexpectedBreak.into()),
+      31 if matches!(major, 2 | 3) => return Err(CborError::IndefiniteLengthString.into()),
+      _ => return Err(CborError::InvalidAdditionalInfo.into()),
+    };
+    Ok((major, arg))
+  }
+
+  fn simple(&mut self) -> crate::Result<u64> {
+    let (major, arg) = self.header()?;
+    if major != 7 {
+      return Err(CborError::UnexpectedTy.into());
+    }
+    Ok(arg)
+  }
+
+  fn skip_with_depth(&mut self, depth: u8) -> crate::Result<()> {
+    if depth >= MAX_DEPTH {
+      return Err(CborError::MaxDepthReached.into());
+    }
+    let local_depth = depth.wrapping_add(1);
+    match self.peek()? {
+      CborTy::Array => {
+        let len = self.array_len()?;
+        self.skip_elements(len, local_depth, 1)?;
+      }
+      CborTy::Map => {
+        let len = self.map_len()?;
+        self.skip_elements(len, local_depth, 2)?;
+      }
+      CborTy::Tag => {
+        let _ = self.tag()?;
+        self.skip_with_depth(local_depth)?;
+      }
+      CborTy::Bytes | CborTy::Text => {
+        let (_, len) = self.header()?;
+        let _ = self.take(usize::try_from(len).map_err(|_err| CborError::IntegerOverflow)?)?;
+      }
+      CborTy::Bool
+      | CborTy::Float
+      | CborTy::NegativeInt
+      | CborTy::Null
+      | CborTy::Simple
+      | CborTy::Undefined
+      | CborTy::UnsignedInt => {
+        let _ = self.header()?;
+      }
+    }
+    Ok(())
+  }
+
+  fn skip_elements(&mut self, len: Option<usize>, depth: u8, items: u8) -> crate::Result<()> {
+    if let Some(elem) = len {
+      for _ in 0..elem {
+        for _ in 0..items {
+          self.skip_with_depth(depth)?;
+        }
+      }
+    } else {
+      while !self.consume_break() {
+        for _ in 0..items {
+          self.skip_with_depth(depth)?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  fn string_len(&mut self, expected_major: u8) -> crate::Result<usize> {
+    let (major, arg) = self.header()?;
+    if major != expected_major {
+      return Err(CborError::UnexpectedTy.into());
+    }
+    Ok(usize::try_from(arg).map_err(|_err| CborError::IntegerOverflow)?)
+  }
+
+  fn take(&mut self, len: usize) -> crate::Result<&'de [u8]> {
+    let Some((data, rest)) = self.bytes.split_at_checked(len) else {
+      return Err(CborError::UnexpectedEof.into());
+    };
+    self.bytes = rest;
+    Ok(data)
+  }
+}
+
+fn f16_to_f64(half: u16) -> f64 {
+  let sign = u64::from(half >> 15) << 63;
+  let exp = u64::from((half >> 10) & 0b1_1111);
+  let mantissa = u64::from(half & 0b11_1111_1111);
+  let abs = match exp {
+    // 2^-24
+    0 => f64::from(half & 0b11_1111_1111) * f64::from_bits(0x3e70_0000_0000_0000),
+    31 => f64::from_bits(0x7ff0_0000_0000_0000 | (mantissa << 42)),
+    _ => f64::from_bits((exp.wrapping_add(1008) << 52) | (mantissa << 42)),
+  };
+  f64::from_bits(abs.to_bits() | sign)
+}
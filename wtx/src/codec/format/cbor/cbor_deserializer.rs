@@ -0,0 +1,287 @@
+use crate::codec::format::{CborDecoder, CborError, CborTy, cbor::MAX_DEPTH};
+use serde::{
+  Deserializer,
+  de::{
+    DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+    value::BorrowedStrDeserializer,
+  },
+  forward_to_deserialize_any,
+};
+
+/// A `serde` deserializer that borrows texts and byte strings from the original CBOR bytes.
+///
+/// Accepts the same representations produced by
+/// [`CborSerializer`](crate::codec::format::CborSerializer) as well as indefinite-length arrays
+/// and maps.
+#[derive(Debug)]
+pub struct CborDeserializer<'de> {
+  decoder: CborDecoder<'de>,
+  depth: u8,
+}
+
+impl<'de> CborDeserializer<'de> {
+  /// New instance
+  #[inline]
+  pub const fn new(bytes: &'de [u8]) -> Self {
+    Self { decoder: CborDecoder::new(bytes), depth: 0 }
+  }
+
+  /// Bytes that weren't deserialized yet.
+  #[inline]
+  pub const fn remaining(&self) -> &'de [u8] {
+    self.decoder.remaining()
+  }
+
+  fn nested<R>(&mut self, cb: impl FnOnce(&mut Self) -> crate::Result<R>) -> crate::Result<R> {
+    if self.depth >= MAX_DEPTH {
+      return Err(CborError::MaxDepthReached.into());
+    }
+    self.depth = self.depth.wrapping_add(1);
+    let rslt = cb(self);
+    self.depth = self.depth.wrapping_sub(1);
+    rslt
+  }
+}
+
+impl<'de> Deserializer<'de> for &mut CborDeserializer<'de> {
+  type Error = crate::Error;
+
+  #[inline]
+  fn deserialize_any<V>(self, visitor: V) -> crate::Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    match self.decoder.peek()? {
+      CborTy::Array => {
+        let len = self.decoder.array_len()?;
+        self.nested(|this| {
+          let mut access = CborCompoundAccess { de: this, remaining: len };
+          let value = visitor.visit_seq(&mut access)?;
+          access.finish()?;
+          Ok(value)
+        })
+      }
+      CborTy::Bool => visitor.visit_bool(self.decoder.bool()?),
+      CborTy::Bytes => visitor.visit_borrowed_bytes(self.decoder.bytes()?),
+      CborTy::Float => visitor.visit_f64(self.decoder.f64()?),
+      CborTy::Map => {
+        let len = self.decoder.map_len()?;
+        self.nested(|this| {
+          let mut access = CborCompoundAccess { de: this, remaining: len };
+          let value = visitor.visit_map(&mut access)?;
+          access.finish()?;
+          Ok(value)
+        })
+      }
+      CborTy::NegativeInt => visitor.visit_i64(self.decoder.i64()?),
+      CborTy::Null | CborTy::Undefined => {
+        self.decoder.skip()?;
+        visitor.visit_unit()
+      }
+      CborTy::Simple => Err(CborError::UnexpectedTy.into()),
+      CborTy::Tag => {
+        let _ = self.decoder.tag()?;
+        self.nested(|this| this.deserialize_any(visitor))
+      }
+      CborTy::Text => visitor.visit_borrowed_str(self.decoder.text()?),
+      CborTy::UnsignedInt => visitor.visit_u64(self.decoder.u64()?),
+    }
+  }
+
+  #[inline]
+  fn deserialize_enum<V>(
+    self,
+    _: &'static str,
+    _: &'static [&'static str],
+    visitor: V,
+  ) -> crate::Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    if self.decoder.peek()? == CborTy::Text {
+      let variant = self.decoder.text()?;
+      return visitor.visit_enum(BorrowedStrDeserializer::<crate::Error>::new(variant));
+    }
+    let len = self.decoder.map_len()?;
+    if len.is_some_and(|el| el != 1) {
+      return Err(CborError::UnexpectedTy.into());
+    }
+    self.nested(|this| {
+      let value = visitor.visit_enum(&mut *this)?;
+      if len.is_none() && !this.decoder.consume_break() {
+        return Err(CborError::TrailingBytes.into());
+      }
+      Ok(value)
+    })
+  }
+
+  #[inline]
+  fn deserialize_ignored_any<V>(self, visitor: V) -> crate::Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    self.decoder.skip()?;
+    visitor.visit_unit()
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> crate::Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    visitor.visit_newtype_struct(self)
+  }
+
+  #[inline]
+  fn deserialize_option<V>(self, visitor: V) -> crate::Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    if matches!(self.decoder.peek()?, CborTy::Null | CborTy::Undefined) {
+      self.decoder.skip()?;
+      visitor.visit_none()
+    } else {
+      visitor.visit_some(self)
+    }
+  }
+
+  #[inline]
+  fn is_human_readable(&self) -> bool {
+    false
+  }
+
+  forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+    unit_struct seq tuple tuple_struct map struct identifier
+  }
+}
+
+impl<'de> EnumAccess<'de> for &mut CborDeserializer<'de> {
+  type Error = crate::Error;
+  type Variant = Self;
+
+  #[inline]
+  fn variant_seed<V>(self, seed: V) -> crate::Result<(V::Value, Self::Variant)>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    Ok((seed.deserialize(&mut *self)?, self))
+  }
+}
+
+impl<'de> VariantAccess<'de> for &mut CborDeserializer<'de> {
+  type Error = crate::Error;
+
+  #[inline]
+  fn unit_variant(self) -> crate::Result<()> {
+    serde::Deserialize::deserialize(self)
+  }
+
+  #[inline]
+  fn newtype_variant_seed<T>(self, seed: T) -> crate::Result<T::Value>
+  where
+    T: DeserializeSeed<'de>,
+  {
+    seed.deserialize(self)
+  }
+
+  #[inline]
+  fn tuple_variant<V>(self, _: usize, visitor: V) -> crate::Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    self.deserialize_any(visitor)
+  }
+
+  #[inline]
+  fn struct_variant<V>(self, _: &'static [&'static str], visitor: V) -> crate::Result<V::Value>
+  where
+    V: Visitor<'de>,
+  {
+    self.deserialize_any(visitor)
+  }
+}
+
+// Elements of arrays or entries of maps. `None` indicates an indefinite-length item.
+struct CborCompoundAccess<'any, 'de> {
+  de: &'any mut CborDeserializer<'de>,
+  remaining: Option<usize>,
+}
+
+impl CborCompoundAccess<'_, '_> {
+  fn finish(&mut self) -> crate::Result<()> {
+    let is_finished = match self.remaining {
+      Some(elem) => elem == 0,
+      None => self.de.decoder.consume_break(),
+    };
+    if !is_finished {
+      return Err(CborError::TrailingBytes.into());
+    }
+    Ok(())
+  }
+
+  fn has_next(&mut self) -> bool {
+    match &mut self.remaining {
+      Some(0) => false,
+      Some(elem) => {
+        *elem = elem.wrapping_sub(1);
+        true
+      }
+      None => {
+        if self.de.decoder.consume_break() {
+          self.remaining = Some(0);
+          return false;
+        }
+        true
+      }
+    }
+  }
+}
+
+impl<'de> MapAccess<'de> for &mut CborCompoundAccess<'_, 'de> {
+  type Error = crate::Error;
+
+  #[inline]
+  fn next_key_seed<K>(&mut self, seed: K) -> crate::Result<Option<K::Value>>
+  where
+    K: DeserializeSeed<'de>,
+  {
+    if !self.has_next() {
+      return Ok(None);
+    }
+    Ok(Some(seed.deserialize(&mut *self.de)?))
+  }
+
+  #[inline]
+  fn next_value_seed<V>(&mut self, seed: V) -> crate::Result<V::Value>
+  where
+    V: DeserializeSeed<'de>,
+  {
+    seed.deserialize(&mut *self.de)
+  }
+
+  #[inline]
+  fn size_hint(&self) -> Option<usize> {
+    self.remaining
+  }
+}
+
+impl<'de> SeqAccess<'de> for &mut CborCompoundAccess<'_, 'de> {
+  type Error = crate::Error;
+
+  #[inline]
+  fn next_element_seed<T>(&mut self, seed: T) -> crate::Result<Option<T::Value>>
+  where
+    T: DeserializeSeed<'de>,
+  {
+    if !self.has_next() {
+      return Ok(None);
+    }
+    Ok(Some(seed.deserialize(&mut *self.de)?))
+  }
+
+  #[inline]
+  fn size_hint(&self) -> Option<usize> {
+    self.remaining
+  }
+}
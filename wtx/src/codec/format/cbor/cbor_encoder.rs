@@ -0,0 +1,201 @@
+use crate::{
+  codec::format::{CborDecoder, CborError},
+  collections::Vector,
+  misc::Usize,
+};
+
+/// Encoder of CBOR items (RFC 8949) that always uses the preferred serialization, i.e., the
+/// shortest forms of arguments and floats.
+#[derive(Debug)]
+pub struct CborEncoder<'buffer> {
+  buffer: &'buffer mut Vector<u8>,
+}
+
+impl<'buffer> CborEncoder<'buffer> {
+  /// New instance that appends items to `buffer`.
+  #[inline]
+  pub const fn new(buffer: &'buffer mut Vector<u8>) -> Self {
+    Self { buffer }
+  }
+
+  /// Encodes the header of an array with `len` elements.
+  #[inline]
+  pub fn array_len(&mut self, len: usize) -> crate::Result<()> {
+    self.header(4, Usize::from(len).into_u64())
+  }
+
+  /// Encodes a boolean.
+  #[inline]
+  pub fn bool(&mut self, value: bool) -> crate::Result<()> {
+    self.buffer.push(if value { 0xf5 } else { 0xf4 })
+  }
+
+  /// Encodes a byte string.
+  #[inline]
+  pub fn bytes(&mut self, value: &[u8]) -> crate::Result<()> {
+    self.header(2, Usize::from(value.len()).into_u64())?;
+    self.buffer.extend_from_copyable_slice(value)
+  }
+
+  /// Encodes a float using the shortest representation that preserves its value. All `NaN`s are
+  /// encoded as the canonical half-precision `NaN`.
+  #[inline]
+  pub fn f64(&mut self, value: f64) -> crate::Result<()> {
+    if value.is_nan() {
+      return self.buffer.extend_from_copyable_slice(&[0xf9, 0x7e, 0]);
+    }
+    let bits = value.to_bits();
+    let _ = if let Some(elem) = narrow_float(bits, 5, 10).and_then(|el| u16::try_from(el).ok()) {
+      self.buffer.extend_from_copyable_slices([&[0xf9][..], &elem.to_be_bytes()])?
+    } else if let Some(elem) = narrow_float(bits, 8, 23).and_then(|el| u32::try_from(el).ok()) {
+      self.buffer.extend_from_copyable_slices([&[0xfa][..], &elem.to_be_bytes()])?
+    } else {
+      self.buffer.extend_from_copyable_slices([&[0xfb][..], &bits.to_be_bytes()])?
+    };
+    Ok(())
+  }
+
+  /// Encodes an unsigned or a negative integer.
+  #[inline]
+  pub fn i64(&mut self, value: i64) -> crate::Result<()> {
+    match u64::try_from(value) {
+      Ok(elem) => self.header(0, elem),
+      Err(_) => self.header(1, value.wrapping_add(1).unsigned_abs()),
+    }
+  }
+
+  /// Encodes the header of a map with `len` entries.
+  #[inline]
+  pub fn map_len(&mut self, len: usize) -> crate::Result<()> {
+    self.header(5, Usize::from(len).into_u64())
+  }
+
+  /// Encodes a map with `len` entries written by `cb` and then sorts the entries by the bytewise
+  /// lexicographic order of their encoded keys, as required by the core deterministic encoding
+  /// of RFC 8949.
+  #[inline]
+  pub fn map_sorted(
+    &mut self,
+    len: usize,
+    cb: impl FnOnce(&mut Self) -> crate::Result<()>,
+  ) -> crate::Result<()> {
+    let start = self.buffer.len();
+    self.map_len(len)?;
+    cb(self)?;
+    sort_map(self.buffer, start)
+  }
+
+  /// Encodes a `null` simple value.
+  #[inline]
+  pub fn null(&mut self) -> crate::Result<()> {
+    self.buffer.push(0xf6)
+  }
+
+  /// Encodes the number of a tag. The tagged item must be encoded right after.
+  #[inline]
+  pub fn tag(&mut self, value: u64) -> crate::Result<()> {
+    self.header(6, value)
+  }
+
+  /// Encodes a text string.
+  #[inline]
+  pub fn text(&mut self, value: &str) -> crate::Result<()> {
+    self.header(3, Usize::from(value.len()).into_u64())?;
+    self.buffer.extend_from_copyable_slice(value.as_bytes())
+  }
+
+  /// Encodes an unsigned integer.
+  #[inline]
+  pub fn u64(&mut self, value: u64) -> crate::Result<()> {
+    self.header(0, value)
+  }
+
+  #[cfg(feature = "serde")]
+  pub(crate) fn buffer(&mut self) -> &mut Vector<u8> {
+    self.buffer
+  }
+
+  pub(crate) fn header(&mut self, major: u8, arg: u64) -> crate::Result<()> {
+    let initial = major << 5;
+    let _ = if let Ok(elem) = u8::try_from(arg) {
+      if elem < 24 {
+        return self.buffer.push(initial | elem);
+      }
+      self.buffer.extend_from_copyable_slices([&[initial | 0x18][..], &[elem]])?
+    } else if let Ok(elem) = u16::try_from(arg) {
+      self.buffer.extend_from_copyable_slices([&[initial | 0x19][..], &elem.to_be_bytes()])?
+    } else if let Ok(elem) = u32::try_from(arg) {
+      self.buffer.extend_from_copyable_slices([&[initial | 0x1a][..], &elem.to_be_bytes()])?
+    } else {
+      self.buffer.extend_from_copyable_slices([&[initial | 0x1b][..], &arg.to_be_bytes()])?
+    };
+    Ok(())
+  }
+}
+
+// Sorts the entries of the definite-length map that starts at `start` and ends at the end of
+// `buffer`.
+pub(crate) fn sort_map(buffer: &mut Vector<u8>, start: usize) -> crate::Result<()> {
+  let map = buffer.get(start..).unwrap_or_default();
+  let mut decoder = CborDecoder::new(map);
+  let Some(len) = decoder.map_len()? else {
+    return Err(CborError::UnexpectedTy.into());
+  };
+  let offset =
+    |local_decoder: &CborDecoder<'_>| map.len().wrapping_sub(local_decoder.remaining().len());
+  let header_len = offset(&decoder);
+  let mut entries = Vector::with_capacity(len)?;
+  for _ in 0..len {
+    let begin = offset(&decoder);
+    decoder.skip()?;
+    let key_end = offset(&decoder);
+    decoder.skip()?;
+    entries.push((begin, key_end, offset(&decoder)))?;
+  }
+  let key = |(begin, key_end, _): &(usize, usize, usize)| map.get(*begin..*key_end);
+  entries.sort_unstable_by(|lhs, rhs| key(lhs).cmp(&key(rhs)));
+  let mut sorted = Vector::with_capacity(map.len().wrapping_sub(header_len))?;
+  for (begin, _, end) in &entries {
+    sorted.extend_from_copyable_slice(map.get(*begin..*end).unwrap_or_default())?;
+  }
+  if let Some(elem) = buffer.get_mut(start.wrapping_add(header_len)..) {
+    elem.copy_from_slice(&sorted);
+  }
+  Ok(())
+}
+
+// Returns the bits of a float with `exp_len` exponent bits and `mantissa_len` mantissa bits that
+// represents exactly the same value of the double-precision `bits`. `NaN`s are not supported.
+fn narrow_float(bits: u64, exp_len: u32, mantissa_len: u32) -> Option<u64> {
+  let sign = bits >> 63 << exp_len.wrapping_add(mantissa_len);
+  let exp = (bits >> 52) & 0x7ff;
+  let mantissa = bits & 0x000f_ffff_ffff_ffff;
+  let max_exp = (1u64 << exp_len).wrapping_sub(1);
+  if exp == 0x7ff {
+    return (mantissa == 0).then_some(sign | (max_exp << mantissa_len));
+  }
+  if exp == 0 {
+    return (mantissa == 0).then_some(sign);
+  }
+  let bias = i64::try_from(max_exp >> 1).ok()?;
+  let unbiased = i64::try_from(exp).ok()?.wrapping_sub(1023);
+  let dropped = 52u32.wrapping_sub(mantissa_len);
+  let min_normal = 1i64.wrapping_sub(bias);
+  if unbiased >= min_normal && unbiased <= bias {
+    if mantissa & (1u64 << dropped).wrapping_sub(1) != 0 {
+      return None;
+    }
+    let local_exp = u64::try_from(unbiased.wrapping_add(bias)).ok()?;
+    return Some(sign | (local_exp << mantissa_len) | (mantissa >> dropped));
+  }
+  let min_subnormal = min_normal.wrapping_sub(mantissa_len.into());
+  if unbiased < min_normal && unbiased >= min_subnormal {
+    let shift = dropped.wrapping_add(u32::try_from(min_normal.wrapping_sub(unbiased)).ok()?);
+    let full = mantissa | (1 << 52);
+    if full & (1u64 << shift).wrapping_sub(1) != 0 {
+      return None;
+    }
+    return Some(sign | (full >> shift));
+  }
+  None
+}
@@ -0,0 +1,23 @@
+/// CBOR error
+#[derive(Clone, Copy, Debug)]
+pub enum CborError {
+  /// Indefinite-length strings can not be decoded without allocations.
+  IndefiniteLengthString,
+  /// Integer doesn't fit into the requested type.
+  IntegerOverflow,
+  /// Additional information values 28, 29 and 30 are reserved.
+  InvalidAdditionalInfo,
+  /// Text strings must be valid UTF-8.
+  InvalidUtf8,
+  /// Nested arrays, maps or tags exceeded the maximum allowed depth.
+  MaxDepthReached,
+  /// Bytes remained after the decoding of the top-level item or elements remained after the
+  /// decoding of an array or map.
+  TrailingBytes,
+  /// The "break" stop code appeared outside of an indefinite-length item.
+  UnexpectedBreak,
+  /// Received less bytes than what was declared by the item header.
+  UnexpectedEof,
+  /// The major type or simple value differs from the expected type.
+  UnexpectedTy,
+}
@@ -0,0 +1,434 @@
+use crate::{
+  codec::format::{CborEncoder, CborError, cbor::cbor_encoder::sort_map},
+  collections::Vector,
+  misc::Usize,
+};
+use serde::{
+  Serialize, Serializer,
+  ser::{
+    SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+  },
+};
+
+/// A `serde` serializer that outputs CBOR items.
+///
+/// Structures are encoded as maps with text keys, unit variants as texts and other variants as
+/// single-entry maps whose keys are the variant names. Sequences and maps of unknown lengths are
+/// still encoded with definite lengths.
+#[derive(Debug)]
+pub struct CborSerializer<'buffer> {
+  encoder: CborEncoder<'buffer>,
+  is_deterministic: bool,
+}
+
+impl<'buffer> CborSerializer<'buffer> {
+  /// New instance that appends items to `buffer`.
+  ///
+  /// If `is_deterministic` is `true`, the entries of all maps are sorted according to the core
+  /// deterministic encoding requirements of RFC 8949.
+  #[inline]
+  pub const fn new(buffer: &'buffer mut Vector<u8>, is_deterministic: bool) -> Self {
+    Self { encoder: CborEncoder::new(buffer), is_deterministic }
+  }
+
+  fn compound(
+    &mut self,
+    major: u8,
+    len: Option<usize>,
+  ) -> crate::Result<CborSerializeCompound<'_, 'buffer>> {
+    let start = self.encoder.buffer().len();
+    if let Some(elem) = len {
+      self.encoder.header(major, Usize::from(elem).into_u64())?;
+    }
+    Ok(CborSerializeCompound { count: 0, has_header: len.is_some(), major, ser: self, start })
+  }
+
+  fn variant(&mut self, variant: &str) -> crate::Result<()> {
+    self.encoder.map_len(1)?;
+    self.encoder.text(variant)
+  }
+}
+
+/// Arrays and maps that are being serialized by [`CborSerializer`].
+#[derive(Debug)]
+pub struct CborSerializeCompound<'ser, 'buffer> {
+  count: usize,
+  has_header: bool,
+  major: u8,
+  ser: &'ser mut CborSerializer<'buffer>,
+  start: usize,
+}
+
+impl CborSerializeCompound<'_, '_> {
+  fn element<T>(&mut self, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    value.serialize(&mut *self.ser)?;
+    self.count = self.count.wrapping_add(1);
+    Ok(())
+  }
+
+  fn finish(self) -> crate::Result<()> {
+    let is_sorted_map = self.major == 5 && self.ser.is_deterministic;
+    let buffer = self.ser.encoder.buffer();
+    if !self.has_header {
+      let before = buffer.len();
+      CborEncoder::new(buffer).header(self.major, Usize::from(self.count).into_u64())?;
+      let header_len = buffer.len().wrapping_sub(before);
+      if let Some(elem) = buffer.get_mut(self.start..) {
+        elem.rotate_right(header_len);
+      }
+    }
+    if is_sorted_map {
+      sort_map(buffer, self.start)?;
+    }
+    Ok(())
+  }
+}
+
+impl<'ser, 'buffer> Serializer for &'ser mut CborSerializer<'buffer> {
+  type Ok = ();
+  type Error = crate::Error;
+  type SerializeSeq = CborSerializeCompound<'ser, 'buffer>;
+  type SerializeTuple = CborSerializeCompound<'ser, 'buffer>;
+  type SerializeTupleStruct = CborSerializeCompound<'ser, 'buffer>;
+  type SerializeTupleVariant = CborSerializeCompound<'ser, 'buffer>;
+  type SerializeMap = CborSerializeCompound<'ser, 'buffer>;
+  type SerializeStruct = CborSerializeCompound<'ser, 'buffer>;
+  type SerializeStructVariant = CborSerializeCompound<'ser, 'buffer>;
+
+  #[inline]
+  fn is_human_readable(&self) -> bool {
+    false
+  }
+
+  #[inline]
+  fn serialize_bool(self, v: bool) -> crate::Result<()> {
+    self.encoder.bool(v)
+  }
+
+  #[inline]
+  fn serialize_i8(self, v: i8) -> crate::Result<()> {
+    self.encoder.i64(v.into())
+  }
+
+  #[inline]
+  fn serialize_i16(self, v: i16) -> crate::Result<()> {
+    self.encoder.i64(v.into())
+  }
+
+  #[inline]
+  fn serialize_i32(self, v: i32) -> crate::Result<()> {
+    self.encoder.i64(v.into())
+  }
+
+  #[inline]
+  fn serialize_i64(self, v: i64) -> crate::Result<()> {
+    self.encoder.i64(v)
+  }
+
+  #[inline]
+  fn serialize_i128(self, v: i128) -> crate::Result<()> {
+    if let Ok(elem) = u64::try_from(v) {
+      return self.encoder.u64(elem);
+    }
+    self.encoder.i64(i64::try_from(v).map_err(|_err| CborError::IntegerOverflow)?)
+  }
+
+  #[inline]
+  fn serialize_u8(self, v: u8) -> crate::Result<()> {
+    self.encoder.u64(v.into())
+  }
+
+  #[inline]
+  fn serialize_u16(self, v: u16) -> crate::Result<()> {
+    self.encoder.u64(v.into())
+  }
+
+  #[inline]
+  fn serialize_u32(self, v: u32) -> crate::Result<()> {
+    self.encoder.u64(v.into())
+  }
+
+  #[inline]
+  fn serialize_u64(self, v: u64) -> crate::Result<()> {
+    self.encoder.u64(v)
+  }
+
+  #[inline]
+  fn serialize_u128(self, v: u128) -> crate::Result<()> {
+    self.encoder.u64(u64::try_from(v).map_err(|_err| CborError::IntegerOverflow)?)
+  }
+
+  #[inline]
+  fn serialize_f32(self, v: f32) -> crate::Result<()> {
+    self.encoder.f64(v.into())
+  }
+
+  #[inline]
+  fn serialize_f64(self, v: f64) -> crate::Result<()> {
+    self.encoder.f64(v)
+  }
+
+  #[inline]
+  fn serialize_char(self, v: char) -> crate::Result<()> {
+    self.encoder.text(v.encode_utf8(&mut [0; 4]))
+  }
+
+  #[inline]
+  fn serialize_str(self, v: &str) -> crate::Result<()> {
+    self.encoder.text(v)
+  }
+
+  #[inline]
+  fn serialize_bytes(self, v: &[u8]) -> crate::Result<()> {
+    self.encoder.bytes(v)
+  }
+
+  #[inline]
+  fn serialize_none(self) -> crate::Result<()> {
+    self.encoder.null()
+  }
+
+  #[inline]
+  fn serialize_some<T>(self, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    value.serialize(self)
+  }
+
+  #[inline]
+  fn serialize_unit(self) -> crate::Result<()> {
+    self.encoder.null()
+  }
+
+  #[inline]
+  fn serialize_unit_struct(self, _: &'static str) -> crate::Result<()> {
+    self.encoder.null()
+  }
+
+  #[inline]
+  fn serialize_unit_variant(
+    self,
+    _: &'static str,
+    _: u32,
+    variant: &'static str,
+  ) -> crate::Result<()> {
+    self.encoder.text(variant)
+  }
+
+  #[inline]
+  fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    value.serialize(self)
+  }
+
+  #[inline]
+  fn serialize_newtype_variant<T>(
+    self,
+    _: &'static str,
+    _: u32,
+    variant: &'static str,
+    value: &T,
+  ) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.variant(variant)?;
+    value.serialize(self)
+  }
+
+  #[inline]
+  fn serialize_seq(self, len: Option<usize>) -> crate::Result<Self::SerializeSeq> {
+    self.compound(4, len)
+  }
+
+  #[inline]
+  fn serialize_tuple(self, len: usize) -> crate::Result<Self::SerializeTuple> {
+    self.compound(4, Some(len))
+  }
+
+  #[inline]
+  fn serialize_tuple_struct(
+    self,
+    _: &'static str,
+    len: usize,
+  ) -> crate::Result<Self::SerializeTupleStruct> {
+    self.compound(4, Some(len))
+  }
+
+  #[inline]
+  fn serialize_tuple_variant(
+    self,
+    _: &'static str,
+    _: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> crate::Result<Self::SerializeTupleVariant> {
+    self.variant(variant)?;
+    self.compound(4, Some(len))
+  }
+
+  #[inline]
+  fn serialize_map(self, len: Option<usize>) -> crate::Result<Self::SerializeMap> {
+    self.compound(5, len)
+  }
+
+  #[inline]
+  fn serialize_struct(self, _: &'static str, len: usize) -> crate::Result<Self::SerializeStruct> {
+    self.compound(5, Some(len))
+  }
+
+  #[inline]
+  fn serialize_struct_variant(
+    self,
+    _: &'static str,
+    _: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> crate::Result<Self::SerializeStructVariant> {
+    self.variant(variant)?;
+    self.compound(5, Some(len))
+  }
+}
+
+impl SerializeMap for CborSerializeCompound<'_, '_> {
+  type Ok = ();
+  type Error = crate::Error;
+
+  #[inline]
+  fn serialize_key<T>(&mut self, key: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    key.serialize(&mut *self.ser)
+  }
+
+  #[inline]
+  fn serialize_value<T>(&mut self, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.element(value)
+  }
+
+  #[inline]
+  fn end(self) -> crate::Result<()> {
+    self.finish()
+  }
+}
+
+impl SerializeSeq for CborSerializeCompound<'_, '_> {
+  type Ok = ();
+  type Error = crate::Error;
+
+  #[inline]
+  fn serialize_element<T>(&mut self, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.element(value)
+  }
+
+  #[inline]
+  fn end(self) -> crate::Result<()> {
+    self.finish()
+  }
+}
+
+impl SerializeStruct for CborSerializeCompound<'_, '_> {
+  type Ok = ();
+  type Error = crate::Error;
+
+  #[inline]
+  fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.ser.encoder.text(key)?;
+    self.element(value)
+  }
+
+  #[inline]
+  fn end(self) -> crate::Result<()> {
+    self.finish()
+  }
+}
+
+impl SerializeStructVariant for CborSerializeCompound<'_, '_> {
+  type Ok = ();
+  type Error = crate::Error;
+
+  #[inline]
+  fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.ser.encoder.text(key)?;
+    self.element(value)
+  }
+
+  #[inline]
+  fn end(self) -> crate::Result<()> {
+    self.finish()
+  }
+}
+
+impl SerializeTuple for CborSerializeCompound<'_, '_> {
+  type Ok = ();
+  type Error = crate::Error;
+
+  #[inline]
+  fn serialize_element<T>(&mut self, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.element(value)
+  }
+
+  #[inline]
+  fn end(self) -> crate::Result<()> {
+    self.finish()
+  }
+}
+
+impl SerializeTupleStruct for CborSerializeCompound<'_, '_> {
+  type Ok = ();
+  type Error = crate::Error;
+
+  #[inline]
+  fn serialize_field<T>(&mut self, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.element(value)
+  }
+
+  #[inline]
+  fn end(self) -> crate::Result<()> {
+    self.finish()
+  }
+}
+
+impl SerializeTupleVariant for CborSerializeCompound<'_, '_> {
+  type Ok = ();
+  type Error = crate::Error;
+
+  #[inline]
+  fn serialize_field<T>(&mut self, value: &T) -> crate::Result<()>
+  where
+    T: Serialize + ?Sized,
+  {
+    self.element(value)
+  }
+
+  #[inline]
+  fn end(self) -> crate::Result<()> {
+    self.finish()
+  }
+}
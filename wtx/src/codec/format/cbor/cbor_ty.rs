@@ -0,0 +1,28 @@
+/// Types of CBOR items.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CborTy {
+  /// Major type 4
+  Array,
+  /// `false` or `true` simple values
+  Bool,
+  /// Major type 2
+  Bytes,
+  /// Half, single or double-precision float
+  Float,
+  /// Major type 5
+  Map,
+  /// Major type 1
+  NegativeInt,
+  /// `null` simple value
+  Null,
+  /// Unassigned or reserved simple values
+  Simple,
+  /// Major type 6
+  Tag,
+  /// Major type 3
+  Text,
+  /// `undefined` simple value
+  Undefined,
+  /// Major type 0
+  UnsignedInt,
+}
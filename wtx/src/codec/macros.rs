@@ -77,6 +77,7 @@ macro_rules! _impl_se_collections {
         fn encode(&self, ew: &mut crate::codec::EncodeWrapper<'_, &mut $drsr>) -> crate::Result<()> {
           let $array_self = self;
           let $array_bytes = &mut *ew.buffer;
+          let $array_drsr = &mut *ew.encode_aux;
           $array_block;
           Ok(())
         }
@@ -93,6 +94,7 @@ macro_rules! _impl_se_collections {
         fn encode(&self, ew: &mut crate::codec::EncodeWrapper<'_, &mut $drsr>) -> crate::Result<()> {
           let $arrayvector_self = self;
           let $arrayvector_bytes = &mut *ew.buffer;
+          let $arrayvector_drsr = &mut *ew.encode_aux;
           $arrayvector_block;
           Ok(())
         }
@@ -107,6 +109,7 @@ macro_rules! _impl_se_collections {
       fn encode(&self, ew: &mut crate::codec::EncodeWrapper<'_, &mut $drsr>) -> crate::Result<()> {
         let $slice_ref_self = self;
         let $slice_ref_bytes = &mut *ew.buffer;
+        let $slice_ref_drsr = &mut *ew.encode_aux;
         $slice_ref_block;
         Ok(())
       }
@@ -120,6 +123,7 @@ macro_rules! _impl_se_collections {
       fn encode(&self, ew: &mut crate::codec::EncodeWrapper<'_, &mut $drsr>) -> crate::Result<()> {
         let $vec_self = self;
         let $vec_bytes = &mut *ew.buffer;
+        let $vec_drsr = &mut *ew.encode_aux;
         $vec_block;
         Ok(())
       }
@@ -10,6 +10,7 @@ use crate::{
 /// Decodes Base64 into a buffer
 #[inline]
 pub fn decode_base64_into_buffer<'buffer, B, L>(
+  alphabet: Base64Alphabet,
   buffer: &'buffer mut B,
   bytes: &[u8],
 ) -> crate::Result<&'buffer mut [u8]>
@@ -19,7 +20,7 @@ where
 {
   let max_decoded_len = base64_decoded_len_ub(bytes.len());
   decode_into_buffer(buffer, max_decoded_len.min(B::CAPACITY_UPPER_BOUND), |slice| {
-    Ok(base64_decode(Base64Alphabet::Standard, bytes, slice)?.len())
+    Ok(base64_decode(alphabet, bytes, slice)?.len())
   })
 }
 
@@ -47,6 +47,42 @@ impl<D, DA> Encode<GenericCodec<DA, &mut ()>> for VerbatimEncoder<D> {
   }
 }
 
+#[cfg(all(feature = "cbor", feature = "serde"))]
+mod cbor {
+  use crate::{
+    codec::{
+      format::{Cbor, CborDeserializer, cbor_from_slice, cbor_to_vector},
+      protocol::VerbatimEncoder,
+    },
+    misc::deserialize_seq_into_buffer_with_serde,
+  };
+  use serde::{Deserialize, Serialize};
+
+  _impl_dec! {
+    VerbatimEncoder<(D): Deserialize<'de>>,
+    Cbor,
+    |_aux, dw| {
+      cbor_from_slice(dw.bytes)
+    }
+  }
+
+  _impl_dec_seq! {
+    VerbatimEncoder<D: Deserialize<'de>>,
+    Cbor,
+    |_aux, buffer, dw| {
+      deserialize_seq_into_buffer_with_serde(&mut CborDeserializer::new(dw.bytes), buffer)
+    }
+  }
+
+  _impl_enc! {
+    VerbatimEncoder<D: Serialize>,
+    Cbor,
+    |this, _aux, ew| {
+      cbor_to_vector(&mut *ew.buffer, ew.encode_aux.is_deterministic(), &this.data)?;
+    }
+  }
+}
+
 #[cfg(feature = "quick-protobuf")]
 mod quick_protobuf {
   use crate::codec::{CodecError, format::QuickProtobuf, protocol::VerbatimEncoder};
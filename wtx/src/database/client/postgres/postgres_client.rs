@@ -41,6 +41,10 @@ where
   TCX: TlsCtx,
 {
   /// Connects with an unencrypted stream.
+  ///
+  /// Connections can be tunneled through proxies with
+  /// [`crate::tls::TlsConnectorBuilder::build_with_proxy`]. With the `dns` feature, hosts can be
+  /// resolved with `DnsResolver` through `TlsConnectorBuilder::build_with_resolver`.
   #[inline]
   pub async fn connect<RNG, STR, TCG, U>(
     mut client_buffer: ClientBuffer,
@@ -151,7 +155,11 @@ where
     let ClientBuffer { common, .. } = &mut self.cb;
     let CommonClientBuffer { read_buffer, records_params, stmts, values_params } = common;
     clear_query_buffers(records_params, values_params);
-    Self::simple_query_execute(
+    #[cfg(feature = "metrics")]
+    let _timer = crate::metrics::MetricsRegistry::global().postgres_query_duration.start_timer();
+    let span =
+      _trace_span!("Postgres query", db.system = "postgresql", db.operation = sql_operation(cmd));
+    let future = Self::simple_query_execute(
       buffer,
       cmd,
       &mut self.cs,
@@ -161,8 +169,8 @@ where
       &mut self.stream,
       values_params,
       cb,
-    )
-    .await
+    );
+    span.instrument(future).await
   }
 
   #[expect(clippy::wildcard_enum_match_arm, reason = "too many variants")]
@@ -181,14 +189,24 @@ where
     let ClientBuffer { common, .. } = client_buffer;
     let CommonClientBuffer { read_buffer, records_params, stmts, values_params } = common;
     clear_query_buffers(records_params, values_params);
-    let tuple =
-      Self::write_send_await_stmt_prepare(cs, read_buffer, &rv, sc, stmts, stream).await?;
+    #[cfg(feature = "metrics")]
+    let _timer = crate::metrics::MetricsRegistry::global().postgres_query_duration.start_timer();
+    let span = _trace_span!(
+      "Postgres statement",
+      db.system = "postgresql",
+      db.operation = sql_operation(sc.cmd().unwrap_or_default())
+    );
+    let tuple = span
+      .instrument(Self::write_send_await_stmt_prepare(cs, read_buffer, &rv, sc, stmts, stream))
+      .await?;
     let (_, stmt_cmd_id_array, stmt_mut) = tuple;
-    Self::write_send_await_stmt_bind(cs, read_buffer, rv, &stmt_cmd_id_array, stream).await?;
+    span
+      .instrument(Self::write_send_await_stmt_bind(cs, read_buffer, rv, &stmt_cmd_id_array, stream))
+      .await?;
     let begin_data = read_buffer.current_end_idx().wrapping_add(7);
     *read_buffer.forbid_clear_mut() = true;
     loop {
-      let msg = Self::fetch_msg(cs, read_buffer, stream).await?;
+      let msg = span.instrument(Self::fetch_msg(cs, read_buffer, stream)).await?;
       match msg.ty {
         MessageTy::CommandComplete(_) | MessageTy::EmptyQueryResponse => {}
         MessageTy::DataRow(values_len) => {
@@ -237,3 +255,12 @@ where
     Ok(Self::write_send_await_stmt_prepare(cs, read_buffer, &(), cmd, stmts, stream).await?.0)
   }
 }
+
+// Only the leading keyword is recorded because statements can carry sensitive literals and have
+// arbitrary lengths.
+#[cfg(feature = "tracing")]
+fn sql_operation(cmd: &str) -> &str {
+  let keyword =
+    cmd.trim_start().split(|el: char| !el.is_ascii_alphabetic()).next().unwrap_or_default();
+  keyword.get(..keyword.len().min(16)).unwrap_or_default()
+}
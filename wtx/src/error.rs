@@ -263,6 +263,9 @@ pub enum Error {
   BlocksQueueError(BlocksDequeError),
   #[doc = associated_element_doc!()]
   CalendarError(CalendarError),
+  #[cfg(feature = "cbor")]
+  #[doc = associated_element_doc!()]
+  CborError(crate::codec::format::CborError),
   #[cfg(feature = "client-api-framework")]
   #[doc = associated_element_doc!()]
   ClientApiFrameworkError(crate::client_api_framework::ClientApiFrameworkError),
@@ -277,6 +280,9 @@ pub enum Error {
   #[cfg(feature = "database")]
   #[doc = associated_element_doc!()]
   DatabaseError(crate::database::DatabaseError),
+  #[cfg(feature = "dns")]
+  #[doc = associated_element_doc!()]
+  DnsError(crate::net::dns::DnsError),
   #[doc = associated_element_doc!()]
   ExecutorError(crate::executor::ExecutorError),
   #[doc = associated_element_doc!()]
@@ -297,8 +303,14 @@ pub enum Error {
   /// Non-fatal HTTP/2 stream error
   #[cfg(feature = "http2")]
   Http2FlowControlError(crate::http2::Http2Error, u32),
+  #[cfg(feature = "http-jwt")]
+  #[doc = associated_element_doc!()]
+  JwtError(crate::http::jwt::JwtError),
   #[doc = associated_element_doc!()]
   NetError(crate::net::NetError),
+  #[cfg(feature = "http-oauth2")]
+  #[doc = associated_element_doc!()]
+  OAuth2Error(crate::http::oauth2::OAuth2Error),
   #[cfg(feature = "postgres")]
   #[doc = associated_element_doc!()]
   PostgresDbError(Box<crate::database::client::postgres::DbError>),
@@ -327,6 +339,9 @@ pub enum Error {
   TlsErrorReply(crate::tls::TlsError, crate::tls::AlertDescription),
   #[doc = associated_element_doc!()]
   VectorError(VectorError),
+  #[cfg(feature = "http-web-authn")]
+  #[doc = associated_element_doc!()]
+  WebAuthnError(crate::http::web_authn::WebAuthnError),
   #[cfg(feature = "web-socket")]
   #[doc = associated_element_doc!()]
   WebSocketError(crate::web_socket::WebSocketError),
@@ -225,4 +225,14 @@ mod serde {
       Self::Generic(string.try_into().unwrap_or_default())
     }
   }
+
+  impl serde::de::Error for crate::Error {
+    #[inline]
+    fn custom<T>(msg: T) -> Self
+    where
+      T: Display,
+    {
+      <Self as serde::ser::Error>::custom(msg)
+    }
+  }
 }
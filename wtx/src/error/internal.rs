@@ -70,6 +70,14 @@ impl From<CalendarError> for Error {
   }
 }
 
+#[cfg(feature = "cbor")]
+impl From<crate::codec::format::CborError> for Error {
+  #[inline]
+  fn from(from: crate::codec::format::CborError) -> Self {
+    Self::CborError(from)
+  }
+}
+
 #[cfg(feature = "client-api-framework")]
 impl From<crate::client_api_framework::ClientApiFrameworkError> for Error {
   #[inline]
@@ -111,6 +119,14 @@ impl From<crate::database::DatabaseError> for Error {
   }
 }
 
+#[cfg(feature = "dns")]
+impl From<crate::net::dns::DnsError> for Error {
+  #[inline]
+  fn from(from: crate::net::dns::DnsError) -> Self {
+    Self::DnsError(from)
+  }
+}
+
 impl From<crate::executor::ExecutorError> for Error {
   #[inline]
   fn from(from: crate::executor::ExecutorError) -> Self {
@@ -147,6 +163,14 @@ impl From<crate::http::HttpError> for Error {
   }
 }
 
+#[cfg(feature = "http-jwt")]
+impl From<crate::http::jwt::JwtError> for Error {
+  #[inline]
+  fn from(from: crate::http::jwt::JwtError) -> Self {
+    Self::JwtError(from)
+  }
+}
+
 impl From<crate::net::NetError> for Error {
   #[inline]
   fn from(from: crate::net::NetError) -> Self {
@@ -154,6 +178,14 @@ impl From<crate::net::NetError> for Error {
   }
 }
 
+#[cfg(feature = "http-oauth2")]
+impl From<crate::http::oauth2::OAuth2Error> for Error {
+  #[inline]
+  fn from(from: crate::http::oauth2::OAuth2Error) -> Self {
+    Self::OAuth2Error(from)
+  }
+}
+
 #[cfg(feature = "postgres")]
 impl From<crate::database::client::postgres::DbError> for Error {
   #[inline]
@@ -224,6 +256,14 @@ impl From<VectorError> for Error {
   }
 }
 
+#[cfg(feature = "http-web-authn")]
+impl From<crate::http::web_authn::WebAuthnError> for Error {
+  #[inline]
+  fn from(from: crate::http::web_authn::WebAuthnError) -> Self {
+    Self::WebAuthnError(from)
+  }
+}
+
 #[cfg(feature = "web-socket")]
 impl From<crate::web_socket::WebSocketError> for Error {
   #[inline]
@@ -11,14 +11,15 @@ mod std_runtime;
 #[cfg(feature = "tokio")]
 mod tokio_executor;
 
-use crate::net::{TcpListener, TcpStream};
+#[cfg(feature = "std")]
+use crate::net::ToSocketAddrs;
+use crate::net::{TcpListener, TcpStream, UdpStream};
+use core::net::SocketAddr;
 pub use executor_error::ExecutorError;
 pub use no_std_runtime::NoStdRuntime;
 #[cfg(feature = "tokio")]
 pub use tokio_executor::{TokioExecutor, TokioSpawnFutureFuture};
 #[cfg(feature = "std")]
-use {crate::net::ToSocketAddrs, core::net::SocketAddr};
-#[cfg(feature = "std")]
 pub use {
   std_executor::{StdExecutor, StdSpawnFuture, StdSpawnLocalFuture},
   std_runtime::{SpawnFuture, StdRuntime},
@@ -57,6 +58,11 @@ pub trait Executor: Default {
   type TcpListener: TcpListener<TcpStream = Self::TcpStream>;
   /// See [`TcpStream`].
   type TcpStream: TcpStream<Executor = Self>;
+  /// See [`UdpStream`].
+  type UdpStream: UdpStream;
+
+  /// Creates a UDP socket bound to the specified address.
+  fn bind_udp(addr: SocketAddr) -> impl Future<Output = crate::Result<Self::UdpStream>>;
 
   /// Spawns a future to run concurrently on the executor.
   fn spawn<F>(&self, future: F) -> Self::SpawnFuture<F::Output>
@@ -25,6 +25,12 @@ impl Executor for StdExecutor {
   type SpawnLocalFuture<T> = StdSpawnLocalFuture<T>;
   type TcpListener = std::net::TcpListener;
   type TcpStream = std::net::TcpStream;
+  type UdpStream = std::net::UdpSocket;
+
+  #[inline]
+  async fn bind_udp(addr: SocketAddr) -> crate::Result<Self::UdpStream> {
+    Ok(std::net::UdpSocket::bind(addr)?)
+  }
 
   #[inline]
   fn spawn<F>(&self, future: F) -> Self::SpawnFuture<F::Output>
@@ -23,6 +23,12 @@ impl Executor for TokioExecutor {
   type SpawnLocalFuture<T> = TokioSpawnFutureFuture<T>;
   type TcpListener = tokio::net::TcpListener;
   type TcpStream = tokio::net::TcpStream;
+  type UdpStream = tokio::net::UdpSocket;
+
+  #[inline]
+  async fn bind_udp(addr: SocketAddr) -> crate::Result<Self::UdpStream> {
+    Ok(tokio::net::UdpSocket::bind(addr).await?)
+  }
 
   #[inline]
   fn spawn<F>(&self, future: F) -> Self::SpawnFuture<F::Output>
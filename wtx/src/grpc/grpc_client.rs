@@ -7,9 +7,10 @@ use crate::{
   grpc::serialize,
   http::{
     Header, Headers, HttpClient, KnownHeaderName, MsgBuffer, MsgBufferString, MsgDataMut as _,
-    ReqBuilder, Response, WTX_USER_AGENT,
+    ReqBuilder, Response, TraceContext, WTX_USER_AGENT,
   },
   misc::Lease,
+  rng::{Xorshift64, simple_seed},
 };
 
 /// Performs requests to gRPC servers.
@@ -18,6 +19,8 @@ pub struct GrpcClient<C, DRSR> {
   client: C,
   drsr: DRSR,
   enc_buffer: Vector<u8>,
+  rng: Xorshift64,
+  trace_context: Option<TraceContext>,
 }
 
 impl<C, DRSR> GrpcClient<C, DRSR>
@@ -26,8 +29,14 @@ where
 {
   /// Constructor
   #[inline]
-  pub const fn new(client: C, drsr: DRSR) -> Self {
-    Self { client, drsr, enc_buffer: Vector::new() }
+  pub fn new(client: C, drsr: DRSR) -> Self {
+    Self {
+      client,
+      drsr,
+      enc_buffer: Vector::new(),
+      rng: Xorshift64::from(simple_seed()),
+      trace_context: None,
+    }
   }
 
   /// Deserialize From Response Bytes
@@ -57,15 +66,26 @@ where
   {
     msg_buffer.clear_body_and_headers();
     serialize(&mut msg_buffer.body, VerbatimEncoder { data }, &mut self.drsr)?;
-    Self::push_headers(&mut msg_buffer.headers)?;
+    let trace_context = self.trace_context.as_ref().map(|el| el.child(&mut self.rng));
+    Self::push_headers(&mut msg_buffer.headers, trace_context.as_ref())?;
     let rb = ReqBuilder::post(msg_buffer);
     let req_id = self.client.send_req(&mut self.enc_buffer, rb.into_request()).await?;
     let res = self.client.recv_res(req_id).await?;
     Ok(Response::new(res.msg_data, res.status_code))
   }
 
+  /// Parent context of subsequent requests. Each request carries, as metadata, a child of this
+  /// context with a fresh span id.
   #[inline]
-  fn push_headers(headers: &mut Headers) -> crate::Result<()> {
+  pub const fn trace_context_mut(&mut self) -> &mut Option<TraceContext> {
+    &mut self.trace_context
+  }
+
+  #[inline]
+  fn push_headers(
+    headers: &mut Headers,
+    trace_context: Option<&TraceContext>,
+  ) -> crate::Result<()> {
     headers.push_from_iter_many([
       Header::from_name_and_value(
         KnownHeaderName::ContentType.into(),
@@ -74,6 +94,9 @@ where
       Header::from_name_and_value(KnownHeaderName::Te.into(), ["trailers"].into_iter()),
       Header::from_name_and_value(KnownHeaderName::UserAgent.into(), [WTX_USER_AGENT].into_iter()),
     ])?;
+    if let Some(elem) = trace_context {
+      elem.push_headers(headers)?;
+    }
     Ok(())
   }
 }
@@ -12,22 +12,35 @@ pub mod http2_client_pool;
 #[cfg(feature = "http2-server-framework")]
 pub mod http2_server_framework;
 mod http_client;
+#[cfg(feature = "http-client-framework")]
+pub mod http_client_framework;
 mod http_error;
 mod http_recv_params;
+#[cfg(feature = "http-jwt")]
+pub mod jwt;
 mod method;
 mod mime;
 mod misc;
 mod msg_buffer;
 mod msg_builder;
 mod msg_data;
+#[cfg(feature = "http-oauth2")]
+pub mod oauth2;
 mod operation_mode;
+mod otlp_json_exporter;
+mod priority;
 mod protocol;
 mod request;
 mod response;
 mod router;
 #[cfg(feature = "http-session")]
 mod session;
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket"))]
+mod shutdown_signal;
 mod status_code;
+mod trace_context;
+mod trace_span;
+mod traced_http_client;
 mod u31;
 mod version;
 pub mod web_authn;
@@ -51,13 +64,20 @@ pub use msg_buffer::{MsgBuffer, MsgBufferStr, MsgBufferString};
 pub use msg_builder::{MsgBuilder, MsgBuilderInput, ReqBuilder, ResBuilder};
 pub use msg_data::{MsgData, MsgDataMut};
 pub use operation_mode::*;
+pub use otlp_json_exporter::OtlpJsonExporter;
+pub use priority::Priority;
 pub use protocol::Protocol;
 pub use request::Request;
 pub use response::Response;
 pub use router::{Router, RouterBuilder, RouterError, RouterMatch, RouterMatchParam};
 #[cfg(feature = "http-session")]
 pub use session::*;
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket"))]
+pub use shutdown_signal::ShutdownSignal;
 pub use status_code::StatusCode;
+pub use trace_context::TraceContext;
+pub use trace_span::{TraceSpan, TraceSpanKind};
+pub use traced_http_client::TracedHttpClient;
 pub use u31::U31;
 pub use version::Version;
 #[cfg(feature = "web-socket-server-framework")]
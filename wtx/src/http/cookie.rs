@@ -1,17 +1,20 @@
 mod cookie_error;
-#[cfg(feature = "http-session")]
+#[cfg(any(feature = "http-client-framework", feature = "http-session"))]
 pub(crate) mod cookie_generic;
-#[cfg(all(feature = "http2-server-framework", feature = "http-session"))]
+#[cfg(any(
+  feature = "http-client-framework",
+  all(feature = "http2-server-framework", feature = "http-session")
+))]
 pub(crate) mod cookie_str;
 mod same_site;
 
-#[cfg(feature = "http-session")]
+#[cfg(any(feature = "http-client-framework", feature = "http-session"))]
 use crate::calendar::CalendarToken;
 pub use cookie_error::CookieError;
 pub use same_site::SameSite;
 
-#[cfg(feature = "http-session")]
-static FMT1: &[CalendarToken] = &[
+#[cfg(any(feature = "http-client-framework", feature = "http-session"))]
+pub(crate) static FMT1: &[CalendarToken] = &[
   CalendarToken::AbbreviatedWeekdayName,
   CalendarToken::Comma,
   CalendarToken::Space,
@@ -1,12 +1,14 @@
 use crate::{
   calendar::{DateTime, Utc},
-  collections::{ArrayStringU8, Clear},
-  http::{
-    Header, Headers, KnownHeaderName,
-    cookie::{FMT1, SameSite},
-  },
+  collections::ArrayStringU8,
+  http::cookie::{FMT1, SameSite},
   misc::Lease,
 };
+#[cfg(feature = "http-session")]
+use crate::{
+  collections::Clear,
+  http::{Header, Headers, KnownHeaderName},
+};
 use core::{
   fmt::{Display, Formatter},
   time::Duration,
@@ -25,6 +27,7 @@ pub(crate) struct CookieGeneric<T, V> {
   pub(crate) value: V,
 }
 
+#[cfg(feature = "http-session")]
 impl<T, V> CookieGeneric<T, V> {
   pub(crate) fn delete(&mut self, headers: &mut Headers) -> crate::Result<()>
   where
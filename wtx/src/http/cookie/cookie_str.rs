@@ -15,10 +15,31 @@ pub(crate) struct CookieStr<'str> {
 
 impl<'str> CookieStr<'str> {
   /// Creates a new instance based on a sequence of bytes received from a request.
+  ///
+  /// Percent-encoded names and values are decoded into `vector`.
+  #[cfg(all(feature = "http2-server-framework", feature = "http-session"))]
   pub(crate) fn parse<'local_str, 'vector>(
     str: &'local_str str,
     vector: &'vector mut Vector<u8>,
   ) -> crate::Result<Self>
+  where
+    'local_str: 'str,
+    'vector: 'str,
+  {
+    Self::do_parse(str, Some(vector))
+  }
+
+  /// Creates a new instance based on the contents of a `Set-Cookie` header. Names and values are
+  /// kept as is.
+  #[cfg(feature = "http-client-framework")]
+  pub(crate) fn parse_encoded(str: &'str str) -> crate::Result<Self> {
+    Self::do_parse(str, None)
+  }
+
+  fn do_parse<'local_str, 'vector>(
+    str: &'local_str str,
+    vector: Option<&'vector mut Vector<u8>>,
+  ) -> crate::Result<Self>
   where
     'local_str: 'str,
     'vector: 'str,
@@ -36,29 +57,37 @@ impl<'str> CookieStr<'str> {
       if name.is_empty() {
         return Err(crate::Error::from(CookieError::MissingName));
       }
-      let before_name_len = vector.len();
-      let has_decoded_name = PercentDecode::new(name.as_bytes()).decode(vector)?;
-      let before_value_len = vector.len();
-      let has_decoded_value = PercentDecode::new(value.as_bytes()).decode(vector)?;
+      let (local_name, local_value) = match vector {
+        Some(elem) => {
+          let before_name_len = elem.len();
+          let has_decoded_name = PercentDecode::new(name.as_bytes()).decode(elem)?;
+          let before_value_len = elem.len();
+          let has_decoded_value = PercentDecode::new(value.as_bytes()).decode(elem)?;
+          let local_name = if has_decoded_name {
+            elem.get(before_name_len..before_value_len).unwrap_or_default()
+          } else {
+            name.as_bytes()
+          };
+          let local_value = if has_decoded_value {
+            // SAFETY: everything after before_value_len is ASCII percent-encoding
+            unsafe { str::from_utf8_unchecked(elem.get(before_value_len..).unwrap_or_default()) }
+          } else {
+            value
+          };
+          (local_name, local_value)
+        }
+        None => (name.as_bytes(), value),
+      };
       CookieGeneric {
         domain: "",
         expires: None,
         http_only: false,
         max_age: None,
-        name: ArrayStringU8::try_from(if has_decoded_name {
-          vector.get(before_name_len..before_value_len).unwrap_or_default()
-        } else {
-          name.as_bytes()
-        })?,
+        name: ArrayStringU8::try_from(local_name)?,
         path: "",
         same_site: None,
         secure: false,
-        value: if has_decoded_value {
-          // SAFETY: everything after before_value_len is ASCII percent-encoding
-          unsafe { str::from_utf8_unchecked(vector.get(before_value_len..).unwrap_or_default()) }
-        } else {
-          value
-        },
+        value: local_value,
       }
     };
 
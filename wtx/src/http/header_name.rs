@@ -190,18 +190,21 @@ create_statics! {
   Forwarded = "forwarded";
   From = "from";
   Host = "host";
+  Http2Settings = "http2-settings";
   IfMatch = "if-match";
   IfModifiedSince = "if-modified-since";
   IfNoneMatch = "if-none-match";
   IfRange = "if-range";
   IfUnmodifiedSince = "if-unmodified-since";
   KeepAlive = "keep-alive";
+  LastEventId = "last-event-id";
   LastModified = "last-modified";
   Link = "link";
   Location = "location";
   MaxForwards = "max-forwards";
   Origin = "origin";
   Pragma = "pragma";
+  Priority = "priority";
   ProxyAuthenticate = "proxy-authenticate";
   ProxyAuthorization = "proxy-authorization";
   ProxyConnection = "proxy-connection";
@@ -212,6 +215,7 @@ create_statics! {
   SecWebsocketAccept = "sec-websocket-accept";
   SecWebsocketExtensions = "sec-websocket-extensions";
   SecWebsocketKey = "sec-websocket-key";
+  SecWebsocketProtocol = "sec-websocket-protocol";
   SecWebsocketVersion = "sec-websocket-version";
   Server = "server";
   ServerTiming = "server-timing";
@@ -221,6 +225,7 @@ create_statics! {
   Te = "te";
   TimingAllowOrigin = "timing-allow-origin";
   Traceparent = "traceparent";
+  Tracestate = "tracestate";
   Trailer = "trailer";
   TransferEncoding = "transfer-encoding";
   Upgrade = "upgrade";
@@ -230,4 +235,5 @@ create_statics! {
   Warning = "warning";
   WwwAuthenticate = "www-authenticate";
   XCsrfToken = "x-csrf-token";
+  XRequestId = "x-request-id";
 }
@@ -1,45 +1,412 @@
 //! Structures used to construct a pool of HTTP connections
 
 mod http2_client_pool_builder;
+mod http2_client_pool_guard;
 mod http2_client_pool_resource;
+mod http2_client_pool_state;
 mod http2_rm;
 #[cfg(all(feature = "_integration-tests", test))]
 mod integration_tests;
 
 use crate::{
-  net::UriRef,
-  pool::{ResourceManager, SimplePool, SimplePoolGetElem, SimplePoolResource},
-  sync::AsyncMutexGuard,
+  calendar::Instant,
+  collections::Vector,
+  http::http2_client_pool::http2_client_pool_state::{
+    Http2ClientPoolState, PoolConn, PoolWaiters, Reservation,
+  },
+  http2::Http2ErrorCode,
+  net::{StreamWriter, UriRef},
+  pool::ResourceManager,
+  sync::{Arc, SyncMutex},
+  tls::TlsCtx,
+};
+#[cfg(feature = "web-socket")]
+use crate::{
+  http::Headers,
+  http2::{ClientStream, WebSocketOverClientStream},
+  rng::{SeedableRng as _, Xorshift64},
+};
+use alloc::string::String;
+use core::{
+  fmt::{Debug, Formatter},
+  future::poll_fn,
+  sync::atomic::Ordering,
+  task::{Poll, Waker},
+  time::Duration,
 };
 pub use http2_client_pool_builder::Http2ClientPoolBuilder;
+pub use http2_client_pool_guard::Http2ClientPoolGuard;
 pub use http2_client_pool_resource::Http2ClientPoolResource;
 pub use http2_rm::Http2RM;
 
-/// An optioned pool of different HTTP connections lazily constructed from different URIs.
+type Conns<RM> = Vector<Arc<PoolConn<<RM as ResourceManager>::Resource>>>;
+
+/// An optioned pool of HTTP/2 connections lazily constructed from different origins.
+///
+/// Requests of the same origin (scheme, host and port) are multiplexed on a single connection
+/// until the `MAX_CONCURRENT_STREAMS` parameter is reached and only then another connection is
+/// opened, as long as the per-origin and the global limits allow it. Otherwise, requests wait
+/// until streams are closed, reservations are released or connections are evicted.
 ///
-/// Currently supports only one domain with multiple connections.
-#[derive(Debug)]
+/// Connections without guards and streams are gracefully closed after the idle timeout or when
+/// another origin needs a place in a full pool.
 pub struct Http2ClientPool<AUX, EX, TCX>
 where
   Http2RM<AUX, EX, TCX>: ResourceManager,
 {
-  pool: SimplePool<Http2RM<AUX, EX, TCX>>,
+  idle_timeout: Duration,
+  len: usize,
+  origin_len: usize,
+  rm: Http2RM<AUX, EX, TCX>,
+  state: SyncMutex<Http2ClientPoolState<<Http2RM<AUX, EX, TCX> as ResourceManager>::Resource>>,
+  waiters: Arc<PoolWaiters>,
 }
 
-impl<AUX, EX, TCX, R> Http2ClientPool<AUX, EX, TCX>
+impl<AUX, EX, SW, TCX> Http2ClientPool<AUX, EX, TCX>
 where
-  Http2RM<AUX, EX, TCX>:
-    ResourceManager<CreateAux = str, Error = crate::Error, RecycleAux = str, Resource = R>,
+  SW: StreamWriter,
+  TCX: TlsCtx,
+  Http2RM<AUX, EX, TCX>: ResourceManager<
+      CreateAux = str,
+      Error = crate::Error,
+      RecycleAux = str,
+      Resource = Http2ClientPoolResource<AUX, SW, TCX>,
+    >,
 {
-  /// Returns a guard that contains the internal elements.
+  /// Returns a connection related to the origin of `uri` with at least one reserved stream.
   #[inline]
-  pub async fn lock<'this>(
-    &'this self,
+  pub async fn lock(
+    &self,
     uri: &UriRef<'_>,
-  ) -> crate::Result<SimplePoolGetElem<AsyncMutexGuard<'this, SimplePoolResource<R>>>>
-  where
-    R: 'this,
-  {
-    self.pool.get(uri.as_str(), uri.as_str()).await
+  ) -> crate::Result<Http2ClientPoolGuard<Http2ClientPoolResource<AUX, SW, TCX>>> {
+    let origin = origin(uri);
+    let mut conns = Vector::new();
+    let mut invalid = Vector::new();
+    loop {
+      let generation = self.waiters.generation();
+      let waker = poll_fn(|cx| Poll::Ready(cx.waker().clone())).await;
+      self.state.lock().stale(&mut conns, self.idle_timeout, Instant::new())?;
+      let _evicted = self.evict(&mut conns, usize::MAX, None).await?;
+      self.state.lock().conns(&origin, &mut conns, &mut invalid, |el| self.rm.is_invalid(el))?;
+      while let Some(conn) = invalid.pop() {
+        conn.resource.client.send_go_away(Http2ErrorCode::NoError).await;
+      }
+      while let Some(conn) = conns.pop() {
+        if !conn.resource.client.connection_state().is_open() {
+          continue;
+        }
+        let (active, limit) = conn.resource.client.streams_usage(Some(&waker)).await?;
+        if conn.try_reserve(limit.saturating_sub(active)) {
+          self.state.lock().touch(&origin, &conn, Instant::new());
+          conns.clear();
+          return Ok(Http2ClientPoolGuard::new(conn, Arc::clone(&self.waiters)));
+        }
+      }
+      let reservation = self.state.lock().reserve(self.len, &origin, self.origin_len)?;
+      match reservation {
+        Reservation::Connect => return self.connect(&origin).await,
+        Reservation::Full => {
+          self.state.lock().evictable(&origin, &mut conns)?;
+          if self.evict(&mut conns, 1, Some(&waker)).await? > 0 {
+            continue;
+          }
+        }
+        Reservation::Wait => {}
+      }
+      self.waiters.wait(generation).await;
+    }
+  }
+
+  /// Opens a WebSocket stream, as defined in RFC 8441, in a connection related to the origin of
+  /// `uri`.
+  ///
+  /// Tunnels are multiplexed alongside other requests and occupy a stream of the connection until
+  /// they are closed. Returns an error if servers don't enable `SETTINGS_ENABLE_CONNECT_PROTOCOL`.
+  #[cfg(feature = "web-socket")]
+  #[inline]
+  pub async fn web_socket(
+    &self,
+    enc_buffer: &mut Vector<u8>,
+    headers: &mut Headers,
+    no_masking: bool,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<WebSocketOverClientStream<ClientStream<SW, TCX>>> {
+    let stream = self.lock(uri).await?.client.stream().await?;
+    let rng = Xorshift64::from_simple_seed()?;
+    WebSocketOverClientStream::connect(enc_buffer, headers, no_masking, rng, stream, uri).await
+  }
+
+  async fn connect(
+    &self,
+    origin: &str,
+  ) -> crate::Result<Http2ClientPoolGuard<Http2ClientPoolResource<AUX, SW, TCX>>> {
+    let mut connecting =
+      Connecting { conn: None, origin, state: &self.state, waiters: &self.waiters };
+    let conn = Arc::new(PoolConn::new(self.rm.create(origin).await?));
+    let _ = conn.reserved.fetch_add(1, Ordering::AcqRel);
+    let guard = Http2ClientPoolGuard::new(Arc::clone(&conn), Arc::clone(&self.waiters));
+    connecting.conn = Some(conn);
+    drop(connecting);
+    Ok(guard)
+  }
+
+  // Closes up to `max` connections of `conns` that don't have active streams. Closing is
+  // graceful because remote peers don't need to wait for timeouts.
+  //
+  // `waker`, if any, is awakened when a stream of a connection that couldn't be evicted is
+  // closed.
+  async fn evict(
+    &self,
+    conns: &mut Conns<Http2RM<AUX, EX, TCX>>,
+    max: usize,
+    waker: Option<&Waker>,
+  ) -> crate::Result<usize> {
+    let mut evicted: usize = 0;
+    while let Some(conn) = conns.pop() {
+      if evicted >= max {
+        continue;
+      }
+      if conn.resource.client.streams_usage(waker).await?.0 > 0 || !self.state.lock().remove(&conn)
+      {
+        continue;
+      }
+      self.waiters.wake_all();
+      conn.resource.client.send_go_away(Http2ErrorCode::NoError).await;
+      evicted = evicted.wrapping_add(1);
+    }
+    Ok(evicted)
+  }
+}
+
+impl<AUX, EX, TCX> Debug for Http2ClientPool<AUX, EX, TCX>
+where
+  Http2RM<AUX, EX, TCX>: ResourceManager,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("Http2ClientPool")
+      .field("idle_timeout", &self.idle_timeout)
+      .field("len", &self.len)
+      .field("origin_len", &self.origin_len)
+      .finish_non_exhaustive()
+  }
+}
+
+// Releases the reservation of a new connection even if the creation is cancelled.
+struct Connecting<'any, R> {
+  conn: Option<Arc<PoolConn<R>>>,
+  origin: &'any str,
+  state: &'any SyncMutex<Http2ClientPoolState<R>>,
+  waiters: &'any PoolWaiters,
+}
+
+impl<R> Drop for Connecting<'_, R> {
+  fn drop(&mut self) {
+    let conn = self.conn.take();
+    let _rslt = self.state.lock().finish(self.origin, conn, Instant::new());
+    self.waiters.wake_all();
+  }
+}
+
+// Schemes and hosts are case-insensitive and missing ports are filled with the default ports of
+// their schemes.
+pub(crate) fn origin(uri: &UriRef<'_>) -> String {
+  let scheme = uri.scheme();
+  let port = uri.port().unwrap_or(if scheme.eq_ignore_ascii_case("http") { 80 } else { 443 });
+  let mut rslt = String::new();
+  rslt.push_str(scheme);
+  rslt.push_str("://");
+  rslt.push_str(uri.hostname());
+  rslt.make_ascii_lowercase();
+  rslt.push(':');
+  rslt.push_str(crate::codec::u16_string(port).as_str());
+  rslt
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    executor::{StdExecutor, StdRuntime},
+    http::{
+      HttpClient as _, HttpRecvParams, ReqBuilder, StatusCode,
+      http2_client_pool::{Http2ClientPoolBuilder, origin},
+    },
+    http2::{Http2, Http2Buffer},
+    net::{Stream as _, UriRef},
+    rng::{ChaCha20, CryptoSeedableRng as _, SeedableRng as _, Xorshift64},
+    tests::_uri,
+    tls::{TlsAcceptor, TlsConfig},
+  };
+  use alloc::string::String;
+  use std::{
+    io::{Read as _, Write as _},
+    net::{TcpListener, TcpStream},
+  };
+
+  // The listener acts as an HTTP proxy that forwards the tunnel to itself.
+  //
+  // FIXME(MIRI): socket support
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn connects_through_proxies() {
+    let runtime = StdRuntime::new();
+    let uri = _uri();
+    let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+    let server_jh = runtime
+      .spawn(async move {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut req = Vector::new();
+        while !req.ends_with(b"\r\n\r\n") {
+          let mut byte = [0];
+          stream.read_exact(&mut byte).unwrap();
+          req.push(byte[0]).unwrap();
+        }
+        stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap();
+        respond_ok(HttpRecvParams::with_optioned_params(), runtime, stream, 1).await;
+        req
+      })
+      .unwrap();
+    runtime.block_on(async {
+      let mut builder = Http2ClientPoolBuilder::new(
+        StdExecutor::default(),
+        1,
+        ChaCha20::from_std_random().unwrap(),
+        TlsConfig::plaintext(),
+      )
+      .unwrap();
+      builder.proxy_vars_mut().http_proxy = Some(String::from(uri.as_str()));
+      let pool = builder.build();
+      let req = ReqBuilder::get(UriRef::new("http://foo.com/bar")).into_request();
+      let res = pool.send_req_recv_res(&mut Vector::new(), req).await.unwrap();
+      assert_eq!(res.status_code, StatusCode::Ok);
+      let req = server_jh.await;
+      assert_eq!(req.as_slice(), b"CONNECT foo.com:80 HTTP/1.1\r\nHost: foo.com:80\r\n\r\n");
+    });
+  }
+
+  // FIXME(MIRI): socket support
+  #[cfg(all(feature = "dns", not(feature = "tokio")))]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn connects_with_dns_resolvers() {
+    use crate::net::dns::{DnsConfig, DnsResolver};
+    use core::net::{IpAddr, Ipv4Addr};
+
+    let runtime = StdRuntime::new();
+    let uri = _uri();
+    let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+    let server_jh = runtime
+      .spawn(async move {
+        let (stream, _) = listener.accept().unwrap();
+        respond_ok(HttpRecvParams::with_optioned_params(), runtime, stream, 1).await;
+      })
+      .unwrap();
+    runtime.block_on(async {
+      let mut builder = Http2ClientPoolBuilder::new(
+        StdExecutor::default(),
+        1,
+        ChaCha20::from_std_random().unwrap(),
+        TlsConfig::plaintext(),
+      )
+      .unwrap();
+      let mut config = DnsConfig::new();
+      config.hosts_mut().push((String::from("foo.com"), IpAddr::V4(Ipv4Addr::LOCALHOST))).unwrap();
+      *builder.dns_resolver_mut() =
+        Some(DnsResolver::new(config, ChaCha20::from_std_random().unwrap()));
+      let pool = builder.build();
+      let req_uri = alloc::format!("http://foo.com:{}/bar", uri.port().unwrap());
+      let req = ReqBuilder::get(UriRef::new(&req_uri)).into_request();
+      let res = pool.send_req_recv_res(&mut Vector::new(), req).await.unwrap();
+      assert_eq!(res.status_code, StatusCode::Ok);
+      server_jh.await;
+    });
+  }
+
+  // The second request only gets a stream after the response of the first one is received.
+  //
+  // FIXME(MIRI): socket support
+  #[cfg(not(feature = "tokio"))]
+  #[cfg_attr(miri, ignore)]
+  #[test]
+  fn waits_saturated_connections() {
+    use crate::sync::Arc;
+    use core::time::Duration;
+    use std::thread;
+
+    let runtime = StdRuntime::new();
+    let uri = _uri();
+    let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+    let server_jh = runtime
+      .spawn(async move {
+        let (stream, _) = listener.accept().unwrap();
+        let hrp = HttpRecvParams::with_optioned_params().set_max_concurrent_streams_num(1);
+        respond_ok(hrp, runtime, stream, 3).await;
+      })
+      .unwrap();
+    runtime.block_on(async {
+      let pool = Arc::new(
+        Http2ClientPoolBuilder::new(
+          StdExecutor::default(),
+          1,
+          ChaCha20::from_std_random().unwrap(),
+          TlsConfig::plaintext(),
+        )
+        .unwrap()
+        .build(),
+      );
+      let req = || ReqBuilder::get(uri.to_ref()).into_request();
+      // Receives the settings of the server
+      let _res = pool.send_req_recv_res(&mut Vector::new(), req()).await.unwrap();
+      let first_id = pool.send_req(&mut Vector::new(), req()).await.unwrap();
+      let local_pool = Arc::clone(&pool);
+      let local_uri = uri.clone();
+      let second_jh = runtime
+        .spawn(async move {
+          let local_req = ReqBuilder::get(local_uri.to_ref()).into_request();
+          local_pool.send_req_recv_res(&mut Vector::new(), local_req).await.unwrap().status_code
+        })
+        .unwrap();
+      thread::sleep(Duration::from_millis(100));
+      assert_eq!(pool.recv_res(first_id).await.unwrap().status_code, StatusCode::Ok);
+      assert_eq!(second_jh.await, StatusCode::Ok);
+      server_jh.await;
+    });
+  }
+
+  #[test]
+  fn normalizes_origins() {
+    assert_eq!(origin(&UriRef::new("HTTPS://Foo.com/bar")), "https://foo.com:443");
+    assert_eq!(origin(&UriRef::new("http://foo.com:8080/a?b=1")), "http://foo.com:8080");
+    assert_eq!(origin(&UriRef::new("http://user:pw@foo.com")), "http://foo.com:80");
+  }
+
+  // Responds the first `streams_num` streams of the connection one after another.
+  async fn respond_ok(
+    hrp: HttpRecvParams,
+    runtime: StdRuntime,
+    stream: TcpStream,
+    streams_num: usize,
+  ) {
+    let tls_stream =
+      TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+        .accept()
+        .await
+        .unwrap()
+        .tls_stream;
+    let (frame_reader, http2) = Http2::accept(
+      Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+      hrp,
+      tls_stream.into_split().unwrap(),
+    )
+    .await
+    .unwrap();
+    let _jh = runtime.spawn(frame_reader);
+    for _ in 0..streams_num {
+      let (mut stream, _) = http2.stream(|_, _| {}).await.unwrap().unwrap();
+      let (_, req_rrb) = stream.recv_req().await.unwrap();
+      let res = req_rrb.as_response(StatusCode::Ok);
+      let _ = stream.send_res(&mut Vector::new(), res).await.unwrap();
+    }
   }
 }
@@ -1,31 +1,44 @@
 use crate::{
   http::{
     HttpRecvParams,
-    http2_client_pool::{Http2ClientPool, Http2RM},
+    http2_client_pool::{
+      Http2ClientPool, Http2RM,
+      http2_client_pool_state::{Http2ClientPoolState, PoolWaiters},
+    },
     push_h2_alpn,
   },
-  net::TcpParams,
-  pool::{ResourceManager, SimplePool},
+  net::{TcpParams, proxy::ProxyVars},
+  pool::ResourceManager,
   rng::ChaCha20,
-  sync::{AsyncMutex, AtomicCell},
+  sync::{Arc, AsyncMutex, AtomicCell, SyncMutex},
   tls::TlsConfig,
 };
+use core::time::Duration;
 
 /// Allows the customization of parameters that control HTTP requests and responses.
 #[derive(Debug)]
 pub struct Http2ClientPoolBuilder<AUX, EX, TCX> {
   aux_fn: fn() -> AUX,
   disable_auto_sni: bool,
+  #[cfg(feature = "dns")]
+  dns_resolver: Option<crate::net::dns::DnsResolver<ChaCha20>>,
   executor: EX,
   hrp: HttpRecvParams,
+  idle_timeout: Duration,
   len: usize,
+  origin_len: usize,
+  proxy_vars: ProxyVars,
   rng: ChaCha20,
   tcp_params: TcpParams,
   tls_config: TlsConfig<TCX>,
 }
 
 impl<EX, TCX> Http2ClientPoolBuilder<(), EX, TCX> {
-  /// Creates a new builder with the maximum number of connections delimited by `len`.
+  /// Creates a new builder with the maximum number of connections of all origins delimited by
+  /// `len`. If `0`, then `len` will be stored as `1`.
+  ///
+  /// By default, each origin can use all connections and idle connections are closed after 90
+  /// seconds.
   ///
   /// The "h2" ALPN will always be pushed into the TLS configuration.
   #[inline]
@@ -39,9 +52,14 @@ impl<EX, TCX> Http2ClientPoolBuilder<(), EX, TCX> {
     Ok(Self {
       aux_fn: || {},
       disable_auto_sni: false,
+      #[cfg(feature = "dns")]
+      dns_resolver: None,
       executor,
       hrp: HttpRecvParams::with_optioned_params(),
-      len,
+      idle_timeout: Duration::from_secs(90),
+      len: len.max(1),
+      origin_len: len.max(1),
+      proxy_vars: ProxyVars::default(),
       rng,
       tcp_params: TcpParams::default(),
       tls_config,
@@ -71,12 +89,41 @@ impl<AUX, EX, TCX> Http2ClientPoolBuilder<AUX, EX, TCX> {
     &mut self.disable_auto_sni
   }
 
+  /// Resolver of the hosts of direct connections, which are then established with "Happy
+  /// Eyeballs". Defaults to `None`, i.e., hosts are resolved by the executor.
+  #[cfg(feature = "dns")]
+  #[inline]
+  pub const fn dns_resolver_mut(&mut self) -> &mut Option<crate::net::dns::DnsResolver<ChaCha20>> {
+    &mut self.dns_resolver
+  }
+
   /// See [`HttpRecvParams`].
   #[inline]
   pub const fn http_conn_params_mut(&mut self) -> &mut HttpRecvParams {
     &mut self.hrp
   }
 
+  /// Connections without guards and streams are closed after this period of inactivity.
+  #[inline]
+  pub const fn idle_timeout_mut(&mut self) -> &mut Duration {
+    &mut self.idle_timeout
+  }
+
+  /// Maximum number of connections of a single origin. Values greater than the global limit
+  /// have no effect.
+  #[inline]
+  pub const fn origin_len_mut(&mut self) -> &mut usize {
+    &mut self.origin_len
+  }
+
+  /// Proxies that tunnel the connections of each origin. Defaults to no proxies.
+  ///
+  /// [`ProxyVars`] can also be extracted from environment variables.
+  #[inline]
+  pub const fn proxy_vars_mut(&mut self) -> &mut ProxyVars {
+    &mut self.proxy_vars
+  }
+
   /// See [`ChaCha20`].
   #[inline]
   pub const fn rng_mut(&mut self) -> &mut ChaCha20 {
@@ -89,9 +136,14 @@ impl<AUX, EX, TCX> Http2ClientPoolBuilder<AUX, EX, TCX> {
     Http2ClientPoolBuilder {
       aux_fn: value,
       disable_auto_sni: self.disable_auto_sni,
+      #[cfg(feature = "dns")]
+      dns_resolver: self.dns_resolver,
       executor: self.executor,
       hrp: self.hrp,
+      idle_timeout: self.idle_timeout,
       len: self.len,
+      origin_len: self.origin_len,
+      proxy_vars: self.proxy_vars,
       rng: self.rng,
       tcp_params: self.tcp_params,
       tls_config: self.tls_config,
@@ -113,18 +165,23 @@ where
   #[inline]
   pub fn build(self) -> Http2ClientPool<AUX, EX, TCX> {
     Http2ClientPool {
-      pool: SimplePool::new(
-        self.len,
-        Http2RM {
-          aux_fn: self.aux_fn,
-          disable_auto_sni: self.disable_auto_sni,
-          executor: self.executor,
-          hrp: self.hrp,
-          rng: AtomicCell::new(self.rng),
-          tcp_params: self.tcp_params,
-          tls_config: AsyncMutex::new(self.tls_config),
-        },
-      ),
+      idle_timeout: self.idle_timeout,
+      len: self.len,
+      origin_len: self.origin_len.max(1),
+      rm: Http2RM {
+        aux_fn: self.aux_fn,
+        disable_auto_sni: self.disable_auto_sni,
+        #[cfg(feature = "dns")]
+        dns_resolver: self.dns_resolver,
+        executor: self.executor,
+        hrp: self.hrp,
+        proxy_vars: self.proxy_vars,
+        rng: AtomicCell::new(self.rng),
+        tcp_params: self.tcp_params,
+        tls_config: AsyncMutex::new(self.tls_config),
+      },
+      state: SyncMutex::new(Http2ClientPoolState::new()),
+      waiters: Arc::new(PoolWaiters::new()),
     }
   }
 }
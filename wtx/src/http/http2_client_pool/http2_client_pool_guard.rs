@@ -0,0 +1,48 @@
+use crate::{
+  http::http2_client_pool::http2_client_pool_state::{PoolConn, PoolWaiters},
+  misc::Lease,
+  sync::Arc,
+};
+use core::{ops::Deref, sync::atomic::Ordering};
+
+/// Connection of [`crate::http::http2_client_pool::Http2ClientPool`] with a stream reserved to the
+/// holder of this guard.
+///
+/// The reservation is released once the guard is dropped, which means that streams should be
+/// opened before that.
+#[derive(Debug)]
+pub struct Http2ClientPoolGuard<R> {
+  conn: Arc<PoolConn<R>>,
+  waiters: Arc<PoolWaiters>,
+}
+
+impl<R> Http2ClientPoolGuard<R> {
+  /// `conn` must have a stream reserved to the returned instance.
+  pub(crate) const fn new(conn: Arc<PoolConn<R>>, waiters: Arc<PoolWaiters>) -> Self {
+    Self { conn, waiters }
+  }
+}
+
+impl<R> Deref for Http2ClientPoolGuard<R> {
+  type Target = R;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.conn.resource
+  }
+}
+
+impl<R> Drop for Http2ClientPoolGuard<R> {
+  #[inline]
+  fn drop(&mut self) {
+    let _ = self.conn.reserved.fetch_sub(1, Ordering::AcqRel);
+    self.waiters.wake_all();
+  }
+}
+
+impl<R> Lease<R> for Http2ClientPoolGuard<R> {
+  #[inline]
+  fn lease(&self) -> &R {
+    &self.conn.resource
+  }
+}
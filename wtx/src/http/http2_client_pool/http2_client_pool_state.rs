@@ -0,0 +1,373 @@
+use crate::{
+  calendar::Instant,
+  collections::Vector,
+  sync::{Arc, AtomicU32, SyncMutex},
+};
+use alloc::string::String;
+use core::{
+  future::poll_fn,
+  mem,
+  sync::atomic::Ordering,
+  task::{Poll, Waker},
+  time::Duration,
+};
+
+/// Connection shared by all the requests of an origin.
+#[derive(Debug)]
+pub(crate) struct PoolConn<R> {
+  /// Streams promised to guards that weren't necessarily opened yet.
+  pub(crate) reserved: AtomicU32,
+  pub(crate) resource: R,
+}
+
+impl<R> PoolConn<R> {
+  pub(crate) const fn new(resource: R) -> Self {
+    Self { reserved: AtomicU32::new(0), resource }
+  }
+
+  /// Reserves a stream if the number of previous reservations is less than `available`.
+  pub(crate) fn try_reserve(&self, available: u32) -> bool {
+    let mut current = self.reserved.load(Ordering::Acquire);
+    loop {
+      if current >= available {
+        return false;
+      }
+      let new = current.wrapping_add(1);
+      match self.reserved.compare_exchange_weak(current, new, Ordering::AcqRel, Ordering::Acquire) {
+        Ok(_) => return true,
+        Err(elem) => current = elem,
+      }
+    }
+  }
+}
+
+/// Tasks waiting for changes in the pool like released reservations or finished connections.
+#[derive(Debug)]
+pub(crate) struct PoolWaiters {
+  list: SyncMutex<(u64, Vector<Waker>)>,
+}
+
+impl PoolWaiters {
+  pub(crate) const fn new() -> Self {
+    Self { list: SyncMutex::new((0, Vector::new())) }
+  }
+
+  /// Number of notifications sent so far.
+  pub(crate) fn generation(&self) -> u64 {
+    self.list.lock().0
+  }
+
+  /// Resolves after the first notification sent after `generation` or after any other wake-up of
+  /// the current task.
+  pub(crate) async fn wait(&self, generation: u64) {
+    let mut is_registered = false;
+    poll_fn(|cx| {
+      if is_registered {
+        return Poll::Ready(());
+      }
+      let mut lock = self.list.lock();
+      if lock.0 != generation {
+        return Poll::Ready(());
+      }
+      if lock.1.push(cx.waker().clone()).is_err() {
+        cx.waker().wake_by_ref();
+      }
+      is_registered = true;
+      Poll::Pending
+    })
+    .await;
+  }
+
+  pub(crate) fn wake_all(&self) {
+    let wakers = {
+      let mut lock = self.list.lock();
+      lock.0 = lock.0.wrapping_add(1);
+      mem::take(&mut lock.1)
+    };
+    for waker in wakers {
+      waker.wake();
+    }
+  }
+}
+
+/// Outcome of [`Http2ClientPoolState::reserve`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum Reservation {
+  /// A new connection can be created.
+  Connect,
+  /// The global limit was reached. Connections of other origins should be evicted.
+  Full,
+  /// The origin limit was reached or another connection is being created for the same origin.
+  Wait,
+}
+
+/// Connections grouped by origins.
+#[derive(Debug)]
+pub(crate) struct Http2ClientPoolState<R> {
+  conns_len: usize,
+  origins: Vector<PoolOrigin<R>>,
+}
+
+impl<R> Http2ClientPoolState<R> {
+  pub(crate) const fn new() -> Self {
+    Self { conns_len: 0, origins: Vector::new() }
+  }
+
+  /// Removes invalid connections of `origin` into `invalid` and pushes the remaining ones into
+  /// `buffer`.
+  pub(crate) fn conns(
+    &mut self,
+    origin: &str,
+    buffer: &mut Vector<Arc<PoolConn<R>>>,
+    invalid: &mut Vector<Arc<PoolConn<R>>>,
+    is_invalid: impl Fn(&R) -> bool,
+  ) -> crate::Result<()> {
+    let Some(po_idx) = self.origins.iter().position(|el| el.origin == origin) else {
+      return Ok(());
+    };
+    let Some(po) = self.origins.get_mut(po_idx) else {
+      return Ok(());
+    };
+    let mut idx: usize = 0;
+    while let Some(entry) = po.entries.get(idx) {
+      if is_invalid(&entry.conn.resource) {
+        if let Some(elem) = po.entries.remove(idx) {
+          invalid.push(elem.conn)?;
+        }
+        self.conns_len = self.conns_len.wrapping_sub(1);
+      } else {
+        buffer.push(Arc::clone(&entry.conn))?;
+        idx = idx.wrapping_add(1);
+      }
+    }
+    if po.entries.is_empty() && !po.is_connecting {
+      drop(self.origins.remove(po_idx));
+    }
+    Ok(())
+  }
+
+  /// Pushes the connections of other origins that don't have guards into `buffer`, from the
+  /// most recently used to the least recently used.
+  pub(crate) fn evictable(
+    &self,
+    origin: &str,
+    buffer: &mut Vector<Arc<PoolConn<R>>>,
+  ) -> crate::Result<()> {
+    let mut entries = Vector::new();
+    for po in self.origins.iter().filter(|el| el.origin != origin) {
+      for entry in po.entries.iter().filter(|el| Arc::strong_count(&el.conn) == 1) {
+        entries.push(entry)?;
+      }
+    }
+    entries.sort_by_key(|el| core::cmp::Reverse(el.last_used));
+    for entry in entries {
+      buffer.push(Arc::clone(&entry.conn))?;
+    }
+    Ok(())
+  }
+
+  /// Finishes a reservation made by [`Self::reserve`]. `None` indicates a connection failure.
+  pub(crate) fn finish(
+    &mut self,
+    origin: &str,
+    conn: Option<Arc<PoolConn<R>>>,
+    now: Instant,
+  ) -> crate::Result<()> {
+    let Some(po_idx) = self.origins.iter().position(|el| el.origin == origin) else {
+      return Ok(());
+    };
+    let Some(po) = self.origins.get_mut(po_idx) else {
+      return Ok(());
+    };
+    po.is_connecting = false;
+    if let Some(elem) = conn {
+      po.entries.push(PoolEntry { conn: elem, last_used: now })?;
+    } else {
+      self.conns_len = self.conns_len.wrapping_sub(1);
+      if po.entries.is_empty() {
+        drop(self.origins.remove(po_idx));
+      }
+    }
+    Ok(())
+  }
+
+  /// Removes `conn` if there are no other references besides the pool and the caller.
+  pub(crate) fn remove(&mut self, conn: &Arc<PoolConn<R>>) -> bool {
+    if Arc::strong_count(conn) > 2 {
+      return false;
+    }
+    let mut iter = self.origins.iter().enumerate();
+    let Some((po_idx, idx)) = iter.find_map(|(po_idx, po)| {
+      Some((po_idx, po.entries.iter().position(|el| Arc::ptr_eq(&el.conn, conn))?))
+    }) else {
+      return false;
+    };
+    let Some(po) = self.origins.get_mut(po_idx) else {
+      return false;
+    };
+    drop(po.entries.remove(idx));
+    self.conns_len = self.conns_len.wrapping_sub(1);
+    if po.entries.is_empty() && !po.is_connecting {
+      drop(self.origins.remove(po_idx));
+    }
+    true
+  }
+
+  /// Tries to reserve the creation of a new connection for `origin`.
+  pub(crate) fn reserve(
+    &mut self,
+    len: usize,
+    origin: &str,
+    origin_len: usize,
+  ) -> crate::Result<Reservation> {
+    // Unknown origins are only stored when a connection is going to be created.
+    let po_opt = self.origins.iter_mut().find(|el| el.origin == origin);
+    if let Some(po) = &po_opt
+      && (po.is_connecting || po.entries.len() >= origin_len)
+    {
+      return Ok(Reservation::Wait);
+    }
+    if self.conns_len >= len {
+      return Ok(Reservation::Full);
+    }
+    if let Some(po) = po_opt {
+      po.is_connecting = true;
+    } else {
+      self.origins.push(PoolOrigin {
+        entries: Vector::new(),
+        is_connecting: true,
+        origin: String::from(origin),
+      })?;
+    }
+    self.conns_len = self.conns_len.wrapping_add(1);
+    Ok(Reservation::Connect)
+  }
+
+  /// Pushes connections without guards that weren't used for at least `idle_timeout` into
+  /// `buffer`.
+  pub(crate) fn stale(
+    &self,
+    buffer: &mut Vector<Arc<PoolConn<R>>>,
+    idle_timeout: Duration,
+    now: Instant,
+  ) -> crate::Result<()> {
+    for po in &self.origins {
+      for entry in &po.entries {
+        let elapsed = now.duration_since(entry.last_used).unwrap_or_default();
+        if Arc::strong_count(&entry.conn) == 1 && elapsed >= idle_timeout {
+          buffer.push(Arc::clone(&entry.conn))?;
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// Updates the last time `conn` was used.
+  pub(crate) fn touch(&mut self, origin: &str, conn: &Arc<PoolConn<R>>, now: Instant) {
+    let Some(po) = self.origins.iter_mut().find(|el| el.origin == origin) else {
+      return;
+    };
+    if let Some(entry) = po.entries.iter_mut().find(|el| Arc::ptr_eq(&el.conn, conn)) {
+      entry.last_used = now;
+    }
+  }
+}
+
+#[derive(Debug)]
+struct PoolEntry<R> {
+  conn: Arc<PoolConn<R>>,
+  last_used: Instant,
+}
+
+#[derive(Debug)]
+struct PoolOrigin<R> {
+  entries: Vector<PoolEntry<R>>,
+  is_connecting: bool,
+  origin: String,
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    calendar::Instant,
+    collections::Vector,
+    executor::StdRuntime,
+    http::http2_client_pool::http2_client_pool_state::{
+      Http2ClientPoolState, PoolConn, PoolWaiters, Reservation,
+    },
+    sync::Arc,
+  };
+  use core::time::Duration;
+
+  const A: &str = "https://a:443";
+  const B: &str = "https://b:443";
+
+  #[test]
+  fn evicts_stale_and_invalid_connections() {
+    let now = Instant::new();
+    let mut buffer = Vector::new();
+    let mut state = Http2ClientPoolState::new();
+    assert_eq!(state.reserve(4, A, 4).unwrap(), Reservation::Connect);
+    state
+      .finish(A, Some(Arc::new(PoolConn::new(1))), now.sub(Duration::from_secs(10)).unwrap())
+      .unwrap();
+    assert_eq!(state.reserve(4, A, 4).unwrap(), Reservation::Connect);
+    state.finish(A, Some(Arc::new(PoolConn::new(2))), now).unwrap();
+    state.stale(&mut buffer, Duration::from_secs(5), now).unwrap();
+    assert_eq!((buffer.len(), buffer.first().map(|el| el.resource)), (1, Some(1)));
+    let conn = buffer.pop().unwrap();
+    assert!(state.remove(&conn));
+    let mut invalid = Vector::new();
+    state.conns(A, &mut buffer, &mut invalid, |el| *el == 2).unwrap();
+    assert!(buffer.is_empty());
+    assert_eq!((invalid.len(), invalid.first().map(|el| el.resource)), (1, Some(2)));
+    assert_eq!(state.conns_len, 0);
+  }
+
+  #[test]
+  fn only_stores_origins_of_reserved_connections() {
+    let mut state = Http2ClientPoolState::<()>::new();
+    assert_eq!(state.reserve(1, A, 1).unwrap(), Reservation::Connect);
+    assert_eq!(state.reserve(1, B, 1).unwrap(), Reservation::Full);
+    assert_eq!(state.reserve(1, A, 1).unwrap(), Reservation::Wait);
+    assert_eq!(state.origins.len(), 1);
+    state.finish(A, None, Instant::new()).unwrap();
+    assert!(state.origins.is_empty());
+  }
+
+  #[test]
+  fn notifies_waiters() {
+    let waiters = PoolWaiters::new();
+    let generation = waiters.generation();
+    waiters.wake_all();
+    assert_ne!(waiters.generation(), generation);
+    StdRuntime::new().block_on(waiters.wait(generation));
+  }
+
+  #[test]
+  fn reserves_available_streams() {
+    let conn = PoolConn::new(());
+    assert!(conn.try_reserve(2));
+    assert!(conn.try_reserve(2));
+    assert!(!conn.try_reserve(2));
+  }
+
+  #[test]
+  fn respects_limits() {
+    let now = Instant::new();
+    let mut buffer = Vector::new();
+    let mut state = Http2ClientPoolState::new();
+    assert_eq!(state.reserve(2, A, 1).unwrap(), Reservation::Connect);
+    assert_eq!(state.reserve(2, A, 1).unwrap(), Reservation::Wait);
+    state.finish(A, Some(Arc::new(PoolConn::new(1))), now).unwrap();
+    assert_eq!(state.reserve(2, A, 1).unwrap(), Reservation::Wait);
+    assert_eq!(state.reserve(2, B, 1).unwrap(), Reservation::Connect);
+    state.finish(B, None, now).unwrap();
+    assert_eq!(state.reserve(1, B, 1).unwrap(), Reservation::Full);
+    state.evictable(B, &mut buffer).unwrap();
+    assert_eq!((buffer.len(), buffer.first().map(|el| el.resource)), (1, Some(1)));
+    let conn = buffer.pop().unwrap();
+    assert!(state.remove(&conn));
+    assert_eq!(state.reserve(1, B, 1).unwrap(), Reservation::Connect);
+  }
+}
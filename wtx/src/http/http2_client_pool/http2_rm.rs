@@ -2,7 +2,10 @@ use crate::{
   executor::Executor,
   http::{HttpRecvParams, http2_client_pool::Http2ClientPoolResource},
   http2::{Http2, Http2Buffer},
-  net::{Stream, StreamReader, StreamWriter, TcpParams, Uri, UriRef},
+  net::{
+    Stream, StreamReader, StreamWriter, TcpParams, Uri, UriRef,
+    proxy::{ProxyStream, ProxyVars},
+  },
   pool::ResourceManager,
   rng::ChaCha20,
   sync::{AsyncMutex, AtomicCell},
@@ -15,8 +18,11 @@ use core::fmt::Debug;
 pub struct Http2RM<AUX, EX, TCX> {
   pub(crate) aux_fn: fn() -> AUX,
   pub(crate) disable_auto_sni: bool,
+  #[cfg(feature = "dns")]
+  pub(crate) dns_resolver: Option<crate::net::dns::DnsResolver<ChaCha20>>,
   pub(crate) executor: EX,
   pub(crate) hrp: HttpRecvParams,
+  pub(crate) proxy_vars: ProxyVars,
   pub(crate) rng: AtomicCell<ChaCha20>,
   pub(crate) tcp_params: TcpParams,
   pub(crate) tls_config: AsyncMutex<TlsConfig<TCX>>,
@@ -27,21 +33,31 @@ where
   EX: Executor,
   TCX: TlsCtx,
 {
-  async fn tls_stream(&self, aux: &str) -> crate::Result<TlsStream<EX::TcpStream, TCX, true>> {
+  async fn tls_stream(
+    &self,
+    aux: &str,
+  ) -> crate::Result<TlsStream<ProxyStream<EX::TcpStream>, TCX, true>> {
     let uri = UriRef::new(aux);
+    let proxy = self.proxy_vars.proxy(&uri)?;
     let mut tls_config = self.tls_config.lock().await;
     if !self.disable_auto_sni {
       push_server_name(&mut tls_config, &uri)?;
     }
-    Ok(
-      TlsConnectorBuilder::new(EX::default(), uri)
-        .set_tcp_params(self.tcp_params)
-        .build(&*tls_config, &self.rng)
-        .await?
-        .connect()
-        .await?
-        .tls_stream,
-    )
+    let builder = TlsConnectorBuilder::new(EX::default(), uri).set_tcp_params(self.tcp_params);
+    let tls_connector = cfg_select! {
+      feature = "dns" => {
+        builder
+          .build_with_proxy_and_resolver(
+            &*tls_config,
+            proxy.as_ref(),
+            self.dns_resolver.as_ref(),
+            &self.rng,
+          )
+          .await?
+      }
+      _ => builder.build_with_proxy(&*tls_config, proxy.as_ref(), &self.rng).await?,
+    };
+    Ok(tls_connector.connect().await?.tls_stream)
   }
 }
 
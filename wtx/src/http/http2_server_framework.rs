@@ -1,10 +1,9 @@
 //! Tools and libraries that make it easier to write, maintain, and scale web applications.
 
-#![expect(clippy::infinite_loop, reason = "expected behavior of a server")]
-
 #[macro_use]
 mod macros;
 
+mod access_log_middleware;
 mod cors_middleware;
 mod dyn_params;
 mod endpoint;
@@ -13,23 +12,33 @@ mod http2_server_framework_error;
 mod http_router;
 mod json_reply;
 mod methods;
+#[cfg(feature = "metrics")]
+mod metrics_middleware;
+#[cfg(feature = "metrics")]
+mod metrics_reply;
 mod middleware;
 mod path;
 mod path_params;
 mod redirect;
 mod res_finalizer;
 mod route_match;
+mod sse_event;
+mod sse_stream;
 mod state;
 #[cfg(test)]
 mod tests;
+mod trace_context_middleware;
 mod verbatim_params;
 
+#[cfg(feature = "http2-h2c")]
+use crate::misc::Either;
 use crate::{
   collections::ArrayVectorCopy,
   executor::{Executor, Runtime as _},
   http::{
     AutoStream, HttpRecvParams, ManualStream, MsgBufferString, OperationMode, Request, Response,
-    push_h2_alpn,
+    ShutdownSignal, push_h2_alpn,
+    shutdown_signal::{InFlight, InFlightGuard},
   },
   http2::{Http2, Http2Buffer, Http2ErrorCode, Http2RecvStatus, ServerStream},
   net::{Stream, StreamReader, StreamWriter, TcpListener as _, TcpParams, TcpStream as _, Uri},
@@ -37,7 +46,10 @@ use crate::{
   sync::Arc,
   tls::{TlsAcceptor, TlsConfig, TlsCtx, TlsCtxSk},
 };
-use core::{mem, net::IpAddr, num::NonZeroUsize};
+pub use access_log_middleware::{
+  AccessLogAux, AccessLogEntry, AccessLogFormat, AccessLogMiddleware, AccessLogSink,
+};
+use core::{mem, net::IpAddr, num::NonZeroUsize, time::Duration};
 pub use cors_middleware::{CorsMiddleware, OriginResponse};
 pub use dyn_params::DynParams;
 pub use endpoint::Endpoint;
@@ -52,15 +64,23 @@ pub use methods::{
   patch::{Patch, patch},
   post::{Post, post},
   put::{Put, put},
+  sse::{Sse, sse},
   web_socket::{WebSocket, web_socket},
 };
+#[cfg(feature = "metrics")]
+pub use metrics_middleware::{MetricsAux, MetricsMiddleware};
+#[cfg(feature = "metrics")]
+pub use metrics_reply::MetricsReply;
 pub use middleware::Middleware;
 pub use path::Path;
 pub use path_params::PathParams;
 pub use redirect::Redirect;
 pub use res_finalizer::ResFinalizer;
 pub use route_match::RouteMatch;
+pub use sse_event::SseEvent;
+pub use sse_stream::SseStream;
 pub use state::{State, StateClean, StateGeneric, StateTest};
+pub use trace_context_middleware::TraceContextMiddleware;
 pub use verbatim_params::VerbatimParams;
 
 type ConnRsltTy<EX, TCX, ER> = Option<(
@@ -72,6 +92,9 @@ type LocalStream<EX, TCX> =
 type WriteHalf<EX> = <<EX as Executor>::TcpStream as Stream>::WriteHalfOwned;
 
 /// HTTP/2 Server Framework
+///
+/// With the `http2-h2c` feature, cleartext connections are accepted either with prior knowledge
+/// or with an HTTP/1.1 upgrade request.
 #[derive(Debug)]
 pub struct Http2ServerFramework<DA, EC, EX, RC, RNG, TCX> {
   data: DA,
@@ -81,6 +104,8 @@ pub struct Http2ServerFramework<DA, EC, EX, RC, RNG, TCX> {
   local_runtime_cb: RC,
   local_runtimes: Option<NonZeroUsize>,
   rng: RNG,
+  shutdown_signal: ShutdownSignal,
+  shutdown_timeout: Duration,
   tcp_params: TcpParams,
   tls_config: Arc<TlsConfig<TCX>>,
 }
@@ -114,6 +139,8 @@ where
       local_runtime_cb,
       local_runtimes: None,
       rng,
+      shutdown_signal: ShutdownSignal::new(),
+      shutdown_timeout: Duration::from_secs(30),
       tcp_params: TcpParams::default(),
       tls_config: tls_config.into(),
     })
@@ -151,6 +178,16 @@ impl<DA, EC, EX, RC, RNG, TCX> Http2ServerFramework<DA, EC, EX, RC, RNG, TCX> {
     &mut self.rng
   }
 
+  /// Handle that stops the server when triggered.
+  ///
+  /// New connections are no longer accepted, HTTP/2 connections receive a GOAWAY frame and
+  /// running methods return after all in-flight streams finish or after the duration specified
+  /// by [`Self::set_shutdown_timeout`].
+  #[inline]
+  pub fn shutdown_signal(&self) -> ShutdownSignal {
+    self.shutdown_signal.clone()
+  }
+
   /// Shared data that is cloned across connections and streams.
   #[inline]
   pub fn set_data<_DA>(self, value: _DA) -> Http2ServerFramework<_DA, EC, EX, RC, RNG, TCX> {
@@ -162,6 +199,8 @@ impl<DA, EC, EX, RC, RNG, TCX> Http2ServerFramework<DA, EC, EX, RC, RNG, TCX> {
       local_runtime_cb: self.local_runtime_cb,
       local_runtimes: self.local_runtimes,
       rng: self.rng,
+      shutdown_signal: self.shutdown_signal,
+      shutdown_timeout: self.shutdown_timeout,
       tcp_params: self.tcp_params,
       tls_config: self.tls_config,
     }
@@ -178,6 +217,8 @@ impl<DA, EC, EX, RC, RNG, TCX> Http2ServerFramework<DA, EC, EX, RC, RNG, TCX> {
       local_runtime_cb: self.local_runtime_cb,
       local_runtimes: self.local_runtimes,
       rng: self.rng,
+      shutdown_signal: self.shutdown_signal,
+      shutdown_timeout: self.shutdown_timeout,
       tcp_params: self.tcp_params,
       tls_config: self.tls_config,
     }
@@ -205,6 +246,8 @@ impl<DA, EC, EX, RC, RNG, TCX> Http2ServerFramework<DA, EC, EX, RC, RNG, TCX> {
       local_runtime_cb: value,
       local_runtimes: self.local_runtimes,
       rng: self.rng,
+      shutdown_signal: self.shutdown_signal,
+      shutdown_timeout: self.shutdown_timeout,
       tcp_params: self.tcp_params,
       tls_config: self.tls_config,
     }
@@ -220,6 +263,15 @@ impl<DA, EC, EX, RC, RNG, TCX> Http2ServerFramework<DA, EC, EX, RC, RNG, TCX> {
     self
   }
 
+  /// Maximum amount of time spent waiting for in-flight streams after a shutdown. Defaults to 30
+  /// seconds.
+  #[inline]
+  #[must_use]
+  pub const fn set_shutdown_timeout(mut self, value: Duration) -> Self {
+    self.shutdown_timeout = value;
+    self
+  }
+
   /// See [`TcpParams`].
   #[inline]
   #[must_use]
@@ -275,18 +327,22 @@ where
     <<EX::TcpStream as Stream>::WriteHalfOwned as StreamWriter>::write_all_vectored(..): Send,
   {
     let http_router = Arc::new(hr);
+    let in_flight = InFlight::new();
     let uri = Uri::new(addr);
     let listener = EX::TcpListener::bind(uri.hostname_with_implied_port(), self.tcp_params).await?;
     let xorshift = &mut Xorshift64::from_simple_seed()?;
     loop {
-      let Ok(cp) = conn_params(
+      let cp = match conn_params(
         (&self.data, &self.error_cb, &self.executor, self.hrc),
         (&mut self.rng, self.tcp_params, &self.tls_config),
         (&http_router, &listener, xorshift),
+        (&in_flight, &self.shutdown_signal, self.shutdown_timeout),
       )
       .await
-      else {
-        continue;
+      {
+        Ok(Some(elem)) => elem,
+        Ok(None) => break,
+        Err(_) => continue,
       };
       let conn_fut = async move {
         let fut = http2::<EX, _, _>(cp.hrc, cp.rng, cp.stream, cp.tls_config, cp.xorshift);
@@ -297,11 +353,18 @@ where
           }
           Ok(elem) => elem,
         };
-        let _frame_reader_jh = cp.executor.spawn(frame_reader);
+        let frame_reader_jh = cp.executor.spawn(frame_reader);
+        let conn_streams = InFlight::new();
+        let mut is_draining = false;
         loop {
-          let (server_stream, headers_aux, opt) = match conn_rslt::<ER, EX, TCX>(
-            http2.stream(|req, _| stream_cb::<_, _, _, EX, _, _>(&cp.http_router, req)).await,
-          ) {
+          let (server_stream, headers_aux, opt) = match next_stream::<_, _, _, EX, _, _>(
+            &http2,
+            &cp.http_router,
+            &mut is_draining,
+            &cp.shutdown_signal,
+          )
+          .await
+          {
             Ok(Some(el)) => el,
             Ok(None) => break,
             Err(err) => {
@@ -312,6 +375,7 @@ where
           };
           let stream_data = cp.data.clone();
           let stream_error_cb = cp.error_cb.clone();
+          let stream_guards = (cp.in_flight.guard(), conn_streams.guard());
           let stream_http_router = cp.http_router.clone();
           let _stream_jh = cp.executor.spawn(stream_fut::<DA, EC, EN, ER, EX, M, TCX>(
             headers_aux,
@@ -320,12 +384,25 @@ where
             server_stream,
             stream_data,
             stream_error_cb,
+            stream_guards,
             stream_http_router,
           ));
         }
+        close_conn::<EX, TCX>(
+          &conn_streams,
+          frame_reader_jh,
+          &http2,
+          is_draining,
+          cp.shutdown_timeout,
+        )
+        .await;
+        // Only released after the connection is closed
+        drop(cp.conn_guard);
       };
       let _conn_jh = self.executor.spawn(conn_fut);
     }
+    let _is_idle = in_flight.wait_idle(self.shutdown_timeout).await?;
+    Ok(())
   }
 
   /// Starts the server using a runtime-per-thread architecture.
@@ -336,6 +413,7 @@ where
   /// You must call this method inside the main thread to allow the interruption of the remaining
   /// threads once an error arises.
   #[cfg(feature = "std")]
+  #[expect(clippy::too_many_lines, reason = "variables are highly coupled")]
   #[inline]
   pub fn run_in_threads<EN, M>(
     mut self,
@@ -363,6 +441,7 @@ where
       std::thread::available_parallelism().map_err(crate::Error::from)?.get()
     };
     let http_router = Arc::new(hr);
+    let in_flight = InFlight::new();
     let mut join_handles = Vector::<std::thread::JoinHandle<Result<(), ER>>>::new();
     for _ in 0..runtimes {
       let thread_data = self.data.clone();
@@ -370,8 +449,11 @@ where
       let thread_executor = self.executor.clone();
       let thread_hrc = self.hrc;
       let thread_http_router = http_router.clone();
+      let thread_in_flight = in_flight.clone();
       let thread_local_runtime_cb = self.local_runtime_cb.clone();
       let mut thread_rng = RNG::from_crypto_rng(&mut self.rng)?;
+      let thread_shutdown_signal = self.shutdown_signal.clone();
+      let thread_shutdown_timeout = self.shutdown_timeout;
       let thread_tcp_params = self.tcp_params;
       let thread_tls_config = self.tls_config.clone();
       let thread_uri = Uri::new(String::from(addr));
@@ -382,14 +464,17 @@ where
           let listener = EX::TcpListener::bind(hostname, thread_tcp_params).await?;
           let xorshift = &mut Xorshift64::from_simple_seed()?;
           loop {
-            let Ok(cp) = conn_params(
+            let cp = match conn_params(
               (&thread_data, &thread_error_cb, &thread_executor, thread_hrc),
               (&mut thread_rng, thread_tcp_params, &thread_tls_config),
               (&thread_http_router, &listener, xorshift),
+              (&thread_in_flight, &thread_shutdown_signal, thread_shutdown_timeout),
             )
             .await
-            else {
-              continue;
+            {
+              Ok(Some(elem)) => elem,
+              Ok(None) => break,
+              Err(_) => continue,
             };
             let conn_runtime = lc.clone();
             let _conn_jh = thread_executor.spawn_local(
@@ -402,13 +487,18 @@ where
                   }
                   Ok(elem) => elem,
                 };
-                let _frame_reader_jh = cp.executor.spawn_local(frame_reader, &conn_runtime);
+                let frame_reader_jh = cp.executor.spawn_local(frame_reader, &conn_runtime);
+                let conn_streams = InFlight::new();
+                let mut is_draining = false;
                 loop {
-                  let (server_stream, headers_aux, opt) = match conn_rslt::<ER, EX, TCX>(
-                    http2
-                      .stream(|req, _| stream_cb::<_, _, _, EX, _, _>(&cp.http_router, req))
-                      .await,
-                  ) {
+                  let (server_stream, headers_aux, opt) = match next_stream::<_, _, _, EX, _, _>(
+                    &http2,
+                    &cp.http_router,
+                    &mut is_draining,
+                    &cp.shutdown_signal,
+                  )
+                  .await
+                  {
                     Ok(Some(el)) => el,
                     Ok(None) => break,
                     Err(err) => {
@@ -419,6 +509,7 @@ where
                   };
                   let stream_data = cp.data.clone();
                   let stream_error_cb = cp.error_cb.clone();
+                  let stream_guards = (cp.in_flight.guard(), conn_streams.guard());
                   let stream_http_router = cp.http_router.clone();
                   let _stream_jh = cp.executor.spawn_local(
                     stream_fut::<DA, EC, EN, ER, EX, M, TCX>(
@@ -428,15 +519,28 @@ where
                       server_stream,
                       stream_data,
                       stream_error_cb,
+                      stream_guards,
                       stream_http_router,
                     ),
                     &conn_runtime,
                   );
                 }
+                close_conn::<EX, TCX>(
+                  &conn_streams,
+                  frame_reader_jh,
+                  &http2,
+                  is_draining,
+                  cp.shutdown_timeout,
+                )
+                .await;
+                // Only released after the connection is closed
+                drop(cp.conn_guard);
               },
               &lc,
             );
           }
+          let _is_idle = thread_in_flight.wait_idle(thread_shutdown_timeout).await?;
+          Ok(())
         })
       }))?;
     }
@@ -461,18 +565,22 @@ where
     M: Middleware<DA, ER> + 'static,
   {
     let http_router = Arc::new(hr);
+    let in_flight = InFlight::new();
     let uri = Uri::new(addr);
     let listener = EX::TcpListener::bind(uri.hostname_with_implied_port(), self.tcp_params).await?;
     let xorshift = &mut Xorshift64::from_simple_seed()?;
     loop {
-      let Ok(cp) = conn_params(
+      let cp = match conn_params(
         (&self.data, &self.error_cb, &self.executor, self.hrc),
         (&mut self.rng, self.tcp_params, &self.tls_config),
         (&http_router, &listener, xorshift),
+        (&in_flight, &self.shutdown_signal, self.shutdown_timeout),
       )
       .await
-      else {
-        continue;
+      {
+        Ok(Some(elem)) => elem,
+        Ok(None) => break,
+        Err(_) => continue,
       };
       let conn_lc = lc.clone();
       let _conn_jh = self.executor.spawn_local(
@@ -485,11 +593,18 @@ where
             }
             Ok(elem) => elem,
           };
-          let _frame_reader_jh = cp.executor.spawn_local(frame_reader, &conn_lc);
+          let frame_reader_jh = cp.executor.spawn_local(frame_reader, &conn_lc);
+          let conn_streams = InFlight::new();
+          let mut is_draining = false;
           loop {
-            let (server_stream, headers_aux, opt) = match conn_rslt::<ER, EX, TCX>(
-              http2.stream(|req, _| stream_cb::<_, _, _, EX, _, _>(&cp.http_router, req)).await,
-            ) {
+            let (server_stream, headers_aux, opt) = match next_stream::<_, _, _, EX, _, _>(
+              &http2,
+              &cp.http_router,
+              &mut is_draining,
+              &cp.shutdown_signal,
+            )
+            .await
+            {
               Ok(Some(el)) => el,
               Ok(None) => break,
               Err(err) => {
@@ -500,6 +615,7 @@ where
             };
             let stream_data = cp.data.clone();
             let stream_error_cb = cp.error_cb.clone();
+            let stream_guards = (cp.in_flight.guard(), conn_streams.guard());
             let stream_http_router = cp.http_router.clone();
             let _stream_jh = cp.executor.spawn_local(
               stream_fut::<DA, EC, EN, ER, EX, M, TCX>(
@@ -509,15 +625,28 @@ where
                 server_stream,
                 stream_data,
                 stream_error_cb,
+                stream_guards,
                 stream_http_router,
               ),
               &conn_lc,
             );
           }
+          close_conn::<EX, TCX>(
+            &conn_streams,
+            frame_reader_jh,
+            &http2,
+            is_draining,
+            cp.shutdown_timeout,
+          )
+          .await;
+          // Only released after the connection is closed
+          drop(cp.conn_guard);
         },
         &*lc,
       );
     }
+    let _is_idle = in_flight.wait_idle(self.shutdown_timeout).await?;
+    Ok(())
   }
 }
 
@@ -525,12 +654,16 @@ struct ConnParams<DA, EC, EN, ER, EX, M, RNG, TCX>
 where
   EX: Executor,
 {
+  conn_guard: InFlightGuard,
   data: DA,
   error_cb: EC,
   executor: EX,
   hrc: HttpRecvParams,
   http_router: Arc<HttpRouter<DA, EN, ER, M, LocalStream<EX, TCX>>>,
+  in_flight: InFlight,
   rng: RNG,
+  shutdown_signal: ShutdownSignal,
+  shutdown_timeout: Duration,
   stream: EX::TcpStream,
   tls_config: Arc<TlsConfig<TCX>>,
   xorshift: Xorshift64,
@@ -545,7 +678,8 @@ async fn conn_params<DA, EC, EN, ER, EX, M, RNG, TCX>(
     &EX::TcpListener,
     &mut Xorshift64,
   ),
-) -> crate::Result<ConnParams<DA, EC, EN, ER, EX, M, RNG, TCX>>
+  (in_flight, shutdown_signal, shutdown_timeout): (&InFlight, &ShutdownSignal, Duration),
+) -> crate::Result<Option<ConnParams<DA, EC, EN, ER, EX, M, RNG, TCX>>>
 where
   EC: Clone,
   DA: Clone,
@@ -553,17 +687,45 @@ where
   RNG: CryptoRng + CryptoSeedableRng,
   TCX: TlsCtx,
 {
-  Ok(ConnParams {
+  let Some(accept_rslt) = shutdown_signal.or_triggered(listener.accept(tcp_params)).await else {
+    return Ok(None);
+  };
+  Ok(Some(ConnParams {
+    conn_guard: in_flight.guard(),
     data: data.clone(),
     error_cb: error_cb.clone(),
     executor: executor.clone(),
     hrc,
     http_router: http_router.clone(),
+    in_flight: in_flight.clone(),
     rng: RNG::from_crypto_rng(rng)?,
-    stream: listener.accept(tcp_params).await?.0,
+    shutdown_signal: shutdown_signal.clone(),
+    shutdown_timeout,
+    stream: accept_rslt?.0,
     tls_config: tls_config.clone(),
     xorshift: Xorshift64::from_rng(xorshift)?,
-  })
+  }))
+}
+
+// Connections that received a graceful GOAWAY are closed once their streams are finished. The
+// frame reader is also awaited to make sure that the network connection is released before the
+// server returns.
+#[inline]
+async fn close_conn<EX, TCX>(
+  conn_streams: &InFlight,
+  frame_reader_jh: impl Future,
+  http2: &Http2<WriteHalf<EX>, TCX, false>,
+  is_draining: bool,
+  shutdown_timeout: Duration,
+) where
+  EX: Executor,
+  TCX: TlsCtx,
+{
+  if is_draining {
+    let _is_idle = conn_streams.wait_idle(shutdown_timeout).await;
+    http2.send_go_away(Http2ErrorCode::NoError).await;
+    let _frame_reader_rslt = frame_reader_jh.await;
+  }
 }
 
 #[inline]
@@ -586,6 +748,36 @@ where
   }
 }
 
+// Once the shutdown signal is triggered, a graceful GOAWAY frame is sent and only the streams
+// that were already received are returned.
+#[inline]
+async fn next_stream<DA, EN, ER, EX, M, TCX>(
+  http2: &Http2<WriteHalf<EX>, TCX, false>,
+  http_router: &HttpRouter<DA, EN, ER, M, LocalStream<EX, TCX>>,
+  is_draining: &mut bool,
+  shutdown_signal: &ShutdownSignal,
+) -> Result<
+  Option<(LocalStream<EX, TCX>, ArrayVectorCopy<RouteMatch, 4>, Option<MsgBufferString>)>,
+  ER,
+>
+where
+  ER: From<crate::Error>,
+  EX: Executor,
+  TCX: TlsCtx,
+{
+  if !*is_draining {
+    let stream = http2.stream(|req, _| stream_cb::<_, _, _, EX, _, _>(http_router, req));
+    if let Some(elem) = shutdown_signal.or_triggered(stream).await {
+      return conn_rslt::<ER, EX, TCX>(elem);
+    }
+    *is_draining = true;
+    http2.send_graceful_go_away().await;
+  }
+  conn_rslt::<ER, EX, TCX>(
+    http2.stream(|req, _| stream_cb::<_, _, _, EX, _, _>(http_router, req)).await,
+  )
+}
+
 #[inline]
 async fn http2<EX, RNG, TCX>(
   hrc: HttpRecvParams,
@@ -602,8 +794,30 @@ where
   let ip = stream.peer_addr()?.ip();
   let tar = TlsAcceptor::new(&*tls_config, &mut rng, stream).accept().await?;
   let split = tar.tls_stream.into_split()?;
-  let tuple = Http2::accept(Http2Buffer::new(&mut xorshift), hrc, split).await?;
-  Ok((tuple.0, tuple.1, ip))
+  let hb = Http2Buffer::new(&mut xorshift);
+  cfg_select! {
+    feature = "http2-h2c" => {
+      // Cleartext connections can also start with an HTTP/1.1 upgrade request
+      let (frame_reader, http2) = if TCX::TY.is_plain_text() {
+        let tuple = Http2::accept_h2c(hb, hrc, split).await?;
+        (Either::Left(tuple.0), tuple.1)
+      } else {
+        let tuple = Http2::accept(hb, hrc, split).await?;
+        (Either::Right(tuple.0), tuple.1)
+      };
+      let fut = async move {
+        match frame_reader {
+          Either::Left(elem) => elem.await,
+          Either::Right(elem) => elem.await,
+        }
+      };
+      Ok((fut, http2, ip))
+    }
+    _ => {
+      let tuple = Http2::accept(hb, hrc, split).await?;
+      Ok((tuple.0, tuple.1, ip))
+    }
+  }
 }
 
 #[inline]
@@ -638,6 +852,7 @@ async fn stream_fut<DA, EC, EN, ER, EX, M, TCX>(
   mut server_stream: LocalStream<EX, TCX>,
   stream_data: DA,
   stream_error_cb: EC,
+  _stream_guards: (InFlightGuard, InFlightGuard),
   stream_http_router: Arc<HttpRouter<DA, EN, ER, M, LocalStream<EX, TCX>>>,
 ) where
   EC: Fn(ER),
@@ -0,0 +1,343 @@
+use crate::{
+  calendar::{CalendarToken, DateTime, Instant, Utc},
+  codec::hex_encode,
+  http::{
+    Header, KnownHeaderName, Method, MsgBufferString, Request, Response, StatusCode,
+    http2_server_framework::Middleware,
+  },
+  rng::Rng,
+  sync::AtomicU64,
+};
+use alloc::string::String;
+use core::{fmt::Write as _, ops::ControlFlow, sync::atomic::Ordering, time::Duration};
+
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+static FMT: &[CalendarToken] = &[
+  CalendarToken::TwoDigitDay,
+  CalendarToken::Slash,
+  CalendarToken::AbbreviatedMonthName,
+  CalendarToken::Slash,
+  CalendarToken::FourDigitYear,
+  CalendarToken::Colon,
+  CalendarToken::TwoDigitHour,
+  CalendarToken::Colon,
+  CalendarToken::TwoDigitMinute,
+  CalendarToken::Colon,
+  CalendarToken::TwoDigitSecond,
+];
+
+/// Line representation of each access log entry.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccessLogFormat {
+  /// Common Log Format with additional `Referer` and `User-Agent` fields.
+  Combined,
+  /// Common Log Format
+  Common,
+}
+
+/// Information gathered from a single request/response pair.
+#[derive(Clone, Copy, Debug)]
+pub struct AccessLogEntry<'any> {
+  /// Number of bytes of the response body
+  pub bytes: usize,
+  /// When the request was received. `None` if there is no time backend.
+  pub date_time: Option<DateTime<Utc>>,
+  /// Authority of the request without user information. `None` if absent.
+  pub host: Option<&'any str>,
+  /// Time spent between the request middleware and the response middleware
+  pub latency: Duration,
+  /// Request method
+  pub method: Method,
+  /// Path, query and fragment of the request
+  pub path: &'any str,
+  /// Contents of the `Referer` header
+  pub referer: Option<&'any str>,
+  /// Received or generated identifier of the request
+  pub request_id: &'any str,
+  /// Response status code
+  pub status_code: StatusCode,
+  /// Contents of the `User-Agent` header
+  pub user_agent: Option<&'any str>,
+}
+
+impl AccessLogEntry<'_> {
+  /// Writes a single line according to the given `format`. The first field is the requested host
+  /// because peer information is not available to middlewares, which also means that the
+  /// identity and user fields are always `-`.
+  #[inline]
+  pub fn write_line(&self, format: AccessLogFormat, buffer: &mut String) -> crate::Result<()> {
+    buffer.push_str(self.host.unwrap_or("-"));
+    buffer.push_str(" - - [");
+    if let Some(elem) = self.date_time {
+      buffer.push_str(elem.to_string::<32>(FMT.iter().copied())?.as_str());
+      buffer.push_str(" +0000");
+    } else {
+      buffer.push('-');
+    }
+    let method = self.method.strings().custom[0];
+    let status_code = u16::from(self.status_code);
+    buffer.write_fmt(format_args!(r#"] "{method} {} HTTP/2" {status_code} "#, self.path))?;
+    if self.bytes == 0 {
+      buffer.push('-');
+    } else {
+      buffer.write_fmt(format_args!("{}", self.bytes))?;
+    }
+    if format == AccessLogFormat::Combined {
+      let referer = self.referer.unwrap_or("-");
+      let user_agent = self.user_agent.unwrap_or("-");
+      buffer.write_fmt(format_args!(r#" "{referer}" "{user_agent}""#))?;
+    }
+    Ok(())
+  }
+}
+
+/// Destination of access log entries.
+pub trait AccessLogSink {
+  /// Receives a finished entry alongside its formatted line.
+  fn emit(&self, entry: &AccessLogEntry<'_>, line: &str);
+}
+
+/// Emits entries through `tracing` using the `wtx-http-access` target, if the feature is active.
+impl AccessLogSink for () {
+  #[inline]
+  fn emit(&self, _entry: &AccessLogEntry<'_>, _line: &str) {
+    _info!(
+      target: crate::_WTX_HTTP_ACCESS,
+      latency_us = _entry.latency.as_micros(),
+      request_id = _entry.request_id,
+      "{_line}"
+    );
+  }
+}
+
+impl<F> AccessLogSink for F
+where
+  F: Fn(&AccessLogEntry<'_>, &str),
+{
+  #[inline]
+  fn emit(&self, entry: &AccessLogEntry<'_>, line: &str) {
+    self(entry, line);
+  }
+}
+
+/// Used internally to carry request information to the response phase.
+#[derive(Debug)]
+pub struct AccessLogAux {
+  date_time: Option<DateTime<Utc>>,
+  host: String,
+  instant: Instant,
+  method: Method,
+  path: String,
+  referer: Option<String>,
+  request_id: String,
+  user_agent: Option<String>,
+}
+
+/// Assigns or propagates request identifiers and records an access log entry per request.
+///
+/// The identifier is taken from the `X-Request-Id` header, then from the trace ID of the
+/// `traceparent` header, and is otherwise generated. Either way, it is returned in the
+/// `X-Request-Id` response header.
+///
+/// Manual streams are not logged because responses don't pass through middlewares.
+#[derive(Debug)]
+pub struct AccessLogMiddleware<S> {
+  counter: AtomicU64,
+  format: AccessLogFormat,
+  prefix: [u8; 8],
+  sink: S,
+}
+
+impl AccessLogMiddleware<()> {
+  /// Instance that emits entries through `tracing`.
+  #[inline]
+  pub fn new<RNG>(format: AccessLogFormat, rng: &mut RNG) -> Self
+  where
+    RNG: Rng,
+  {
+    Self::with_sink(format, rng, ())
+  }
+}
+
+impl<S> AccessLogMiddleware<S>
+where
+  S: AccessLogSink,
+{
+  /// Instance that emits entries through a custom `sink`.
+  ///
+  /// `rng` is used to create the prefix of generated identifiers.
+  #[inline]
+  pub fn with_sink<RNG>(format: AccessLogFormat, rng: &mut RNG, sink: S) -> Self
+  where
+    RNG: Rng,
+  {
+    Self { counter: AtomicU64::new(0), format, prefix: rng.u8_8(), sink }
+  }
+
+  fn generate_request_id(&self, buffer: &mut String) -> crate::Result<()> {
+    let counter = self.counter.fetch_add(1, Ordering::Relaxed);
+    let mut hex = [0; 16];
+    buffer.push_str(hex_encode(&self.prefix, None, &mut hex)?);
+    buffer.push_str(hex_encode(&counter.to_be_bytes(), None, &mut hex)?);
+    Ok(())
+  }
+}
+
+impl<D, E, S> Middleware<D, E> for AccessLogMiddleware<S>
+where
+  E: From<crate::Error>,
+  S: AccessLogSink,
+{
+  type Aux = AccessLogAux;
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {
+    AccessLogAux {
+      date_time: None,
+      host: String::new(),
+      instant: Instant::new(),
+      method: Method::Get,
+      path: String::new(),
+      referer: None,
+      request_id: String::new(),
+      user_agent: None,
+    }
+  }
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut D,
+    mw_aux: &mut Self::Aux,
+    req: &mut Request<MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    mw_aux.date_time = Instant::now_date_time().ok();
+    mw_aux.host.push_str(req.msg_data.uri.host());
+    mw_aux.instant = Instant::new();
+    mw_aux.method = req.method;
+    mw_aux.path.push_str(req.msg_data.uri.relative_reference_slash());
+    let [referer, traceparent, user_agent, x_request_id] = req.msg_data.headers.get_by_names([
+      KnownHeaderName::Referer.into(),
+      KnownHeaderName::Traceparent.into(),
+      KnownHeaderName::UserAgent.into(),
+      KnownHeaderName::XRequestId.into(),
+    ]);
+    if self.format == AccessLogFormat::Combined {
+      mw_aux.referer = referer.map(|el| String::from(el.value));
+      mw_aux.user_agent = user_agent.map(|el| String::from(el.value));
+    }
+    if let Some(elem) = x_request_id.map(|el| el.value).filter(|el| is_valid_request_id(el)) {
+      mw_aux.request_id.push_str(elem);
+    } else if let Some(elem) = traceparent.and_then(|el| trace_id(el.value)) {
+      mw_aux.request_id.push_str(elem);
+    } else {
+      self.generate_request_id(&mut mw_aux.request_id)?;
+    }
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut D,
+    mw_aux: &mut Self::Aux,
+    res: Response<&mut MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    res.msg_data.headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::XRequestId.into(),
+      [mw_aux.request_id.as_str()],
+    ))?;
+    let entry = AccessLogEntry {
+      bytes: res.msg_data.body.len(),
+      date_time: mw_aux.date_time,
+      host: Some(mw_aux.host.as_str()).filter(|el| !el.is_empty()),
+      latency: Instant::new().duration_since(mw_aux.instant).unwrap_or_default(),
+      method: mw_aux.method,
+      path: &mw_aux.path,
+      referer: mw_aux.referer.as_deref(),
+      request_id: &mw_aux.request_id,
+      status_code: res.status_code,
+      user_agent: mw_aux.user_agent.as_deref(),
+    };
+    let mut line = String::new();
+    entry.write_line(self.format, &mut line)?;
+    self.sink.emit(&entry, &line);
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+fn is_valid_request_id(value: &str) -> bool {
+  (1..=MAX_REQUEST_ID_LEN).contains(&value.len()) && value.bytes().all(|el| el.is_ascii_graphic())
+}
+
+// Only the second field of `version-traceid-parentid-flags` is returned.
+fn trace_id(traceparent: &str) -> Option<&str> {
+  let trace_id = traceparent.split('-').nth(1)?;
+  let is_valid = trace_id.len() == 32
+    && trace_id.bytes().all(|el| matches!(el, b'0'..=b'9' | b'a'..=b'f'))
+    && trace_id.bytes().any(|el| el != b'0');
+  is_valid.then_some(trace_id)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    calendar::{DateTime, Utc},
+    http::{
+      Method, StatusCode,
+      http2_server_framework::{
+        AccessLogEntry, AccessLogFormat,
+        access_log_middleware::{is_valid_request_id, trace_id},
+      },
+    },
+  };
+  use alloc::string::String;
+  use core::time::Duration;
+
+  #[test]
+  fn extracts_trace_ids() {
+    assert_eq!(
+      trace_id("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+      Some("4bf92f3577b34da6a3ce929d0e0e4736")
+    );
+    assert_eq!(trace_id("00-00000000000000000000000000000000-00f067aa0ba902b7-01"), None);
+    assert_eq!(trace_id("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01"), None);
+    assert_eq!(trace_id("foo"), None);
+  }
+
+  #[test]
+  fn validates_request_ids() {
+    assert!(is_valid_request_id("abc-123"));
+    assert!(!is_valid_request_id(""));
+    assert!(!is_valid_request_id("a b"));
+  }
+
+  #[test]
+  fn writes_lines() {
+    let mut entry = AccessLogEntry {
+      bytes: 0,
+      date_time: Some(DateTime::<Utc>::from_timestamp_secs(971_186_136).unwrap()),
+      host: Some("localhost:9000"),
+      latency: Duration::from_millis(3),
+      method: Method::Get,
+      path: "/foo?bar=1",
+      referer: None,
+      request_id: "1",
+      status_code: StatusCode::Ok,
+      user_agent: Some("curl"),
+    };
+    let mut buffer = String::new();
+    entry.write_line(AccessLogFormat::Common, &mut buffer).unwrap();
+    assert_eq!(
+      buffer,
+      r#"localhost:9000 - - [10/Oct/2000:13:55:36 +0000] "GET /foo?bar=1 HTTP/2" 200 -"#
+    );
+    buffer.clear();
+    entry.bytes = 12;
+    entry.date_time = None;
+    entry.host = None;
+    entry.write_line(AccessLogFormat::Combined, &mut buffer).unwrap();
+    assert_eq!(buffer, r#"- - - [-] "GET /foo?bar=1 HTTP/2" 200 12 "-" "curl""#);
+  }
+}
@@ -11,6 +11,8 @@ pub enum Http2ServerFrameworkError {
   /// `Access-Control-Allow-Methods: *`, and `Access-Control-Expose-Headers: *` are not valid when
   /// credentials are involved.
   ForbiddenLocalCorsParameters,
+  /// A Server-Sent Event field contains line breaks or other forbidden characters
+  InvalidSseField,
   /// Client sent a request with invalid WebSocket tunneling parameters
   InvalidWebSocketParameters,
   /// Entered in a route that has an incompatible operation mode
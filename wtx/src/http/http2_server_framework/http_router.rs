@@ -78,6 +78,7 @@ where
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<StatusCode, ER> {
     let mw_aux = &mut self.middlewares.aux();
+    self.middlewares.route(mw_aux, path_defs.1);
     let status_code = if let ControlFlow::Break(el) =
       self.middlewares.req(&mut auto_stream.data, mw_aux, &mut auto_stream.req).await?
     {
@@ -106,6 +107,7 @@ where
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<(), ER> {
     let mw_aux = &mut self.middlewares.aux();
+    self.middlewares.route(mw_aux, path_defs.1);
     if let ControlFlow::Break(_) =
       self.middlewares.req(&mut manual_stream.data, mw_aux, &mut manual_stream.req).await?
     {
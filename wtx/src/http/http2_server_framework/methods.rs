@@ -4,6 +4,7 @@ pub(crate) mod json;
 pub(crate) mod patch;
 pub(crate) mod post;
 pub(crate) mod put;
+pub(crate) mod sse;
 pub(crate) mod web_socket;
 
 use crate::http::{HttpError, Method};
@@ -0,0 +1,176 @@
+use crate::{
+  collections::{ArrayVectorCopy, Vector},
+  futures::FnFut,
+  http::{
+    AutoStream, Headers, HttpError, KnownHeaderName, ManualStream, Method, Mime, OperationMode,
+    StatusCode,
+    http2_server_framework::{Endpoint, EndpointNode, RouteMatch, methods::check_method},
+  },
+};
+
+/// Requires a `GET` request that accepts `text/event-stream` responses. Should be used with
+/// manual streams alongside [`crate::http::http2_server_framework::SseStream`].
+#[derive(Clone, Debug)]
+pub struct Sse<T>(
+  /// Function
+  pub T,
+);
+
+/// Creates a new [`Sse`] instance.
+#[inline]
+pub fn sse<A, T>(ty: T) -> Sse<T::Wrapper>
+where
+  T: FnFut<A>,
+{
+  Sse(ty.into_wrapper())
+}
+
+impl<D, E, S, T> Endpoint<D, E, S> for Sse<T>
+where
+  E: From<crate::Error>,
+  T: Endpoint<D, E, S>,
+{
+  const OM: OperationMode = T::OM;
+
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<D>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    check_sse(&auto_stream.req.msg_data.headers, auto_stream.req.method)?;
+    self.0.auto(auto_stream, path_defs).await
+  }
+
+  #[inline]
+  async fn manual(
+    &self,
+    manual_stream: ManualStream<D, S>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    check_sse(&manual_stream.req.msg_data.headers, manual_stream.req.method)?;
+    self.0.manual(manual_stream, path_defs).await
+  }
+}
+
+impl<D, E, S, T> EndpointNode<D, E, S> for Sse<T>
+where
+  E: From<crate::Error>,
+  T: Endpoint<D, E, S>,
+{
+  const IS_ROUTER: bool = false;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    _: ArrayVectorCopy<RouteMatch, 4>,
+    _: &mut Vector<ArrayVectorCopy<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+// A missing `Accept` header means that any type of content is acceptable. Otherwise, the most
+// specific media range that matches `text/event-stream` must have a non-zero quality.
+fn check_sse<E>(req_headers: &Headers, req_method: Method) -> Result<(), E>
+where
+  E: From<crate::Error>,
+{
+  check_method(Method::Get, req_method)?;
+  let mut has_accept = false;
+  // Specificity and acceptance of the best match
+  let mut best: Option<(u8, bool)> = None;
+  for header in req_headers.iter() {
+    if !header.name.eq_ignore_ascii_case(KnownHeaderName::Accept.into()) {
+      continue;
+    }
+    has_accept = true;
+    for media_range in header.value.split(',') {
+      let mut params = media_range.split(';');
+      let Some(specificity) = media_range_specificity(params.next().unwrap_or_default()) else {
+        continue;
+      };
+      if best.is_some_and(|el| el.0 >= specificity) {
+        continue;
+      }
+      let is_accepted = params
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+        .is_none_or(|(_, value)| has_positive_quality(value.trim()));
+      best = Some((specificity, is_accepted));
+    }
+  }
+  if has_accept && !best.is_some_and(|el| el.1) {
+    return Err(E::from(crate::Error::from(HttpError::UnexpectedContentType)));
+  }
+  Ok(())
+}
+
+// https://datatracker.ietf.org/doc/html/rfc9110#section-12.4.2
+fn has_positive_quality(qvalue: &str) -> bool {
+  match qvalue.as_bytes() {
+    [b'0', rest @ ..] => matches!(
+      rest,
+      [b'.', decimals @ ..] if decimals.len() <= 3
+        && decimals.iter().all(u8::is_ascii_digit)
+        && decimals.iter().any(|el| *el != b'0')
+    ),
+    [b'1', rest @ ..] => matches!(
+      rest,
+      [] | [b'.', ..] if rest.len() <= 4 && rest.iter().skip(1).all(|el| *el == b'0')
+    ),
+    _ => false,
+  }
+}
+
+// Returns how specific `media_type` is when it matches `text/event-stream`.
+fn media_range_specificity(media_type: &str) -> Option<u8> {
+  let (ty, subtype) = media_type.trim().split_once('/')?;
+  let (mime_ty, mime_subtype) = Mime::TextEventStream.as_str().split_once('/')?;
+  match (ty.trim(), subtype.trim()) {
+    ("*", "*") => Some(0),
+    (local_ty, "*") if local_ty.eq_ignore_ascii_case(mime_ty) => Some(1),
+    (local_ty, local_subtype)
+      if local_ty.eq_ignore_ascii_case(mime_ty)
+        && local_subtype.eq_ignore_ascii_case(mime_subtype) =>
+    {
+      Some(2)
+    }
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::{
+    Header, Headers, KnownHeaderName, Method, http2_server_framework::methods::sse::check_sse,
+  };
+
+  #[test]
+  fn negotiates_media_ranges() {
+    let is_accepted = |values: &[&str]| {
+      let mut headers = Headers::new();
+      for value in values {
+        headers
+          .push_from_iter(Header::from_name_and_value(KnownHeaderName::Accept.into(), [*value]))
+          .unwrap();
+      }
+      check_sse::<crate::Error>(&headers, Method::Get).is_ok()
+    };
+    assert!(is_accepted(&[]));
+    assert!(is_accepted(&["text/event-stream"]));
+    assert!(is_accepted(&["TEXT/Event-Stream; charset=utf-8"]));
+    assert!(is_accepted(&["application/json, text/*;q=0.5"]));
+    assert!(is_accepted(&["*/*"]));
+    assert!(is_accepted(&["text/event-stream;q=0.001", "text/html;q=1.000"]));
+    assert!(is_accepted(&["text/html", "*/*;q=0.1"]));
+    assert!(!is_accepted(&["text/event-stream;q=0"]));
+    assert!(!is_accepted(&["text/event-stream; q=0.000"]));
+    assert!(!is_accepted(&["*/*, text/event-stream;q=0"]));
+    assert!(!is_accepted(&["text/*;q=0, */*"]));
+    assert!(!is_accepted(&["text/event-streams"]));
+    assert!(!is_accepted(&["application/json, text/html-*/*"]));
+    assert!(!is_accepted(&["text/event-stream;q=2"]));
+    assert!(check_sse::<crate::Error>(&Headers::new(), Method::Post).is_err());
+  }
+}
@@ -1,3 +1,14 @@
+#[cfg(feature = "web-socket")]
+use crate::{
+  collections::SingleTypeStorage,
+  futures::FnFutWrapper,
+  http::{Header, Headers, KnownHeaderName, has_web_socket_no_masking},
+  http2::{ServerStream, WebSocketOverStream},
+  misc::LeaseMut,
+  net::StreamWriter,
+  rng::{SeedableRng as _, Xorshift64},
+  tls::TlsCtx,
+};
 use crate::{
   collections::{ArrayVectorCopy, Vector},
   futures::FnFut,
@@ -16,6 +27,11 @@ pub struct WebSocket<T>(
 );
 
 /// Creates a new [`WebSocket`] instance.
+///
+/// Besides [`ManualStream`]s, `ty` can also receive a buffer alongside an established
+/// [`WebSocketOverStream`], which is the same signature of the handlers of
+/// [`crate::http::WebSocketServerFramework`]. In other words, handlers that are generic over
+/// [`crate::web_socket::GenericWebSocket`] can be used in both HTTP/1.1 and HTTP/2 servers.
 #[inline]
 pub fn web_socket<A, T>(ty: T) -> WebSocket<T::Wrapper>
 where
@@ -80,3 +96,35 @@ where
     Ok(())
   }
 }
+
+#[cfg(feature = "web-socket")]
+impl<D, E, F, S, SW, TCX> Endpoint<D, E, S>
+  for FnFutWrapper<(Vector<u8>, WebSocketOverStream<S>), F>
+where
+  E: From<crate::Error>,
+  F: FnFut<(Vector<u8>, WebSocketOverStream<S>), Result = Result<(), E>>,
+  S: LeaseMut<ServerStream<SW, TCX>> + SingleTypeStorage<Item = (SW, TCX)>,
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  const OM: OperationMode = OperationMode::Manual;
+
+  #[inline]
+  async fn manual(
+    &self,
+    manual_stream: ManualStream<D, S>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    let no_masking = has_web_socket_no_masking(&manual_stream.req.msg_data.headers);
+    let mut headers = Headers::new();
+    if no_masking {
+      headers.push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::SecWebsocketExtensions.into(),
+        ["no-masking"],
+      ))?;
+    }
+    let rng = Xorshift64::from_simple_seed()?;
+    let ws = WebSocketOverStream::new(&headers, no_masking, rng, manual_stream.stream).await?;
+    self.0.call((Vector::new(), ws)).await
+  }
+}
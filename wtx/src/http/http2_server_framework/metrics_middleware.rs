@@ -0,0 +1,74 @@
+use crate::{
+  calendar::Instant,
+  http::{
+    MsgBufferString, Request, Response, StatusCode,
+    http2_server_framework::{Middleware, RouteMatch},
+  },
+  metrics::MetricsRegistry,
+};
+use alloc::string::String;
+use core::ops::ControlFlow;
+
+/// Used internally to carry request information to the response phase.
+#[derive(Debug)]
+pub struct MetricsAux {
+  instant: Instant,
+  method: &'static str,
+  route: String,
+}
+
+/// Records the number of requests by method, route and status code as well as their durations
+/// into [`MetricsRegistry::global`].
+///
+/// Routes are the registered patterns, for example, `/user/{id}`, instead of the received paths,
+/// which means that dynamic parameters don't produce distinct series.
+#[derive(Debug, Default)]
+pub struct MetricsMiddleware;
+
+impl<D, E> Middleware<D, E> for MetricsMiddleware
+where
+  E: From<crate::Error>,
+{
+  type Aux = MetricsAux;
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {
+    MetricsAux { instant: Instant::new(), method: "", route: String::new() }
+  }
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut D,
+    mw_aux: &mut Self::Aux,
+    req: &mut Request<MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    mw_aux.instant = Instant::new();
+    mw_aux.method = req.method.strings().custom[0];
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut D,
+    mw_aux: &mut Self::Aux,
+    res: Response<&mut MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    MetricsRegistry::global().record_http_server_request(
+      mw_aux.instant.elapsed().unwrap_or_default(),
+      mw_aux.method,
+      &mw_aux.route,
+      res.status_code.into(),
+    );
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  fn route(&self, mw_aux: &mut Self::Aux, route: &[RouteMatch]) {
+    mw_aux.route.clear();
+    for elem in route {
+      mw_aux.route.push_str(elem.path());
+    }
+  }
+}
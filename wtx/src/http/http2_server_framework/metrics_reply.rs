@@ -0,0 +1,30 @@
+use crate::{
+  http::{
+    Header, KnownHeaderName, MsgBufferString, Request, StatusCode,
+    http2_server_framework::ResFinalizer,
+  },
+  metrics::MetricsRegistry,
+};
+
+/// Responds with all the metrics of [`MetricsRegistry::global`] using the Prometheus text
+/// exposition format.
+///
+/// Usually returned by a `/metrics` endpoint.
+#[derive(Debug, Default)]
+pub struct MetricsReply;
+
+impl<E> ResFinalizer<E> for MetricsReply
+where
+  E: From<crate::Error>,
+{
+  #[inline]
+  fn finalize_response(self, req: &mut Request<MsgBufferString>) -> Result<StatusCode, E> {
+    req.clear();
+    MetricsRegistry::global().encode_text(&mut req.msg_data.body)?;
+    req.msg_data.headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::ContentType.into(),
+      ["text/plain; version=0.0.4"],
+    ))?;
+    Ok(StatusCode::Ok)
+  }
+}
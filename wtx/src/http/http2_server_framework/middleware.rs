@@ -1,4 +1,6 @@
-use crate::http::{MsgBufferString, Request, Response, StatusCode};
+use crate::http::{
+  MsgBufferString, Request, Response, StatusCode, http2_server_framework::RouteMatch,
+};
 use core::ops::ControlFlow;
 
 /// Request middleware
@@ -27,4 +29,13 @@ where
     mw_aux: &mut Self::Aux,
     res: Response<&mut MsgBufferString>,
   ) -> impl Future<Output = Result<ControlFlow<StatusCode, ()>, ER>>;
+
+  /// Receives the matched route before [`Self::req`]. Each element is the pattern of a nested
+  /// level, which means that the concatenation of all paths forms the full pattern.
+  ///
+  /// Does nothing by default.
+  #[inline]
+  fn route(&self, mw_aux: &mut Self::Aux, route: &[RouteMatch]) {
+    let _ = (mw_aux, route);
+  }
 }
@@ -12,4 +12,10 @@ impl RouteMatch {
   pub(crate) const fn new(idx: u8, om: OperationMode, path: ShortStrU8<'static>) -> Self {
     Self { idx, om, path }
   }
+
+  /// Registered pattern of this level, for example, `/user/{id}`.
+  #[inline]
+  pub fn path(&self) -> &'static str {
+    self.path.into_str()
+  }
 }
@@ -0,0 +1,144 @@
+use crate::{
+  codec::u32_string, collections::Vector, http::http2_server_framework::Http2ServerFrameworkError,
+};
+
+/// A message dispatched to `text/event-stream` consumers.
+///
+/// Multi-line data is automatically split into several `data` fields.
+///
+/// <https://html.spec.whatwg.org/multipage/server-sent-events.html>
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SseEvent<'any> {
+  /// Payload
+  pub data: &'any str,
+  /// Event type. Consumers use `message` when absent.
+  pub event: Option<&'any str>,
+  /// Identifier that is going to be sent back by consumers in the `Last-Event-ID` header
+  /// after a reconnection.
+  pub id: Option<&'any str>,
+  /// Reconnection time, in milliseconds, that should be used by consumers.
+  pub retry: Option<u32>,
+}
+
+impl<'any> SseEvent<'any> {
+  /// Instance with only data.
+  #[inline]
+  pub const fn new(data: &'any str) -> Self {
+    Self { data, event: None, id: None, retry: None }
+  }
+
+  /// Sets the event type.
+  #[inline]
+  #[must_use]
+  pub const fn set_event(mut self, value: Option<&'any str>) -> Self {
+    self.event = value;
+    self
+  }
+
+  /// Sets the identifier.
+  #[inline]
+  #[must_use]
+  pub const fn set_id(mut self, value: Option<&'any str>) -> Self {
+    self.id = value;
+    self
+  }
+
+  /// Sets the reconnection time.
+  #[inline]
+  #[must_use]
+  pub const fn set_retry(mut self, value: Option<u32>) -> Self {
+    self.retry = value;
+    self
+  }
+
+  pub(crate) fn encode(&self, buffer: &mut Vector<u8>) -> crate::Result<()> {
+    let Self { data, event, id, retry } = *self;
+    if let Some(elem) = event {
+      if has_line_break(elem) {
+        return Err(Http2ServerFrameworkError::InvalidSseField.into());
+      }
+      let _ = buffer.extend_from_copyable_slices([b"event: ", elem.as_bytes(), b"\n"])?;
+    }
+    if let Some(elem) = id {
+      if has_line_break(elem) || elem.as_bytes().contains(&0) {
+        return Err(Http2ServerFrameworkError::InvalidSseField.into());
+      }
+      let _ = buffer.extend_from_copyable_slices([b"id: ", elem.as_bytes(), b"\n"])?;
+    }
+    if let Some(elem) = retry {
+      let _ =
+        buffer.extend_from_copyable_slices([b"retry: ", u32_string(elem).as_bytes(), b"\n"])?;
+    }
+    let mut bytes = data.as_bytes();
+    loop {
+      let idx = bytes.iter().position(|el| matches!(el, b'\n' | b'\r')).unwrap_or(bytes.len());
+      let (line, rest) = bytes.split_at_checked(idx).unwrap_or_default();
+      let _ = buffer.extend_from_copyable_slices([b"data: ", line, b"\n"])?;
+      bytes = match rest {
+        [b'\r', b'\n', after @ ..] | [b'\r' | b'\n', after @ ..] => after,
+        _ => break,
+      };
+    }
+    buffer.push(b'\n')?;
+    Ok(())
+  }
+}
+
+pub(crate) fn encode_comment(buffer: &mut Vector<u8>, comment: &str) -> crate::Result<()> {
+  if has_line_break(comment) {
+    return Err(Http2ServerFrameworkError::InvalidSseField.into());
+  }
+  let _ = buffer.extend_from_copyable_slices([b":", comment.as_bytes(), b"\n\n"])?;
+  Ok(())
+}
+
+fn has_line_break(str: &str) -> bool {
+  str.as_bytes().iter().any(|el| matches!(el, b'\n' | b'\r'))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    http::http2_server_framework::{SseEvent, sse_event::encode_comment},
+  };
+
+  #[test]
+  fn encodes_comments() {
+    let mut buffer = Vector::new();
+    encode_comment(&mut buffer, "").unwrap();
+    encode_comment(&mut buffer, " ping").unwrap();
+    assert_eq!(buffer.as_slice(), b":\n\n: ping\n\n");
+    assert!(encode_comment(&mut buffer, "a\nb").is_err());
+  }
+
+  #[test]
+  fn encodes_all_fields() {
+    let mut buffer = Vector::new();
+    SseEvent::new("hello")
+      .set_event(Some("greeting"))
+      .set_id(Some("7"))
+      .set_retry(Some(3000))
+      .encode(&mut buffer)
+      .unwrap();
+    assert_eq!(buffer.as_slice(), b"event: greeting\nid: 7\nretry: 3000\ndata: hello\n\n");
+  }
+
+  #[test]
+  fn encodes_multi_line_data() {
+    let mut buffer = Vector::new();
+    SseEvent::new("a\nb\r\nc\rd\n").encode(&mut buffer).unwrap();
+    assert_eq!(buffer.as_slice(), b"data: a\ndata: b\ndata: c\ndata: d\ndata: \n\n");
+    buffer.clear();
+    SseEvent::new("").encode(&mut buffer).unwrap();
+    assert_eq!(buffer.as_slice(), b"data: \n\n");
+  }
+
+  #[test]
+  fn rejects_line_breaks_in_fields() {
+    let mut buffer = Vector::new();
+    assert!(SseEvent::new("").set_event(Some("a\nb")).encode(&mut buffer).is_err());
+    assert!(SseEvent::new("").set_id(Some("a\rb")).encode(&mut buffer).is_err());
+    assert!(SseEvent::new("").set_id(Some("a\0b")).encode(&mut buffer).is_err());
+  }
+}
@@ -0,0 +1,139 @@
+use crate::{
+  collections::{SingleTypeStorage, Vector},
+  futures::Sleep,
+  http::{
+    Header, Headers, KnownHeaderName, Mime, StatusCode,
+    http2_server_framework::{SseEvent, sse_event::encode_comment},
+  },
+  http2::ServerStream,
+  misc::LeaseMut,
+  net::StreamWriter,
+  tls::TlsCtx,
+};
+use alloc::string::String;
+use core::{
+  future::poll_fn,
+  pin::pin,
+  task::{Poll, ready},
+  time::Duration,
+};
+
+/// Server-Sent Events over an opened HTTP/2 stream.
+///
+/// The stream is kept open until [`Self::close`] is called or until the remote peer goes away.
+#[derive(Debug)]
+pub struct SseStream<S> {
+  buffer: Vector<u8>,
+  last_event_id: Option<String>,
+  stream: S,
+}
+
+impl<S, SW, TCX> SseStream<S>
+where
+  S: LeaseMut<ServerStream<SW, TCX>> + SingleTypeStorage<Item = (SW, TCX)>,
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  /// Creates a new instance sending an `Ok` status code that confirms the opening of the event
+  /// stream.
+  ///
+  /// `headers` should contain the received request headers. The `Last-Event-ID` value is
+  /// extracted and then the same instance is cleared and reused to send the response headers.
+  #[inline]
+  pub async fn new(headers: &mut Headers, mut stream: S) -> crate::Result<Self> {
+    let last_event_id = headers
+      .get_by_name(KnownHeaderName::LastEventId.into())
+      .map(|header| String::from(header.value));
+    headers.clear();
+    headers.push_from_iter_many([
+      Header::from_name_and_value(
+        KnownHeaderName::ContentType.into(),
+        [Mime::TextEventStream.as_str()].into_iter(),
+      ),
+      Header::from_name_and_value(KnownHeaderName::CacheControl.into(), ["no-cache"].into_iter()),
+    ])?;
+    let hss = stream
+      .lease_mut()
+      .common()
+      .send_headers(&mut Vector::new(), headers, false, StatusCode::Ok)
+      .await?;
+    if hss.is_closed() {
+      return Err(crate::Error::ClosedHttpConnection);
+    }
+    Ok(Self { buffer: Vector::new(), last_event_id, stream })
+  }
+
+  /// Ends the event stream.
+  #[inline]
+  pub async fn close(&mut self) -> crate::Result<()> {
+    let _hss = self.stream.lease_mut().common().send_data(&[], true).await?;
+    Ok(())
+  }
+
+  /// The identifier of the last event received by the consumer before a reconnection, if any.
+  #[inline]
+  pub fn last_event_id(&self) -> Option<&str> {
+    self.last_event_id.as_deref()
+  }
+
+  /// Sends a comment line, which is ignored by consumers.
+  #[inline]
+  pub async fn send_comment(&mut self, comment: &str) -> crate::Result<()> {
+    self.buffer.clear();
+    encode_comment(&mut self.buffer, comment)?;
+    self.send_buffer().await
+  }
+
+  /// Sends a single event.
+  #[inline]
+  pub async fn send_event(&mut self, event: &SseEvent<'_>) -> crate::Result<()> {
+    self.buffer.clear();
+    event.encode(&mut self.buffer)?;
+    self.send_buffer().await
+  }
+
+  /// Sends an empty comment that prevents intermediaries from closing idle connections.
+  #[inline]
+  pub async fn send_keep_alive(&mut self) -> crate::Result<()> {
+    self.send_comment("").await
+  }
+
+  /// Awaits `future` sending keep-alive comments every time `interval` elapses without a
+  /// completion.
+  ///
+  /// Useful when events are produced by channels or other sources with unpredictable delays.
+  #[inline]
+  pub async fn wait_with_keep_alive<F>(
+    &mut self,
+    interval: Duration,
+    future: F,
+  ) -> crate::Result<F::Output>
+  where
+    F: Future,
+  {
+    let mut future_pin = pin!(future);
+    loop {
+      let mut sleep = pin!(Sleep::new(interval)?);
+      let opt = poll_fn(|cx| {
+        if let Poll::Ready(elem) = future_pin.as_mut().poll(cx) {
+          return Poll::Ready(Ok(Some(elem)));
+        }
+        ready!(sleep.as_mut().poll(cx))?;
+        Poll::Ready(crate::Result::Ok(None))
+      })
+      .await?;
+      if let Some(elem) = opt {
+        return Ok(elem);
+      }
+      self.send_keep_alive().await?;
+    }
+  }
+
+  async fn send_buffer(&mut self) -> crate::Result<()> {
+    let hss = self.stream.lease_mut().common().send_data(&self.buffer, false).await?;
+    if hss.is_closed() {
+      return Err(crate::Error::ClosedHttpConnection);
+    }
+    Ok(())
+  }
+}
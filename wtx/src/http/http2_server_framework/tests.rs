@@ -2,9 +2,12 @@ use crate::{
   executor::StdRuntime,
   http::{
     AutoStream, ManualStream, Method, MsgBufferString, Request, Response, StatusCode,
-    http2_server_framework::{HttpRouter, Middleware, StateClean, endpoint::Endpoint, get},
+    http2_server_framework::{
+      HttpRouter, Middleware, RouteMatch, StateClean, endpoint::Endpoint, get, sse,
+    },
   },
 };
+use alloc::string::String;
 use core::{
   net::{IpAddr, Ipv4Addr},
   ops::ControlFlow,
@@ -29,10 +32,215 @@ fn compiles() {
     ("/eee", get(two)),
     ("/fff", HttpRouter::paths(paths!(("/ggg", get(one)))).unwrap()),
     ("/hhh", get(three)),
+    ("/iii", sse(three)),
   ))
   .unwrap();
 }
 
+// A stream that is being processed when the shutdown is triggered is still answered while new
+// streams are refused.
+//
+// FIXME(MIRI): socket support
+#[cfg(feature = "tokio")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn graceful_shutdown() {
+  use crate::{
+    executor::{Runtime, TokioExecutor},
+    futures::Sleep,
+    http::{ShutdownSignal, http2_server_framework::Http2ServerFramework},
+    http2::PREFACE,
+    rng::{ChaCha20, CryptoSeedableRng},
+    sync::Arc,
+    tests::_uri,
+    tls::TlsConfig,
+  };
+  use alloc::vec::Vec;
+  use core::time::Duration;
+  use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    thread,
+  };
+
+  const GET_SLOW: &[u8] = b"\x82\x86\x04\x05/slow\x41\x09localhost";
+
+  async fn slow(state: StateClean<'_, ShutdownSignal>) -> crate::Result<StatusCode> {
+    state.data.trigger();
+    Sleep::new(Duration::from_millis(100))?.await?;
+    Ok(StatusCode::Ok)
+  }
+
+  fn frame(ty: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+    let [_, len0, len1, len2] = u32::try_from(payload.len()).unwrap().to_be_bytes();
+    let mut rslt = Vec::from([len0, len1, len2, ty, flags]);
+    rslt.extend(stream_id.to_be_bytes());
+    rslt.extend(payload);
+    rslt
+  }
+
+  let uri = _uri();
+  let client_uri = uri.clone();
+  let client_jh = thread::spawn(move || {
+    let mut stream = loop {
+      if let Ok(elem) = TcpStream::connect(client_uri.hostname_with_implied_port()) {
+        break elem;
+      }
+      thread::sleep(Duration::from_millis(10));
+    };
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let headers = frame(1, 5, 1, GET_SLOW);
+    stream.write_all(&[&PREFACE[..], &frame(4, 0, 0, &[]), &headers].concat()).unwrap();
+    let mut go_aways = Vec::new();
+    let mut resets = Vec::new();
+    let mut responses = Vec::new();
+    let mut header = [0; 9];
+    while stream.read_exact(&mut header).is_ok() {
+      let [len0, len1, len2, ty, _, s0, s1, s2, s3] = header;
+      let len = u32::from_be_bytes([0, len0, len1, len2]);
+      let stream_id = u32::from_be_bytes([s0, s1, s2, s3]);
+      let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+      stream.read_exact(&mut payload).unwrap();
+      let [b0, b1, b2, b3, b4, b5, b6, b7, ..] = *payload.as_slice() else {
+        if ty == 3 {
+          resets.push((stream_id, u32::from_be_bytes(payload.try_into().unwrap())));
+        } else if ty == 1 {
+          responses.push((stream_id, payload.first().copied()));
+        }
+        continue;
+      };
+      if ty == 7 {
+        if go_aways.is_empty() {
+          // Sent after the graceful GOAWAY and thus must be refused
+          stream.write_all(&frame(1, 5, 3, GET_SLOW)).unwrap();
+        }
+        let last_stream_id = u32::from_be_bytes([b0, b1, b2, b3]);
+        go_aways.push((last_stream_id, u32::from_be_bytes([b4, b5, b6, b7])));
+        // The second GOAWAY closes the connection
+        if go_aways.len() == 2 {
+          break;
+        }
+      } else if ty == 1 {
+        responses.push((stream_id, Some(b0)));
+      }
+    }
+    (go_aways, resets, responses)
+  });
+
+  let http_router = HttpRouter::paths(paths!(("/slow", get(slow)))).unwrap();
+  let lr = Arc::new(<tokio::runtime::LocalRuntime as Runtime>::new().unwrap());
+  lr.block_on(async {
+    let server = Http2ServerFramework::new(
+      TokioExecutor::default(),
+      ChaCha20::from_std_random().unwrap(),
+      TlsConfig::plaintext(),
+    )
+    .unwrap()
+    .set_shutdown_timeout(Duration::from_secs(5));
+    let signal = server.shutdown_signal();
+    server.set_data(signal).run_local(uri.as_str(), http_router, lr.clone()).await.unwrap();
+  });
+
+  let (go_aways, resets, responses) = client_jh.join().unwrap();
+  assert_eq!(go_aways.first(), Some(&(1, 0)));
+  assert_eq!(go_aways.len(), 2);
+  assert_eq!(go_aways.get(1).map(|el| el.1), Some(0));
+  assert_eq!(resets, [(3, 7)]);
+  // `:status 200` is the 8th entry of the HPACK static table
+  assert_eq!(responses, [(1, Some(0x88))]);
+}
+
+// Cleartext connections that start with an HTTP/1.1 upgrade request are served like any other
+// HTTP/2 connection.
+//
+// FIXME(MIRI): socket support
+#[cfg(all(feature = "http2-h2c", feature = "tokio"))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn h2c_upgrade() {
+  use crate::{
+    executor::{Runtime, TokioExecutor},
+    http::{ShutdownSignal, http2_server_framework::Http2ServerFramework},
+    http2::PREFACE,
+    rng::{ChaCha20, CryptoSeedableRng},
+    sync::Arc,
+    tests::_uri,
+    tls::TlsConfig,
+  };
+  use alloc::vec::Vec;
+  use core::time::Duration;
+  use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    thread,
+  };
+
+  const UPGRADE: &[u8] = b"GET /hello HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade, \
+    HTTP2-Settings\r\nHTTP2-Settings: \r\nUpgrade: h2c\r\n\r\n";
+
+  async fn hello(state: StateClean<'_, ShutdownSignal>) -> crate::Result<StatusCode> {
+    state.data.trigger();
+    Ok(StatusCode::Ok)
+  }
+
+  let uri = _uri();
+  let client_uri = uri.clone();
+  let client_jh = thread::spawn(move || {
+    let mut stream = loop {
+      if let Ok(elem) = TcpStream::connect(client_uri.hostname_with_implied_port()) {
+        break elem;
+      }
+      thread::sleep(Duration::from_millis(10));
+    };
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    stream.write_all(UPGRADE).unwrap();
+    let mut status_line = [0; 12];
+    stream.read_exact(&mut status_line).unwrap();
+    assert_eq!(&status_line, b"HTTP/1.1 101");
+    let mut rest = [0; 59];
+    stream.read_exact(&mut rest).unwrap();
+    assert!(rest.ends_with(b"\r\n\r\n"));
+    stream.write_all(&[&PREFACE[..], &[0, 0, 0, 4, 0, 0, 0, 0, 0]].concat()).unwrap();
+    let mut go_aways = 0;
+    let mut responses = Vec::new();
+    let mut header = [0; 9];
+    while stream.read_exact(&mut header).is_ok() {
+      let [len0, len1, len2, ty, _, s0, s1, s2, s3] = header;
+      let len = u32::from_be_bytes([0, len0, len1, len2]);
+      let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+      stream.read_exact(&mut payload).unwrap();
+      if ty == 1 {
+        responses.push((u32::from_be_bytes([s0, s1, s2, s3]), payload.first().copied()));
+      } else if ty == 7 {
+        go_aways += 1;
+        // The second GOAWAY closes the connection
+        if go_aways == 2 {
+          break;
+        }
+      }
+    }
+    responses
+  });
+
+  let http_router = HttpRouter::paths(paths!(("/hello", get(hello)))).unwrap();
+  let lr = Arc::new(<tokio::runtime::LocalRuntime as Runtime>::new().unwrap());
+  lr.block_on(async {
+    let server = Http2ServerFramework::new(
+      TokioExecutor::default(),
+      ChaCha20::from_std_random().unwrap(),
+      TlsConfig::plaintext(),
+    )
+    .unwrap()
+    .set_shutdown_timeout(Duration::from_secs(5));
+    let signal = server.shutdown_signal();
+    server.set_data(signal).run_local(uri.as_str(), http_router, lr.clone()).await.unwrap();
+  });
+
+  // The upgrade request is answered in the stream `1`. `:status 200` is the 8th entry of the
+  // HPACK static table.
+  assert_eq!(client_jh.join().unwrap(), [(1, Some(0x88))]);
+}
+
 // /aaa ->   /bbb ->  /ccc
 //      \         \
 //       \         -> /ddd
@@ -146,3 +354,163 @@ fn nested_middlewares() {
     }
   });
 }
+
+#[test]
+fn routes_of_middlewares() {
+  struct RouteMw;
+
+  impl Middleware<String, crate::Error> for RouteMw {
+    type Aux = String;
+
+    fn aux(&self) -> Self::Aux {
+      String::new()
+    }
+
+    async fn req(
+      &self,
+      _: &mut String,
+      _: &mut Self::Aux,
+      _: &mut Request<MsgBufferString>,
+    ) -> crate::Result<ControlFlow<StatusCode, ()>> {
+      Ok(ControlFlow::Continue(()))
+    }
+
+    async fn res(
+      &self,
+      data: &mut String,
+      mw_aux: &mut Self::Aux,
+      _: Response<&mut MsgBufferString>,
+    ) -> crate::Result<ControlFlow<StatusCode, ()>> {
+      data.push_str(mw_aux);
+      Ok(ControlFlow::Continue(()))
+    }
+
+    fn route(&self, mw_aux: &mut Self::Aux, route: &[RouteMatch]) {
+      for elem in route {
+        mw_aux.push_str(elem.path());
+      }
+    }
+  }
+
+  async fn endpoint(_: StateClean<'_, String>) -> crate::Result<StatusCode> {
+    Ok(StatusCode::Ok)
+  }
+
+  let http_router = HttpRouter::new(
+    paths!((
+      "/user",
+      HttpRouter::<_, _, _, _, ()>::paths(paths!(("/{id}", get(endpoint)))).unwrap()
+    )),
+    RouteMw,
+  )
+  .unwrap();
+
+  StdRuntime::new().block_on(async {
+    let mut auto_stream = AutoStream {
+      data: String::new(),
+      peer: IpAddr::V4(Ipv4Addr::from_bits(0)),
+      protocol: None,
+      req: Request::new(Method::Get, MsgBufferString::default()),
+    };
+    auto_stream.req.msg_data.uri.reset().push_str("http://localhost/user/123");
+    let path = auto_stream.req.msg_data.uri.path();
+    let path_defs = http_router.router.find(path).unwrap().data().0;
+    let _ = http_router.auto(&mut auto_stream, (0, &path_defs)).await.unwrap();
+    assert_eq!(auto_stream.data, "/user/{id}");
+  });
+}
+
+// FIXME(MIRI): socket support
+#[cfg(not(feature = "tokio"))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn sse_stream_frames() {
+  use crate::{
+    collections::Vector,
+    futures::Sleep,
+    http::{
+      Header, HttpRecvParams, KnownHeaderName, MsgDataMut as _, ReqBuilder,
+      http2_server_framework::{SseEvent, SseStream},
+    },
+    http2::{Http2, Http2Buffer},
+    net::Stream as _,
+    rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+    tests::_uri,
+    tls::{TlsAcceptor, TlsConfig, TlsConnectorBuilder},
+  };
+  use core::{mem, time::Duration};
+  use std::net::TcpListener;
+
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let _server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let tls_stream =
+        TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+          .accept()
+          .await
+          .unwrap()
+          .tls_stream;
+      let (frame_header, http2) = Http2::accept(
+        Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+        HttpRecvParams::with_optioned_params(),
+        tls_stream.into_split().unwrap(),
+      )
+      .await
+      .unwrap();
+      let _jh = runtime.spawn(frame_header);
+      let (mut stream, mut req) =
+        http2.stream(|req, _| mem::take(req.msg_data)).await.unwrap().unwrap();
+      let mut sse_stream = SseStream::new(&mut req.headers, &mut stream).await.unwrap();
+      assert_eq!(sse_stream.last_event_id(), Some("6"));
+      let event = SseEvent::new("hello\nworld").set_id(Some("7")).set_retry(Some(3000));
+      sse_stream.send_event(&event).await.unwrap();
+      sse_stream.send_comment("ping").await.unwrap();
+      let sleep = Sleep::new(Duration::from_millis(50)).unwrap();
+      sse_stream.wait_with_keep_alive(Duration::from_millis(10), sleep).await.unwrap().unwrap();
+      sse_stream.send_event(&SseEvent::new("bye")).await.unwrap();
+      sse_stream.close().await.unwrap();
+      Sleep::new(Duration::from_millis(100)).unwrap().await.unwrap();
+    })
+    .unwrap();
+
+  runtime.block_on(async {
+    let tls_stream = TlsConnectorBuilder::std(&uri)
+      .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+      .await
+      .unwrap()
+      .connect()
+      .await
+      .unwrap()
+      .tls_stream;
+    let (frame_header, http2) = Http2::connect(
+      Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+      HttpRecvParams::with_optioned_params(),
+      tls_stream.into_split().unwrap(),
+    )
+    .await
+    .unwrap();
+    let _jh = runtime.spawn(frame_header).unwrap();
+    let mut msg_buffer = MsgBufferString::default();
+    msg_buffer
+      .headers_mut()
+      .push_from_iter(Header::from_name_and_value(KnownHeaderName::LastEventId.into(), ["6"]))
+      .unwrap();
+    let mut stream = http2.stream().await.unwrap();
+    let rb = ReqBuilder::get((msg_buffer.body.as_ref(), &msg_buffer.headers, uri.to_ref()));
+    let _ = stream.send_req(&mut Vector::new(), rb.into_request()).await.unwrap();
+    let (_, res) = stream.recv_res().await.unwrap();
+    let content_type = res.headers.get_by_name(KnownHeaderName::ContentType.into()).unwrap();
+    assert_eq!(content_type.value, "text/event-stream");
+    let first = b"id: 7\nretry: 3000\ndata: hello\ndata: world\n\n:ping\n\n";
+    let last = b"data: bye\n\n";
+    let (prefix, rest) = res.body.split_at(first.len());
+    assert_eq!(prefix, first);
+    let (keep_alives, suffix) = rest.split_at(rest.len().wrapping_sub(last.len()));
+    assert_eq!(suffix, last);
+    assert!(!keep_alives.is_empty());
+    assert!(keep_alives.chunks(3).all(|chunk| chunk == b":\n\n"));
+  });
+}
@@ -0,0 +1,78 @@
+use crate::{
+  http::{
+    MsgBufferString, Request, Response, StatusCode, TraceContext,
+    http2_server_framework::Middleware,
+  },
+  misc::LeaseMut,
+  rng::Rng,
+  sync::SyncMutex,
+};
+use core::ops::ControlFlow;
+
+/// Extracts the W3C Trace Context of incoming requests and stores the context of the current
+/// operation into the shared data.
+///
+/// Received contexts have their parent identifiers replaced by a new one that represents the
+/// server. Requests without valid contexts start new traces. In both cases, outgoing calls
+/// should use [`TraceContext::child`] to continue the trace.
+#[derive(Debug)]
+pub struct TraceContextMiddleware<RNG> {
+  is_sampled: bool,
+  rng: SyncMutex<RNG>,
+}
+
+impl<RNG> TraceContextMiddleware<RNG>
+where
+  RNG: Rng,
+{
+  /// `is_sampled` is only used when a new trace is started.
+  #[inline]
+  pub const fn new(is_sampled: bool, rng: RNG) -> Self {
+    Self { is_sampled, rng: SyncMutex::new(rng) }
+  }
+}
+
+impl<D, E, RNG> Middleware<D, E> for TraceContextMiddleware<RNG>
+where
+  D: LeaseMut<Option<TraceContext>>,
+  E: From<crate::Error>,
+  RNG: Rng,
+{
+  type Aux = ();
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {}
+
+  #[inline]
+  async fn req(
+    &self,
+    data: &mut D,
+    _: &mut Self::Aux,
+    req: &mut Request<MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    let trace_context = {
+      let mut rng = self.rng.lock();
+      match TraceContext::from_headers(&req.msg_data.headers) {
+        Some(elem) => elem.child(&mut *rng),
+        None => TraceContext::new(self.is_sampled, &mut *rng),
+      }
+    };
+    _trace!(
+      target: crate::_WTX_HTTP,
+      traceparent = trace_context.traceparent()?.as_str(),
+      "Request trace context"
+    );
+    *data.lease_mut() = Some(trace_context);
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut D,
+    _: &mut Self::Aux,
+    _: Response<&mut MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    Ok(ControlFlow::Continue(()))
+  }
+}
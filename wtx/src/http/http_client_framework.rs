@@ -0,0 +1,320 @@
+//! High-level HTTP client that follows redirects, retries failed requests and persists cookies.
+
+mod cookie_jar;
+mod redirect_policy;
+mod retry_policy;
+#[cfg(test)]
+mod tests;
+
+use crate::{
+  calendar::Instant,
+  collections::Vector,
+  futures::{Sleep, Timeout},
+  http::{
+    Header, Headers, HttpClient as _, HttpError, KnownHeaderName, Method, MsgBufferString, MsgData,
+    ReqBuilder, Request, Response, StatusCode, WTX_USER_AGENT,
+    http2_client_pool::{Http2ClientPool, Http2ClientPoolResource, Http2RM, origin},
+  },
+  misc::Lease,
+  net::{StreamWriter, UriRef, UriString},
+  pool::ResourceManager,
+  sync::SyncMutex,
+  tls::TlsCtx,
+};
+use alloc::string::String;
+pub use cookie_jar::CookieJar;
+use core::{
+  fmt::{Debug, Formatter},
+  time::Duration,
+};
+pub use redirect_policy::RedirectPolicy;
+pub use retry_policy::RetryPolicy;
+
+/// High-level HTTP client built on top of [`Http2ClientPool`].
+///
+/// Requests receive the default headers that they don't already have as well as the matching
+/// cookies of the [`CookieJar`], which in turn stores the cookies of all received responses.
+/// Redirects are followed according to [`RedirectPolicy`] and idempotent requests are retried
+/// according to [`RetryPolicy`]. The optional timeout is applied to each attempt.
+pub struct HttpClientFramework<AUX, EX, TCX>
+where
+  Http2RM<AUX, EX, TCX>: ResourceManager,
+{
+  cookie_jar: SyncMutex<CookieJar>,
+  default_headers: Headers,
+  pool: Http2ClientPool<AUX, EX, TCX>,
+  redirect_policy: RedirectPolicy,
+  retry_policy: RetryPolicy,
+  timeout: Option<Duration>,
+}
+
+impl<AUX, EX, TCX> HttpClientFramework<AUX, EX, TCX>
+where
+  Http2RM<AUX, EX, TCX>: ResourceManager,
+{
+  /// Creates a new instance with an empty cookie jar, a `user-agent` default header, the default
+  /// policies and without timeouts.
+  #[inline]
+  pub fn new(pool: Http2ClientPool<AUX, EX, TCX>) -> crate::Result<Self> {
+    let mut default_headers = Headers::new();
+    default_headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::UserAgent.into(),
+      [WTX_USER_AGENT],
+    ))?;
+    Ok(Self {
+      cookie_jar: SyncMutex::new(CookieJar::new()),
+      default_headers,
+      pool,
+      redirect_policy: RedirectPolicy::default(),
+      retry_policy: RetryPolicy::default(),
+      timeout: None,
+    })
+  }
+
+  /// See [`CookieJar`].
+  #[inline]
+  pub const fn cookie_jar(&self) -> &SyncMutex<CookieJar> {
+    &self.cookie_jar
+  }
+
+  /// Headers sent in all requests unless a request has a header with the same name.
+  #[inline]
+  pub const fn default_headers_mut(&mut self) -> &mut Headers {
+    &mut self.default_headers
+  }
+
+  /// See [`Http2ClientPool`].
+  #[inline]
+  pub const fn pool(&self) -> &Http2ClientPool<AUX, EX, TCX> {
+    &self.pool
+  }
+
+  /// See [`RedirectPolicy`].
+  #[inline]
+  pub const fn redirect_policy_mut(&mut self) -> &mut RedirectPolicy {
+    &mut self.redirect_policy
+  }
+
+  /// See [`RetryPolicy`].
+  #[inline]
+  pub const fn retry_policy_mut(&mut self) -> &mut RetryPolicy {
+    &mut self.retry_policy
+  }
+
+  /// Maximum duration of each attempt. Every redirect and every retry is a new attempt with its
+  /// own limit, which means that the total duration of a call can be greater. `None` means no
+  /// limit.
+  #[inline]
+  pub const fn timeout_mut(&mut self) -> &mut Option<Duration> {
+    &mut self.timeout
+  }
+}
+
+impl<AUX, EX, SW, TCX> HttpClientFramework<AUX, EX, TCX>
+where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+  Http2RM<AUX, EX, TCX>: ResourceManager<
+      CreateAux = str,
+      Error = crate::Error,
+      RecycleAux = str,
+      Resource = Http2ClientPoolResource<AUX, SW, TCX>,
+    >,
+{
+  /// Sends a request and receives the final response.
+  ///
+  /// The contents of `req` are copied because redirects and retries may need to send them more
+  /// than once.
+  #[inline]
+  pub async fn send_req_recv_res<MD>(
+    &self,
+    enc_buffer: &mut Vector<u8>,
+    req: Request<MD>,
+  ) -> crate::Result<Response<MsgBufferString>>
+  where
+    MD: MsgData,
+    MD::Body: Lease<[u8]>,
+  {
+    let mut body = Vector::new();
+    body.extend_from_copyable_slice(req.msg_data.body().lease())?;
+    let mut headers = Headers::new();
+    copy_headers(&mut headers, req.msg_data.headers(), |_| true)?;
+    let mut method = req.method;
+    let mut redirects: u8 = 0;
+    let mut uri = UriString::new(String::from(req.msg_data.uri().as_str()));
+    loop {
+      let res = self.send_with_retries(&body, enc_buffer, &headers, method, &uri.to_ref()).await?;
+      if self.redirect_policy == RedirectPolicy::None
+        || !matches!(
+          res.status_code,
+          StatusCode::MovedPermanently
+            | StatusCode::Found
+            | StatusCode::SeeOther
+            | StatusCode::TemporaryRedirect
+            | StatusCode::PermanentRedirect
+        )
+      {
+        return Ok(res);
+      }
+      let Some(location) = res.msg_data.headers.get_by_name(KnownHeaderName::Location.into())
+      else {
+        return Ok(res);
+      };
+      let next_uri = UriString::new(resolve_location(&uri.to_ref(), location.value));
+      let is_cross_origin = origin(&uri.to_ref()) != origin(&next_uri.to_ref());
+      if is_cross_origin && matches!(self.redirect_policy, RedirectPolicy::SameOrigin(_)) {
+        return Ok(res);
+      }
+      if redirects >= self.redirect_policy.max_redirects() {
+        return Err(HttpError::TooManyRedirects.into());
+      }
+      let is_get = (matches!(res.status_code, StatusCode::MovedPermanently | StatusCode::Found)
+        && method == Method::Post)
+        || (res.status_code == StatusCode::SeeOther && method != Method::Head);
+      if is_get {
+        body.clear();
+        method = Method::Get;
+        retain_headers(&mut headers, |el| {
+          !el.eq_ignore_ascii_case(KnownHeaderName::ContentType.into())
+        })?;
+      }
+      if is_cross_origin {
+        retain_headers(&mut headers, |el| {
+          !el.eq_ignore_ascii_case(KnownHeaderName::Authorization.into())
+            && !el.eq_ignore_ascii_case(KnownHeaderName::Cookie.into())
+        })?;
+      }
+      redirects = redirects.wrapping_add(1);
+      uri = next_uri;
+    }
+  }
+
+  async fn send_once(
+    &self,
+    body: &[u8],
+    enc_buffer: &mut Vector<u8>,
+    headers: &Headers,
+    method: Method,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<Response<MsgBufferString>> {
+    let now = Instant::now_timestamp()?;
+    let mut local_headers = Headers::new();
+    copy_headers(&mut local_headers, &self.default_headers, |el| {
+      headers.get_by_name(el.as_bytes()).is_none()
+    })?;
+    copy_headers(&mut local_headers, headers, |_| true)?;
+    self.cookie_jar.lock().push_header(&mut local_headers, now, uri)?;
+    let req = ReqBuilder::new(method, (body, &local_headers, uri.to_ref())).into_request();
+    let future = self.pool.send_req_recv_res(enc_buffer, req);
+    let res = match self.timeout {
+      Some(elem) => Timeout::new(future, elem)?.await?.ok_or(crate::Error::ExpiredFuture)??,
+      None => future.await?,
+    };
+    self.cookie_jar.lock().store_from_headers(&res.msg_data.headers, now, uri)?;
+    Ok(res)
+  }
+
+  async fn send_with_retries(
+    &self,
+    body: &[u8],
+    enc_buffer: &mut Vector<u8>,
+    headers: &Headers,
+    method: Method,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<Response<MsgBufferString>> {
+    let mut retry: u8 = 0;
+    loop {
+      let rslt = self.send_once(body, enc_buffer, headers, method, uri).await;
+      let is_retryable = match &rslt {
+        Ok(res) => matches!(
+          res.status_code,
+          StatusCode::BadGateway | StatusCode::ServiceUnavailable | StatusCode::GatewayTimeout
+        ),
+        Err(err) => is_transport_err(err),
+      };
+      if !is_retryable || !method.is_idempotent() || retry >= self.retry_policy.max_retries {
+        return rslt;
+      }
+      Sleep::new(self.retry_policy.backoff(retry))?.await?;
+      retry = retry.wrapping_add(1);
+    }
+  }
+}
+
+impl<AUX, EX, TCX> Debug for HttpClientFramework<AUX, EX, TCX>
+where
+  Http2RM<AUX, EX, TCX>: ResourceManager,
+{
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("HttpClientFramework")
+      .field("pool", &self.pool)
+      .field("redirect_policy", &self.redirect_policy)
+      .field("retry_policy", &self.retry_policy)
+      .field("timeout", &self.timeout)
+      .finish_non_exhaustive()
+  }
+}
+
+fn copy_headers(
+  to: &mut Headers,
+  from: &Headers,
+  mut cb: impl FnMut(&str) -> bool,
+) -> crate::Result<()> {
+  for header in from.iter() {
+    if cb(header.name) {
+      to.push_from_iter(Header::new(
+        header.is_sensitive,
+        header.is_trailer,
+        header.name,
+        [header.value],
+      ))?;
+    }
+  }
+  Ok(())
+}
+
+// Failures that happened before a response was received, as such, protocol, parsing or
+// security errors are not retried.
+fn is_transport_err(err: &crate::Error) -> bool {
+  match err {
+    crate::Error::ClosedHttpConnection | crate::Error::ExpiredFuture => true,
+    #[cfg(feature = "std")]
+    crate::Error::IoError(_) => true,
+    _ => false,
+  }
+}
+
+// Dot segments are not removed.
+fn resolve_location(base: &UriRef<'_>, location: &str) -> String {
+  let has_scheme = location.split_once("://").is_some_and(|(scheme, _)| {
+    !scheme.is_empty()
+      && scheme.bytes().all(|el| el.is_ascii_alphanumeric() || b"+-.".contains(&el))
+  });
+  if has_scheme {
+    return String::from(location);
+  }
+  let mut rslt = String::new();
+  if location.starts_with("//") {
+    rslt.push_str(base.scheme());
+    rslt.push(':');
+  } else {
+    rslt.push_str(base.origin());
+    if location.starts_with('?') {
+      rslt.push_str(base.path());
+    } else if !location.starts_with('/') {
+      let path = base.path();
+      rslt.push_str(path.get(..path.rfind('/').unwrap_or_default()).unwrap_or_default());
+      rslt.push('/');
+    }
+  }
+  rslt.push_str(location);
+  rslt
+}
+
+fn retain_headers(headers: &mut Headers, mut cb: impl FnMut(&str) -> bool) -> crate::Result<()> {
+  let mut new_headers = Headers::new();
+  copy_headers(&mut new_headers, headers, &mut cb)?;
+  *headers = new_headers;
+  Ok(())
+}
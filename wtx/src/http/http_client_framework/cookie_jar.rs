@@ -0,0 +1,276 @@
+use crate::{
+  collections::Vector,
+  http::{
+    Header, Headers, KnownHeaderName,
+    cookie::{cookie_generic::CookieGeneric, cookie_str::CookieStr},
+  },
+  net::UriRef,
+};
+use alloc::string::String;
+use core::time::Duration;
+
+/// Stores cookies received through `Set-Cookie` headers and returns them in subsequent requests
+/// according to the domain, path, expiry and security rules of RFC 6265.
+///
+/// Public suffixes are not verified, which means that a server can set cookies for domains like
+/// `com` as long as the requested host matches. Like other cookies of this crate, names can't
+/// have more than 15 bytes.
+#[derive(Debug, Default)]
+pub struct CookieJar {
+  cookies: Vector<JarCookie>,
+}
+
+impl CookieJar {
+  /// Empty instance
+  #[inline]
+  pub const fn new() -> Self {
+    Self { cookies: Vector::new() }
+  }
+
+  /// Removes all cookies.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.cookies.clear();
+  }
+
+  /// If there are no cookies.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.cookies.is_empty()
+  }
+
+  /// Number of stored cookies, including expired ones that weren't removed yet.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.cookies.len()
+  }
+
+  /// Pushes a `Cookie` header with all the cookies that should be sent to `uri`, if any.
+  ///
+  /// `now` is the current number of seconds since the UNIX epoch.
+  #[inline]
+  pub fn push_header(
+    &mut self,
+    headers: &mut Headers,
+    now: Duration,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<()> {
+    self.remove_expired(now);
+    let host = uri.hostname();
+    let is_secure = uri.scheme().eq_ignore_ascii_case("https");
+    let path = path_or_slash(uri.path());
+    let mut matches = Vector::new();
+    for cookie in &self.cookies {
+      let generic = &cookie.generic;
+      let is_domain_match = if cookie.is_host_only {
+        host.eq_ignore_ascii_case(&generic.domain)
+      } else {
+        domain_match(host, &generic.domain)
+      };
+      if is_domain_match && path_match(path, &generic.path) && (is_secure || !generic.secure) {
+        matches.push(cookie)?;
+      }
+    }
+    if matches.is_empty() {
+      return Ok(());
+    }
+    matches.sort_by_key(|el| core::cmp::Reverse(el.generic.path.len()));
+    let mut value = String::new();
+    for (idx, cookie) in matches.iter().enumerate() {
+      if idx > 0 {
+        value.push_str("; ");
+      }
+      value.push_str(&cookie.generic.name);
+      value.push('=');
+      value.push_str(&cookie.generic.value);
+    }
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::Cookie.into(),
+      [value.as_str()],
+    ))?;
+    Ok(())
+  }
+
+  /// Parses and stores the contents of a `Set-Cookie` header received from `uri`. Cookies that
+  /// don't satisfy the domain or security rules are ignored and expired cookies remove previous
+  /// entries.
+  ///
+  /// `now` is the current number of seconds since the UNIX epoch.
+  #[inline]
+  pub fn store(&mut self, now: Duration, set_cookie: &str, uri: &UriRef<'_>) -> crate::Result<()> {
+    let Some(cookie) = JarCookie::parse(now, set_cookie, uri)? else {
+      return Ok(());
+    };
+    if let Some(idx) = self.cookies.iter().position(|el| {
+      el.generic.name == cookie.generic.name
+        && el.generic.domain == cookie.generic.domain
+        && el.generic.path == cookie.generic.path
+    }) {
+      drop(self.cookies.remove(idx));
+    }
+    if cookie.expires.is_none_or(|el| el > now.as_secs()) {
+      self.cookies.push(cookie)?;
+    }
+    Ok(())
+  }
+
+  /// Stores the contents of all the `Set-Cookie` headers of a response received from `uri`.
+  /// Headers that can't be parsed are ignored.
+  ///
+  /// `now` is the current number of seconds since the UNIX epoch.
+  #[inline]
+  pub fn store_from_headers(
+    &mut self,
+    headers: &Headers,
+    now: Duration,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<()> {
+    let name: &str = KnownHeaderName::SetCookie.into();
+    for header in headers.iter() {
+      if header.name.eq_ignore_ascii_case(name) {
+        let _rslt = self.store(now, header.value, uri);
+      }
+    }
+    Ok(())
+  }
+
+  fn remove_expired(&mut self, now: Duration) {
+    self.cookies.vec_mut().retain(|el| el.expires.is_none_or(|elem| elem > now.as_secs()));
+  }
+}
+
+#[derive(Debug)]
+struct JarCookie {
+  expires: Option<u64>,
+  generic: CookieGeneric<String, String>,
+  is_host_only: bool,
+}
+
+impl JarCookie {
+  fn parse(now: Duration, set_cookie: &str, uri: &UriRef<'_>) -> crate::Result<Option<Self>> {
+    let generic = CookieStr::parse_encoded(set_cookie)?.generic;
+    let domain = generic.domain.strip_prefix('.').unwrap_or(generic.domain);
+    let host = uri.hostname();
+    if !domain.is_empty() && !domain_match(host, domain) {
+      return Ok(None);
+    }
+    if generic.secure && !uri.scheme().eq_ignore_ascii_case("https") {
+      return Ok(None);
+    }
+    let expires = match (generic.max_age, generic.expires) {
+      (Some(elem), _) => {
+        Some(if elem.is_zero() { 0 } else { now.as_secs().saturating_add(elem.as_secs()) })
+      }
+      (None, Some(elem)) => Some(u64::try_from(elem.timestamp_secs_and_ns().0).unwrap_or_default()),
+      (None, None) => None,
+    };
+    let is_host_only = domain.is_empty();
+    let mut cookie_domain = String::from(if is_host_only { host } else { domain });
+    cookie_domain.make_ascii_lowercase();
+    let path = if generic.path.starts_with('/') { generic.path } else { default_path(uri.path()) };
+    Ok(Some(Self {
+      expires,
+      generic: CookieGeneric {
+        domain: cookie_domain,
+        expires: generic.expires,
+        http_only: generic.http_only,
+        max_age: generic.max_age,
+        name: generic.name,
+        path: String::from(path),
+        same_site: generic.same_site,
+        secure: generic.secure,
+        value: String::from(generic.value),
+      },
+      is_host_only,
+    }))
+  }
+}
+
+// https://www.rfc-editor.org/rfc/rfc6265#section-5.1.4
+fn default_path(uri_path: &str) -> &str {
+  match uri_path.rfind('/') {
+    Some(0) | None => "/",
+    Some(idx) => uri_path.get(..idx).unwrap_or("/"),
+  }
+}
+
+// https://www.rfc-editor.org/rfc/rfc6265#section-5.1.3
+fn domain_match(host: &str, domain: &str) -> bool {
+  if host.eq_ignore_ascii_case(domain) {
+    return true;
+  }
+  let Some(idx) = host.len().checked_sub(domain.len()) else {
+    return false;
+  };
+  let (prefix, suffix) = host.split_at_checked(idx).unwrap_or_default();
+  prefix.ends_with('.')
+    && suffix.eq_ignore_ascii_case(domain)
+    && !host.bytes().all(|el| el.is_ascii_digit() || el == b'.')
+}
+
+fn path_or_slash(path: &str) -> &str {
+  if path.is_empty() { "/" } else { path }
+}
+
+// https://www.rfc-editor.org/rfc/rfc6265#section-5.1.4
+fn path_match(request_path: &str, cookie_path: &str) -> bool {
+  let Some(rest) = request_path.strip_prefix(cookie_path) else {
+    return false;
+  };
+  rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/')
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    http::{Headers, KnownHeaderName, http_client_framework::CookieJar},
+    net::UriRef,
+  };
+  use core::time::Duration;
+
+  const NOW: Duration = Duration::from_secs(1_000_000_000);
+
+  fn cookie_header(jar: &mut CookieJar, uri: &str) -> Option<alloc::string::String> {
+    let mut headers = Headers::new();
+    jar.push_header(&mut headers, NOW, &UriRef::new(uri)).unwrap();
+    headers
+      .get_by_name(KnownHeaderName::Cookie.into())
+      .map(|el| alloc::string::String::from(el.value))
+  }
+
+  #[test]
+  fn applies_domain_and_path_rules() {
+    let mut jar = CookieJar::new();
+    let uri = UriRef::new("https://www.foo.com/a/b");
+    jar.store(NOW, "a=1", &uri).unwrap();
+    jar.store(NOW, "b=2; Domain=.foo.com; Path=/", &uri).unwrap();
+    jar.store(NOW, "c=3; Domain=bar.com", &uri).unwrap();
+    jar.store(NOW, "d=4; Path=/a/b/c", &uri).unwrap();
+    assert_eq!(jar.len(), 3);
+    assert_eq!(cookie_header(&mut jar, "https://www.foo.com/a/x").as_deref(), Some("a=1; b=2"));
+    assert_eq!(cookie_header(&mut jar, "https://api.foo.com/a").as_deref(), Some("b=2"));
+    assert_eq!(
+      cookie_header(&mut jar, "https://www.foo.com/a/b/c/d").as_deref(),
+      Some("d=4; a=1; b=2")
+    );
+    assert_eq!(cookie_header(&mut jar, "https://www.foo.com/ab").as_deref(), Some("b=2"));
+    assert_eq!(cookie_header(&mut jar, "https://bar.com/").as_deref(), None);
+  }
+
+  #[test]
+  fn applies_expiry_and_security_rules() {
+    let mut jar = CookieJar::new();
+    let uri = UriRef::new("https://foo.com");
+    jar.store(NOW, "a=1; Max-Age=10", &uri).unwrap();
+    jar.store(NOW, "b=2; Expires=Sun, 09 Sep 2001 01:46:39 GMT", &uri).unwrap();
+    jar.store(NOW, "c=3; Secure", &uri).unwrap();
+    jar.store(NOW, "d=4; Secure", &UriRef::new("http://foo.com")).unwrap();
+    assert_eq!(cookie_header(&mut jar, "http://foo.com").as_deref(), Some("a=1"));
+    assert_eq!(cookie_header(&mut jar, "https://foo.com").as_deref(), Some("a=1; c=3"));
+    jar.store(NOW, "a=1; Max-Age=0", &uri).unwrap();
+    assert_eq!(cookie_header(&mut jar, "https://foo.com").as_deref(), Some("c=3"));
+    let mut headers = Headers::new();
+    jar.push_header(&mut headers, NOW.saturating_add(Duration::from_secs(20)), &uri).unwrap();
+    assert_eq!(jar.len(), 1);
+  }
+}
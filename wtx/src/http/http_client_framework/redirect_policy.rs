@@ -0,0 +1,34 @@
+/// Determines how responses with a `Location` header and a 301, 302, 303, 307 or 308 status
+/// code are handled by [`crate::http::http_client_framework::HttpClientFramework`].
+///
+/// 301 and 302 responses to `POST` requests as well as 303 responses to anything other than
+/// `HEAD` are followed with a bodiless `GET` request. 307 and 308 responses keep the method and
+/// the body.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RedirectPolicy {
+  /// Follows up to the specified number of redirects to any location. The `Authorization` and
+  /// `Cookie` headers of the original request are removed when the origin changes. Cookies of the
+  /// jar are still sent if they match the new location.
+  Follow(u8),
+  /// Redirect responses are returned to the caller.
+  None,
+  /// Follows up to the specified number of redirects as long as the scheme, host and port remain
+  /// the same. Otherwise, the redirect response is returned to the caller.
+  SameOrigin(u8),
+}
+
+impl RedirectPolicy {
+  pub(crate) const fn max_redirects(self) -> u8 {
+    match self {
+      Self::Follow(elem) | Self::SameOrigin(elem) => elem,
+      Self::None => 0,
+    }
+  }
+}
+
+impl Default for RedirectPolicy {
+  #[inline]
+  fn default() -> Self {
+    Self::Follow(10)
+  }
+}
@@ -0,0 +1,54 @@
+use core::time::Duration;
+
+/// Retries requests with idempotent methods that failed with network errors, closed connections,
+/// timeouts or with the 502, 503 and 504 status codes. Other errors are returned immediately.
+///
+/// The waiting time between attempts doubles after each attempt, starting at `initial_backoff`
+/// and never exceeding `max_backoff`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+  /// Initial waiting time
+  pub initial_backoff: Duration,
+  /// Upper bound of the waiting time
+  pub max_backoff: Duration,
+  /// Maximum number of additional attempts. `0` disables retries.
+  pub max_retries: u8,
+}
+
+impl RetryPolicy {
+  /// Instance that doesn't retry.
+  pub const NONE: Self =
+    Self { initial_backoff: Duration::ZERO, max_backoff: Duration::ZERO, max_retries: 0 };
+
+  /// Waiting time before the retry number `retry`, starting at `0`.
+  #[inline]
+  pub fn backoff(&self, retry: u8) -> Duration {
+    let factor = 1u32.checked_shl(retry.into()).unwrap_or(u32::MAX);
+    self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+  }
+}
+
+impl Default for RetryPolicy {
+  #[inline]
+  fn default() -> Self {
+    Self {
+      initial_backoff: Duration::from_millis(100),
+      max_backoff: Duration::from_secs(5),
+      max_retries: 2,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::http_client_framework::RetryPolicy;
+  use core::time::Duration;
+
+  #[test]
+  fn backoff() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.backoff(0), Duration::from_millis(100));
+    assert_eq!(policy.backoff(3), Duration::from_millis(800));
+    assert_eq!(policy.backoff(200), Duration::from_secs(5));
+  }
+}
@@ -0,0 +1,112 @@
+use crate::{
+  collections::Vector,
+  executor::{StdExecutor, StdRuntime},
+  http::{
+    Header, HttpRecvParams, KnownHeaderName, MsgDataMut as _, ReqBuilder, StatusCode,
+    http_client_framework::{HttpClientFramework, RetryPolicy, resolve_location},
+    http2_client_pool::Http2ClientPoolBuilder,
+  },
+  http2::{Http2, Http2Buffer},
+  net::{Stream as _, UriRef},
+  rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+  tests::_uri,
+  tls::{TlsAcceptor, TlsConfig},
+};
+use alloc::{format, string::String};
+use core::time::Duration;
+use std::net::TcpListener;
+
+// A redirect that sets a cookie is followed with the cookie and a request that received a 503
+// response is retried.
+//
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn follows_redirects_and_retries() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let runtime_fut = runtime;
+  let _server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let tls_stream =
+        TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+          .accept()
+          .await
+          .unwrap()
+          .tls_stream;
+      let (frame_reader, http2) = Http2::accept(
+        Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+        HttpRecvParams::with_optioned_params(),
+        tls_stream.into_split().unwrap(),
+      )
+      .await
+      .unwrap();
+      let _jh = runtime_fut.spawn(frame_reader);
+      let mut has_failed = false;
+      for _ in 0..4 {
+        let (mut stream, _) = http2.stream(|_, _| {}).await.unwrap().unwrap();
+        let (_, mut req_rrb) = stream.recv_req().await.unwrap();
+        let path = String::from(req_rrb.uri.path());
+        let has_cookie = req_rrb
+          .headers
+          .get_by_name(KnownHeaderName::Cookie.into())
+          .is_some_and(|el| el.value == "a=1");
+        req_rrb.clear();
+        let status_code = match path.as_str() {
+          "/flaky" if !has_failed => {
+            has_failed = true;
+            StatusCode::ServiceUnavailable
+          }
+          "/flaky" => StatusCode::Ok,
+          "/redirect" => {
+            let headers = &mut req_rrb.headers;
+            let location = KnownHeaderName::Location.into();
+            let set_cookie = KnownHeaderName::SetCookie.into();
+            headers.push_from_iter(Header::from_name_and_value(location, ["/target"])).unwrap();
+            headers.push_from_iter(Header::from_name_and_value(set_cookie, ["a=1"])).unwrap();
+            StatusCode::SeeOther
+          }
+          "/target" if has_cookie => StatusCode::Ok,
+          _ => StatusCode::BadRequest,
+        };
+        let res = req_rrb.as_response(status_code);
+        let _ = stream.send_res(&mut Vector::new(), res).await.unwrap();
+      }
+    })
+    .unwrap();
+  runtime.block_on(async {
+    let pool = Http2ClientPoolBuilder::new(
+      StdExecutor::default(),
+      1,
+      ChaCha20::from_std_random().unwrap(),
+      TlsConfig::plaintext(),
+    )
+    .unwrap()
+    .build();
+    let mut client = HttpClientFramework::new(pool).unwrap();
+    *client.retry_policy_mut() = RetryPolicy {
+      initial_backoff: Duration::from_millis(1),
+      max_backoff: Duration::from_millis(1),
+      max_retries: 1,
+    };
+    let mut enc_buffer = Vector::new();
+    for (path, status_code) in [("/redirect", StatusCode::Ok), ("/flaky", StatusCode::Ok)] {
+      let local_uri = format!("{}{path}", uri.as_str());
+      let req = ReqBuilder::get(UriRef::new(&local_uri)).into_request();
+      let res = client.send_req_recv_res(&mut enc_buffer, req).await.unwrap();
+      assert_eq!(res.status_code, status_code);
+    }
+  });
+}
+
+#[test]
+fn resolves_locations() {
+  let base = UriRef::new("https://foo.com:8443/a/b?c=d");
+  assert_eq!(resolve_location(&base, "http://bar.com/x"), "http://bar.com/x");
+  assert_eq!(resolve_location(&base, "//bar.com/x"), "https://bar.com/x");
+  assert_eq!(resolve_location(&base, "/x"), "https://foo.com:8443/x");
+  assert_eq!(resolve_location(&base, "x?y=z"), "https://foo.com:8443/a/x?y=z");
+  assert_eq!(resolve_location(&base, "?y=z"), "https://foo.com:8443/a/b?y=z");
+}
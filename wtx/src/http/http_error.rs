@@ -7,6 +7,8 @@ pub enum HttpError {
   BadRequest,
   /// Invalid HTTP/2 or HTTP/3 header
   InvalidHttp2pContent,
+  /// `traceparent` header doesn't follow the W3C Trace Context specification
+  InvalidTraceparent,
   /// Missing Header
   MissingHeader(
     /// Expected header name
@@ -20,6 +22,8 @@ pub enum HttpError {
   MissingUriPlaceholder,
   /// `TlsConfig` is mandatory for TLS connections
   TlsConnectionRequireTlsConfig,
+  /// The maximum number of redirects was exceeded
+  TooManyRedirects,
   /// Content-Type mismatch
   UnexpectedContentType,
   /// HTTP version does not match the expected method.
@@ -27,6 +31,8 @@ pub enum HttpError {
     /// Expected method
     expected: Method,
   },
+  /// Received a status code that isn't expected by the operation
+  UnexpectedHttpStatus(u16),
   /// Unknown header name.
   UnknownHeaderNameFromBytes {
     /// Received length
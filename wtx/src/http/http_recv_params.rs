@@ -3,19 +3,30 @@ use crate::http::{
   DEFAULT_MAX_HEADERS_LEN, DEFAULT_MAX_HPACK_LEN, MAX_FRAME_LEN_LOWER_BOUND,
   MAX_FRAME_LEN_UPPER_BOUND, u31::U31,
 };
+use core::time::Duration;
 
 /// Indicates to a remote peer the receiving parameters of a connection as well as its streams.
 #[derive(Clone, Copy, Debug)]
 pub struct HttpRecvParams {
   enable_connect_protocol: bool,
+  enable_push: bool,
+  idle_timeout: Option<Duration>,
   initial_window_len: U31,
+  keepalive_interval: Option<Duration>,
+  keepalive_timeout: Duration,
   linger: bool,
   max_body_len: u32,
+  max_concurrent_pushes_num: u32,
   max_concurrent_streams_num: u32,
+  max_empty_data_frames_rate: (u32, Duration),
   max_frame_len: u32,
+  max_header_block_len: u32,
   max_headers_len: u32,
   max_hpack_len: (u32, u32),
+  max_ping_frames_rate: (u32, Duration),
   max_recv_streams_num: u32,
+  max_reset_frames_rate: (u32, Duration),
+  max_settings_frames_rate: (u32, Duration),
 }
 
 impl HttpRecvParams {
@@ -26,14 +37,24 @@ impl HttpRecvParams {
   pub const fn with_default_params() -> Self {
     Self {
       enable_connect_protocol: false,
+      enable_push: false,
+      idle_timeout: None,
       initial_window_len: U31::from_u32(DEFAULT_INITIAL_WINDOW_LEN),
+      keepalive_interval: None,
+      keepalive_timeout: Duration::from_secs(20),
       linger: true,
       max_body_len: 1024 * 1024,
+      max_concurrent_pushes_num: 16,
       max_concurrent_streams_num: DEFAULT_MAX_CONCURRENT_STREAMS_NUM,
+      max_empty_data_frames_rate: (32, Duration::from_secs(1)),
       max_frame_len: DEFAULT_MAX_FRAME_LEN,
+      max_header_block_len: 64 * 1024,
       max_headers_len: DEFAULT_MAX_HEADERS_LEN,
       max_hpack_len: (DEFAULT_MAX_HPACK_LEN, DEFAULT_MAX_HPACK_LEN),
+      max_ping_frames_rate: (32, Duration::from_secs(1)),
       max_recv_streams_num: 32,
+      max_reset_frames_rate: (128, Duration::from_secs(1)),
+      max_settings_frames_rate: (32, Duration::from_secs(1)),
     }
   }
 
@@ -43,14 +64,24 @@ impl HttpRecvParams {
   pub const fn with_optioned_params() -> Self {
     Self {
       enable_connect_protocol: false,
+      enable_push: false,
+      idle_timeout: None,
       initial_window_len: U31::from_i32(4 * 1024 * 1024),
+      keepalive_interval: None,
+      keepalive_timeout: Duration::from_secs(20),
       linger: true,
       max_body_len: 64 * 1024 * 1024,
+      max_concurrent_pushes_num: 64,
       max_concurrent_streams_num: 256,
+      max_empty_data_frames_rate: (32, Duration::from_secs(1)),
       max_frame_len: MAX_FRAME_LEN_UPPER_BOUND,
+      max_header_block_len: 256 * 1024,
       max_headers_len: 64 * 1024,
       max_hpack_len: (128 * 1024, 128 * 1024),
+      max_ping_frames_rate: (32, Duration::from_secs(1)),
       max_recv_streams_num: 256,
+      max_reset_frames_rate: (128, Duration::from_secs(1)),
+      max_settings_frames_rate: (32, Duration::from_secs(1)),
     }
   }
 
@@ -59,14 +90,24 @@ impl HttpRecvParams {
   pub const fn with_permissive_params() -> Self {
     Self {
       enable_connect_protocol: false,
+      enable_push: false,
+      idle_timeout: None,
       initial_window_len: U31::MAX,
+      keepalive_interval: None,
+      keepalive_timeout: Duration::from_secs(20),
       linger: true,
       max_body_len: u32::MAX,
+      max_concurrent_pushes_num: u32::MAX,
       max_concurrent_streams_num: u32::MAX,
+      max_empty_data_frames_rate: (u32::MAX, Duration::from_secs(1)),
       max_frame_len: MAX_FRAME_LEN_UPPER_BOUND,
+      max_header_block_len: u32::MAX,
       max_headers_len: u32::MAX,
       max_hpack_len: (u32::MAX, u32::MAX),
+      max_ping_frames_rate: (u32::MAX, Duration::from_secs(1)),
       max_recv_streams_num: u32::MAX,
+      max_reset_frames_rate: (u32::MAX, Duration::from_secs(1)),
+      max_settings_frames_rate: (u32::MAX, Duration::from_secs(1)),
     }
   }
 
@@ -81,6 +122,27 @@ impl HttpRecvParams {
     self.enable_connect_protocol
   }
 
+  /// Enable push
+  ///
+  /// Clients only. Allows servers to send `PUSH_PROMISE` frames, whose promised streams are
+  /// received through [`crate::http2::ClientStream::recv_push_promise`]. Browsers and major
+  /// third-parties deprecated this feature, as such, it is only useful in controlled environments.
+  ///
+  /// Corresponds to `SETTINGS_ENABLE_PUSH`. Defaults to `false`.
+  #[inline]
+  pub const fn enable_push(&self) -> bool {
+    self.enable_push
+  }
+
+  /// Idle timeout
+  ///
+  /// Closes the connection with a GOAWAY frame if there are no active streams and no frames
+  /// other than PING were received within the specified duration. Defaults to `None`.
+  #[inline]
+  pub const fn idle_timeout(&self) -> Option<Duration> {
+    self.idle_timeout
+  }
+
   /// Initial window length
   ///
   /// The initial amount of "credit" a counterpart can have for sending data.
@@ -91,6 +153,26 @@ impl HttpRecvParams {
     self.initial_window_len.u32()
   }
 
+  /// Keepalive interval
+  ///
+  /// Periodically sends PING frames to detect dead connections and to prevent intermediaries
+  /// like NATs or load balancers from discarding quiet connections. Round-trip times are measured
+  /// with the received acknowledgements. Defaults to `None`.
+  #[inline]
+  pub const fn keepalive_interval(&self) -> Option<Duration> {
+    self.keepalive_interval
+  }
+
+  /// Keepalive timeout
+  ///
+  /// If the acknowledgement of a PING frame isn't received within the specified duration, then
+  /// the connection is closed with a GOAWAY frame. Only used when
+  /// [`Self::keepalive_interval`] is set. Defaults to 20 seconds.
+  #[inline]
+  pub const fn keepalive_timeout(&self) -> Duration {
+    self.keepalive_timeout
+  }
+
   /// If true, then streams will remain alive for a short period of time to allow the possible
   /// receiving of control frames.
   #[inline]
@@ -106,6 +188,15 @@ impl HttpRecvParams {
     self.max_body_len
   }
 
+  /// Maximum number of concurrent pushed streams
+  ///
+  /// Clients only. Promised streams that weren't fully received count towards this limit and
+  /// servers that promise more streams have their connections terminated.
+  #[inline]
+  pub const fn max_concurrent_pushes_num(&self) -> u32 {
+    self.max_concurrent_pushes_num
+  }
+
   /// Maximum number of active concurrent streams
   ///
   /// Corresponds to `SETTINGS_MAX_CONCURRENT_STREAMS`.
@@ -114,6 +205,25 @@ impl HttpRecvParams {
     self.max_concurrent_streams_num
   }
 
+  /// Maximum rate of empty DATA frames
+  ///
+  /// The maximum number of received DATA frames without payload and without the END_STREAM flag
+  /// within the specified interval. Such frames don't convey anything and can be used to exhaust
+  /// resources.
+  #[inline]
+  pub const fn max_empty_data_frames_rate(&self) -> (u32, Duration) {
+    self.max_empty_data_frames_rate
+  }
+
+  /// Maximum header block length
+  ///
+  /// The maximum sum of the length of a HEADERS frame and all its following CONTINUATION frames,
+  /// before decoding. Protects against CONTINUATION floods.
+  #[inline]
+  pub const fn max_header_block_len(&self) -> u32 {
+    self.max_header_block_len
+  }
+
   /// Maximum headers length
   ///
   /// The final Request/Response header is composed by the sum of headers and trailers. Contents
@@ -151,6 +261,15 @@ impl HttpRecvParams {
     self.max_frame_len
   }
 
+  /// Maximum rate of PING frames
+  ///
+  /// The maximum number of received PING frames that demand an acknowledgement within the
+  /// specified interval.
+  #[inline]
+  pub const fn max_ping_frames_rate(&self) -> (u32, Duration) {
+    self.max_ping_frames_rate
+  }
+
   /// Maximum number of receiving streams
   ///
   /// Servers only. Prevents clients from opening more than the specified number of streams.
@@ -159,6 +278,23 @@ impl HttpRecvParams {
     self.max_recv_streams_num
   }
 
+  /// Maximum rate of RST_STREAM frames
+  ///
+  /// The maximum number of received RST_STREAM frames within the specified interval. Protects
+  /// against rapid resets (CVE-2023-44487) where streams are opened and immediately cancelled.
+  #[inline]
+  pub const fn max_reset_frames_rate(&self) -> (u32, Duration) {
+    self.max_reset_frames_rate
+  }
+
+  /// Maximum rate of SETTINGS frames
+  ///
+  /// The maximum number of received SETTINGS frames within the specified interval.
+  #[inline]
+  pub const fn max_settings_frames_rate(&self) -> (u32, Duration) {
+    self.max_settings_frames_rate
+  }
+
   /// Mutable version of [`Self::enable_connect_protocol`].
   #[inline]
   #[must_use]
@@ -167,6 +303,22 @@ impl HttpRecvParams {
     self
   }
 
+  /// Mutable version of [`Self::enable_push`].
+  #[inline]
+  #[must_use]
+  pub const fn set_enable_push(mut self, value: bool) -> Self {
+    self.enable_push = value;
+    self
+  }
+
+  /// Mutable version of [`Self::idle_timeout`].
+  #[inline]
+  #[must_use]
+  pub const fn set_idle_timeout(mut self, value: Option<Duration>) -> Self {
+    self.idle_timeout = value;
+    self
+  }
+
   /// Mutable version of [`Self::initial_window_len`].
   #[inline]
   #[must_use]
@@ -175,6 +327,22 @@ impl HttpRecvParams {
     self
   }
 
+  /// Mutable version of [`Self::keepalive_interval`].
+  #[inline]
+  #[must_use]
+  pub const fn set_keepalive_interval(mut self, value: Option<Duration>) -> Self {
+    self.keepalive_interval = value;
+    self
+  }
+
+  /// Mutable version of [`Self::keepalive_timeout`].
+  #[inline]
+  #[must_use]
+  pub const fn set_keepalive_timeout(mut self, value: Duration) -> Self {
+    self.keepalive_timeout = value;
+    self
+  }
+
   /// Mutable version of [`Self::linger`].
   #[inline]
   #[must_use]
@@ -191,6 +359,14 @@ impl HttpRecvParams {
     self
   }
 
+  /// Mutable version of [`Self::max_concurrent_pushes_num`].
+  #[inline]
+  #[must_use]
+  pub const fn set_max_concurrent_pushes_num(mut self, value: u32) -> Self {
+    self.max_concurrent_pushes_num = value;
+    self
+  }
+
   /// Mutable version of [`Self::max_concurrent_streams_num`].
   #[inline]
   #[must_use]
@@ -199,6 +375,22 @@ impl HttpRecvParams {
     self
   }
 
+  /// Mutable version of [`Self::max_empty_data_frames_rate`].
+  #[inline]
+  #[must_use]
+  pub const fn set_max_empty_data_frames_rate(mut self, value: (u32, Duration)) -> Self {
+    self.max_empty_data_frames_rate = value;
+    self
+  }
+
+  /// Mutable version of [`Self::max_header_block_len`].
+  #[inline]
+  #[must_use]
+  pub const fn set_max_header_block_len(mut self, value: u32) -> Self {
+    self.max_header_block_len = value;
+    self
+  }
+
   /// Mutable version of [`Self::max_headers_len`].
   #[inline]
   #[must_use]
@@ -230,6 +422,14 @@ impl HttpRecvParams {
     self
   }
 
+  /// Mutable version of [`Self::max_ping_frames_rate`].
+  #[inline]
+  #[must_use]
+  pub const fn set_max_ping_frames_rate(mut self, value: (u32, Duration)) -> Self {
+    self.max_ping_frames_rate = value;
+    self
+  }
+
   /// Mutable version of [`Self::max_recv_streams_num`].
   #[inline]
   #[must_use]
@@ -237,4 +437,20 @@ impl HttpRecvParams {
     self.max_recv_streams_num = value;
     self
   }
+
+  /// Mutable version of [`Self::max_reset_frames_rate`].
+  #[inline]
+  #[must_use]
+  pub const fn set_max_reset_frames_rate(mut self, value: (u32, Duration)) -> Self {
+    self.max_reset_frames_rate = value;
+    self
+  }
+
+  /// Mutable version of [`Self::max_settings_frames_rate`].
+  #[inline]
+  #[must_use]
+  pub const fn set_max_settings_frames_rate(mut self, value: (u32, Duration)) -> Self {
+    self.max_settings_frames_rate = value;
+    self
+  }
 }
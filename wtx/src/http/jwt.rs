@@ -0,0 +1,84 @@
+//! JSON Web Tokens signed with the JWS compact serialization.
+//!
+//! Tokens are issued by [`JwtSigner`] and checked by [`JwtVerifier`], which validates the
+//! registered claims against a [`JwtValidation`] and selects public keys from a [`JwkSet`].
+//! Encrypted tokens (JWE) are not supported.
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc7519>
+
+mod jwk;
+mod jwk_set;
+mod jws_alg;
+mod jws_header;
+mod jwt_claims;
+mod jwt_error;
+#[cfg(feature = "http2-server-framework")]
+mod jwt_middleware;
+mod jwt_signer;
+mod jwt_validation;
+mod jwt_verifier;
+#[cfg(test)]
+mod tests;
+
+use crate::{
+  asn1::{INTEGER_TAG, SEQUENCE_TAG, asn1_unsigned_integers, decode_asn1_tlv},
+  codec::{Base64Alphabet, decode_base64_into_buffer, encode_base64_into_buffer},
+  collections::{ExpansionTy, Vector},
+};
+pub use jwk::{Jwk, JwkMaterial};
+pub use jwk_set::JwkSet;
+pub use jws_alg::JwsAlg;
+pub use jws_header::JwsHeader;
+pub use jwt_claims::{JwtAudience, JwtClaims};
+pub use jwt_error::JwtError;
+#[cfg(feature = "http2-server-framework")]
+pub use jwt_middleware::JwtMiddleware;
+pub use jwt_signer::{JwsSigningKey, JwtSigner};
+pub use jwt_validation::JwtValidation;
+pub use jwt_verifier::{JwtVerifier, VerifiedJwt};
+
+fn decode_base64url<'buffer>(
+  buffer: &'buffer mut Vector<u8>,
+  bytes: &[u8],
+) -> crate::Result<&'buffer mut [u8]> {
+  decode_base64_into_buffer(Base64Alphabet::UrlNoPad, buffer, bytes)
+}
+
+fn encode_base64url(buffer: &mut Vector<u8>, bytes: &[u8]) -> crate::Result<()> {
+  let _ = encode_base64_into_buffer(Base64Alphabet::UrlNoPad, buffer, bytes)?;
+  Ok(())
+}
+
+// DER `Ecdsa-Sig-Value` into the fixed-size `r || s` representation of RFC 7518.
+fn ecdsa_der_to_raw(
+  buffer: &mut Vector<u8>,
+  coordinate_len: usize,
+  der: &[u8],
+) -> crate::Result<()> {
+  let (SEQUENCE_TAG, _, mut integers, []) = decode_asn1_tlv(der)? else {
+    return Err(JwtError::InvalidSignature.into());
+  };
+  for _ in 0..2 {
+    let (INTEGER_TAG, _, integer, rest) = decode_asn1_tlv(integers)? else {
+      return Err(JwtError::InvalidSignature.into());
+    };
+    integers = rest;
+    let idx = integer.iter().position(|el| *el != 0).unwrap_or(integer.len());
+    let trimmed = integer.get(idx..).unwrap_or_default();
+    let Some(padding) = coordinate_len.checked_sub(trimmed.len()) else {
+      return Err(JwtError::InvalidSignature.into());
+    };
+    buffer.expand(ExpansionTy::Additional(padding), 0)?;
+    buffer.extend_from_copyable_slice(trimmed)?;
+  }
+  Ok(())
+}
+
+// Fixed-size `r || s` representation of RFC 7518 into a DER `Ecdsa-Sig-Value`.
+fn ecdsa_raw_to_der(coordinate_len: usize, raw: &[u8]) -> crate::Result<Vector<u8>> {
+  if raw.len() != coordinate_len.wrapping_mul(2) {
+    return Err(JwtError::InvalidSignature.into());
+  }
+  let (r_int, s_int) = raw.split_at(coordinate_len);
+  asn1_unsigned_integers(&[r_int, s_int])
+}
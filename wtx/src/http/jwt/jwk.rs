@@ -0,0 +1,121 @@
+use crate::{
+  asn1::asn1_unsigned_integers,
+  collections::Vector,
+  http::jwt::{JwsAlg, JwtError, decode_base64url},
+  misc::serde_json_deserialize_from_slice,
+};
+use alloc::string::String;
+
+/// JSON Web Key of <https://datatracker.ietf.org/doc/html/rfc7517> that can verify signatures.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jwk {
+  /// Algorithm that this key is restricted to, if any.
+  pub alg: Option<JwsAlg>,
+  /// Key identifier
+  pub kid: Option<String>,
+  /// See [`JwkMaterial`].
+  pub material: JwkMaterial,
+}
+
+impl Jwk {
+  /// Parses a single JSON object.
+  #[inline]
+  pub fn from_json(bytes: &[u8]) -> crate::Result<Self> {
+    let raw: RawJwk<'_> = serde_json_deserialize_from_slice(bytes)?;
+    Self::from_raw(&raw)
+  }
+
+  /// If the key material and the optional `alg` restriction allow `alg`.
+  #[inline]
+  pub fn is_compatible(&self, alg: JwsAlg) -> bool {
+    if self.alg.is_some_and(|el| el != alg) {
+      return false;
+    }
+    matches!(
+      (&self.material, alg),
+      (JwkMaterial::EcP256(_), JwsAlg::ES256)
+        | (JwkMaterial::EcP384(_), JwsAlg::ES384)
+        | (JwkMaterial::Ed25519(_), JwsAlg::EdDSA)
+        | (JwkMaterial::Oct(_), JwsAlg::HS256 | JwsAlg::HS384)
+        | (JwkMaterial::Rsa(_), JwsAlg::PS256 | JwsAlg::RS256)
+    )
+  }
+
+  pub(crate) fn from_raw(raw: &RawJwk<'_>) -> crate::Result<Self> {
+    let alg = raw.alg.map(JwsAlg::try_from).transpose()?;
+    let material = match (raw.kty, raw.crv) {
+      ("EC", Some("P-256")) => JwkMaterial::EcP256(ec_point(raw, 32)?),
+      ("EC", Some("P-384")) => JwkMaterial::EcP384(ec_point(raw, 48)?),
+      ("OKP", Some("Ed25519")) => {
+        let mut buffer = Vector::new();
+        let _ = decode_base64url(&mut buffer, required(raw.x)?)?;
+        if buffer.len() != 32 {
+          return Err(JwtError::InvalidJwk.into());
+        }
+        JwkMaterial::Ed25519(buffer)
+      }
+      ("oct", _) => {
+        let mut buffer = Vector::new();
+        let _ = decode_base64url(&mut buffer, required(raw.secret)?)?;
+        JwkMaterial::Oct(buffer)
+      }
+      ("RSA", _) => {
+        let mut buffer = Vector::new();
+        let _ = decode_base64url(&mut buffer, required(raw.n)?)?;
+        let n_len = buffer.len();
+        let _ = decode_base64url(&mut buffer, required(raw.exponent)?)?;
+        let (modulus, exponent) = buffer.split_at(n_len);
+        JwkMaterial::Rsa(asn1_unsigned_integers(&[modulus, exponent])?)
+      }
+      _ => return Err(JwtError::InvalidJwk.into()),
+    };
+    Ok(Self { alg, kid: raw.kid.map(String::from), material })
+  }
+}
+
+/// Public parameters of a [`Jwk`] in the formats expected by the crypto backends.
+#[derive(Clone, Debug, PartialEq)]
+pub enum JwkMaterial {
+  /// Uncompressed P-256 point
+  EcP256(Vector<u8>),
+  /// Uncompressed P-384 point
+  EcP384(Vector<u8>),
+  /// Raw Ed25519 public key
+  Ed25519(Vector<u8>),
+  /// HMAC secret
+  Oct(Vector<u8>),
+  /// PKCS#1 `RSAPublicKey`
+  Rsa(Vector<u8>),
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct RawJwk<'any> {
+  pub(crate) alg: Option<&'any str>,
+  pub(crate) crv: Option<&'any str>,
+  #[serde(rename = "e")]
+  pub(crate) exponent: Option<&'any str>,
+  pub(crate) kid: Option<&'any str>,
+  pub(crate) kty: &'any str,
+  pub(crate) n: Option<&'any str>,
+  #[serde(rename = "k")]
+  pub(crate) secret: Option<&'any str>,
+  #[serde(rename = "use")]
+  pub(crate) usage: Option<&'any str>,
+  pub(crate) x: Option<&'any str>,
+  pub(crate) y: Option<&'any str>,
+}
+
+fn ec_point(raw: &RawJwk<'_>, coordinate_len: usize) -> crate::Result<Vector<u8>> {
+  let mut buffer = Vector::new();
+  buffer.push(4)?;
+  let _ = decode_base64url(&mut buffer, required(raw.x)?)?;
+  let _ = decode_base64url(&mut buffer, required(raw.y)?)?;
+  if buffer.len() != coordinate_len.wrapping_mul(2).wrapping_add(1) {
+    return Err(JwtError::InvalidJwk.into());
+  }
+  Ok(buffer)
+}
+
+fn required(opt: Option<&str>) -> crate::Result<&[u8]> {
+  Ok(opt.ok_or(JwtError::InvalidJwk)?.as_bytes())
+}
@@ -0,0 +1,86 @@
+use crate::{
+  collections::Vector,
+  http::jwt::{Jwk, JwsHeader, jwk::RawJwk},
+  misc::serde_json_deserialize_from_slice,
+};
+
+/// Collection of [`Jwk`] used to verify tokens.
+///
+/// Keys are selected by the `kid` header or, in its absence, by their compatibility with `alg`.
+/// Rotations are handled by replacing the set with a freshly fetched JWKS document, which is
+/// usually done when verifications fail with [`crate::http::jwt::JwtError::NoMatchingKey`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JwkSet {
+  keys: Vector<Jwk>,
+}
+
+impl JwkSet {
+  /// Empty instance
+  #[inline]
+  pub const fn new() -> Self {
+    Self { keys: Vector::new() }
+  }
+
+  /// Parses a JWKS document (`{"keys":[...]}`).
+  ///
+  /// Encryption keys and keys with unsupported types or curves are ignored.
+  #[inline]
+  pub fn from_json(bytes: &[u8]) -> crate::Result<Self> {
+    let mut this = Self::new();
+    this.replace_from_json(bytes)?;
+    Ok(this)
+  }
+
+  /// All stored keys
+  #[inline]
+  pub fn keys(&self) -> &[Jwk] {
+    &self.keys
+  }
+
+  /// Adds a new key.
+  #[inline]
+  pub fn push(&mut self, jwk: Jwk) -> crate::Result<()> {
+    self.keys.push(jwk)
+  }
+
+  /// Removes all keys identified by `kid`.
+  #[inline]
+  pub fn remove(&mut self, kid: &str) {
+    self.keys.vec_mut().retain(|el| el.kid.as_deref() != Some(kid));
+  }
+
+  /// Replaces all stored keys with the keys of a JWKS document. The current keys are kept if the
+  /// document is invalid.
+  #[inline]
+  pub fn replace_from_json(&mut self, bytes: &[u8]) -> crate::Result<()> {
+    let raw: RawJwkSet<'_> = serde_json_deserialize_from_slice(bytes)?;
+    let mut keys = Vector::with_capacity(raw.keys.len())?;
+    for raw_jwk in &raw.keys {
+      if raw_jwk.usage.is_some_and(|el| el != "sig") {
+        continue;
+      }
+      if let Ok(jwk) = Jwk::from_raw(raw_jwk) {
+        keys.push(jwk)?;
+      }
+    }
+    self.keys = keys;
+    Ok(())
+  }
+
+  pub(crate) fn candidates<'this>(
+    &'this self,
+    header: &JwsHeader<'_>,
+  ) -> impl Iterator<Item = &'this Jwk> {
+    let alg = header.alg;
+    let kid = header.kid;
+    self.keys.iter().filter(move |el| {
+      el.is_compatible(alg) && kid.is_none_or(|local_kid| el.kid.as_deref() == Some(local_kid))
+    })
+  }
+}
+
+#[derive(serde::Deserialize)]
+struct RawJwkSet<'any> {
+  #[serde(borrow)]
+  keys: Vector<RawJwk<'any>>,
+}
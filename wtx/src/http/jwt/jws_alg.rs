@@ -0,0 +1,63 @@
+use crate::http::jwt::JwtError;
+
+/// Digital signature and MAC algorithms of
+/// <https://datatracker.ietf.org/doc/html/rfc7518#section-3.1>.
+///
+/// `none` is intentionally not supported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JwsAlg {
+  /// Ed25519 as defined in RFC 8037.
+  EdDSA,
+  /// ECDSA using P-256 and SHA-256
+  ES256,
+  /// ECDSA using P-384 and SHA-384
+  ES384,
+  /// HMAC using SHA-256
+  HS256,
+  /// HMAC using SHA-384
+  HS384,
+  /// RSASSA-PSS using SHA-256 and MGF1 with SHA-256
+  PS256,
+  /// RSASSA-PKCS1-v1_5 using SHA-256
+  RS256,
+}
+
+impl JwsAlg {
+  /// Name used by the `alg` header and JWK parameter.
+  #[inline]
+  pub const fn as_str(self) -> &'static str {
+    match self {
+      Self::EdDSA => "EdDSA",
+      Self::ES256 => "ES256",
+      Self::ES384 => "ES384",
+      Self::HS256 => "HS256",
+      Self::HS384 => "HS384",
+      Self::PS256 => "PS256",
+      Self::RS256 => "RS256",
+    }
+  }
+
+  /// If the algorithm uses a shared secret instead of a key pair.
+  #[inline]
+  pub const fn is_symmetric(self) -> bool {
+    matches!(self, Self::HS256 | Self::HS384)
+  }
+}
+
+impl TryFrom<&str> for JwsAlg {
+  type Error = crate::Error;
+
+  #[inline]
+  fn try_from(from: &str) -> Result<Self, Self::Error> {
+    Ok(match from {
+      "EdDSA" => Self::EdDSA,
+      "ES256" => Self::ES256,
+      "ES384" => Self::ES384,
+      "HS256" => Self::HS256,
+      "HS384" => Self::HS384,
+      "PS256" => Self::PS256,
+      "RS256" => Self::RS256,
+      _ => return Err(JwtError::UnsupportedAlg.into()),
+    })
+  }
+}
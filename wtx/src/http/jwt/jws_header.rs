@@ -0,0 +1,49 @@
+use crate::{
+  collections::Vector,
+  http::jwt::{JwsAlg, JwtError},
+  misc::serde_json_deserialize_from_slice,
+};
+
+/// JOSE header of a JWS.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JwsHeader<'any> {
+  /// Signing algorithm
+  pub alg: JwsAlg,
+  /// Identifier of the key used to sign the token.
+  pub kid: Option<&'any str>,
+  /// Media type of the complete token, usually `JWT`.
+  pub typ: Option<&'any str>,
+}
+
+impl<'any> JwsHeader<'any> {
+  pub(crate) fn from_json(bytes: &'any [u8]) -> crate::Result<Self> {
+    let raw: RawHeader<'any> = serde_json_deserialize_from_slice(bytes)?;
+    if raw.crit.is_some() {
+      return Err(JwtError::CriticalHeader.into());
+    }
+    Ok(Self { alg: JwsAlg::try_from(raw.alg)?, kid: raw.kid, typ: raw.typ })
+  }
+
+  pub(crate) fn write_json(self, buffer: &mut Vector<u8>) -> crate::Result<()> {
+    let raw = SerHeader { alg: self.alg.as_str(), kid: self.kid, typ: self.typ };
+    serde_json::to_writer(buffer, &raw)?;
+    Ok(())
+  }
+}
+
+#[derive(serde::Deserialize)]
+struct RawHeader<'any> {
+  alg: &'any str,
+  crit: Option<serde::de::IgnoredAny>,
+  kid: Option<&'any str>,
+  typ: Option<&'any str>,
+}
+
+#[derive(serde::Serialize)]
+struct SerHeader<'any> {
+  alg: &'any str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  kid: Option<&'any str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  typ: Option<&'any str>,
+}
@@ -0,0 +1,52 @@
+use crate::collections::Vector;
+
+/// Registered claims of <https://datatracker.ietf.org/doc/html/rfc7519#section-4.1>.
+///
+/// Temporal claims are UNIX timestamps in seconds. Custom claims can be obtained through
+/// [`crate::http::jwt::VerifiedJwt::custom_claims`].
+#[derive(Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct JwtClaims<'any> {
+  /// Audience
+  #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+  pub aud: Option<JwtAudience<'any>>,
+  /// Expiration time
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub exp: Option<i64>,
+  /// Issued at
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub iat: Option<i64>,
+  /// Issuer
+  #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+  pub iss: Option<&'any str>,
+  /// JWT ID
+  #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+  pub jti: Option<&'any str>,
+  /// Not before
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub nbf: Option<i64>,
+  /// Subject
+  #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+  pub sub: Option<&'any str>,
+}
+
+/// The `aud` claim can be a single string or an array of strings.
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum JwtAudience<'any> {
+  /// Array of audiences
+  #[serde(borrow)]
+  Many(Vector<&'any str>),
+  /// Single audience
+  One(&'any str),
+}
+
+impl JwtAudience<'_> {
+  /// If `audience` is one of the intended recipients.
+  #[inline]
+  pub fn contains(&self, audience: &str) -> bool {
+    match self {
+      Self::Many(elem) => elem.contains(&audience),
+      Self::One(elem) => *elem == audience,
+    }
+  }
+}
@@ -0,0 +1,28 @@
+/// JWT error
+#[derive(Clone, Copy, Debug)]
+pub enum JwtError {
+  /// The `aud` claim doesn't contain the expected audience.
+  AudienceMismatch,
+  /// Tokens with a `crit` header are rejected because no extension is understood.
+  CriticalHeader,
+  /// The `exp` claim, plus leeway, is in the past.
+  ExpiredToken,
+  /// A JSON Web Key is malformed or has unsupported parameters.
+  InvalidJwk,
+  /// The signature doesn't match any of the candidate keys.
+  InvalidSignature,
+  /// A token must contain three base64url parts separated by dots.
+  InvalidToken,
+  /// The `iss` claim differs from the expected issuer.
+  IssuerMismatch,
+  /// The signing key can't be used with the chosen algorithm.
+  MismatchedSigningKey,
+  /// Validation requires an `exp` claim.
+  MissingExp,
+  /// There are no keys compatible with the `kid` and `alg` of the header.
+  NoMatchingKey,
+  /// The `nbf` claim, minus leeway, is in the future.
+  PrematureToken,
+  /// The algorithm is unknown, is `none` or is not supported by the crypto backends.
+  UnsupportedAlg,
+}
@@ -0,0 +1,107 @@
+use crate::{
+  calendar::Instant,
+  collections::Vector,
+  http::{
+    KnownHeaderName, MsgBufferString, Request, Response, StatusCode,
+    http2_server_framework::Middleware, jwt::JwtVerifier,
+  },
+  misc::LeaseMut,
+};
+use alloc::string::String;
+use core::{marker::PhantomData, ops::ControlFlow};
+use serde::de::DeserializeOwned;
+
+/// Verifies `Authorization: Bearer` tokens and stores their claims in the connection data. The
+/// authentication scheme is case-insensitive.
+///
+/// Requests without a valid token are answered with `401 Unauthorized` unless their paths are
+/// listed in `allowed_paths`. Keys can be rotated by replacing [`JwtVerifier::keys`].
+#[derive(Debug)]
+pub struct JwtMiddleware<C> {
+  allowed_paths: Vector<String>,
+  phantom: PhantomData<C>,
+  verifier: JwtVerifier,
+}
+
+impl<C> JwtMiddleware<C> {
+  /// New instance
+  #[inline]
+  pub const fn new(allowed_paths: Vector<String>, verifier: JwtVerifier) -> Self {
+    Self { allowed_paths, phantom: PhantomData, verifier }
+  }
+
+  /// See [`JwtVerifier`].
+  #[inline]
+  pub const fn verifier(&self) -> &JwtVerifier {
+    &self.verifier
+  }
+
+  /// Mutable version of [`Self::verifier`].
+  #[inline]
+  pub const fn verifier_mut(&mut self) -> &mut JwtVerifier {
+    &mut self.verifier
+  }
+}
+
+impl<C, D, E> Middleware<D, E> for JwtMiddleware<C>
+where
+  C: DeserializeOwned,
+  D: LeaseMut<Option<C>>,
+  E: From<crate::Error>,
+{
+  type Aux = Vector<u8>;
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {
+    Vector::new()
+  }
+
+  #[inline]
+  async fn req(
+    &self,
+    data: &mut D,
+    mw_aux: &mut Self::Aux,
+    req: &mut Request<MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    *data.lease_mut() = None;
+    let mut token = None;
+    for header in req.msg_data.headers.iter() {
+      if header.name == <&str>::from(KnownHeaderName::Authorization) {
+        token = bearer_token(header.value);
+        break;
+      }
+    }
+    if let Some(elem) = token {
+      let now = Instant::now_date_time()?;
+      let rslt =
+        self.verifier.verify(mw_aux, now, elem).and_then(|verified| verified.custom_claims::<C>());
+      mw_aux.clear();
+      if let Ok(claims) = rslt {
+        _trace!(target: crate::_WTX_HTTP, "Bearer token has been successfully verified");
+        *data.lease_mut() = Some(claims);
+        return Ok(ControlFlow::Continue(()));
+      }
+      _trace!(target: crate::_WTX_HTTP, "Bearer token is invalid");
+    }
+    let path = req.msg_data.uri.path();
+    if self.allowed_paths.iter().all(|el| el != path) {
+      return Ok(ControlFlow::Break(StatusCode::Unauthorized));
+    }
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut D,
+    _: &mut Self::Aux,
+    _: Response<&mut MsgBufferString>,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+fn bearer_token(value: &str) -> Option<&str> {
+  let (scheme, token) = value.split_at_checked(7)?;
+  scheme.eq_ignore_ascii_case("Bearer ").then_some(token)
+}
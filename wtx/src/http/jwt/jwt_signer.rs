@@ -0,0 +1,148 @@
+use crate::{
+  collections::Vector,
+  crypto::{
+    DynSigningKey, DynSigningOutput, HashTy, Hmac, HmacSha256Global, HmacSha384Global,
+    SigningOutput,
+  },
+  http::jwt::{JwsAlg, JwsHeader, JwtError, ecdsa_der_to_raw, encode_base64url},
+  rng::CryptoRng,
+};
+use alloc::{boxed::Box, string::String};
+use serde::Serialize;
+
+/// Private material used by [`JwtSigner`].
+#[derive(Debug)]
+pub enum JwsSigningKey {
+  /// Key pair used by `ES256`, `ES384`, `EdDSA`, `PS256` or `RS256`. RSA keys must be created
+  /// with [`HashTy::Sha256`].
+  Dyn(Box<DynSigningKey>),
+  /// Shared secret used by `HS256` or `HS384`.
+  Hmac(Vector<u8>),
+}
+
+/// Issues tokens using the JWS compact serialization.
+#[derive(Debug)]
+pub struct JwtSigner {
+  alg: JwsAlg,
+  key: JwsSigningKey,
+  kid: Option<String>,
+}
+
+impl JwtSigner {
+  /// New instance that verifies if `key` can be used with `alg`.
+  ///
+  /// `kid` is written into the header of all issued tokens.
+  #[inline]
+  pub fn new(alg: JwsAlg, key: JwsSigningKey, kid: Option<String>) -> crate::Result<Self> {
+    if !is_compatible(alg, &key) {
+      return Err(JwtError::MismatchedSigningKey.into());
+    }
+    Ok(Self { alg, key, kid })
+  }
+
+  /// See [`JwsAlg`].
+  #[inline]
+  pub const fn alg(&self) -> JwsAlg {
+    self.alg
+  }
+
+  /// Identifier of the current key.
+  #[inline]
+  pub fn kid(&self) -> Option<&str> {
+    self.kid.as_deref()
+  }
+
+  /// Replaces the current key. Verifiers should already know the new public key and keep the old
+  /// one until all previously issued tokens expire.
+  #[inline]
+  pub fn rotate(
+    &mut self,
+    alg: JwsAlg,
+    key: JwsSigningKey,
+    kid: Option<String>,
+  ) -> crate::Result<()> {
+    *self = Self::new(alg, key, kid)?;
+    Ok(())
+  }
+
+  /// Appends a signed token whose payload is the JSON representation of `claims` into `buffer`.
+  ///
+  /// `claims` is usually [`crate::http::jwt::JwtClaims`] or a structure that flattens it.
+  #[inline]
+  pub fn sign<RNG, T>(
+    &self,
+    buffer: &mut Vector<u8>,
+    claims: &T,
+    rng: &mut RNG,
+  ) -> crate::Result<()>
+  where
+    RNG: CryptoRng,
+    T: Serialize,
+  {
+    let start = buffer.len();
+    let mut scratch = Vector::new();
+    let header = JwsHeader { alg: self.alg, kid: self.kid.as_deref(), typ: Some("JWT") };
+    header.write_json(&mut scratch)?;
+    encode_base64url(buffer, &scratch)?;
+    buffer.push(b'.')?;
+    scratch.clear();
+    serde_json::to_writer(&mut scratch, claims)?;
+    encode_base64url(buffer, &scratch)?;
+    scratch.clear();
+    let msg = buffer.get(start..).unwrap_or_default();
+    match &self.key {
+      JwsSigningKey::Dyn(key) => dyn_signature(&mut scratch, &key.sign(msg, rng)?)?,
+      JwsSigningKey::Hmac(secret) => {
+        if self.alg == JwsAlg::HS384 {
+          scratch.extend_from_copyable_slice(hmac::<HmacSha384Global>(secret, msg)?.as_ref())?;
+        } else {
+          scratch.extend_from_copyable_slice(hmac::<HmacSha256Global>(secret, msg)?.as_ref())?;
+        }
+      }
+    }
+    buffer.push(b'.')?;
+    encode_base64url(buffer, &scratch)?;
+    Ok(())
+  }
+}
+
+#[allow(clippy::match_same_arms, reason = "depends on feature")]
+fn dyn_signature(buffer: &mut Vector<u8>, output: &DynSigningOutput) -> crate::Result<()> {
+  match output {
+    DynSigningOutput::EcdsaP256(_) => ecdsa_der_to_raw(buffer, 32, output.as_ref()),
+    DynSigningOutput::EcdsaP384(_) => ecdsa_der_to_raw(buffer, 48, output.as_ref()),
+    DynSigningOutput::Ed25519(_) => buffer.extend_from_copyable_slice(output.as_ref()),
+    DynSigningOutput::RsaPkcs1(so) => rsa_signature(buffer, &so.as_bytes()),
+    DynSigningOutput::RsaPss(so) => rsa_signature(buffer, &so.as_bytes()),
+  }
+}
+
+fn hmac<H>(secret: &[u8], msg: &[u8]) -> crate::Result<H::Digest>
+where
+  H: Hmac,
+{
+  let mut instance = H::from_key(secret)?;
+  instance.update(msg);
+  Ok(instance.finalize())
+}
+
+fn is_compatible(alg: JwsAlg, key: &JwsSigningKey) -> bool {
+  match key {
+    JwsSigningKey::Dyn(elem) => matches!(
+      (alg, &**elem),
+      (JwsAlg::EdDSA, DynSigningKey::Ed25519(_))
+        | (JwsAlg::ES256, DynSigningKey::EcdsaP256(_))
+        | (JwsAlg::ES384, DynSigningKey::EcdsaP384(_))
+        | (JwsAlg::PS256, DynSigningKey::RsaPss(_))
+        | (JwsAlg::RS256, DynSigningKey::RsaPkcs1(_))
+    ),
+    JwsSigningKey::Hmac(_) => alg.is_symmetric(),
+  }
+}
+
+fn rsa_signature(buffer: &mut Vector<u8>, so: &SigningOutput<&[u8]>) -> crate::Result<()> {
+  if so.hash_ty() != HashTy::Sha256 {
+    return Err(JwtError::MismatchedSigningKey.into());
+  }
+  buffer.extend_from_copyable_slice(so.signature())
+}
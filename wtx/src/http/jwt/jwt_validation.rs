@@ -0,0 +1,63 @@
+use crate::{
+  calendar::{DateTime, Utc},
+  http::jwt::{JwtClaims, JwtError},
+};
+use alloc::string::String;
+use core::time::Duration;
+
+/// Rules applied to the registered claims of verified tokens.
+#[derive(Clone, Debug)]
+pub struct JwtValidation {
+  /// If set, `aud` must contain this value.
+  pub audience: Option<String>,
+  /// If set, `iss` must be equal to this value.
+  pub issuer: Option<String>,
+  /// Tolerated clock skew when checking `exp` and `nbf`. Defaults to 60 seconds.
+  pub leeway: Duration,
+  /// Rejects tokens without an `exp` claim. Defaults to `true`.
+  pub require_exp: bool,
+}
+
+impl JwtValidation {
+  /// Checks `claims` against the current instance using `now` as the current time.
+  #[inline]
+  pub fn validate(&self, claims: &JwtClaims<'_>, now: DateTime<Utc>) -> crate::Result<()> {
+    let timestamp = now.timestamp_secs_and_ns().0;
+    let leeway = i64::try_from(self.leeway.as_secs()).unwrap_or(i64::MAX);
+    match claims.exp {
+      Some(exp) => {
+        if timestamp > exp.saturating_add(leeway) {
+          return Err(JwtError::ExpiredToken.into());
+        }
+      }
+      None => {
+        if self.require_exp {
+          return Err(JwtError::MissingExp.into());
+        }
+      }
+    }
+    if let Some(nbf) = claims.nbf
+      && timestamp < nbf.saturating_sub(leeway)
+    {
+      return Err(JwtError::PrematureToken.into());
+    }
+    if let Some(issuer) = &self.issuer
+      && claims.iss != Some(issuer.as_str())
+    {
+      return Err(JwtError::IssuerMismatch.into());
+    }
+    if let Some(audience) = &self.audience
+      && !claims.aud.as_ref().is_some_and(|el| el.contains(audience))
+    {
+      return Err(JwtError::AudienceMismatch.into());
+    }
+    Ok(())
+  }
+}
+
+impl Default for JwtValidation {
+  #[inline]
+  fn default() -> Self {
+    Self { audience: None, issuer: None, leeway: Duration::from_secs(60), require_exp: true }
+  }
+}
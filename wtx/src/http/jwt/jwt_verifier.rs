@@ -0,0 +1,151 @@
+use crate::{
+  calendar::{DateTime, Utc},
+  collections::Vector,
+  crypto::{
+    EcdsaP256SigningKeyGlobal, EcdsaP384SigningKeyGlobal, Ed25519SigningKeyGlobal, HashTy, Hmac,
+    HmacSha256Global, HmacSha384Global, RsaPkcs1SigningKeyGlobal, RsaPssSigningKeyGlobal,
+    SigningKey as _, SigningOutput,
+  },
+  http::jwt::{
+    JwkMaterial, JwkSet, JwsAlg, JwsHeader, JwtClaims, JwtError, JwtValidation, decode_base64url,
+    ecdsa_raw_to_der,
+  },
+  misc::serde_json_deserialize_from_slice,
+};
+use serde::Deserialize;
+
+/// Verifies tokens issued with the JWS compact serialization.
+#[derive(Debug)]
+pub struct JwtVerifier {
+  /// Public keys or shared secrets. Can be replaced at any time to rotate keys.
+  pub keys: JwkSet,
+  /// See [`JwtValidation`].
+  pub validation: JwtValidation,
+}
+
+impl JwtVerifier {
+  /// New instance
+  #[inline]
+  pub const fn new(keys: JwkSet, validation: JwtValidation) -> Self {
+    Self { keys, validation }
+  }
+
+  /// Decodes `token` into `buffer`, checks its signature against all compatible keys and then
+  /// validates the registered claims using `now` as the current time.
+  #[inline]
+  pub fn verify<'buffer>(
+    &self,
+    buffer: &'buffer mut Vector<u8>,
+    now: DateTime<Utc>,
+    token: &str,
+  ) -> crate::Result<VerifiedJwt<'buffer>> {
+    let mut parts = token.split('.');
+    let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) =
+      (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+      return Err(JwtError::InvalidToken.into());
+    };
+    let start = buffer.len();
+    let header_len = decode_base64url(buffer, header_b64.as_bytes())?.len();
+    let payload_len = decode_base64url(buffer, payload_b64.as_bytes())?.len();
+    let _ = decode_base64url(buffer, signature_b64.as_bytes())?;
+    let local_buffer: &'buffer Vector<u8> = buffer;
+    let decoded = local_buffer.get(start..).unwrap_or_default();
+    let (header_bytes, rest) = decoded.split_at_checked(header_len).unwrap_or_default();
+    let (payload, signature) = rest.split_at_checked(payload_len).unwrap_or_default();
+    let header = JwsHeader::from_json(header_bytes)?;
+    let msg_len = header_b64.len().wrapping_add(1).wrapping_add(payload_b64.len());
+    let msg = token.as_bytes().get(..msg_len).unwrap_or_default();
+    let mut has_candidates = false;
+    let mut is_valid = false;
+    for jwk in self.keys.candidates(&header) {
+      has_candidates = true;
+      if verify_signature(header.alg, &jwk.material, msg, signature).is_ok() {
+        is_valid = true;
+        break;
+      }
+    }
+    if !has_candidates {
+      return Err(JwtError::NoMatchingKey.into());
+    }
+    if !is_valid {
+      return Err(JwtError::InvalidSignature.into());
+    }
+    let claims: JwtClaims<'buffer> = serde_json_deserialize_from_slice(payload)?;
+    self.validation.validate(&claims, now)?;
+    Ok(VerifiedJwt { claims, header, payload })
+  }
+}
+
+/// A token whose signature and registered claims were successfully verified.
+#[derive(Debug)]
+pub struct VerifiedJwt<'any> {
+  /// See [`JwtClaims`].
+  pub claims: JwtClaims<'any>,
+  /// See [`JwsHeader`].
+  pub header: JwsHeader<'any>,
+  /// Decoded JSON payload
+  pub payload: &'any [u8],
+}
+
+impl<'any> VerifiedJwt<'any> {
+  /// Deserializes the payload into a custom structure.
+  #[inline]
+  pub fn custom_claims<T>(&self) -> crate::Result<T>
+  where
+    T: Deserialize<'any>,
+  {
+    serde_json_deserialize_from_slice(self.payload)
+  }
+}
+
+fn verify_hmac<H>(secret: &[u8], msg: &[u8], tag: &[u8]) -> crate::Result<()>
+where
+  H: Hmac,
+{
+  let mut instance = H::from_key(secret)?;
+  instance.update(msg);
+  instance.verify(tag)
+}
+
+fn verify_signature(
+  alg: JwsAlg,
+  material: &JwkMaterial,
+  msg: &[u8],
+  signature: &[u8],
+) -> crate::Result<()> {
+  match (alg, material) {
+    (JwsAlg::EdDSA, JwkMaterial::Ed25519(pk)) => {
+      Ed25519SigningKeyGlobal::validate(msg, pk, &SigningOutput::new(HashTy::Sha512, signature))
+    }
+    (JwsAlg::ES256, JwkMaterial::EcP256(pk)) => {
+      let der = ecdsa_raw_to_der(32, signature)?;
+      EcdsaP256SigningKeyGlobal::validate(
+        msg,
+        pk,
+        &SigningOutput::new(HashTy::Sha256, der.as_slice()),
+      )
+    }
+    (JwsAlg::ES384, JwkMaterial::EcP384(pk)) => {
+      let der = ecdsa_raw_to_der(48, signature)?;
+      EcdsaP384SigningKeyGlobal::validate(
+        msg,
+        pk,
+        &SigningOutput::new(HashTy::Sha384, der.as_slice()),
+      )
+    }
+    (JwsAlg::HS256, JwkMaterial::Oct(secret)) => {
+      verify_hmac::<HmacSha256Global>(secret, msg, signature)
+    }
+    (JwsAlg::HS384, JwkMaterial::Oct(secret)) => {
+      verify_hmac::<HmacSha384Global>(secret, msg, signature)
+    }
+    (JwsAlg::PS256, JwkMaterial::Rsa(pk)) => {
+      RsaPssSigningKeyGlobal::validate(msg, pk, &SigningOutput::new(HashTy::Sha256, signature))
+    }
+    (JwsAlg::RS256, JwkMaterial::Rsa(pk)) => {
+      RsaPkcs1SigningKeyGlobal::validate(msg, pk, &SigningOutput::new(HashTy::Sha256, signature))
+    }
+    _ => Err(JwtError::NoMatchingKey.into()),
+  }
+}
@@ -40,6 +40,14 @@ impl Method {
   /// The number of variants
   pub const VARIANTS: u8 = 9;
 
+  /// If multiple identical requests have the same effect as a single request.
+  ///
+  /// <https://www.rfc-editor.org/rfc/rfc9110#section-9.2.2>
+  #[inline]
+  pub const fn is_idempotent(self) -> bool {
+    matches!(self, Self::Delete | Self::Get | Self::Head | Self::Options | Self::Put | Self::Trace)
+  }
+
   /// If the method is intended to modify something. For example, `POST`.
   #[inline]
   pub const fn is_mutable(self) -> bool {
@@ -3,6 +3,10 @@ use crate::collections::ShortStrU8;
 /// Used to specify the data type that is going to be sent to a counterpart.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Mime {
+  /// application/cbor
+  ApplicationCbor,
+  /// application/dns-message
+  ApplicationDnsMessage,
   /// application/grpc
   ApplicationGrpc,
   /// application/json
@@ -55,6 +59,8 @@ pub enum Mime {
   TextCss,
   /// text/csv
   TextCsv,
+  /// text/event-stream
+  TextEventStream,
   /// text/html
   TextHtml,
   /// text/javascript
@@ -76,6 +82,8 @@ impl Mime {
   #[inline]
   pub fn as_str(&self) -> &'static str {
     match self {
+      Self::ApplicationCbor => "application/cbor",
+      Self::ApplicationDnsMessage => "application/dns-message",
       Self::ApplicationGrpc => "application/grpc",
       Self::ApplicationJson => "application/json",
       Self::ApplicationOctetStream => "application/octet-stream",
@@ -102,6 +110,7 @@ impl Mime {
       Self::MultipartFormData => "multipart/form-data",
       Self::TextCss => "text/css",
       Self::TextCsv => "text/csv",
+      Self::TextEventStream => "text/event-stream",
       Self::TextHtml => "text/html",
       Self::TextJavascript => "text/javascript",
       Self::TextMarkdown => "text/markdown",
@@ -1,5 +1,14 @@
 use crate::http::{Headers, KnownHeaderName, Method, Protocol};
 
+/// Verifies if the `no-masking` parameter of WebSocket connections is present in `headers`.
+#[cfg(all(feature = "http2", feature = "web-socket"))]
+pub(crate) fn has_web_socket_no_masking(headers: &Headers) -> bool {
+  let header = KnownHeaderName::SecWebsocketExtensions.into();
+  headers.get_by_name(header).is_some_and(|el| {
+    el.value.split(',').any(|elem| elem.trim().eq_ignore_ascii_case("no-masking"))
+  })
+}
+
 /// Verifies if the initial received HTTP/2 headers represent a WebSocket connection.
 #[inline]
 pub fn is_web_socket_handshake(
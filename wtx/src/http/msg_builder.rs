@@ -4,7 +4,7 @@ mod res_builder;
 use crate::{
   codec::u32_string,
   collections::Vector,
-  http::{Header, KnownHeaderName, Mime, MsgDataMut},
+  http::{Header, KnownHeaderName, Mime, MsgDataMut, TraceContext},
   misc::{Either, LeaseMut},
 };
 use core::fmt::Arguments;
@@ -167,6 +167,13 @@ where
     Ok(self)
   }
 
+  /// Propagates a distributed trace through the `traceparent` and `tracestate` headers.
+  #[inline]
+  pub fn trace_context(&mut self, value: &TraceContext) -> crate::Result<&mut Self> {
+    value.push_headers(self.msg_data.headers_mut())?;
+    Ok(self)
+  }
+
   /// Characteristic string that lets servers and network peers identify the application.
   #[inline]
   pub fn user_agent<'left, 'right, I>(&mut self, value: I) -> crate::Result<&mut Self>
@@ -0,0 +1,35 @@
+//! OAuth 2.0 and OpenID Connect client flows.
+//!
+//! [`OAuth2Client`] performs the authorization code grant with PKCE, the client credentials grant
+//! and the refresh token grant over any [`crate::http::HttpClient`]. Providers can be discovered
+//! through [`OidcProviderMetadata`] and ID tokens are checked by [`IdTokenVerifier`].
+//!
+//! [`OAuth2TokenCache`] keeps an access token that is renewed before its expiration. With the
+//! `client-api-framework` feature, APIs can be wrapped by `OAuth2Api` to automatically
+//! authenticate all HTTP requests of their packages.
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc6749>
+
+mod id_token_verifier;
+#[cfg(feature = "client-api-framework")]
+mod oauth2_api;
+mod oauth2_client;
+mod oauth2_config;
+mod oauth2_error;
+mod oauth2_token;
+mod oauth2_token_cache;
+mod oidc_provider_metadata;
+mod pkce;
+#[cfg(test)]
+mod tests;
+
+pub use id_token_verifier::{IdTokenClaims, IdTokenVerifier};
+#[cfg(feature = "client-api-framework")]
+pub use oauth2_api::OAuth2Api;
+pub use oauth2_client::OAuth2Client;
+pub use oauth2_config::OAuth2Config;
+pub use oauth2_error::{OAuth2Error, OAuth2ErrorCode};
+pub use oauth2_token::OAuth2Token;
+pub use oauth2_token_cache::OAuth2TokenCache;
+pub use oidc_provider_metadata::OidcProviderMetadata;
+pub use pkce::Pkce;
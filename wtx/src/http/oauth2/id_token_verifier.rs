@@ -0,0 +1,84 @@
+use crate::{
+  calendar::{DateTime, Utc},
+  collections::Vector,
+  http::{
+    jwt::{JwkSet, JwtAudience, JwtValidation, JwtVerifier, VerifiedJwt},
+    oauth2::OAuth2Error,
+  },
+};
+use alloc::string::String;
+
+/// Claims of <https://openid.net/specs/openid-connect-core-1_0.html#IDToken> that aren't
+/// registered JWT claims.
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+pub struct IdTokenClaims<'any> {
+  /// Time when the authentication occurred
+  #[serde(default)]
+  pub auth_time: Option<i64>,
+  /// Authorized party
+  #[serde(borrow, default)]
+  pub azp: Option<&'any str>,
+  /// Value sent in the authentication request
+  #[serde(borrow, default)]
+  pub nonce: Option<&'any str>,
+}
+
+/// Validates ID tokens issued by an OpenID Connect provider to a specific client.
+#[derive(Debug)]
+pub struct IdTokenVerifier {
+  client_id: String,
+  verifier: JwtVerifier,
+}
+
+impl IdTokenVerifier {
+  /// Tokens must be signed by one of the `keys`, issued by `issuer` and intended to `client_id`.
+  #[inline]
+  pub fn new(client_id: String, issuer: String, keys: JwkSet) -> Self {
+    let validation = JwtValidation {
+      audience: Some(client_id.clone()),
+      issuer: Some(issuer),
+      ..JwtValidation::default()
+    };
+    Self { client_id, verifier: JwtVerifier::new(keys, validation) }
+  }
+
+  /// Verifies the signature and the claims of `token` using `now` as the current time.
+  ///
+  /// `nonce` must be the value sent in the authentication request, if any.
+  #[inline]
+  pub fn verify<'buffer>(
+    &self,
+    buffer: &'buffer mut Vector<u8>,
+    nonce: Option<&str>,
+    now: DateTime<Utc>,
+    token: &str,
+  ) -> crate::Result<(VerifiedJwt<'buffer>, IdTokenClaims<'buffer>)> {
+    let verified = self.verifier.verify(buffer, now, token)?;
+    let claims: IdTokenClaims<'buffer> = verified.custom_claims()?;
+    if let Some(elem) = nonce
+      && claims.nonce != Some(elem)
+    {
+      return Err(OAuth2Error::MismatchedNonce.into());
+    }
+    let has_many_audiences =
+      matches!(&verified.claims.aud, Some(JwtAudience::Many(elem)) if elem.len() > 1);
+    match claims.azp {
+      Some(azp) if azp != self.client_id => return Err(OAuth2Error::MismatchedAzp.into()),
+      None if has_many_audiences => return Err(OAuth2Error::MismatchedAzp.into()),
+      _ => {}
+    }
+    Ok((verified, claims))
+  }
+
+  /// See [`JwtVerifier`].
+  #[inline]
+  pub const fn verifier(&self) -> &JwtVerifier {
+    &self.verifier
+  }
+
+  /// Mutable version of [`Self::verifier`]. Useful to replace keys after fetching a new JWK set.
+  #[inline]
+  pub const fn verifier_mut(&mut self) -> &mut JwtVerifier {
+    &mut self.verifier
+  }
+}
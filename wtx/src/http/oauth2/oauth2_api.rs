@@ -0,0 +1,74 @@
+use crate::{
+  calendar::Instant,
+  client_api_framework::Api,
+  http::{HttpClient, oauth2::OAuth2TokenCache},
+};
+
+/// [`Api`] that authenticates the HTTP requests of another [`Api`] with the access tokens of an
+/// [`OAuth2TokenCache`].
+///
+/// Tokens are renewed before sending requests and then sent in the `Authorization: Bearer`
+/// header. Other hooks are delegated to the inner [`Api`].
+#[derive(Debug)]
+pub struct OAuth2Api<A, C> {
+  api: A,
+  token_cache: OAuth2TokenCache<C>,
+}
+
+impl<A, C> OAuth2Api<A, C> {
+  /// New instance
+  #[inline]
+  pub const fn new(api: A, token_cache: OAuth2TokenCache<C>) -> Self {
+    Self { api, token_cache }
+  }
+
+  /// Inner [`Api`]
+  #[inline]
+  pub const fn api(&self) -> &A {
+    &self.api
+  }
+
+  /// Mutable version of [`Self::api`].
+  #[inline]
+  pub const fn api_mut(&mut self) -> &mut A {
+    &mut self.api
+  }
+
+  /// See [`OAuth2TokenCache`].
+  #[inline]
+  pub const fn token_cache(&self) -> &OAuth2TokenCache<C> {
+    &self.token_cache
+  }
+
+  /// Mutable version of [`Self::token_cache`].
+  #[inline]
+  pub const fn token_cache_mut(&mut self) -> &mut OAuth2TokenCache<C> {
+    &mut self.token_cache
+  }
+}
+
+impl<A, C> Api for OAuth2Api<A, C>
+where
+  A: Api,
+  C: HttpClient,
+{
+  type Error = A::Error;
+  type Id = A::Id;
+
+  #[inline]
+  async fn after_sending(&mut self) -> Result<(), Self::Error> {
+    self.api.after_sending().await
+  }
+
+  #[inline]
+  fn auth_bearer(&self) -> Option<&str> {
+    self.token_cache.access_token()
+  }
+
+  #[inline]
+  async fn before_sending(&mut self) -> Result<(), Self::Error> {
+    let now = Instant::now_date_time()?;
+    let _access_token = self.token_cache.refresh_if_needed(now).await?;
+    self.api.before_sending().await
+  }
+}
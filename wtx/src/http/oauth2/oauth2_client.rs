@@ -0,0 +1,255 @@
+use crate::{
+  calendar::{DateTime, Utc},
+  codec::{AsciiSet, Base64Alphabet, FormUrlEncode, FormUrlSerializer, encode_base64_into_buffer},
+  collections::Vector,
+  http::{
+    Header, Headers, HttpClient, KnownHeaderName, Method, Mime, ReqBuilder, StatusCode,
+    oauth2::{OAuth2Config, OAuth2Error, OAuth2Token, Pkce},
+  },
+  misc::serde_json_deserialize_from_slice,
+  net::Uri,
+};
+use alloc::string::String;
+use serde::Serialize as _;
+
+/// Performs the grants of an [`OAuth2Config`] using any [`HttpClient`].
+#[derive(Debug)]
+pub struct OAuth2Client<C> {
+  client: C,
+  config: OAuth2Config,
+  enc_buffer: Vector<u8>,
+  form_buffer: Vector<u8>,
+}
+
+impl<C> OAuth2Client<C>
+where
+  C: HttpClient,
+{
+  /// New instance
+  #[inline]
+  pub const fn new(client: C, config: OAuth2Config) -> Self {
+    Self { client, config, enc_buffer: Vector::new(), form_buffer: Vector::new() }
+  }
+
+  /// Builds the URI that users should visit to start the authorization code grant.
+  ///
+  /// `state` protects against CSRF and `nonce`, used by OpenID Connect, binds the ID token to the
+  /// authorization request. Both must be stored to check the redirection and the returned tokens.
+  #[inline]
+  pub fn authorization_uri(
+    &mut self,
+    nonce: Option<&str>,
+    pkce: &Pkce,
+    scope: &str,
+    state: &str,
+  ) -> crate::Result<String> {
+    let Some(endpoint) = &self.config.authorization_endpoint else {
+      return Err(OAuth2Error::MissingAuthorizationEndpoint.into());
+    };
+    let challenge = pkce.challenge()?;
+    let params = AuthorizationParams {
+      client_id: &self.config.client_id,
+      code_challenge: challenge.as_str(),
+      code_challenge_method: pkce.method(),
+      nonce,
+      redirect_uri: self.config.redirect_uri.as_deref(),
+      response_type: "code",
+      scope,
+      state,
+    };
+    let query = params.serialize(FormUrlSerializer::new(None, &mut self.form_buffer))?;
+    let mut uri = String::from(endpoint.as_str());
+    uri.push(if endpoint.contains('?') { '&' } else { '?' });
+    uri.push_str(query);
+    Ok(uri)
+  }
+
+  /// See [`HttpClient`].
+  #[inline]
+  pub const fn client(&self) -> &C {
+    &self.client
+  }
+
+  /// Obtains a token on behalf of the client itself.
+  #[inline]
+  pub async fn client_credentials(
+    &mut self,
+    now: DateTime<Utc>,
+    scope: Option<&str>,
+  ) -> crate::Result<OAuth2Token> {
+    let Self { client, config, enc_buffer, form_buffer } = self;
+    let params = TokenParams { grant_type: "client_credentials", scope, ..TokenParams::default() };
+    token_request(client, config, enc_buffer, form_buffer, now, params).await
+  }
+
+  /// See [`OAuth2Config`].
+  #[inline]
+  pub const fn config(&self) -> &OAuth2Config {
+    &self.config
+  }
+
+  /// Exchanges the `code` received in the redirection URI for a token.
+  ///
+  /// `pkce` must be the same instance used to build the authorization URI.
+  #[inline]
+  pub async fn exchange_code(
+    &mut self,
+    code: &str,
+    now: DateTime<Utc>,
+    pkce: &Pkce,
+  ) -> crate::Result<OAuth2Token> {
+    let Self { client, config, enc_buffer, form_buffer } = self;
+    let params = TokenParams {
+      code: Some(code),
+      code_verifier: Some(pkce.verifier()),
+      grant_type: "authorization_code",
+      redirect_uri: config.redirect_uri.as_deref(),
+      ..TokenParams::default()
+    };
+    token_request(client, config, enc_buffer, form_buffer, now, params).await
+  }
+
+  /// Obtains a new token using `refresh_token`.
+  ///
+  /// The returned token keeps `refresh_token` if the server didn't issue a new one.
+  #[inline]
+  pub async fn refresh_token(
+    &mut self,
+    now: DateTime<Utc>,
+    refresh_token: &str,
+    scope: Option<&str>,
+  ) -> crate::Result<OAuth2Token> {
+    let Self { client, config, enc_buffer, form_buffer } = self;
+    let params = TokenParams {
+      grant_type: "refresh_token",
+      refresh_token: Some(refresh_token),
+      scope,
+      ..TokenParams::default()
+    };
+    let mut token = token_request(client, config, enc_buffer, form_buffer, now, params).await?;
+    if token.refresh_token.is_none() {
+      token.refresh_token = Some(refresh_token.into());
+    }
+    Ok(token)
+  }
+}
+
+#[derive(serde::Serialize)]
+struct AuthorizationParams<'any> {
+  client_id: &'any str,
+  code_challenge: &'any str,
+  code_challenge_method: &'any str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  nonce: Option<&'any str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  redirect_uri: Option<&'any str>,
+  response_type: &'any str,
+  scope: &'any str,
+  state: &'any str,
+}
+
+#[derive(serde::Deserialize)]
+struct ErrorRes<'any> {
+  error: &'any str,
+}
+
+#[derive(Clone, Copy, Default, serde::Serialize)]
+struct TokenParams<'any> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  client_id: Option<&'any str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  code: Option<&'any str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  code_verifier: Option<&'any str>,
+  grant_type: &'any str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  redirect_uri: Option<&'any str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  refresh_token: Option<&'any str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  scope: Option<&'any str>,
+}
+
+#[derive(serde::Deserialize)]
+struct TokenRes {
+  access_token: String,
+  #[serde(default)]
+  expires_in: Option<u64>,
+  #[serde(default)]
+  id_token: Option<String>,
+  #[serde(default)]
+  refresh_token: Option<String>,
+  #[serde(default)]
+  scope: Option<String>,
+  token_type: String,
+}
+
+async fn token_request<C>(
+  client: &C,
+  config: &OAuth2Config,
+  enc_buffer: &mut Vector<u8>,
+  form_buffer: &mut Vector<u8>,
+  now: DateTime<Utc>,
+  params: TokenParams<'_>,
+) -> crate::Result<OAuth2Token>
+where
+  C: HttpClient,
+{
+  let mut headers = Headers::new();
+  headers.push_from_iter(Header::from_name_and_value(
+    KnownHeaderName::Accept.into(),
+    [Mime::ApplicationJson.as_str()],
+  ))?;
+  headers.push_from_iter(Header::from_name_and_value(
+    KnownHeaderName::ContentType.into(),
+    [Mime::ApplicationXWwwFormUrlEncoded.as_str()],
+  ))?;
+  let client_id = if let Some(secret) = &config.client_secret {
+    let mut credentials = Vector::new();
+    for elem in FormUrlEncode::new(config.client_id.as_bytes(), AsciiSet::UNRESERVED) {
+      credentials.extend_from_copyable_slice(elem)?;
+    }
+    credentials.push(b':')?;
+    for elem in FormUrlEncode::new(secret.as_bytes(), AsciiSet::UNRESERVED) {
+      credentials.extend_from_copyable_slice(elem)?;
+    }
+    let mut buffer = Vector::new();
+    let encoded = encode_base64_into_buffer(Base64Alphabet::Standard, &mut buffer, &credentials)?;
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::Authorization.into(),
+      ["Basic ", encoded],
+    ))?;
+    None
+  } else {
+    Some(config.client_id.as_str())
+  };
+  let body = TokenParams { client_id, ..params }
+    .serialize(FormUrlSerializer::new(None, form_buffer))?
+    .as_bytes();
+  let uri = Uri::new(config.token_endpoint.as_str());
+  let req = ReqBuilder::new(Method::Post, (body, &headers, uri));
+  let res = client.send_req_recv_res(enc_buffer, req.into_request()).await?;
+  let status_code = res.status_code;
+  if status_code != StatusCode::Ok {
+    if let StatusCode::BadRequest | StatusCode::Unauthorized = status_code
+      && let Ok(elem) = serde_json_deserialize_from_slice::<ErrorRes<'_>>(&res.msg_data.body)
+    {
+      return Err(OAuth2Error::ErrorResponse(elem.error.into()).into());
+    }
+    return Err(OAuth2Error::UnexpectedHttpStatus(status_code.into()).into());
+  }
+  let token_res: TokenRes = serde_json_deserialize_from_slice(&res.msg_data.body)?;
+  if !token_res.token_type.eq_ignore_ascii_case("bearer") {
+    return Err(OAuth2Error::UnsupportedTokenType.into());
+  }
+  let expires_at = token_res
+    .expires_in
+    .map(|el| now.timestamp_secs_and_ns().0.saturating_add(i64::try_from(el).unwrap_or(i64::MAX)));
+  Ok(OAuth2Token {
+    access_token: token_res.access_token,
+    expires_at,
+    id_token: token_res.id_token,
+    refresh_token: token_res.refresh_token,
+    scope: token_res.scope,
+  })
+}
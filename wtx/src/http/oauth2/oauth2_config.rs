@@ -0,0 +1,31 @@
+use alloc::string::String;
+
+/// Client registration and endpoints of an authorization server.
+#[derive(Clone, Debug)]
+pub struct OAuth2Config {
+  /// Required to build authorization URIs.
+  pub authorization_endpoint: Option<String>,
+  /// Client identifier
+  pub client_id: String,
+  /// Confidential clients authenticate with HTTP Basic authentication while public clients only
+  /// send `client_id` in the request body.
+  pub client_secret: Option<String>,
+  /// Sent in authorization and authorization code requests.
+  pub redirect_uri: Option<String>,
+  /// Endpoint of all token requests
+  pub token_endpoint: String,
+}
+
+impl OAuth2Config {
+  /// Public client without an authorization endpoint or a redirection URI.
+  #[inline]
+  pub const fn new(client_id: String, token_endpoint: String) -> Self {
+    Self {
+      authorization_endpoint: None,
+      client_id,
+      client_secret: None,
+      redirect_uri: None,
+      token_endpoint,
+    }
+  }
+}
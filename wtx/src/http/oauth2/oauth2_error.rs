@@ -0,0 +1,54 @@
+/// OAuth 2.0 error
+#[derive(Clone, Copy, Debug)]
+pub enum OAuth2Error {
+  /// The authorization server answered with the contained error code.
+  ErrorResponse(OAuth2ErrorCode),
+  /// PKCE verifiers must have between 43 and 128 unreserved characters.
+  InvalidPkceVerifier,
+  /// The `iss` field of a discovery document is different from the requested issuer.
+  IssuerMismatch,
+  /// An ID token with multiple audiences has an absent or unexpected `azp` claim.
+  MismatchedAzp,
+  /// The `nonce` claim of an ID token is absent or different from the expected value.
+  MismatchedNonce,
+  /// Authorization URIs can only be built when an authorization endpoint is configured.
+  MissingAuthorizationEndpoint,
+  /// The server returned a response with the contained non-successful status code.
+  UnexpectedHttpStatus(u16),
+  /// The token endpoint returned a token type other than `Bearer`.
+  UnsupportedTokenType,
+}
+
+/// Error codes of <https://datatracker.ietf.org/doc/html/rfc6749#section-5.2>.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OAuth2ErrorCode {
+  /// `invalid_client`
+  InvalidClient,
+  /// `invalid_grant`
+  InvalidGrant,
+  /// `invalid_request`
+  InvalidRequest,
+  /// `invalid_scope`
+  InvalidScope,
+  /// Any code not defined by the specification
+  Other,
+  /// `unauthorized_client`
+  UnauthorizedClient,
+  /// `unsupported_grant_type`
+  UnsupportedGrantType,
+}
+
+impl From<&str> for OAuth2ErrorCode {
+  #[inline]
+  fn from(from: &str) -> Self {
+    match from {
+      "invalid_client" => Self::InvalidClient,
+      "invalid_grant" => Self::InvalidGrant,
+      "invalid_request" => Self::InvalidRequest,
+      "invalid_scope" => Self::InvalidScope,
+      "unauthorized_client" => Self::UnauthorizedClient,
+      "unsupported_grant_type" => Self::UnsupportedGrantType,
+      _ => Self::Other,
+    }
+  }
+}
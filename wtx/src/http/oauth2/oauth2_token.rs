@@ -0,0 +1,30 @@
+use crate::calendar::{DateTime, Utc};
+use alloc::string::String;
+use core::time::Duration;
+
+/// Successful response of a token endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OAuth2Token {
+  /// Access token
+  pub access_token: String,
+  /// UNIX timestamp in seconds computed from `expires_in`, if any.
+  pub expires_at: Option<i64>,
+  /// Returned by OpenID Connect providers. See [`crate::http::oauth2::IdTokenVerifier`].
+  pub id_token: Option<String>,
+  /// Refresh token
+  pub refresh_token: Option<String>,
+  /// Granted scope if different from the requested scope.
+  pub scope: Option<String>,
+}
+
+impl OAuth2Token {
+  /// If the token expires within `margin` of `now`. Tokens without an expiration never expire.
+  #[inline]
+  pub fn is_expired(&self, margin: Duration, now: DateTime<Utc>) -> bool {
+    let Some(expires_at) = self.expires_at else {
+      return false;
+    };
+    let margin_secs = i64::try_from(margin.as_secs()).unwrap_or(i64::MAX);
+    now.timestamp_secs_and_ns().0 >= expires_at.saturating_sub(margin_secs)
+  }
+}
@@ -0,0 +1,90 @@
+use crate::{
+  calendar::{DateTime, Utc},
+  http::{
+    HttpClient,
+    oauth2::{OAuth2Client, OAuth2Token},
+  },
+};
+use alloc::string::String;
+use core::time::Duration;
+
+/// Stores an [`OAuth2Token`] that is renewed before its expiration.
+///
+/// Tokens are renewed with the refresh token grant when a refresh token is available, otherwise
+/// with the client credentials grant. Tokens obtained through the authorization code grant should
+/// be provided by [`Self::set_token`].
+#[derive(Debug)]
+pub struct OAuth2TokenCache<C> {
+  client: OAuth2Client<C>,
+  refresh_margin: Duration,
+  scope: Option<String>,
+  token: Option<OAuth2Token>,
+}
+
+impl<C> OAuth2TokenCache<C>
+where
+  C: HttpClient,
+{
+  /// New instance that requests `scope` when obtaining new tokens.
+  #[inline]
+  pub const fn new(client: OAuth2Client<C>, scope: Option<String>) -> Self {
+    Self { client, refresh_margin: Duration::from_secs(30), scope, token: None }
+  }
+
+  /// The current access token, if any.
+  #[inline]
+  pub fn access_token(&self) -> Option<&str> {
+    self.token.as_ref().map(|el| el.access_token.as_str())
+  }
+
+  /// See [`OAuth2Client`].
+  #[inline]
+  pub const fn client(&self) -> &OAuth2Client<C> {
+    &self.client
+  }
+
+  /// Mutable version of [`Self::client`].
+  #[inline]
+  pub const fn client_mut(&mut self) -> &mut OAuth2Client<C> {
+    &mut self.client
+  }
+
+  /// Obtains a new token if there is none or if the current one expires within the refresh
+  /// margin of `now`. Returns the valid access token.
+  #[inline]
+  pub async fn refresh_if_needed(&mut self, now: DateTime<Utc>) -> crate::Result<&str> {
+    let Self { client, refresh_margin, scope, token } = self;
+    let is_valid = token.as_ref().is_some_and(|el| !el.is_expired(*refresh_margin, now));
+    if !is_valid {
+      let refresh_token = token.as_ref().and_then(|el| el.refresh_token.as_deref());
+      let new_token = if let Some(elem) = refresh_token {
+        client.refresh_token(now, elem, scope.as_deref()).await?
+      } else {
+        client.client_credentials(now, scope.as_deref()).await?
+      };
+      _trace!(target: crate::_WTX_HTTP, "OAuth 2.0 token has been renewed");
+      *token = Some(new_token);
+    }
+    Ok(token.as_ref().map(|el| el.access_token.as_str()).unwrap_or_default())
+  }
+
+  /// Replaces the current token.
+  #[inline]
+  pub fn set_token(&mut self, token: Option<OAuth2Token>) {
+    self.token = token;
+  }
+
+  /// Tokens are renewed when they expire within this margin. Defaults to 30 seconds.
+  #[inline]
+  #[must_use]
+  pub const fn set_refresh_margin(mut self, value: Duration) -> Self {
+    self.refresh_margin = value;
+    self
+  }
+
+  /// See [`OAuth2Token`].
+  #[inline]
+  pub const fn token(&self) -> Option<&OAuth2Token> {
+    self.token.as_ref()
+  }
+}
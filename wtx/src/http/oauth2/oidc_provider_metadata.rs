@@ -0,0 +1,106 @@
+use crate::{
+  collections::Vector,
+  http::{
+    Header, Headers, HttpClient, KnownHeaderName, Method, Mime, MsgBufferString, ReqBuilder,
+    Response, StatusCode,
+    jwt::JwkSet,
+    oauth2::{OAuth2Config, OAuth2Error},
+  },
+  misc::serde_json_deserialize_from_slice,
+  net::Uri,
+};
+use alloc::string::String;
+
+/// Discovery document of <https://openid.net/specs/openid-connect-discovery-1_0.html>.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct OidcProviderMetadata {
+  /// Authorization endpoint
+  pub authorization_endpoint: String,
+  /// RP-initiated logout endpoint
+  #[serde(default)]
+  pub end_session_endpoint: Option<String>,
+  /// Issuer identifier
+  pub issuer: String,
+  /// URI of the JWK set used to sign ID tokens
+  pub jwks_uri: String,
+  /// Token endpoint
+  pub token_endpoint: String,
+  /// UserInfo endpoint
+  #[serde(default)]
+  pub userinfo_endpoint: Option<String>,
+}
+
+impl OidcProviderMetadata {
+  /// Fetches `{issuer}/.well-known/openid-configuration` and checks that the returned `issuer`
+  /// is equal to `issuer`.
+  #[inline]
+  pub async fn fetch<C>(
+    client: &C,
+    enc_buffer: &mut Vector<u8>,
+    issuer: &str,
+  ) -> crate::Result<Self>
+  where
+    C: HttpClient,
+  {
+    let mut uri = String::from(issuer.trim_end_matches('/'));
+    uri.push_str("/.well-known/openid-configuration");
+    let res = get_json(client, enc_buffer, &uri).await?;
+    let this: Self = serde_json_deserialize_from_slice(&res.msg_data.body)?;
+    if this.issuer != issuer {
+      return Err(OAuth2Error::IssuerMismatch.into());
+    }
+    Ok(this)
+  }
+
+  /// Fetches the keys located at `jwks_uri`.
+  #[inline]
+  pub async fn fetch_jwks<C>(
+    &self,
+    client: &C,
+    enc_buffer: &mut Vector<u8>,
+  ) -> crate::Result<JwkSet>
+  where
+    C: HttpClient,
+  {
+    let res = get_json(client, enc_buffer, &self.jwks_uri).await?;
+    JwkSet::from_json(&res.msg_data.body)
+  }
+
+  /// Configuration of a client registered in this provider.
+  #[inline]
+  pub fn oauth2_config(
+    &self,
+    client_id: String,
+    client_secret: Option<String>,
+    redirect_uri: Option<String>,
+  ) -> OAuth2Config {
+    OAuth2Config {
+      authorization_endpoint: Some(self.authorization_endpoint.clone()),
+      client_id,
+      client_secret,
+      redirect_uri,
+      token_endpoint: self.token_endpoint.clone(),
+    }
+  }
+}
+
+async fn get_json<C>(
+  client: &C,
+  enc_buffer: &mut Vector<u8>,
+  uri: &str,
+) -> crate::Result<Response<MsgBufferString>>
+where
+  C: HttpClient,
+{
+  let mut headers = Headers::new();
+  headers.push_from_iter(Header::from_name_and_value(
+    KnownHeaderName::Accept.into(),
+    [Mime::ApplicationJson.as_str()],
+  ))?;
+  let req = ReqBuilder::new(Method::Get, (&[][..], &headers, Uri::new(uri)));
+  let res = client.send_req_recv_res(enc_buffer, req.into_request()).await?;
+  if res.status_code != StatusCode::Ok {
+    return Err(OAuth2Error::UnexpectedHttpStatus(res.status_code.into()).into());
+  }
+  Ok(res)
+}
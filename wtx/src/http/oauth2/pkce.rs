@@ -0,0 +1,58 @@
+use crate::{
+  codec::{Base64Alphabet, base64_encode},
+  collections::ArrayStringU8,
+  crypto::{Hash as _, Sha256Global},
+  http::oauth2::OAuth2Error,
+  rng::CryptoRng,
+};
+
+/// Proof Key for Code Exchange of <https://datatracker.ietf.org/doc/html/rfc7636>.
+///
+/// Only the `S256` method is supported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pkce {
+  verifier: ArrayStringU8<128>,
+}
+
+impl Pkce {
+  /// Creates a verifier from 32 random bytes.
+  #[inline]
+  pub fn new<RNG>(rng: &mut RNG) -> crate::Result<Self>
+  where
+    RNG: CryptoRng,
+  {
+    let mut buffer = [0; 43];
+    let verifier = base64_encode(Base64Alphabet::UrlNoPad, &rng.u8_32(), &mut buffer)?;
+    Ok(Self { verifier: verifier.try_into()? })
+  }
+
+  /// Uses an existing `verifier`, for example, one restored from a session.
+  #[inline]
+  pub fn from_verifier(verifier: &str) -> crate::Result<Self> {
+    let is_unreserved = |byte: u8| byte.is_ascii_alphanumeric() || b"-._~".contains(&byte);
+    if !(43..=128).contains(&verifier.len()) || !verifier.bytes().all(is_unreserved) {
+      return Err(OAuth2Error::InvalidPkceVerifier.into());
+    }
+    Ok(Self { verifier: verifier.try_into()? })
+  }
+
+  /// The `code_challenge` parameter sent in authorization requests.
+  #[inline]
+  pub fn challenge(&self) -> crate::Result<ArrayStringU8<43>> {
+    let digest = Sha256Global::digest([self.verifier.as_str().as_bytes()]);
+    let mut buffer = [0; 43];
+    base64_encode(Base64Alphabet::UrlNoPad, digest.as_ref(), &mut buffer)?.try_into()
+  }
+
+  /// The `code_challenge_method` parameter sent in authorization requests.
+  #[inline]
+  pub const fn method(&self) -> &'static str {
+    "S256"
+  }
+
+  /// The `code_verifier` parameter sent in token requests.
+  #[inline]
+  pub fn verifier(&self) -> &str {
+    self.verifier.as_str()
+  }
+}
@@ -0,0 +1,321 @@
+use crate::{
+  calendar::{DateTime, Utc},
+  collections::Vector,
+  http::{
+    HttpClient, KnownHeaderName, Method, MsgBufferString, MsgData, Request, Response, StatusCode,
+    jwt::{Jwk, JwkMaterial, JwsAlg, JwsSigningKey, JwtSigner},
+    oauth2::{
+      IdTokenVerifier, OAuth2Client, OAuth2Config, OAuth2Error, OAuth2ErrorCode, OAuth2TokenCache,
+      OidcProviderMetadata, Pkce,
+    },
+  },
+  misc::Lease,
+  rng::{ChaCha20, CryptoSeedableRng},
+};
+use alloc::{
+  string::{String, ToString},
+  vec::Vec,
+};
+use core::cell::RefCell;
+
+const DISCOVERY: &str = r#"{"issuer":"https://op.example","authorization_endpoint":"https://op.example/auth","token_endpoint":"https://op.example/token","jwks_uri":"https://op.example/jwks","response_types_supported":["code"]}"#;
+const HS256_JWK: &str = r#"{"kty":"oct","k":"AyM1SysPpbyDfgZld3umj1qzKObwVMkoqQ-EstJQLr_T-1qS0gZH75aKtMN3Yj0iPS4hcgUuTwjAzZr1Z9CAow"}"#;
+
+// The renewed access token is sent by the HTTP/2 transport of the client API framework.
+//
+// FIXME(MIRI): socket support
+#[cfg(all(feature = "client-api-framework", feature = "http2", not(feature = "tokio")))]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn api_authenticates_http2_requests() {
+  use crate::{
+    client_api_framework::{
+      network::{HttpParams, transport::SendingReceivingTransport as _},
+      pkg::PkgsAux,
+    },
+    executor::StdRuntime,
+    http::{HttpRecvParams, MsgDataMut as _, oauth2::OAuth2Api},
+    http2::{Http2, Http2Buffer},
+    net::Stream as _,
+    rng::{SeedableRng, Xorshift64},
+    tests::_uri,
+    tls::{TlsAcceptor, TlsConfig, TlsConnectorBuilder},
+  };
+  use std::net::TcpListener;
+
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let tls_stream =
+        TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+          .accept()
+          .await
+          .unwrap()
+          .tls_stream;
+      let (frame_header, http2) = Http2::accept(
+        Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+        HttpRecvParams::with_optioned_params(),
+        tls_stream.into_split().unwrap(),
+      )
+      .await
+      .unwrap();
+      let _jh = runtime.spawn(frame_header);
+      let (mut stream, _) = http2.stream(|_, _| {}).await.unwrap().unwrap();
+      let (_, mut msg_buffer) = stream.recv_req().await.unwrap();
+      let authorization = msg_buffer
+        .headers
+        .get_by_name(KnownHeaderName::Authorization.into())
+        .map(|el| el.value.to_string());
+      msg_buffer.clear();
+      msg_buffer.body.extend_from_copyable_slice(b"pong").unwrap();
+      let _ = stream
+        .send_res(&mut Vector::new(), Response::new(&mut msg_buffer, StatusCode::Ok))
+        .await
+        .unwrap();
+      authorization
+    })
+    .unwrap();
+
+  let mut client =
+    Client::new([(StatusCode::Ok, r#"{"access_token":"at","token_type":"Bearer"}"#)]);
+  let oauth2_client = OAuth2Client::new(&mut client, config(Some("secret")));
+  let api = OAuth2Api::new((), OAuth2TokenCache::new(oauth2_client, None));
+  let mut pkgs_aux = PkgsAux::from_minimum(api, (), HttpParams::from_uri(uri.as_str().into()));
+  runtime.block_on(async {
+    let tls_stream = TlsConnectorBuilder::std(&uri)
+      .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+      .await
+      .unwrap()
+      .connect()
+      .await
+      .unwrap()
+      .tls_stream;
+    let (frame_header, mut http2) = Http2::connect(
+      Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+      HttpRecvParams::with_optioned_params(),
+      tls_stream.into_split().unwrap(),
+    )
+    .await
+    .unwrap();
+    let _jh = runtime.spawn(frame_header).unwrap();
+    http2.send_req_recv_res_bytes(Some(b"ping"), &mut pkgs_aux).await.unwrap();
+    assert_eq!(pkgs_aux.bytes_buffer.as_slice(), b"pong");
+    assert_eq!(server_jh.await.as_deref(), Some("Bearer at"));
+  });
+  assert_eq!(pkgs_aux.api.token_cache().access_token(), Some("at"));
+  drop(pkgs_aux);
+  assert_eq!(client.requests.borrow().len(), 1);
+}
+
+#[wtx::test]
+async fn authorization_code_with_pkce() {
+  let mut client =
+    Client::new([(StatusCode::Ok, r#"{"access_token":"at","token_type":"bearer"}"#)]);
+  let mut config = config(None);
+  config.authorization_endpoint = Some("https://as.example/auth?prompt=login".into());
+  config.redirect_uri = Some("https://app.example/cb".into());
+  let mut oauth2_client = OAuth2Client::new(&mut client, config);
+  let pkce = Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk").unwrap();
+  assert_eq!(
+    oauth2_client.authorization_uri(Some("n"), &pkce, "openid profile", "s").unwrap(),
+    "https://as.example/auth?prompt=login&client_id=id\
+      &code_challenge=E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM&code_challenge_method=S256\
+      &nonce=n&redirect_uri=https%3A%2F%2Fapp.example%2Fcb&response_type=code\
+      &scope=openid+profile&state=s"
+  );
+  let token = oauth2_client.exchange_code("abc", date_time(0), &pkce).await.unwrap();
+  assert_eq!((token.access_token.as_str(), token.expires_at), ("at", None));
+  assert_eq!(
+    client.requests.borrow().as_slice(),
+    ["POST https://as.example/token  client_id=id&code=abc\
+        &code_verifier=dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk\
+        &grant_type=authorization_code&redirect_uri=https%3A%2F%2Fapp.example%2Fcb"]
+  );
+}
+
+#[wtx::test]
+async fn error_response() {
+  let mut client = Client::new([
+    (StatusCode::BadRequest, r#"{"error":"invalid_grant","error_description":"foo"}"#),
+    (StatusCode::InternalServerError, ""),
+    (StatusCode::Ok, r#"{"access_token":"at","token_type":"mac"}"#),
+  ]);
+  let mut oauth2_client = OAuth2Client::new(&mut client, config(None));
+  assert!(matches!(
+    oauth2_client.refresh_token(date_time(0), "rt", None).await,
+    Err(crate::Error::OAuth2Error(OAuth2Error::ErrorResponse(OAuth2ErrorCode::InvalidGrant)))
+  ));
+  assert!(matches!(
+    oauth2_client.client_credentials(date_time(0), None).await,
+    Err(crate::Error::OAuth2Error(OAuth2Error::UnexpectedHttpStatus(500)))
+  ));
+  assert!(matches!(
+    oauth2_client.client_credentials(date_time(0), None).await,
+    Err(crate::Error::OAuth2Error(OAuth2Error::UnsupportedTokenType))
+  ));
+}
+
+#[wtx::test]
+async fn id_token() {
+  let jwks = alloc::format!(r#"{{"keys":[{HS256_JWK}]}}"#);
+  let client = Client::new([(StatusCode::Ok, DISCOVERY), (StatusCode::Ok, &jwks)]);
+  let mut enc_buffer = Vector::new();
+  assert!(matches!(
+    OidcProviderMetadata::fetch(&client, &mut enc_buffer, "https://other.example").await,
+    Err(crate::Error::OAuth2Error(OAuth2Error::IssuerMismatch))
+  ));
+  client.responses.borrow_mut().insert(0, (StatusCode::Ok, DISCOVERY.into()));
+  let metadata =
+    OidcProviderMetadata::fetch(&client, &mut enc_buffer, "https://op.example").await.unwrap();
+  assert_eq!(metadata.token_endpoint, "https://op.example/token");
+  let jwks = metadata.fetch_jwks(&client, &mut enc_buffer).await.unwrap();
+  assert_eq!(
+    client.requests.borrow().as_slice(),
+    [
+      "GET https://other.example/.well-known/openid-configuration  ",
+      "GET https://op.example/.well-known/openid-configuration  ",
+      "GET https://op.example/jwks  "
+    ]
+  );
+  let verifier = IdTokenVerifier::new("id".into(), metadata.issuer.clone(), jwks);
+  let ok = sign(&IdToken { aud: &["id"], azp: None, nonce: Some("n") });
+  let mut buffer = Vector::new();
+  let (verified, claims) = verifier.verify(&mut buffer, Some("n"), date_time(0), &ok).unwrap();
+  assert_eq!((verified.claims.iss, claims.nonce), (Some("https://op.example"), Some("n")));
+  assert!(matches!(
+    verifier.verify(&mut Vector::new(), Some("other"), date_time(0), &ok),
+    Err(crate::Error::OAuth2Error(OAuth2Error::MismatchedNonce))
+  ));
+  let many = sign(&IdToken { aud: &["id", "other"], azp: None, nonce: None });
+  assert!(matches!(
+    verifier.verify(&mut Vector::new(), None, date_time(0), &many),
+    Err(crate::Error::OAuth2Error(OAuth2Error::MismatchedAzp))
+  ));
+  let many_azp = sign(&IdToken { aud: &["id", "other"], azp: Some("id"), nonce: None });
+  let _ = verifier.verify(&mut Vector::new(), None, date_time(0), &many_azp).unwrap();
+}
+
+#[test]
+fn pkce() {
+  // https://datatracker.ietf.org/doc/html/rfc7636#appendix-B
+  let pkce = Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk").unwrap();
+  assert_eq!(pkce.challenge().unwrap().as_str(), "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+  let random = Pkce::new(&mut ChaCha20::from_std_random().unwrap()).unwrap();
+  assert_eq!(random.verifier().len(), 43);
+  assert_eq!(Pkce::from_verifier(random.verifier()).unwrap(), random);
+  assert!(Pkce::from_verifier("short").is_err());
+  assert!(Pkce::from_verifier(&"a/".repeat(30)).is_err());
+}
+
+#[wtx::test]
+async fn token_cache() {
+  let mut client = Client::new([
+    (
+      StatusCode::Ok,
+      r#"{"access_token":"at0","token_type":"Bearer","expires_in":60,"refresh_token":"rt"}"#,
+    ),
+    (StatusCode::Ok, r#"{"access_token":"at1","token_type":"Bearer","expires_in":60}"#),
+  ]);
+  let oauth2_client = OAuth2Client::new(&mut client, config(Some("sec ret")));
+  let mut cache = OAuth2TokenCache::new(oauth2_client, Some("read".into()));
+  assert_eq!(cache.access_token(), None);
+  assert_eq!(cache.refresh_if_needed(date_time(0)).await.unwrap(), "at0");
+  assert_eq!(cache.token().unwrap().expires_at, Some(60));
+  assert_eq!(cache.refresh_if_needed(date_time(29)).await.unwrap(), "at0");
+  assert_eq!(cache.refresh_if_needed(date_time(30)).await.unwrap(), "at1");
+  assert_eq!(cache.token().unwrap().refresh_token.as_deref(), Some("rt"));
+  assert_eq!(
+    client.requests.borrow().as_slice(),
+    [
+      "POST https://as.example/token Basic aWQ6c2VjK3JldA== grant_type=client_credentials\
+        &scope=read",
+      "POST https://as.example/token Basic aWQ6c2VjK3JldA== grant_type=refresh_token\
+        &refresh_token=rt&scope=read"
+    ]
+  );
+}
+
+// Replies with queued responses and stores the method, the URI, the authorization header and the
+// body of each request.
+struct Client {
+  requests: RefCell<Vec<String>>,
+  responses: RefCell<Vec<(StatusCode, String)>>,
+}
+
+impl Client {
+  fn new<'any>(responses: impl IntoIterator<Item = (StatusCode, &'any str)>) -> Self {
+    Self {
+      requests: RefCell::new(Vec::new()),
+      responses: RefCell::new(responses.into_iter().map(|el| (el.0, el.1.into())).collect()),
+    }
+  }
+}
+
+impl HttpClient for Client {
+  type ReqId = Response<MsgBufferString>;
+
+  async fn recv_res(&self, req_id: Self::ReqId) -> crate::Result<Response<MsgBufferString>> {
+    Ok(req_id)
+  }
+
+  async fn send_req<MD>(&self, _: &mut Vector<u8>, req: Request<MD>) -> crate::Result<Self::ReqId>
+  where
+    MD: MsgData,
+    MD::Body: Lease<[u8]>,
+  {
+    let headers = req.msg_data.headers();
+    let authorization = headers
+      .get_by_name(KnownHeaderName::Authorization.into())
+      .map(|el| el.value.to_string())
+      .unwrap_or_default();
+    let method = if req.method == Method::Get { "GET" } else { "POST" };
+    let body = String::from_utf8(req.msg_data.body().lease().to_vec()).unwrap();
+    let uri = req.msg_data.uri();
+    let uri_str = uri.as_str();
+    self.requests.borrow_mut().push(alloc::format!("{method} {uri_str} {authorization} {body}"));
+    let (status_code, res_body) = self.responses.borrow_mut().remove(0);
+    let mut msg_buffer = MsgBufferString::default();
+    msg_buffer.body.extend_from_copyable_slice(res_body.as_bytes())?;
+    Ok(Response::new(msg_buffer, status_code))
+  }
+}
+
+#[derive(serde::Serialize)]
+struct IdToken<'any> {
+  aud: &'any [&'any str],
+  #[serde(skip_serializing_if = "Option::is_none")]
+  azp: Option<&'any str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  nonce: Option<&'any str>,
+}
+
+fn config(client_secret: Option<&str>) -> OAuth2Config {
+  let mut config = OAuth2Config::new("id".into(), "https://as.example/token".into());
+  config.client_secret = client_secret.map(String::from);
+  config
+}
+
+fn date_time(timestamp: i64) -> DateTime<Utc> {
+  DateTime::from_timestamp_secs(timestamp).unwrap()
+}
+
+fn sign(id_token: &IdToken<'_>) -> String {
+  #[derive(serde::Serialize)]
+  struct Claims<'any> {
+    exp: i64,
+    iss: &'any str,
+    #[serde(flatten)]
+    rest: &'any IdToken<'any>,
+  }
+  let JwkMaterial::Oct(secret) = Jwk::from_json(HS256_JWK.as_bytes()).unwrap().material else {
+    panic!();
+  };
+  let signer = JwtSigner::new(JwsAlg::HS256, JwsSigningKey::Hmac(secret), None).unwrap();
+  let claims = Claims { exp: 100, iss: "https://op.example", rest: id_token };
+  let mut buffer = Vector::new();
+  signer.sign(&mut buffer, &claims, &mut ChaCha20::from_std_random().unwrap()).unwrap();
+  String::from_utf8(buffer.into_vec()).unwrap()
+}
@@ -0,0 +1,133 @@
+use crate::{
+  collections::Vector,
+  http::{
+    Header, Headers, HttpClient, HttpError, KnownHeaderName, Method, Mime, ReqBuilder, StatusCode,
+    TraceSpan,
+  },
+  net::UriString,
+};
+use alloc::string::String;
+
+/// Sends [`TraceSpan`]s to OpenTelemetry collectors through OTLP/HTTP requests with JSON bodies.
+/// Works with any [`HttpClient`], for example, HTTP/2 connections or pools.
+#[derive(Debug)]
+pub struct OtlpJsonExporter<C> {
+  body: Vector<u8>,
+  client: C,
+  enc_buffer: Vector<u8>,
+  service_name: String,
+  uri: UriString,
+}
+
+impl<C> OtlpJsonExporter<C>
+where
+  C: HttpClient,
+{
+  /// New instance that sends the spans of `service_name` to `uri`, for example,
+  /// `http://localhost:4318/v1/traces`.
+  #[inline]
+  pub const fn new(client: C, service_name: String, uri: UriString) -> Self {
+    Self { body: Vector::new(), client, enc_buffer: Vector::new(), service_name, uri }
+  }
+
+  /// Sends all `spans` in a single request. Nothing is sent if `spans` is empty.
+  #[inline]
+  pub async fn export(&mut self, spans: &[TraceSpan<'_>]) -> crate::Result<()> {
+    if spans.is_empty() {
+      return Ok(());
+    }
+    self.body.clear();
+    TraceSpan::encode_otlp_json(&self.service_name, spans, &mut self.body)?;
+    let mut headers = Headers::new();
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::ContentType.into(),
+      [Mime::ApplicationJson.as_str()],
+    ))?;
+    let req = ReqBuilder::new(Method::Post, (self.body.as_slice(), &headers, self.uri.to_ref()));
+    let res = self.client.send_req_recv_res(&mut self.enc_buffer, req.into_request()).await?;
+    if res.status_code != StatusCode::Ok {
+      return Err(HttpError::UnexpectedHttpStatus(res.status_code.into()).into());
+    }
+    Ok(())
+  }
+}
+
+#[cfg(all(feature = "std", test))]
+mod tests {
+  use crate::{
+    collections::Vector,
+    executor::StdRuntime,
+    http::{
+      HttpClient, KnownHeaderName, Method, MsgBufferString, MsgData, OtlpJsonExporter, Request,
+      Response, StatusCode, TraceSpan, TraceSpanKind,
+    },
+    misc::{Lease, from_utf8_basic},
+    net::UriString,
+    sync::SyncMutex,
+  };
+  use alloc::{string::String, vec::Vec};
+  use core::time::Duration;
+
+  #[test]
+  fn posts_spans() {
+    StdRuntime::new().block_on(async {
+      let span = TraceSpan {
+        attributes: &[],
+        end: Duration::from_secs(2),
+        kind: TraceSpanKind::Client,
+        name: "foo",
+        parent_span_id: None,
+        span_id: [1; 8],
+        start: Duration::from_secs(1),
+        trace_id: [2; 16],
+      };
+      let uri = UriString::new("http://localhost:4318/v1/traces".into());
+      let mut exporter = OtlpJsonExporter::new(
+        Client(SyncMutex::new(Vec::new()), StatusCode::Ok),
+        "bar".into(),
+        uri.clone(),
+      );
+      exporter.export(&[]).await.unwrap();
+      exporter.export(&[span]).await.unwrap();
+      let mut body = Vector::new();
+      TraceSpan::encode_otlp_json("bar", &[span], &mut body).unwrap();
+      let expected = alloc::format!(
+        "POST http://localhost:4318/v1/traces application/json {}",
+        from_utf8_basic(&body).unwrap()
+      );
+      assert_eq!(exporter.client.0.lock().as_slice(), [expected]);
+      let mut failing = OtlpJsonExporter::new(
+        Client(SyncMutex::new(Vec::new()), StatusCode::ServiceUnavailable),
+        "bar".into(),
+        uri,
+      );
+      assert!(failing.export(&[span]).await.is_err());
+    });
+  }
+
+  // Stores the method, the URI, the content type and the body of each request.
+  #[derive(Debug)]
+  struct Client(SyncMutex<Vec<String>>, StatusCode);
+
+  impl HttpClient for Client {
+    type ReqId = ();
+
+    async fn recv_res(&self, _: Self::ReqId) -> crate::Result<Response<MsgBufferString>> {
+      Ok(Response::new(MsgBufferString::default(), self.1))
+    }
+
+    async fn send_req<MD>(&self, _: &mut Vector<u8>, req: Request<MD>) -> crate::Result<()>
+    where
+      MD: MsgData,
+      MD::Body: Lease<[u8]>,
+    {
+      let content_type =
+        req.msg_data.headers().get_by_name(KnownHeaderName::ContentType.into()).unwrap().value;
+      let method = if req.method == Method::Post { "POST" } else { "OTHER" };
+      let uri = req.msg_data.uri();
+      let body = from_utf8_basic(req.msg_data.body().lease()).unwrap();
+      self.0.lock().push(alloc::format!("{method} {} {content_type} {body}", uri.as_str()));
+      Ok(())
+    }
+  }
+}
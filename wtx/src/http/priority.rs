@@ -0,0 +1,149 @@
+use crate::{
+  http::{Header, Headers, KnownHeaderName},
+  misc::bytes_split1,
+};
+
+const DEFAULT_URGENCY: u8 = 3;
+const FIELD_VALUES: [&str; 16] = [
+  "u=0", "u=1", "u=2", "u=3", "u=4", "u=5", "u=6", "u=7", "u=0, i", "u=1, i", "u=2, i", "u=3, i",
+  "u=4, i", "u=5, i", "u=6, i", "u=7, i",
+];
+const MAX_URGENCY: u8 = 7;
+
+/// Extensible priority of a response, transmitted through the `priority` header or through
+/// HTTP/2 `PRIORITY_UPDATE` frames.
+///
+/// Lower urgency values are sent first. Responses with the same urgency share the connection by
+/// taking turns when sending their DATA frames.
+///
+/// <https://www.rfc-editor.org/rfc/rfc9218>
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Priority {
+  incremental: bool,
+  urgency: u8,
+}
+
+impl Priority {
+  /// Urgency of 3 without incremental delivery.
+  pub const DEFAULT: Self = Self { incremental: false, urgency: DEFAULT_URGENCY };
+
+  /// Urgency values greater than 7 are clamped.
+  #[inline]
+  pub const fn new(incremental: bool, urgency: u8) -> Self {
+    Self { incremental, urgency: if urgency > MAX_URGENCY { MAX_URGENCY } else { urgency } }
+  }
+
+  /// Extracts the priority of the `priority` header, if any. Otherwise returns [`Self::DEFAULT`].
+  #[inline]
+  pub fn from_headers(headers: &Headers) -> Self {
+    headers
+      .get_by_name(KnownHeaderName::Priority.into())
+      .map_or(Self::DEFAULT, |el| Self::parse(el.value.as_bytes()))
+  }
+
+  /// Parses a `priority` field value.
+  ///
+  /// Never fails because the specification mandates that unknown or invalid members must be
+  /// ignored, in which case the default values are used instead.
+  #[inline]
+  pub fn parse(bytes: &[u8]) -> Self {
+    let mut rslt = Self::DEFAULT;
+    for member in bytes_split1(bytes, b',') {
+      let key_value = bytes_split1(member, b';').next().unwrap_or_default().trim_ascii();
+      let (key, value) = match key_value.iter().position(|el| *el == b'=') {
+        Some(idx) => {
+          (key_value.get(..idx).unwrap_or_default(), key_value.get(idx.wrapping_add(1)..))
+        }
+        None => (key_value, None),
+      };
+      match (key, value) {
+        (b"i", None | Some(b"?1")) => rslt.incremental = true,
+        (b"i", Some(b"?0")) => rslt.incremental = false,
+        (b"u", Some([digit @ b'0'..=b'7'])) => rslt.urgency = digit.wrapping_sub(b'0'),
+        _ => {}
+      }
+    }
+    rslt
+  }
+
+  /// Textual representation used by the `priority` header.
+  #[inline]
+  pub fn field_value(&self) -> &'static str {
+    let idx = usize::from(self.urgency).wrapping_add(if self.incremental { 8 } else { 0 });
+    FIELD_VALUES.get(idx).copied().unwrap_or_default()
+  }
+
+  /// If the response can be processed in parts, which allows the interleaving with other
+  /// responses of the same urgency.
+  #[inline]
+  pub const fn incremental(&self) -> bool {
+    self.incremental
+  }
+
+  /// Writes the `priority` header.
+  #[inline]
+  pub fn push_header(&self, headers: &mut Headers) -> crate::Result<()> {
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::Priority.into(),
+      [self.field_value()],
+    ))
+  }
+
+  /// From 0 (highest) to 7 (lowest).
+  #[inline]
+  pub const fn urgency(&self) -> u8 {
+    self.urgency
+  }
+
+  // If the DATA frames of `self` should wait for the DATA frames of `other`.
+  #[cfg(feature = "http2")]
+  pub(crate) const fn yields_to(self, other: Self) -> bool {
+    other.urgency < self.urgency
+  }
+}
+
+impl Default for Priority {
+  #[inline]
+  fn default() -> Self {
+    Self::DEFAULT
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::{Headers, Priority};
+
+  #[test]
+  fn ignores_invalid_members() {
+    assert_eq!(Priority::parse(b""), Priority::DEFAULT);
+    assert_eq!(Priority::parse(b"u=8, i=1"), Priority::DEFAULT);
+    assert_eq!(Priority::parse(b"u=a, foo=bar"), Priority::DEFAULT);
+    assert_eq!(Priority::parse(b"u=-1, i=?2"), Priority::DEFAULT);
+  }
+
+  #[test]
+  fn parses_and_encodes() {
+    assert_eq!(Priority::parse(b"u=0"), Priority::new(false, 0));
+    assert_eq!(Priority::parse(b"i"), Priority::new(true, 3));
+    assert_eq!(Priority::parse(b" u=5 ;foo=1 , i=?1"), Priority::new(true, 5));
+    assert_eq!(Priority::parse(b"u=1, i, u=6, i=?0"), Priority::new(false, 6));
+    assert_eq!(Priority::new(true, 9).field_value(), "u=7, i");
+    let mut headers = Headers::new();
+    Priority::new(true, 1).push_header(&mut headers).unwrap();
+    assert_eq!(Priority::from_headers(&headers), Priority::new(true, 1));
+    assert_eq!(Priority::from_headers(&Headers::new()), Priority::DEFAULT);
+  }
+
+  #[cfg(feature = "http2")]
+  #[test]
+  fn yields_to() {
+    let [high, low] = [Priority::new(false, 1), Priority::new(false, 5)];
+    assert!(low.yields_to(high));
+    assert!(!high.yields_to(low));
+    assert!(!low.yields_to(low));
+    let incremental = Priority::new(true, 5);
+    assert!(!incremental.yields_to(low));
+    assert!(!low.yields_to(incremental));
+    assert!(incremental.yields_to(high));
+  }
+}
@@ -1,3 +1,5 @@
+mod cookie_session_store;
+mod memory_session_store;
 mod session_error;
 mod session_manager;
 mod session_manager_builder;
@@ -5,7 +7,11 @@ mod session_manager_builder;
 mod session_middleware;
 mod session_state;
 mod session_store;
+#[cfg(test)]
+mod tests;
 
+pub use cookie_session_store::CookieSessionStore;
+pub use memory_session_store::MemorySessionStore;
 pub use session_error::SessionError;
 pub use session_manager::*;
 pub use session_manager_builder::SessionManagerBuilder;
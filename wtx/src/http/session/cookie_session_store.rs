@@ -0,0 +1,38 @@
+use crate::http::{SessionState, SessionStore, session::SessionKey};
+
+/// Stateless [`SessionStore`] where the whole [`SessionState`] lives in the encrypted cookie.
+///
+/// Cookies are authenticated by the AES-GCM encryption of [`crate::http::SessionManager`], as such,
+/// their contents are trusted as long as they are not expired. Sessions can not be revoked on the
+/// server side, deleting a cookie only instructs the client to discard it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CookieSessionStore;
+
+impl<CS, E> SessionStore<CS, E> for CookieSessionStore {
+  const IS_STATELESS: bool = true;
+
+  #[inline]
+  async fn create(&mut self, _: &SessionState<CS>) -> Result<(), E> {
+    Ok(())
+  }
+
+  #[inline]
+  async fn delete(&mut self, _: &SessionKey) -> Result<(), E> {
+    Ok(())
+  }
+
+  #[inline]
+  async fn delete_expired(&mut self) -> Result<(), E> {
+    Ok(())
+  }
+
+  #[inline]
+  async fn read(&mut self, _: SessionKey) -> Result<Option<SessionState<CS>>, E> {
+    Ok(None)
+  }
+
+  #[inline]
+  async fn update(&mut self, _: &SessionKey, _: &SessionState<CS>) -> Result<(), E> {
+    Ok(())
+  }
+}
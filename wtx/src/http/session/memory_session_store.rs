@@ -0,0 +1,108 @@
+use crate::{
+  calendar::Instant,
+  http::{SessionState, SessionStore, session::SessionKey},
+  sync::{Arc, SyncMutex},
+};
+use hashbrown::HashMap;
+
+type Shard<CS> = SyncMutex<HashMap<SessionKey, SessionState<CS>>>;
+
+/// In-process [`SessionStore`] for single-node deployments.
+///
+/// Sessions are distributed across `N` shards, each one protected by its own lock that is never
+/// held across `await` points. Clones share the same storage, which allows the usage of the same
+/// instance in the expiration future returned by [`crate::http::SessionManagerBuilder`] and in
+/// middlewares.
+#[derive(Debug)]
+pub struct MemorySessionStore<CS, const N: usize = 16> {
+  shards: Arc<[Shard<CS>; N]>,
+}
+
+impl<CS, const N: usize> MemorySessionStore<CS, N> {
+  /// New empty instance
+  #[inline]
+  pub fn new() -> Self {
+    Self { shards: Arc::new(core::array::from_fn(|_| SyncMutex::new(HashMap::new()))) }
+  }
+
+  /// If there are no stored sessions.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.shards.iter().all(|el| el.lock().is_empty())
+  }
+
+  /// Number of stored sessions, including expired ones that weren't removed yet.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.shards.iter().fold(0, |acc, el| acc.wrapping_add(el.lock().len()))
+  }
+
+  fn shard(&self, session_key: &SessionKey) -> Option<&Shard<CS>> {
+    let hash = session_key
+      .as_str()
+      .bytes()
+      .fold(0usize, |acc, el| acc.wrapping_mul(31).wrapping_add(usize::from(el)));
+    self.shards.get(hash.checked_rem(N)?)
+  }
+}
+
+impl<CS, const N: usize> Clone for MemorySessionStore<CS, N> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self { shards: Arc::clone(&self.shards) }
+  }
+}
+
+impl<CS, const N: usize> Default for MemorySessionStore<CS, N> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<CS, E, const N: usize> SessionStore<CS, E> for MemorySessionStore<CS, N>
+where
+  CS: Clone,
+  E: From<crate::Error>,
+{
+  #[inline]
+  async fn create(&mut self, state: &SessionState<CS>) -> Result<(), E> {
+    if let Some(shard) = self.shard(&state.session_key) {
+      let _prev = shard.lock().insert(state.session_key, state.clone());
+    }
+    Ok(())
+  }
+
+  #[inline]
+  async fn delete(&mut self, session_key: &SessionKey) -> Result<(), E> {
+    if let Some(shard) = self.shard(session_key) {
+      let _prev = shard.lock().remove(session_key);
+    }
+    Ok(())
+  }
+
+  #[inline]
+  async fn delete_expired(&mut self) -> Result<(), E> {
+    let now = Instant::now_date_time()?;
+    for shard in self.shards.iter() {
+      shard.lock().retain(|_, el| el.expires_at.is_none_or(|expires_at| expires_at > now));
+    }
+    Ok(())
+  }
+
+  #[inline]
+  async fn read(&mut self, session_key: SessionKey) -> Result<Option<SessionState<CS>>, E> {
+    Ok(self.shard(&session_key).and_then(|el| el.lock().get(&session_key).cloned()))
+  }
+
+  #[inline]
+  async fn update(&mut self, session_key: &SessionKey, state: &SessionState<CS>) -> Result<(), E> {
+    if let Some(shard) = self.shard(session_key) {
+      let _prev = shard.lock().remove(session_key);
+    }
+    if let Some(shard) = self.shard(&state.session_key) {
+      let _prev = shard.lock().insert(state.session_key, state.clone());
+    }
+    Ok(())
+  }
+}
@@ -3,10 +3,10 @@ use crate::{
   collections::{ArrayString, ArrayStringU8, ArrayVectorCopy, Vector},
   crypto::{Aead as _, Aes128GcmGlobal, gen_aead_nonce},
   http::{
-    Header, KnownHeaderName, MsgBufferString, MsgDataMut, SessionManagerBuilder, SessionState,
-    SessionStore, cookie::cookie_generic::CookieGeneric,
+    Header, KnownHeaderName, MsgBufferString, MsgDataMut, SessionError, SessionManagerBuilder,
+    SessionState, SessionStore, cookie::cookie_generic::CookieGeneric,
   },
-  misc::{AsciiGraphic, Lease as _, LeaseMut, Secret},
+  misc::{AsciiGraphic, Lease as _, LeaseMut, Secret, SecretContext},
   rng::CryptoRng,
   sync::{Arc, AsyncMutex},
 };
@@ -14,7 +14,7 @@ use alloc::string::String;
 use core::{
   fmt::{Debug, Formatter},
   marker::PhantomData,
-  str,
+  mem, str,
 };
 use serde::Serialize;
 
@@ -39,6 +39,13 @@ where
     SessionManagerBuilder::new()
   }
 
+  /// Forgets the secret replaced by the last rotation, which invalidates all cookies that were
+  /// encrypted with it.
+  #[inline]
+  pub async fn clear_previous_key(&mut self) {
+    self.inner.1.lock().await.previous_session_secret = None;
+  }
+
   /// Removes the session from the store and also modifies headers.
   #[inline]
   pub async fn delete_session_cookie<MD, S>(
@@ -54,6 +61,46 @@ where
     self.inner.1.lock().await.delete_session_cookie(msg_data, state, store).await
   }
 
+  /// Replaces the secret used to encrypt new cookies with a randomly generated key.
+  ///
+  /// See [`Self::rotate_with_key`].
+  #[inline]
+  pub async fn rotate_generating_key<RNG>(
+    &mut self,
+    rng: &mut RNG,
+    secret_context: SecretContext,
+  ) -> crate::Result<()>
+  where
+    RNG: CryptoRng,
+  {
+    let mut session_secret = [0u8; 16];
+    rng.fill_slice(&mut session_secret);
+    self.rotate_with_key(rng, secret_context, &mut session_secret).await
+  }
+
+  /// Replaces the secret used to encrypt new cookies with the provided 16 bytes key.
+  ///
+  /// Cookies encrypted with the replaced secret are still accepted until the next rotation or
+  /// until [`Self::clear_previous_key`] is called, which gives clients time to receive new cookies.
+  #[inline]
+  pub async fn rotate_with_key<RNG>(
+    &mut self,
+    rng: &mut RNG,
+    secret_context: SecretContext,
+    session_secret: &mut [u8],
+  ) -> crate::Result<()>
+  where
+    RNG: CryptoRng,
+  {
+    if session_secret.len() != 16 {
+      return Err(SessionError::InvalidSecretLength.into());
+    }
+    let secret = Secret::new(session_secret, rng, secret_context)?;
+    let inner = &mut *self.inner.1.lock().await;
+    inner.previous_session_secret = Some(mem::replace(&mut inner.session_secret, secret));
+    Ok(())
+  }
+
   /// Saves the session in the store and also modifies headers.
   ///
   /// The `msg_data` body is used as a temporary buffer but no existing content is erased.
@@ -138,6 +185,7 @@ impl<CS, E> Clone for SessionManager<CS, E> {
 pub struct SessionManagerInner<CS, E> {
   pub(crate) cookie_def: CookieGeneric<String, Vector<u8>>,
   pub(crate) phantom: PhantomData<(CS, E)>,
+  pub(crate) previous_session_secret: Option<Secret>,
   pub(crate) session_secret: Secret,
 }
 
@@ -145,6 +193,22 @@ impl<CS, E> SessionManagerInner<CS, E>
 where
   E: From<crate::Error>,
 {
+  #[cfg(feature = "http2-server-framework")]
+  /// Decrypts `value` into the internal buffer using the current secret or, if unsuccessful, the
+  /// secret replaced by the last rotation.
+  pub(crate) fn decrypt_cookie(&mut self, name: &str, value: &str) -> crate::Result<&[u8]> {
+    let Self { cookie_def, previous_session_secret, session_secret, .. } = self;
+    let mut rslt = decrypt_cookie(&mut cookie_def.value, name, session_secret, value);
+    if rslt.is_err()
+      && let Some(elem) = previous_session_secret
+    {
+      cookie_def.value.clear();
+      rslt = decrypt_cookie(&mut cookie_def.value, name, elem, value);
+    }
+    rslt?;
+    Ok(&cookie_def.value)
+  }
+
   #[inline]
   pub(crate) async fn delete_session_cookie<MD, S>(
     &mut self,
@@ -170,3 +234,21 @@ impl<CS, E> Debug for SessionManagerInner<CS, E> {
     f.debug_struct("SessionManagerInner").finish()
   }
 }
+
+#[cfg(feature = "http2-server-framework")]
+fn decrypt_cookie(
+  buffer: &mut Vector<u8>,
+  name: &str,
+  secret: &Secret,
+  value: &str,
+) -> crate::Result<()> {
+  let _ = secret.peek(&mut ArrayVectorCopy::<_, { 16 + 28 }>::new().into(), |sp| {
+    Aes128GcmGlobal::decrypt_base64_to_buffer(
+      name.as_bytes(),
+      buffer,
+      value.as_bytes(),
+      sp.data().try_into()?,
+    )
+  })??;
+  Ok(())
+}
@@ -107,6 +107,7 @@ impl SessionManagerBuilder {
           AsyncMutex::new(SessionManagerInner {
             cookie_def,
             phantom: PhantomData,
+            previous_session_secret: None,
             session_secret: Secret::new(session_secret, rng, secret_context)?,
           }),
         )),
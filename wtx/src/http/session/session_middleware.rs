@@ -1,52 +1,45 @@
 use crate::{
   calendar::{DateTime, Instant, Utc},
-  collections::{ArrayVectorU8, Vector},
-  crypto::{Aead as _, Aes128GcmGlobal},
+  collections::Vector,
   http::{
-    KnownHeaderName, MsgBufferString, Request, Response, SessionManager, SessionManagerInner,
-    SessionState, SessionStore, StatusCode, cookie::cookie_str::CookieStr,
-    http2_server_framework::Middleware,
+    KnownHeaderName, MsgBufferString, Request, Response, SessionManager, SessionState,
+    SessionStore, StatusCode, cookie::cookie_str::CookieStr, http2_server_framework::Middleware,
   },
   misc::{Lease as _, LeaseMut, serde_json_deserialize_from_slice},
-  pool::{ResourceManager, SimplePool},
 };
 use alloc::string::String;
 use core::ops::ControlFlow;
 use serde::de::DeserializeOwned;
 
 /// Decodes cookies received from requests and manages them.
+///
+/// `session_store` is cloned in every request, as such, it should be a cheap handle like
+/// [`crate::pool::SimplePool`] or [`crate::http::MemorySessionStore`].
 #[derive(Debug)]
-pub struct SessionMiddleware<CS, E, RM>
-where
-  RM: ResourceManager,
-{
+pub struct SessionMiddleware<CS, E, SS> {
   allowed_paths: Vector<String>,
   session_manager: SessionManager<CS, E>,
-  session_store: SimplePool<RM>,
+  session_store: SS,
 }
 
-impl<CS, E, RM> SessionMiddleware<CS, E, RM>
-where
-  RM: ResourceManager,
-{
+impl<CS, E, SS> SessionMiddleware<CS, E, SS> {
   /// New instance
   #[inline]
   pub const fn new(
     allowed_paths: Vector<String>,
     session_manager: SessionManager<CS, E>,
-    session_store: SimplePool<RM>,
+    session_store: SS,
   ) -> Self {
     Self { allowed_paths, session_manager, session_store }
   }
 }
 
-impl<D, CS, E, RM> Middleware<D, E> for SessionMiddleware<CS, E, RM>
+impl<D, CS, E, SS> Middleware<D, E> for SessionMiddleware<CS, E, SS>
 where
   D: LeaseMut<Option<SessionState<CS>>>,
   CS: DeserializeOwned + PartialEq,
   E: From<crate::Error>,
-  RM: ResourceManager<CreateAux = (), Error = E, RecycleAux = ()>,
-  RM::Resource: SessionStore<CS, E>,
+  SS: Clone + SessionStore<CS, E>,
 {
   type Aux = ();
 
@@ -96,27 +89,19 @@ where
           continue;
         }
         let mut session_guard = self.session_manager.inner.1.lock().await;
-        let SessionManagerInner { cookie_def, session_secret, .. } = &mut *session_guard;
         let (name, value) = (cookie_des.generic.name, cookie_des.generic.value);
-        let buffer = ArrayVectorU8::<_, { 16 + 28 }>::new();
-        let decrypt_rslt = session_secret.peek(&mut buffer.into(), |sp| {
-          Aes128GcmGlobal::decrypt_base64_to_buffer(
-            name.as_bytes(),
-            &mut cookie_def.value,
-            value.as_bytes(),
-            sp.data().try_into()?,
-          )
-        });
+        let decrypt_rslt = session_guard.decrypt_cookie(name.as_str(), value);
+        let json_rslt = decrypt_rslt.and_then(serde_json_deserialize_from_slice);
         req.msg_data.body.truncate(idx);
-        let value_json = decrypt_rslt??;
-        let json_rslt = serde_json_deserialize_from_slice(value_json);
-        cookie_def.value.clear();
+        session_guard.cookie_def.value.clear();
         json_rslt?
       };
       _trace!(target: crate::_WTX_HTTP_SM, "A session has been found in headers");
-      let Some(ss_db) =
-        self.session_store.get_with_unit().await?.lease_mut().read(ss_des.session_key).await?
-      else {
+      if SS::IS_STATELESS {
+        *data.lease_mut() = Some(ss_des);
+        continue;
+      }
+      let Some(ss_db) = self.session_store.clone().read(ss_des.session_key).await? else {
         has_stored_session = false;
         break;
       };
@@ -185,17 +170,16 @@ fn check_expiration(expires_at: &Option<DateTime<Utc>>) -> crate::Result<bool> {
 }
 
 #[inline]
-async fn delete_session_cookie<CS, D, E, RM>(
+async fn delete_session_cookie<CS, D, E, SS>(
   data: &mut D,
   req: &mut Request<MsgBufferString>,
   session_manager: &SessionManager<CS, E>,
-  session_store: &SimplePool<RM>,
+  session_store: &SS,
 ) -> Result<(), E>
 where
   D: LeaseMut<Option<SessionState<CS>>>,
   E: From<crate::Error>,
-  RM: ResourceManager<CreateAux = (), Error = E, RecycleAux = ()>,
-  RM::Resource: SessionStore<CS, E>,
+  SS: Clone + SessionStore<CS, E>,
 {
   req.clear();
   let _rslt = session_manager
@@ -203,11 +187,7 @@ where
     .1
     .lock()
     .await
-    .delete_session_cookie(
-      &mut req.msg_data,
-      data.lease_mut(),
-      &mut ***session_store.get_with_unit().await?,
-    )
+    .delete_session_cookie(&mut req.msg_data, data.lease_mut(), &mut session_store.clone())
     .await;
   Ok(())
 }
@@ -1,7 +1,14 @@
-use crate::http::{SessionState, session::SessionKey};
+use crate::{
+  http::{SessionState, session::SessionKey},
+  pool::{ResourceManager, SimplePool},
+};
 
 /// Abstraction for different session storages.
 pub trait SessionStore<CS, E> {
+  /// If the implementation doesn't keep sessions, in which case the contents of decrypted cookies
+  /// are trusted without further lookups.
+  const IS_STATELESS: bool = false;
+
   /// Stores a new [`SessionState`].
   fn create(&mut self, state: &SessionState<CS>) -> impl Future<Output = Result<(), E>>;
 
@@ -60,6 +67,8 @@ impl<CS, E, T> SessionStore<CS, E> for &mut T
 where
   T: SessionStore<CS, E>,
 {
+  const IS_STATELESS: bool = T::IS_STATELESS;
+
   #[inline]
   async fn create(&mut self, state: &SessionState<CS>) -> Result<(), E> {
     (*self).create(state).await
@@ -86,6 +95,40 @@ where
   }
 }
 
+impl<CS, E, RM> SessionStore<CS, E> for SimplePool<RM>
+where
+  E: From<crate::Error>,
+  RM: ResourceManager<CreateAux = (), Error = E, RecycleAux = ()>,
+  RM::Resource: SessionStore<CS, E>,
+{
+  const IS_STATELESS: bool = RM::Resource::IS_STATELESS;
+
+  #[inline]
+  async fn create(&mut self, state: &SessionState<CS>) -> Result<(), E> {
+    self.get_with_unit().await?.create(state).await
+  }
+
+  #[inline]
+  async fn delete(&mut self, session_key: &SessionKey) -> Result<(), E> {
+    self.get_with_unit().await?.delete(session_key).await
+  }
+
+  #[inline]
+  async fn delete_expired(&mut self) -> Result<(), E> {
+    self.get_with_unit().await?.delete_expired().await
+  }
+
+  #[inline]
+  async fn read(&mut self, session_key: SessionKey) -> Result<Option<SessionState<CS>>, E> {
+    self.get_with_unit().await?.read(session_key).await
+  }
+
+  #[inline]
+  async fn update(&mut self, session_key: &SessionKey, state: &SessionState<CS>) -> Result<(), E> {
+    self.get_with_unit().await?.update(session_key, state).await
+  }
+}
+
 #[cfg(feature = "postgres")]
 mod postgres {
   use crate::{
@@ -96,7 +139,6 @@ mod postgres {
     },
     http::session::{SessionKey, SessionState, SessionStore},
     net::Stream,
-    pool::{ResourceManager, SimplePool},
     tls::TlsCtx,
   };
 
@@ -180,41 +222,4 @@ mod postgres {
       Ok(())
     }
   }
-
-  impl<CS, E, RM> SessionStore<CS, E> for SimplePool<RM>
-  where
-    CS: for<'de> Decode<'de, Postgres<E>> + Encode<Postgres<E>>,
-    E: From<crate::Error>,
-    RM: ResourceManager<CreateAux = (), Error = E, RecycleAux = ()>,
-    RM::Resource: SessionStore<CS, E>,
-  {
-    #[inline]
-    async fn create(&mut self, state: &SessionState<CS>) -> Result<(), E> {
-      self.get_with_unit().await?.create(state).await
-    }
-
-    #[inline]
-    async fn delete(&mut self, session_key: &SessionKey) -> Result<(), E> {
-      self.get_with_unit().await?.delete(session_key).await
-    }
-
-    #[inline]
-    async fn delete_expired(&mut self) -> Result<(), E> {
-      self.get_with_unit().await?.delete_expired().await
-    }
-
-    #[inline]
-    async fn read(&mut self, session_key: SessionKey) -> Result<Option<SessionState<CS>>, E> {
-      self.get_with_unit().await?.read(session_key).await
-    }
-
-    #[inline]
-    async fn update(
-      &mut self,
-      session_key: &SessionKey,
-      state: &SessionState<CS>,
-    ) -> Result<(), E> {
-      self.get_with_unit().await?.update(session_key, state).await
-    }
-  }
 }
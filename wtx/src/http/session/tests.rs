@@ -0,0 +1,71 @@
+use crate::{
+  calendar::{DateTime, Instant, SigDuration, Utc},
+  collections::ArrayStringU8,
+  http::{MemorySessionStore, SessionState, SessionStore},
+};
+
+#[wtx::test]
+async fn memory_store() {
+  let mut store = MemorySessionStore::<u8>::new();
+  let now = Instant::now_date_time().unwrap();
+  let expired = state(1, Some(now.sub(SigDuration::from_seconds(10).unwrap()).unwrap()), "a");
+  let valid = state(2, Some(now.add(SigDuration::from_seconds(10).unwrap()).unwrap()), "b");
+  SessionStore::<_, crate::Error>::create(&mut store, &expired).await.unwrap();
+  SessionStore::<_, crate::Error>::create(&mut store.clone(), &valid).await.unwrap();
+  assert_eq!(store.len(), 2);
+  SessionStore::<u8, crate::Error>::delete_expired(&mut store).await.unwrap();
+  assert_eq!(store.len(), 1);
+  assert_eq!(read(&mut store, "a").await, None);
+  assert_eq!(read(&mut store, "b").await, Some(valid));
+  let renewed = state(3, valid.expires_at, "c");
+  SessionStore::<_, crate::Error>::update(&mut store, &valid.session_key, &renewed).await.unwrap();
+  assert_eq!(read(&mut store, "b").await, None);
+  assert_eq!(read(&mut store, "c").await, Some(renewed));
+  SessionStore::<u8, crate::Error>::delete(&mut store, &renewed.session_key).await.unwrap();
+  assert!(store.is_empty());
+}
+
+#[cfg(feature = "http2-server-framework")]
+#[wtx::test]
+async fn key_rotation() {
+  use crate::{
+    http::{CookieSessionStore, KnownHeaderName, MsgBufferString, SessionError, SessionManager},
+    misc::SecretContext,
+    rng::{ChaCha20, CryptoSeedableRng},
+  };
+  let mut rng = ChaCha20::from_std_random().unwrap();
+  let secret_context = SecretContext::new(&mut rng).unwrap();
+  let (_, mut manager) = SessionManager::<u8, crate::Error>::builder()
+    .build_generating_key(&mut rng, secret_context.clone(), CookieSessionStore)
+    .unwrap();
+  let mut msg_buffer = MsgBufferString::default();
+  let _ = manager.set_session_cookie(7, &mut msg_buffer, &mut rng, &mut CookieSessionStore).await;
+  let header = msg_buffer.headers.get_by_name(KnownHeaderName::SetCookie.into()).unwrap();
+  let cookie = header.value.strip_prefix("wsid=").unwrap().split(';').next().unwrap();
+  let decrypt = async |manager: &SessionManager<u8, crate::Error>| {
+    let mut inner = manager.inner.1.lock().await;
+    let rslt = inner.decrypt_cookie("wsid", cookie).map(<[u8]>::to_vec);
+    inner.cookie_def.value.clear();
+    rslt
+  };
+  let plaintext = decrypt(&manager).await.unwrap();
+  manager.rotate_generating_key(&mut rng, secret_context.clone()).await.unwrap();
+  assert_eq!(decrypt(&manager).await.unwrap(), plaintext);
+  manager.clear_previous_key().await;
+  assert!(decrypt(&manager).await.is_err());
+  assert!(matches!(
+    manager.rotate_with_key(&mut rng, secret_context, &mut [0; 8]).await,
+    Err(crate::Error::SessionError(SessionError::InvalidSecretLength))
+  ));
+}
+
+fn state(custom_state: u8, expires_at: Option<DateTime<Utc>>, key: &str) -> SessionState<u8> {
+  let csrf = ArrayStringU8::try_from("csrf").unwrap();
+  SessionState::new(custom_state, expires_at, csrf, ArrayStringU8::try_from(key).unwrap())
+}
+
+async fn read(store: &mut MemorySessionStore<u8>, key: &str) -> Option<SessionState<u8>> {
+  SessionStore::<u8, crate::Error>::read(store, ArrayStringU8::try_from(key).unwrap())
+    .await
+    .unwrap()
+}
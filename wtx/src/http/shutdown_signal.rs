@@ -0,0 +1,248 @@
+use crate::{
+  collections::Vector,
+  sync::{Arc, AtomicBool, SyncMutex},
+};
+use core::{
+  future::poll_fn,
+  sync::atomic::Ordering,
+  task::{Poll, Waker},
+};
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+use {
+  crate::{futures::Sleep, sync::AtomicUsize},
+  core::{pin::pin, time::Duration},
+};
+
+/// Cloneable handle that instructs servers to stop accepting connections and to finish ongoing
+/// work.
+///
+/// WebSockets that hold a signal perform the closing handshake by themselves. Handlers of other
+/// long-lived connections like Server-Sent Events should observe [`Self::triggered`] to start
+/// their own closing procedures.
+#[derive(Clone, Debug)]
+pub struct ShutdownSignal {
+  inner: Arc<ShutdownSignalInner>,
+}
+
+impl ShutdownSignal {
+  /// Signal that is not triggered.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      inner: Arc::new(ShutdownSignalInner {
+        is_triggered: AtomicBool::new(false),
+        waiters: Waiters::new(),
+      }),
+    }
+  }
+
+  /// If [`Self::trigger`] was called.
+  #[inline]
+  pub fn is_triggered(&self) -> bool {
+    self.inner.is_triggered.load(Ordering::Acquire)
+  }
+
+  /// Notifies all current and future listeners. Subsequent calls have no effect.
+  #[inline]
+  pub fn trigger(&self) {
+    if !self.inner.is_triggered.swap(true, Ordering::AcqRel) {
+      self.inner.waiters.wake_all();
+    }
+  }
+
+  /// Resolves once [`Self::trigger`] is called.
+  #[inline]
+  pub async fn triggered(&self) {
+    self.inner.waiters.wait_until(|| self.is_triggered()).await;
+  }
+
+  #[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+  /// Awaits `future` unless the signal is triggered first, in which case `None` is returned.
+  pub(crate) async fn or_triggered<F>(&self, future: F) -> Option<F::Output>
+  where
+    F: Future,
+  {
+    let mut future_pin = pin!(future);
+    let mut triggered = pin!(self.triggered());
+    poll_fn(|cx| {
+      if let Poll::Ready(()) = triggered.as_mut().poll(cx) {
+        return Poll::Ready(None);
+      }
+      future_pin.as_mut().poll(cx).map(Some)
+    })
+    .await
+  }
+}
+
+impl Default for ShutdownSignal {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[derive(Debug)]
+struct ShutdownSignalInner {
+  is_triggered: AtomicBool,
+  waiters: Waiters,
+}
+
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+/// Number of in-flight connections or streams.
+#[derive(Clone, Debug)]
+pub(crate) struct InFlight {
+  inner: Arc<InFlightInner>,
+}
+
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+impl InFlight {
+  pub(crate) fn new() -> Self {
+    Self { inner: Arc::new(InFlightInner { count: AtomicUsize::new(0), waiters: Waiters::new() }) }
+  }
+
+  /// Registers a new in-flight element that lasts until the returned guard is dropped.
+  pub(crate) fn guard(&self) -> InFlightGuard {
+    let _ = self.inner.count.fetch_add(1, Ordering::AcqRel);
+    InFlightGuard { inner: Arc::clone(&self.inner) }
+  }
+
+  /// Waits until there are no in-flight elements or until `timeout` elapses. Returns `false` in
+  /// the latter case.
+  pub(crate) async fn wait_idle(&self, timeout: Duration) -> crate::Result<bool> {
+    let mut idle =
+      pin!(self.inner.waiters.wait_until(|| self.inner.count.load(Ordering::Acquire) == 0));
+    let mut sleep = pin!(Sleep::new(timeout)?);
+    poll_fn(|cx| {
+      if let Poll::Ready(()) = idle.as_mut().poll(cx) {
+        return Poll::Ready(Ok(true));
+      }
+      sleep.as_mut().poll(cx).map(|el| el.map(|()| false))
+    })
+    .await
+  }
+}
+
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+#[derive(Debug)]
+struct InFlightInner {
+  count: AtomicUsize,
+  waiters: Waiters,
+}
+
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+#[derive(Debug)]
+pub(crate) struct InFlightGuard {
+  inner: Arc<InFlightInner>,
+}
+
+#[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+impl Drop for InFlightGuard {
+  #[inline]
+  fn drop(&mut self) {
+    if self.inner.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+      self.inner.waiters.wake_all();
+    }
+  }
+}
+
+#[derive(Debug)]
+struct Waiters {
+  list: SyncMutex<(u64, Vector<(u64, Waker)>)>,
+}
+
+impl Waiters {
+  const fn new() -> Self {
+    Self { list: SyncMutex::new((0, Vector::new())) }
+  }
+
+  // `cond` is verified again while holding the lock to avoid lost notifications.
+  async fn wait_until(&self, cond: impl Fn() -> bool) {
+    let mut guard = WaiterGuard { id: None, waiters: self };
+    poll_fn(|cx| {
+      if cond() {
+        return Poll::Ready(());
+      }
+      let mut lock = self.list.lock();
+      if cond() {
+        return Poll::Ready(());
+      }
+      let (next_id, list) = &mut *lock;
+      if let Some(id) = guard.id
+        && let Some((_, waker)) = list.iter_mut().find(|el| el.0 == id)
+      {
+        waker.clone_from(cx.waker());
+      } else {
+        let id = *next_id;
+        *next_id = next_id.wrapping_add(1);
+        if list.push((id, cx.waker().clone())).is_err() {
+          cx.waker().wake_by_ref();
+        }
+        guard.id = Some(id);
+      }
+      Poll::Pending
+    })
+    .await;
+  }
+
+  fn wake_all(&self) {
+    for (_, waker) in &self.list.lock().1 {
+      waker.wake_by_ref();
+    }
+  }
+}
+
+struct WaiterGuard<'any> {
+  id: Option<u64>,
+  waiters: &'any Waiters,
+}
+
+impl Drop for WaiterGuard<'_> {
+  fn drop(&mut self) {
+    if let Some(id) = self.id {
+      let mut lock = self.waiters.list.lock();
+      if let Some(idx) = lock.1.iter().position(|el| el.0 == id) {
+        drop(lock.1.remove(idx));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{futures::PollOnce, http::ShutdownSignal};
+  use core::pin::pin;
+
+  #[cfg(any(feature = "http2-server-framework", feature = "web-socket-server-framework"))]
+  #[test]
+  fn waits_in_flight_elements() {
+    use crate::{executor::Runtime, http::shutdown_signal::InFlight};
+    use core::time::Duration;
+
+    // Timers of some features must be polled inside their own runtimes
+    fn runtime() -> impl Runtime {
+      cfg_select! {
+        feature = "tokio" => <tokio::runtime::LocalRuntime as Runtime>::new().unwrap(),
+        _ => crate::executor::StdRuntime::new(),
+      }
+    }
+
+    runtime().block_on(async {
+      let in_flight = InFlight::new();
+      let guard = in_flight.guard();
+      assert!(!in_flight.wait_idle(Duration::from_millis(10)).await.unwrap());
+      drop(guard);
+      assert!(in_flight.wait_idle(Duration::from_millis(10)).await.unwrap());
+    });
+  }
+
+  #[wtx::test]
+  async fn wakes_listeners() {
+    let signal = ShutdownSignal::new();
+    let mut fut = pin!(signal.triggered());
+    assert_eq!(PollOnce::new(fut.as_mut()).await, None);
+    signal.trigger();
+    assert!(signal.is_triggered());
+    fut.await;
+    signal.triggered().await;
+  }
+}
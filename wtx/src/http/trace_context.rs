@@ -0,0 +1,257 @@
+use crate::{
+  codec::{hex_decode, hex_encode},
+  collections::ArrayStringU8,
+  http::{Header, Headers, HttpError, KnownHeaderName},
+  misc::bytes_split1,
+  rng::Rng,
+};
+use alloc::string::String;
+
+const SAMPLED: u8 = 0b0000_0001;
+const TRACEPARENT_LEN: usize = 55;
+const TRACESTATE_MAX_LEN: usize = 512;
+
+/// W3C Trace Context propagated through the `traceparent` and `tracestate` headers.
+///
+/// <https://www.w3.org/TR/trace-context>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceContext {
+  flags: u8,
+  parent_id: [u8; 8],
+  trace_id: [u8; 16],
+  trace_state: String,
+}
+
+impl TraceContext {
+  /// Starts a new trace with random identifiers.
+  #[inline]
+  pub fn new<RNG>(is_sampled: bool, rng: &mut RNG) -> Self
+  where
+    RNG: Rng,
+  {
+    Self {
+      flags: if is_sampled { SAMPLED } else { 0 },
+      parent_id: non_zero(rng.u8_8()),
+      trace_id: non_zero(rng.u8_16()),
+      trace_state: String::new(),
+    }
+  }
+
+  /// Extracts the context of the `traceparent` and `tracestate` headers.
+  ///
+  /// Returns `None` if `traceparent` is missing or invalid, in which case the specification
+  /// recommends the creation of a new trace.
+  #[inline]
+  pub fn from_headers(headers: &Headers) -> Option<Self> {
+    let [traceparent, tracestate] = headers
+      .get_by_names([KnownHeaderName::Traceparent.into(), KnownHeaderName::Tracestate.into()]);
+    Self::parse(traceparent?.value, tracestate.map_or("", |el| el.value)).ok()
+  }
+
+  /// Parses the values of the `traceparent` and `tracestate` headers. `tracestate` can be empty.
+  ///
+  /// Future versions of `traceparent` are accepted as long as their first fields are compatible.
+  #[inline]
+  pub fn parse(traceparent: &str, tracestate: &str) -> crate::Result<Self> {
+    let Some((fields, rest)) = traceparent.as_bytes().split_at_checked(TRACEPARENT_LEN) else {
+      return Err(HttpError::InvalidTraceparent.into());
+    };
+    let mut iter = bytes_split1(fields, b'-');
+    let (Some(version), Some(trace_id_bytes), Some(parent_id_bytes), Some(flags_bytes), None) =
+      (iter.next(), iter.next(), iter.next(), iter.next(), iter.next())
+    else {
+      return Err(HttpError::InvalidTraceparent.into());
+    };
+    let is_valid_rest = match decode::<1>(version)? {
+      [0] => rest.is_empty(),
+      [255] => false,
+      _ => rest.first().is_none_or(|el| *el == b'-'),
+    };
+    if !is_valid_rest {
+      return Err(HttpError::InvalidTraceparent.into());
+    }
+    let trace_id = decode::<16>(trace_id_bytes)?;
+    let parent_id = decode::<8>(parent_id_bytes)?;
+    let [flags] = decode::<1>(flags_bytes)?;
+    if trace_id == [0; 16] || parent_id == [0; 8] {
+      return Err(HttpError::InvalidTraceparent.into());
+    }
+    let trace_state = if tracestate.len() <= TRACESTATE_MAX_LEN
+      && tracestate.bytes().all(|el| matches!(el, b' ' | b'\t' | 0x21..=0x7e))
+    {
+      String::from(tracestate.trim())
+    } else {
+      String::new()
+    };
+    Ok(Self { flags, parent_id, trace_id, trace_state })
+  }
+
+  /// Context that should be propagated to downstream calls of the current operation. Keeps the
+  /// trace identifier, flags and state but generates a new parent identifier.
+  #[inline]
+  #[must_use]
+  pub fn child<RNG>(&self, rng: &mut RNG) -> Self
+  where
+    RNG: Rng,
+  {
+    Self {
+      flags: self.flags,
+      parent_id: non_zero(rng.u8_8()),
+      trace_id: self.trace_id,
+      trace_state: self.trace_state.clone(),
+    }
+  }
+
+  /// Trace flags
+  #[inline]
+  pub const fn flags(&self) -> u8 {
+    self.flags
+  }
+
+  /// If the caller may have recorded trace data.
+  #[inline]
+  pub const fn is_sampled(&self) -> bool {
+    self.flags & SAMPLED == SAMPLED
+  }
+
+  /// Identifier of the operation that originated this context, also known as span ID.
+  #[inline]
+  pub const fn parent_id(&self) -> [u8; 8] {
+    self.parent_id
+  }
+
+  /// Writes the `traceparent` and, if not empty, the `tracestate` headers.
+  #[inline]
+  pub fn push_headers(&self, headers: &mut Headers) -> crate::Result<()> {
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::Traceparent.into(),
+      [self.traceparent()?.as_str()],
+    ))?;
+    if !self.trace_state.is_empty() {
+      headers.push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::Tracestate.into(),
+        [self.trace_state.as_str()],
+      ))?;
+    }
+    Ok(())
+  }
+
+  /// Changes the sampled flag.
+  #[inline]
+  pub const fn set_is_sampled(&mut self, value: bool) {
+    if value {
+      self.flags |= SAMPLED;
+    } else {
+      self.flags &= !SAMPLED;
+    }
+  }
+
+  /// Identifier of the whole trace.
+  #[inline]
+  pub const fn trace_id(&self) -> [u8; 16] {
+    self.trace_id
+  }
+
+  /// Vendor-specific data
+  #[inline]
+  pub fn trace_state(&self) -> &str {
+    &self.trace_state
+  }
+
+  /// Mutable version of [`Self::trace_state`].
+  #[inline]
+  pub const fn trace_state_mut(&mut self) -> &mut String {
+    &mut self.trace_state
+  }
+
+  /// Version `00` representation of the `traceparent` header.
+  #[inline]
+  pub fn traceparent(&self) -> crate::Result<ArrayStringU8<TRACEPARENT_LEN>> {
+    let mut flags = [0; 2];
+    let mut parent_id = [0; 16];
+    let mut trace_id = [0; 32];
+    let mut rslt = ArrayStringU8::new();
+    let _ = rslt.push_strs([
+      "00-",
+      hex_encode(&self.trace_id, None, &mut trace_id)?,
+      "-",
+      hex_encode(&self.parent_id, None, &mut parent_id)?,
+      "-",
+      hex_encode(&[self.flags], None, &mut flags)?,
+    ])?;
+    Ok(rslt)
+  }
+}
+
+// Uppercase letters are forbidden
+fn decode<const N: usize>(bytes: &[u8]) -> crate::Result<[u8; N]> {
+  if bytes.len() != N.wrapping_mul(2) || bytes.iter().any(u8::is_ascii_uppercase) {
+    return Err(HttpError::InvalidTraceparent.into());
+  }
+  let mut rslt = [0; N];
+  let _ = hex_decode(bytes, &mut rslt).map_err(|_err| HttpError::InvalidTraceparent)?;
+  Ok(rslt)
+}
+
+fn non_zero<const N: usize>(mut array: [u8; N]) -> [u8; N] {
+  if array == [0; N]
+    && let Some(elem) = array.last_mut()
+  {
+    *elem = 1;
+  }
+  array
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    http::{Headers, TraceContext},
+    rng::{SeedableRng, Xorshift64},
+  };
+
+  const TP: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+  #[test]
+  fn child_keeps_trace() {
+    let mut rng = Xorshift64::from_simple_seed().unwrap();
+    let parent = TraceContext::parse(TP, "congo=t61rcWkgMzE").unwrap();
+    let child = parent.child(&mut rng);
+    assert_eq!(child.trace_id(), parent.trace_id());
+    assert_ne!(child.parent_id(), parent.parent_id());
+    assert_eq!(child.trace_state(), "congo=t61rcWkgMzE");
+    assert!(child.is_sampled());
+  }
+
+  #[test]
+  fn parses_and_encodes() {
+    let tc = TraceContext::parse(TP, "").unwrap();
+    assert!(tc.is_sampled());
+    assert_eq!(tc.parent_id(), [0, 240, 103, 170, 11, 169, 2, 183]);
+    assert_eq!(tc.traceparent().unwrap().as_str(), TP);
+    let mut headers = Headers::new();
+    tc.push_headers(&mut headers).unwrap();
+    assert_eq!(TraceContext::from_headers(&headers), Some(tc));
+  }
+
+  #[test]
+  fn handles_future_versions() {
+    assert!(TraceContext::parse(&[&TP.replacen("00", "01", 1), "-foo"].concat(), "").is_ok());
+    assert!(TraceContext::parse(&[&TP.replacen("00", "ff", 1), "-foo"].concat(), "").is_err());
+    assert!(TraceContext::parse(&[TP, "-foo"].concat(), "").is_err());
+  }
+
+  #[test]
+  fn rejects_invalid_values() {
+    assert!(TraceContext::parse("", "").is_err());
+    assert!(TraceContext::parse(&TP.to_uppercase(), "").is_err());
+    assert!(
+      TraceContext::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01", "").is_err()
+    );
+    assert!(
+      TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01", "").is_err()
+    );
+    assert!(
+      TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736_00f067aa0ba902b7-01", "").is_err()
+    );
+  }
+}
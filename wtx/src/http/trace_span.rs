@@ -0,0 +1,188 @@
+use crate::{
+  codec::{hex_encode, u64_string},
+  collections::Vector,
+};
+use core::time::Duration;
+
+/// The role of a [`TraceSpan`] in a trace.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TraceSpanKind {
+  /// Outgoing request
+  Client,
+  /// Processing of an asynchronous message
+  Consumer,
+  /// Internal operation
+  Internal,
+  /// Creation of an asynchronous message
+  Producer,
+  /// Incoming request
+  Server,
+}
+
+impl TraceSpanKind {
+  const fn otlp_number(self) -> &'static str {
+    match self {
+      Self::Internal => "1",
+      Self::Server => "2",
+      Self::Client => "3",
+      Self::Producer => "4",
+      Self::Consumer => "5",
+    }
+  }
+}
+
+/// A finished operation that can be exported to OpenTelemetry collectors.
+#[derive(Clone, Copy, Debug)]
+pub struct TraceSpan<'any> {
+  /// Key/value pairs that describe the operation
+  pub attributes: &'any [(&'any str, &'any str)],
+  /// Ending time since the UNIX epoch
+  pub end: Duration,
+  /// See [`TraceSpanKind`].
+  pub kind: TraceSpanKind,
+  /// Name of the operation
+  pub name: &'any str,
+  /// Identifier of the parent operation, if any
+  pub parent_span_id: Option<[u8; 8]>,
+  /// Identifier of the operation
+  pub span_id: [u8; 8],
+  /// Starting time since the UNIX epoch
+  pub start: Duration,
+  /// Identifier of the whole trace
+  pub trace_id: [u8; 16],
+}
+
+impl TraceSpan<'_> {
+  /// Writes an OTLP/JSON `ExportTraceServiceRequest` into `buffer`.
+  ///
+  /// The contents should be sent with a `POST` request and the `application/json` content type
+  /// to the `/v1/traces` path of a collector, which usually listens on the port `4318`. See
+  /// [`crate::http::OtlpJsonExporter`].
+  #[inline]
+  pub fn encode_otlp_json(
+    service_name: &str,
+    spans: &[Self],
+    buffer: &mut Vector<u8>,
+  ) -> crate::Result<()> {
+    buffer.extend_from_copyable_slice(br#"{"resourceSpans":[{"resource":{"attributes":["#)?;
+    push_attribute(buffer, "service.name", service_name)?;
+    buffer.extend_from_copyable_slice(br#"]},"scopeSpans":[{"scope":{"name":"wtx"},"spans":["#)?;
+    for (idx, span) in spans.iter().enumerate() {
+      if idx > 0 {
+        buffer.push(b',')?;
+      }
+      span.encode(buffer)?;
+    }
+    buffer.extend_from_copyable_slice(b"]}]}]}")?;
+    Ok(())
+  }
+
+  fn encode(&self, buffer: &mut Vector<u8>) -> crate::Result<()> {
+    let mut span_id = [0; 16];
+    let mut trace_id = [0; 32];
+    let _ = buffer.extend_from_copyable_slices([
+      r#"{"traceId":""#,
+      hex_encode(&self.trace_id, None, &mut trace_id)?,
+      r#"","spanId":""#,
+      hex_encode(&self.span_id, None, &mut span_id)?,
+      "\"",
+    ])?;
+    if let Some(elem) = self.parent_span_id {
+      let _ = buffer.extend_from_copyable_slices([
+        r#","parentSpanId":""#,
+        hex_encode(&elem, None, &mut span_id)?,
+        "\"",
+      ])?;
+    }
+    buffer.extend_from_copyable_slice(br#","name":"#)?;
+    push_json_str(buffer, self.name)?;
+    let _ = buffer.extend_from_copyable_slices([
+      r#","kind":"#,
+      self.kind.otlp_number(),
+      r#","startTimeUnixNano":""#,
+      u64_string(nanos(self.start)).as_str(),
+      r#"","endTimeUnixNano":""#,
+      u64_string(nanos(self.end)).as_str(),
+      r#"","attributes":["#,
+    ])?;
+    for (idx, (key, value)) in self.attributes.iter().enumerate() {
+      if idx > 0 {
+        buffer.push(b',')?;
+      }
+      push_attribute(buffer, key, value)?;
+    }
+    buffer.extend_from_copyable_slice(b"]}")?;
+    Ok(())
+  }
+}
+
+fn nanos(duration: Duration) -> u64 {
+  duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+fn push_attribute(buffer: &mut Vector<u8>, key: &str, value: &str) -> crate::Result<()> {
+  buffer.extend_from_copyable_slice(br#"{"key":"#)?;
+  push_json_str(buffer, key)?;
+  buffer.extend_from_copyable_slice(br#","value":{"stringValue":"#)?;
+  push_json_str(buffer, value)?;
+  buffer.extend_from_copyable_slice(b"}}")?;
+  Ok(())
+}
+
+fn push_json_str(buffer: &mut Vector<u8>, str: &str) -> crate::Result<()> {
+  buffer.push(b'"')?;
+  for byte in str.bytes() {
+    match byte {
+      b'"' => buffer.extend_from_copyable_slice(br#"\""#)?,
+      b'\\' => buffer.extend_from_copyable_slice(br"\\")?,
+      b'\n' => buffer.extend_from_copyable_slice(br"\n")?,
+      b'\r' => buffer.extend_from_copyable_slice(br"\r")?,
+      b'\t' => buffer.extend_from_copyable_slice(br"\t")?,
+      0..=31 => {
+        let mut hex = [0; 2];
+        let _ =
+          buffer.extend_from_copyable_slices([r"\u00", hex_encode(&[byte], None, &mut hex)?])?;
+      }
+      _ => buffer.push(byte)?,
+    }
+  }
+  buffer.push(b'"')?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    http::{TraceSpan, TraceSpanKind},
+  };
+  use core::time::Duration;
+
+  #[test]
+  fn encodes_otlp_json() {
+    let mut buffer = Vector::new();
+    let span = TraceSpan {
+      attributes: &[("http.route", "/a\"b")],
+      end: Duration::from_secs(2),
+      kind: TraceSpanKind::Server,
+      name: "GET",
+      parent_span_id: None,
+      span_id: [1; 8],
+      start: Duration::from_secs(1),
+      trace_id: [2; 16],
+    };
+    TraceSpan::encode_otlp_json("foo", &[span], &mut buffer).unwrap();
+    assert_eq!(
+      buffer.as_slice(),
+      concat!(
+        r#"{"resourceSpans":[{"resource":{"attributes":[{"key":"service.name","value":"#,
+        r#"{"stringValue":"foo"}}]},"scopeSpans":[{"scope":{"name":"wtx"},"spans":["#,
+        r#"{"traceId":"02020202020202020202020202020202","spanId":"0101010101010101","#,
+        r#""name":"GET","kind":2,"startTimeUnixNano":"1000000000","#,
+        r#""endTimeUnixNano":"2000000000","attributes":[{"key":"http.route","value":"#,
+        r#"{"stringValue":"/a\"b"}}]}]}]}]}"#
+      )
+      .as_bytes()
+    );
+  }
+}
@@ -0,0 +1,149 @@
+use crate::{
+  collections::Vector,
+  http::{
+    Header, Headers, HttpClient, KnownHeaderName, MsgBufferString, MsgData, Request, Response,
+    TraceContext,
+  },
+  misc::Lease,
+  rng::{Xorshift64, simple_seed},
+  sync::AtomicCell,
+};
+
+/// [`HttpClient`] that injects the `traceparent` and `tracestate` headers of a child of the stored
+/// [`TraceContext`] into every sent request.
+///
+/// Each request receives a fresh span id that shares the trace id of the stored context. Can wrap
+/// any client, including `Http2` connections and `Http2ClientPool` instances. Requests that already
+/// have a `traceparent` header are sent as is.
+#[derive(Debug)]
+pub struct TracedHttpClient<C> {
+  client: C,
+  rng: AtomicCell<Xorshift64>,
+  trace_context: Option<TraceContext>,
+}
+
+impl<C> TracedHttpClient<C> {
+  /// Constructor
+  #[inline]
+  pub fn new(client: C, trace_context: Option<TraceContext>) -> Self {
+    Self { client, rng: AtomicCell::new(Xorshift64::from(simple_seed())), trace_context }
+  }
+
+  /// Underlying client
+  #[inline]
+  pub const fn client(&self) -> &C {
+    &self.client
+  }
+
+  /// Parent context of subsequent requests. Sent requests carry children of this context.
+  #[inline]
+  pub const fn trace_context_mut(&mut self) -> &mut Option<TraceContext> {
+    &mut self.trace_context
+  }
+}
+
+impl<C> HttpClient for TracedHttpClient<C>
+where
+  C: HttpClient,
+{
+  type ReqId = C::ReqId;
+
+  #[inline]
+  async fn recv_res(&self, req_id: Self::ReqId) -> crate::Result<Response<MsgBufferString>> {
+    self.client.recv_res(req_id).await
+  }
+
+  #[inline]
+  async fn send_req<MD>(
+    &self,
+    enc_buffer: &mut Vector<u8>,
+    req: Request<MD>,
+  ) -> crate::Result<Self::ReqId>
+  where
+    MD: MsgData,
+    MD::Body: Lease<[u8]>,
+  {
+    let Some(trace_context) = &self.trace_context else {
+      return self.client.send_req(enc_buffer, req).await;
+    };
+    let req_headers = req.msg_data.headers();
+    if req_headers.get_by_name(KnownHeaderName::Traceparent.into()).is_some() {
+      return self.client.send_req(enc_buffer, req).await;
+    }
+    let mut headers = Headers::new();
+    trace_context.child(&mut &self.rng).push_headers(&mut headers)?;
+    for header in req_headers.iter() {
+      headers.push_from_iter(Header::new(
+        header.is_sensitive,
+        header.is_trailer,
+        header.name,
+        [header.value],
+      ))?;
+    }
+    let msg_data = (req.msg_data.body().lease(), &headers, req.msg_data.uri());
+    self.client.send_req(enc_buffer, Request::new(req.method, msg_data)).await
+  }
+}
+
+#[cfg(all(feature = "std", test))]
+mod tests {
+  use crate::{
+    collections::Vector,
+    executor::StdRuntime,
+    http::{
+      Header, Headers, HttpClient, KnownHeaderName, Method, MsgBufferString, MsgData, Request,
+      Response, TraceContext, TracedHttpClient,
+    },
+    misc::Lease,
+    rng::Xorshift64,
+    sync::SyncMutex,
+  };
+  use alloc::string::String;
+
+  #[derive(Debug)]
+  struct Client(SyncMutex<Option<String>>);
+
+  impl HttpClient for Client {
+    type ReqId = ();
+
+    async fn recv_res(&self, _: Self::ReqId) -> crate::Result<Response<MsgBufferString>> {
+      Err(crate::Error::ClosedHttpConnection)
+    }
+
+    async fn send_req<MD>(&self, _: &mut Vector<u8>, req: Request<MD>) -> crate::Result<()>
+    where
+      MD: MsgData,
+      MD::Body: Lease<[u8]>,
+    {
+      let header = req.msg_data.headers().get_by_name(KnownHeaderName::Traceparent.into());
+      *self.0.lock() = header.map(|el| String::from(el.value));
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn injects_missing_trace_contexts() {
+    StdRuntime::new().block_on(async {
+      let trace_context = TraceContext::new(true, &mut Xorshift64::from(1));
+      let client = TracedHttpClient::new(Client(SyncMutex::new(None)), Some(trace_context.clone()));
+      let mut enc_buffer = Vector::new();
+      let mut parent_ids = [[0; 8]; 2];
+      for parent_id in &mut parent_ids {
+        client.send_req(&mut enc_buffer, Request::new(Method::Get, ())).await.unwrap();
+        let sent = client.client().0.lock().clone().unwrap();
+        let sent_tc = TraceContext::parse(&sent, "").unwrap();
+        assert_eq!(sent_tc.trace_id(), trace_context.trace_id());
+        assert_ne!(sent_tc.parent_id(), trace_context.parent_id());
+        *parent_id = sent_tc.parent_id();
+      }
+      assert_ne!(parent_ids[0], parent_ids[1]);
+      let mut headers = Headers::new();
+      let other = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+      headers
+        .push_from_iter(Header::from_name_and_value(KnownHeaderName::Traceparent.into(), [other]))
+        .unwrap();
+      client.send_req(&mut enc_buffer, Request::new(Method::Get, ((), &headers))).await.unwrap();
+      assert_eq!(client.client().0.lock().as_deref(), Some(other));
+    });
+  }
+}
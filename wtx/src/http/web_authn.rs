@@ -1,11 +1,27 @@
 //! Structures of <https://www.w3.org/TR/webauthn-3>
 //!
-//! The existence of some elements does not necessarily mean that `WTX` support them.
+//! Ceremonies are verified by [`RelyingParty`], which is available with the `http-web-authn`
+//! feature. The existence of some elements does not necessarily mean that `WTX` support them.
+
+#[cfg(feature = "http-web-authn")]
+mod authenticator_data;
+#[cfg(feature = "http-web-authn")]
+mod relying_party;
+#[cfg(feature = "http-web-authn")]
+mod web_authn_credential;
+#[cfg(feature = "http-web-authn")]
+mod web_authn_error;
 
 use crate::{
   collections::{ArrayVectorU8, Vector},
   http::HttpError,
 };
+#[cfg(feature = "http-web-authn")]
+pub use relying_party::RelyingParty;
+#[cfg(feature = "http-web-authn")]
+pub use web_authn_credential::WebAuthnCredential;
+#[cfg(feature = "http-web-authn")]
+pub use web_authn_error::WebAuthnError;
 
 /// The possible ceremony types of a `WebAuthn` request.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -218,6 +234,9 @@ pub struct AttestationObject<B> {
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Debug, PartialEq)]
 pub struct AttestationStatement<B> {
+  /// Algorithm used to generate the signature.
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub alg: Option<Alg>,
   /// The signature or proof data.
   #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
   pub sig: Option<B>,
@@ -343,11 +362,11 @@ pub struct CoseKey<B> {
   pub kty: i32,
   /// Algorithm.
   pub alg: Alg,
-  /// Curve of EC2 keys.
+  /// Curve of EC2 and OKP keys. Zero for RSA keys.
   pub crv: i32,
-  /// The X coordinate of EC2 keys.
+  /// The X coordinate of EC2 keys, the public key of OKP keys or the modulus of RSA keys.
   pub x: B,
-  /// The Y coordinate (EC2 only). Absent for OKP keys.
+  /// The Y coordinate of EC2 keys or the exponent of RSA keys. Absent for OKP keys.
   pub y: Option<B>,
 }
 
@@ -561,12 +580,12 @@ mod serde_local {
           let mut x = None;
           let mut y = None;
           while let Some(key) = map.next_key::<i64>()? {
-            match key {
-              -3 => y = Some(map.next_value::<B>()?),
-              -2 => x = Some(map.next_value::<B>()?),
-              -1 => crv = Some(map.next_value()?),
-              1 => kty = Some(map.next_value()?),
-              3 => alg = Some(map.next_value()?),
+            match (key, kty) {
+              (-3, _) | (-2, Some(3)) => y = Some(map.next_value::<B>()?),
+              (-2, _) | (-1, Some(3)) => x = Some(map.next_value::<B>()?),
+              (-1, _) => crv = Some(map.next_value()?),
+              (1, _) => kty = Some(map.next_value()?),
+              (3, _) => alg = Some(map.next_value()?),
               _ => {
                 let _ = map.next_value::<IgnoredAny>()?;
               }
@@ -575,7 +594,11 @@ mod serde_local {
           Ok(CoseKey {
             kty: kty.ok_or_else(|| A::Error::missing_field("kty"))?,
             alg: alg.ok_or_else(|| A::Error::missing_field("alg"))?,
-            crv: crv.ok_or_else(|| A::Error::missing_field("crv"))?,
+            crv: match (crv, kty) {
+              (Some(elem), _) => elem,
+              (None, Some(3)) => 0,
+              (None, _) => return Err(A::Error::missing_field("crv")),
+            },
             x: x.ok_or_else(|| A::Error::missing_field("x"))?,
             y,
           })
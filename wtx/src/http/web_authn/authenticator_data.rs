@@ -0,0 +1,167 @@
+use crate::{
+  codec::format::CborDecoder,
+  collections::ArrayVectorU8,
+  http::web_authn::{
+    Alg, AttestationFormat, AttestationObject, AttestationStatement, CoseKey, WebAuthnError,
+  },
+};
+
+pub(crate) const FLAG_AT: u8 = 0b0100_0000;
+pub(crate) const FLAG_UP: u8 = 0b0000_0001;
+pub(crate) const FLAG_UV: u8 = 0b0000_0100;
+
+// https://www.w3.org/TR/webauthn-3/#sctn-authenticator-data
+#[derive(Debug)]
+pub(crate) struct AuthenticatorData<'bytes> {
+  pub(crate) attested_credential: Option<AttestedCredential<'bytes>>,
+  pub(crate) flags: u8,
+  pub(crate) rp_id_hash: &'bytes [u8],
+  pub(crate) sign_count: u32,
+}
+
+impl<'bytes> AuthenticatorData<'bytes> {
+  pub(crate) fn from_bytes(bytes: &'bytes [u8]) -> crate::Result<Self> {
+    let [rp_id_hash @ .., flags, c0, c1, c2, c3] = bytes.get(..37).unwrap_or_default() else {
+      return Err(WebAuthnError::InvalidAuthenticatorData.into());
+    };
+    let sign_count = u32::from_be_bytes([*c0, *c1, *c2, *c3]);
+    let rest = bytes.get(37..).unwrap_or_default();
+    let attested_credential = if flags & FLAG_AT == FLAG_AT {
+      let [aaguid @ .., l0, l1] = rest.get(..18).unwrap_or_default() else {
+        return Err(WebAuthnError::InvalidAuthenticatorData.into());
+      };
+      let id_len = usize::from(u16::from_be_bytes([*l0, *l1]));
+      let Some((id, key_bytes)) = rest.get(18..).unwrap_or_default().split_at_checked(id_len)
+      else {
+        return Err(WebAuthnError::InvalidAuthenticatorData.into());
+      };
+      let public_key = cose_key_from_cbor(&mut CborDecoder::new(key_bytes))?;
+      Some(AttestedCredential { aaguid: aaguid.try_into()?, id, public_key })
+    } else {
+      None
+    };
+    Ok(Self { attested_credential, flags: *flags, rp_id_hash, sign_count })
+  }
+}
+
+#[derive(Debug)]
+pub(crate) struct AttestedCredential<'bytes> {
+  pub(crate) aaguid: [u8; 16],
+  pub(crate) id: &'bytes [u8],
+  pub(crate) public_key: CoseKey<&'bytes [u8]>,
+}
+
+// https://www.w3.org/TR/webauthn-3/#sctn-attestation
+pub(crate) fn attestation_object_from_cbor(
+  bytes: &[u8],
+) -> crate::Result<AttestationObject<&[u8]>> {
+  let mut decoder = CborDecoder::new(bytes);
+  let mut att = None;
+  let mut att_stmt = None;
+  let mut auth_data = None;
+  for _ in 0..decoder.map_len()?.ok_or(WebAuthnError::InvalidCbor)? {
+    match decoder.text()? {
+      "attStmt" => att_stmt = Some(attestation_statement_from_cbor(&mut decoder)?),
+      "authData" => auth_data = Some(decoder.bytes()?),
+      "fmt" => {
+        att = Some(match decoder.text()? {
+          "android-key" => AttestationFormat::AndroidKey,
+          "android-safetynet" => AttestationFormat::AndroidSafetyNet,
+          "fido-u2f" => AttestationFormat::FidoU2f,
+          "none" => AttestationFormat::None,
+          "packed" => AttestationFormat::Packed,
+          "tpm" => AttestationFormat::Tpm,
+          _ => return Err(WebAuthnError::UnsupportedAttestationFormat.into()),
+        });
+      }
+      _ => decoder.skip()?,
+    }
+  }
+  Ok(AttestationObject {
+    att: att.ok_or(WebAuthnError::InvalidCbor)?,
+    att_stmt: att_stmt.ok_or(WebAuthnError::InvalidCbor)?,
+    auth_data: auth_data.ok_or(WebAuthnError::InvalidCbor)?,
+  })
+}
+
+fn attestation_statement_from_cbor<'bytes>(
+  decoder: &mut CborDecoder<'bytes>,
+) -> crate::Result<AttestationStatement<&'bytes [u8]>> {
+  let mut alg = None;
+  let mut sig = None;
+  let mut x5c = None;
+  for _ in 0..decoder.map_len()?.ok_or(WebAuthnError::InvalidCbor)? {
+    match decoder.text()? {
+      "alg" => alg = Some(Alg::try_from(i16::try_from(decoder.i64()?)?)?),
+      "sig" => sig = Some(decoder.bytes()?),
+      "x5c" => {
+        // Intermediate certificates that don't fit are discarded because only the leaf is used.
+        let mut certs = ArrayVectorU8::new();
+        for _ in 0..decoder.array_len()?.ok_or(WebAuthnError::InvalidCbor)? {
+          let cert = decoder.bytes()?;
+          if certs.remaining() > 0 {
+            certs.push(cert)?;
+          }
+        }
+        x5c = Some(certs);
+      }
+      _ => decoder.skip()?,
+    }
+  }
+  Ok(AttestationStatement { alg, sig, x5c })
+}
+
+// https://datatracker.ietf.org/doc/html/rfc9053#section-7
+fn cose_key_from_cbor<'bytes>(
+  decoder: &mut CborDecoder<'bytes>,
+) -> crate::Result<CoseKey<&'bytes [u8]>> {
+  let mut alg = None;
+  let mut crv = 0;
+  let mut kty = None;
+  let mut x = None;
+  let mut y = None;
+  for _ in 0..decoder.map_len()?.ok_or(WebAuthnError::InvalidCbor)? {
+    match (decoder.i64()?, kty) {
+      (-3, _) | (-2, Some(3)) => y = Some(decoder.bytes()?),
+      (-2, _) | (-1, Some(3)) => x = Some(decoder.bytes()?),
+      (-1, _) => crv = i32::try_from(decoder.i64()?)?,
+      (1, _) => kty = Some(i32::try_from(decoder.i64()?)?),
+      (3, _) => alg = Some(Alg::try_from(i16::try_from(decoder.i64()?)?)?),
+      _ => decoder.skip()?,
+    }
+  }
+  Ok(CoseKey {
+    kty: kty.ok_or(WebAuthnError::InvalidCoseKey)?,
+    alg: alg.ok_or(WebAuthnError::InvalidCoseKey)?,
+    crv,
+    x: x.ok_or(WebAuthnError::InvalidCoseKey)?,
+    y,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::web_authn::{
+    Alg,
+    authenticator_data::{AuthenticatorData, FLAG_AT, FLAG_UP},
+  };
+
+  #[test]
+  fn parses_attested_credential() {
+    let mut bytes = [0; 37].to_vec();
+    bytes[32] = FLAG_AT | FLAG_UP;
+    bytes[36] = 7;
+    bytes.extend([9; 16]);
+    bytes.extend([0, 2, 5, 6]);
+    bytes.extend([0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x41, 1, 0x22, 0x41, 2]);
+    let data = AuthenticatorData::from_bytes(&bytes).unwrap();
+    assert_eq!(data.sign_count, 7);
+    let credential = data.attested_credential.unwrap();
+    assert_eq!(credential.aaguid, [9; 16]);
+    assert_eq!(credential.id, &[5, 6]);
+    assert_eq!(credential.public_key.alg, Alg::ES256);
+    assert_eq!(credential.public_key.crv, 1);
+    assert_eq!(credential.public_key.x, &[1]);
+    assert_eq!(credential.public_key.y, Some(&[2][..]));
+  }
+}
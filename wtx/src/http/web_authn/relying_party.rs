@@ -0,0 +1,546 @@
+use crate::{
+  asn1::{Asn1DecodeWrapperAux, OID_X509_ORGANIZATIONAL_UNIT, Oid, asn1_unsigned_integers},
+  codec::{Base64Alphabet, Decode as _, DecodeWrapper, base64_encode, base64_encoded_len},
+  collections::Vector,
+  crypto::{
+    EcdsaP256SigningKeyGlobal, EcdsaP384SigningKeyGlobal, Ed25519SigningKeyGlobal, Hash as _,
+    HashTy, RsaPkcs1SigningKeyGlobal, RsaPssSigningKeyGlobal, Sha256Global, SigningKey as _,
+    SigningOutput,
+  },
+  http::web_authn::{
+    Alg, AttestationFormat, AttestationObject, AuthenticatorAssertionResponse,
+    AuthenticatorAttestationResponse, ClientDataJson, ClientDataJsonTy, CoseKey,
+    UserVerificationRequirement, WebAuthnCredential, WebAuthnError,
+    authenticator_data::{AuthenticatorData, FLAG_UP, FLAG_UV, attestation_object_from_cbor},
+  },
+  misc::Lease,
+  rng::CryptoRng,
+  x509::{Certificate, KeyTy},
+};
+use alloc::string::String;
+
+// id-fido-gen-ce-aaguid
+const OID_AAGUID: Oid = Oid::from_bytes_opt(b"1.3.6.1.4.1.45724.1.1.4").unwrap();
+
+/// Verifies the registration and authentication ceremonies of a `WebAuthn` Relying Party.
+///
+/// Supports the `none`, `packed` and `fido-u2f` attestation formats. Attestation certificates
+/// are not validated against trust anchors, which means that attestations only prove that the
+/// authenticator owns the key of the leaf certificate.
+#[derive(Debug)]
+pub struct RelyingParty {
+  id: String,
+  id_hash: [u8; 32],
+  origins: Vector<String>,
+  user_verification: UserVerificationRequirement,
+}
+
+impl RelyingParty {
+  /// New instance that only accepts ceremonies performed in `origin`.
+  ///
+  /// `id` is usually the domain of `origin` or one of its registrable suffixes, e.g., `example.com`
+  /// for `https://login.example.com`.
+  #[inline]
+  pub fn new(id: &str, origin: &str) -> crate::Result<Self> {
+    Ok(Self {
+      id: String::from(id),
+      id_hash: Sha256Global::digest([id.as_bytes()]),
+      origins: Vector::from_iterator([String::from(origin)])?,
+      user_verification: UserVerificationRequirement::Preferred,
+    })
+  }
+
+  /// Generates a random challenge that must be stored by the server and used only once.
+  #[inline]
+  pub fn gen_challenge<RNG>(rng: &mut RNG) -> [u8; 32]
+  where
+    RNG: CryptoRng,
+  {
+    rng.u8_32()
+  }
+
+  /// Relying Party identifier.
+  #[inline]
+  pub fn id(&self) -> &str {
+    &self.id
+  }
+
+  /// Origins allowed to perform ceremonies.
+  #[inline]
+  pub fn origins(&self) -> &[String] {
+    &self.origins
+  }
+
+  /// Mutable version of [`Self::origins`].
+  #[inline]
+  pub fn origins_mut(&mut self) -> &mut Vector<String> {
+    &mut self.origins
+  }
+
+  /// If [`UserVerificationRequirement::Required`], ceremonies without user verification are
+  /// rejected. Defaults to [`UserVerificationRequirement::Preferred`].
+  #[inline]
+  pub fn set_user_verification(&mut self, user_verification: UserVerificationRequirement) {
+    self.user_verification = user_verification;
+  }
+
+  /// Verifies the response of `navigator.credentials.create()` and returns the credential that
+  /// should be associated with the user.
+  ///
+  /// `challenge` is the value sent in the creation options of the same ceremony.
+  #[inline]
+  pub fn verify_registration(
+    &self,
+    challenge: &[u8],
+    response: &AuthenticatorAttestationResponse<&[u8]>,
+  ) -> crate::Result<WebAuthnCredential<Vector<u8>>> {
+    let client_data_hash =
+      self.verify_client_data(challenge, response.client_data_json, ClientDataJsonTy::Create)?;
+    let AttestationObject { att, att_stmt, auth_data } =
+      attestation_object_from_cbor(response.attestation_object)?;
+    let data = self.verify_authenticator_data(auth_data)?;
+    let Some(credential) = data.attested_credential else {
+      return Err(WebAuthnError::MissingAttestedCredential.into());
+    };
+    let alg = credential.public_key.alg;
+    let public_key = public_key_from_cose(&credential.public_key)?;
+    let leaf = att_stmt.x5c.as_ref().and_then(|el| el.first());
+    match (att, att_stmt.alg, att_stmt.sig, leaf) {
+      (AttestationFormat::None, None, None, None) => {}
+      // https://www.w3.org/TR/webauthn-3/#sctn-packed-attestation
+      (AttestationFormat::Packed, Some(stmt_alg), Some(sig), Some(der)) => {
+        let msg = concat(auth_data, &client_data_hash)?;
+        let cert = cert_from_der(der)?;
+        verify_packed_cert(&credential.aaguid, &cert)?;
+        let cert_pk = cert.tbs_certificate().subject_public_key_info.subject_public_key.bytes();
+        verify_signature(stmt_alg, &msg, cert_pk.lease(), sig)?;
+      }
+      (AttestationFormat::Packed, Some(stmt_alg), Some(sig), None) => {
+        if stmt_alg != alg {
+          return Err(WebAuthnError::InvalidAttestationStatement.into());
+        }
+        verify_signature(alg, &concat(auth_data, &client_data_hash)?, &public_key, sig)?;
+      }
+      // https://www.w3.org/TR/webauthn-3/#sctn-fido-u2f-attestation
+      (AttestationFormat::FidoU2f, None, Some(sig), Some(der)) => {
+        let cert = cert_from_der(der)?;
+        if att_stmt.x5c.as_ref().is_none_or(|el| el.len() != 1)
+          || alg != Alg::ES256
+          || KeyTy::try_from(&cert)? != KeyTy::EcdsaP256
+        {
+          return Err(WebAuthnError::InvalidAttestationStatement.into());
+        }
+        let mut msg = Vector::new();
+        let _ = msg.extend_from_copyable_slices([
+          &[0][..],
+          data.rp_id_hash,
+          &client_data_hash,
+          credential.id,
+          &public_key,
+        ])?;
+        let cert_pk = cert.tbs_certificate().subject_public_key_info.subject_public_key.bytes();
+        verify_signature(Alg::ES256, &msg, cert_pk.lease(), sig)?;
+      }
+      (
+        AttestationFormat::AndroidKey
+        | AttestationFormat::AndroidSafetyNet
+        | AttestationFormat::Tpm,
+        _,
+        _,
+        _,
+      ) => {
+        return Err(WebAuthnError::UnsupportedAttestationFormat.into());
+      }
+      _ => return Err(WebAuthnError::InvalidAttestationStatement.into()),
+    }
+    Ok(WebAuthnCredential {
+      aaguid: credential.aaguid,
+      alg,
+      id: Vector::from_copyable_slice(credential.id)?,
+      public_key,
+      sign_count: data.sign_count,
+    })
+  }
+
+  /// Verifies the response of `navigator.credentials.get()` and updates the signature counter of
+  /// `credential`, which must be persisted afterwards.
+  ///
+  /// `challenge` is the value sent in the request options of the same ceremony and `credential`
+  /// is the stored credential whose identifier is equal to the identifier of the response.
+  #[inline]
+  pub fn verify_authentication<B>(
+    &self,
+    challenge: &[u8],
+    credential: &mut WebAuthnCredential<B>,
+    response: &AuthenticatorAssertionResponse<&[u8]>,
+  ) -> crate::Result<()>
+  where
+    B: Lease<[u8]>,
+  {
+    let client_data_hash =
+      self.verify_client_data(challenge, response.client_data_json, ClientDataJsonTy::Get)?;
+    let data = self.verify_authenticator_data(response.authenticator_data)?;
+    let msg = concat(response.authenticator_data, &client_data_hash)?;
+    verify_signature(credential.alg, &msg, credential.public_key.lease(), response.signature)?;
+    if (data.sign_count != 0 || credential.sign_count != 0)
+      && data.sign_count <= credential.sign_count
+    {
+      return Err(WebAuthnError::SignCountRegression.into());
+    }
+    credential.sign_count = data.sign_count;
+    Ok(())
+  }
+
+  /// Calls [`Self::verify_authentication`] and then starts a new session through
+  /// [`crate::http::SessionManager::set_session_cookie`], returning its CSRF token.
+  #[cfg(feature = "http-session")]
+  #[inline]
+  pub async fn verify_authentication_and_set_session<B, CS, E, MD, RNG, S>(
+    &self,
+    challenge: &[u8],
+    credential: &mut WebAuthnCredential<B>,
+    response: &AuthenticatorAssertionResponse<&[u8]>,
+    custom_state: CS,
+    msg_data: &mut MD,
+    rng: &mut RNG,
+    session_manager: &mut crate::http::SessionManager<CS, E>,
+    store: &mut S,
+  ) -> Result<crate::collections::ArrayStringU8<32>, E>
+  where
+    B: Lease<[u8]>,
+    CS: serde::Serialize,
+    E: From<crate::Error>,
+    MD: crate::misc::LeaseMut<crate::http::MsgBufferString>,
+    RNG: CryptoRng,
+    S: crate::http::SessionStore<CS, E>,
+  {
+    self.verify_authentication(challenge, credential, response)?;
+    session_manager.set_session_cookie(custom_state, msg_data, rng, store).await
+  }
+
+  // https://www.w3.org/TR/webauthn-3/#sctn-verifying-assertion, steps 12 to 17.
+  fn verify_authenticator_data<'bytes>(
+    &self,
+    bytes: &'bytes [u8],
+  ) -> crate::Result<AuthenticatorData<'bytes>> {
+    let data = AuthenticatorData::from_bytes(bytes)?;
+    if data.rp_id_hash != self.id_hash {
+      return Err(WebAuthnError::MismatchedRpId.into());
+    }
+    if data.flags & FLAG_UP != FLAG_UP {
+      return Err(WebAuthnError::UserNotPresent.into());
+    }
+    if self.user_verification == UserVerificationRequirement::Required
+      && data.flags & FLAG_UV != FLAG_UV
+    {
+      return Err(WebAuthnError::UserNotVerified.into());
+    }
+    Ok(data)
+  }
+
+  // https://www.w3.org/TR/webauthn-3/#sctn-verifying-assertion, steps 9 to 11. Returns the hash of
+  // the client data.
+  fn verify_client_data(
+    &self,
+    challenge: &[u8],
+    client_data_json: &[u8],
+    ty: ClientDataJsonTy,
+  ) -> crate::Result<[u8; 32]> {
+    let client_data: ClientDataJson<&str> = serde_json::from_slice(client_data_json)?;
+    if client_data.ty != ty {
+      return Err(WebAuthnError::MismatchedClientDataTy.into());
+    }
+    let len = base64_encoded_len(challenge.len(), false).unwrap_or_default();
+    let mut buffer = Vector::from_cloneable_elem(len, 0)?;
+    if base64_encode(Base64Alphabet::UrlNoPad, challenge, &mut buffer)? != client_data.challenge {
+      return Err(WebAuthnError::MismatchedChallenge.into());
+    }
+    if !self.origins.iter().any(|el| el == client_data.origin) {
+      return Err(WebAuthnError::MismatchedOrigin.into());
+    }
+    Ok(Sha256Global::digest([client_data_json]))
+  }
+}
+
+fn cert_from_der(der: &[u8]) -> crate::Result<Certificate<&[u8]>> {
+  Certificate::<&[u8]>::decode(&mut DecodeWrapper::new(der, Asn1DecodeWrapperAux::default()))
+}
+
+fn concat(auth_data: &[u8], client_data_hash: &[u8; 32]) -> crate::Result<Vector<u8>> {
+  let mut rslt = Vector::new();
+  let _ = rslt.extend_from_copyable_slices([auth_data, client_data_hash])?;
+  Ok(rslt)
+}
+
+// Converts the key into the format expected by the validation methods of the crypto backends.
+fn public_key_from_cose(key: &CoseKey<&[u8]>) -> crate::Result<Vector<u8>> {
+  let coordinate_len = match (key.kty, key.alg, key.crv) {
+    (1, Alg::EdDSA, 6) if key.x.len() == 32 => return Vector::from_copyable_slice(key.x),
+    (2, Alg::ES256, 1) => 32,
+    (2, Alg::ES384, 2) => 48,
+    (3, Alg::PS256 | Alg::PS384 | Alg::PS512 | Alg::RS256 | Alg::RS384 | Alg::RS512, _) => {
+      let Some(exponent) = key.y else {
+        return Err(WebAuthnError::InvalidCoseKey.into());
+      };
+      return asn1_unsigned_integers(&[key.x, exponent]);
+    }
+    (1, Alg::EdDSA, _) | (_, Alg::ES512, _) => return Err(WebAuthnError::UnsupportedAlg.into()),
+    _ => return Err(WebAuthnError::InvalidCoseKey.into()),
+  };
+  let Some(y) = key.y else {
+    return Err(WebAuthnError::InvalidCoseKey.into());
+  };
+  if key.x.len() != coordinate_len || y.len() != coordinate_len {
+    return Err(WebAuthnError::InvalidCoseKey.into());
+  }
+  let mut rslt = Vector::new();
+  let _ = rslt.extend_from_copyable_slices([&[4][..], key.x, y])?;
+  Ok(rslt)
+}
+
+// https://www.w3.org/TR/webauthn-3/#sctn-packed-attestation-cert-requirements
+//
+// The version is not checked here because the decoder only accepts version 3.
+fn verify_packed_cert(aaguid: &[u8; 16], cert: &Certificate<&[u8]>) -> crate::Result<()> {
+  let tbs = cert.tbs_certificate();
+  let has_ou = tbs.subject.rdn_sequence().iter().flat_map(|el| el.entries.iter()).any(|el| {
+    el.oid == OID_X509_ORGANIZATIONAL_UNIT && el.value.data() == b"Authenticator Attestation"
+  });
+  if !has_ou {
+    return Err(WebAuthnError::InvalidAttestationStatement.into());
+  }
+  let mut extensions = tbs.extensions.iter().flat_map(|el| el.entries.iter());
+  if let Some(extension) = extensions.find(|el| el.extn_id == OID_AAGUID) {
+    let [4, 16, value @ ..] = extension.extn_value.bytes() else {
+      return Err(WebAuthnError::InvalidAttestationStatement.into());
+    };
+    if extension.critical || value != aaguid {
+      return Err(WebAuthnError::InvalidAttestationStatement.into());
+    }
+  }
+  Ok(())
+}
+
+// PKCS#1 `RSAPublicKey`
+fn verify_signature(alg: Alg, msg: &[u8], pk: &[u8], signature: &[u8]) -> crate::Result<()> {
+  let so = |hash_ty| SigningOutput::new(hash_ty, signature);
+  match alg {
+    Alg::ES256 => EcdsaP256SigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha256)),
+    Alg::ES384 => EcdsaP384SigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha384)),
+    Alg::ES512 => Err(WebAuthnError::UnsupportedAlg.into()),
+    Alg::EdDSA => Ed25519SigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha512)),
+    Alg::RS256 => RsaPkcs1SigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha256)),
+    Alg::RS384 => RsaPkcs1SigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha384)),
+    Alg::RS512 => RsaPkcs1SigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha512)),
+    Alg::PS256 => RsaPssSigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha256)),
+    Alg::PS384 => RsaPssSigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha384)),
+    Alg::PS512 => RsaPssSigningKeyGlobal::validate(msg, pk, &so(HashTy::Sha512)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    codec::{Base64Alphabet, base64_decode, base64_encode},
+    crypto::{Hash as _, Sha256Global},
+    http::web_authn::{
+      Alg, AuthenticatorAssertionResponse, AuthenticatorAttestationResponse, RelyingParty,
+      WebAuthnCredential, WebAuthnError,
+      authenticator_data::{FLAG_AT, FLAG_UP},
+    },
+  };
+  use alloc::{format, vec, vec::Vec};
+
+  // Generated with the `cryptography` Python package. Both attestations share the same P-256
+  // credential key, which also signed the assertion.
+  const ASSERTION_SIGNATURE: &str = "\
+    MEQCIArSjayGXo-epQTvxpY3sT1NaFkp4kwtapjjj2JebznmAiBCgS71grPi8AlK_s39nknsDX9-UBVxGa65aX92lFcLBg";
+  const CHALLENGE: [u8; 32] = [1; 32];
+  const PACKED: &str = "\
+    o2NmbXRmcGFja2VkZ2F0dFN0bXSjY2FsZyZjc2lnWEcwRQIgblAZCISKAGTZWliZrS9RQO67IPl1v_6VBir0AASVcJkCIQDa\
+    o4JJwyFUrNAev4V7GsA2bACEsLOqWtCotca680j-UWN4NWOBWQGFMIIBgTCCASagAwIBAgIBATAKBggqhkjOPQQDAjARMQ8w\
+    DQYDVQQDDAZ3dHggQ0EwHhcNMjAwMTAxMDAwMDAwWhcNNDkwMTAxMDAwMDAwWjBNMQswCQYDVQQGEwJVUzEMMAoGA1UECgwD\
+    d3R4MSIwIAYDVQQLDBlBdXRoZW50aWNhdG9yIEF0dGVzdGF0aW9uMQwwCgYDVQQDDAN3dHgwWTATBgcqhkjOPQIBBggqhkjO\
+    PQMBBwNCAAQT-wuqfUi75q1H7ZVTvRz_aEVdMBgQAl1YDn6qHlVTTbBUL40h0aNcQZgMdwEaApU-19JmBHZ-jFlMoj7319D_\
+    ozMwMTAMBgNVHRMBAf8EAjAAMCEGCysGAQQBguUcAQEEBBIEEAABAgMEBQYHCAkKCwwNDg8wCgYIKoZIzj0EAwIDSQAwRgIh\
+    AMo2Mmbea8r6vkQOhk0UUm0NmwZb5lGh92I5PnxiiQTXAiEAtkAi7mn8eDRs_p_2jj-10Vur1TERGaSWKc8QaXZJkBFoYXV0\
+    aERhdGFYlKN5pvbur7mlXjeMEYA04nUeaC-rny0wqxPSElWGzhlHQQAAAAAAAQIDBAUGBwgJCgsMDQ4PABAHBwcHBwcHBwcH\
+    BwcHBwcHpQECAyYgASFYIDNgkzTbfBS4db05yivPT3HQJ_O2jJHK5jX8-P8j9WevIlggc76QDJsir1wJ1H0Mp996LQ1zAWkR\
+    ET0kkSPsztc2LG0";
+  const FIDO_U2F: &str = "\
+    o2NmbXRoZmlkby11MmZnYXR0U3RtdKJjc2lnWEcwRQIgOzWt7uQN75x6txtygpNwP37DPHQ8jblvn_o4F4C7itYCIQDKZx8H\
+    e1JVRyUd8Vg0nMnLh2oEtO91Y9arTFqBfc72Y2N4NWOBWQESMIIBDjCBtqADAgECAgEBMAoGCCqGSM49BAMCMBExDzANBgNV\
+    BAMMBnd0eCBDQTAeFw0yMDAxMDEwMDAwMDBaFw00OTAxMDEwMDAwMDBaMBIxEDAOBgNVBAMMB3d0eCBVMkYwWTATBgcqhkjO\
+    PQIBBggqhkjOPQMBBwNCAATq21w1RXE_xBd-4dg_PJBPRWfL2bpoANHjJJCLgpKUOOSFkG9UbmORqiHvqOTLyl5HJxunFnUf\
+    ulxk3LmF2_LBMAoGCCqGSM49BAMCA0cAMEQCIETM5i71Tv0VsJNpwMBj7GYn41c7y6IugIxqDJycC5C2AiB9tax6Reo1w4Bu\
+    IYqixPXkb_zYzJugd45N7O6jUblsyGhhdXRoRGF0YViUo3mm9u6vuaVeN4wRgDTidR5oL6ufLTCrE9ISVYbOGUdBAAAAAAAA\
+    AAAAAAAAAAAAAAAAAAAAEAcHBwcHBwcHBwcHBwcHBwelAQIDJiABIVggM2CTNNt8FLh1vTnKK89PcdAn87aMkcrmNfz4_yP1\
+    Z68iWCBzvpAMmyKvXAnUfQyn33otDXMBaRERPSSRI-zO1zYsbQ";
+
+  #[test]
+  fn authenticates_with_valid_signatures() {
+    let rp = RelyingParty::new("example.com", "https://example.com").unwrap();
+    let attestation_object = decode(PACKED);
+    let create = client_data("webauthn.create", &CHALLENGE, "https://example.com");
+    let mut credential = rp
+      .verify_registration(&CHALLENGE, &attestation_response(&attestation_object, &create))
+      .unwrap();
+    let get = client_data("webauthn.get", &CHALLENGE, "https://example.com");
+    let signature = decode(ASSERTION_SIGNATURE);
+    let up = auth_data(FLAG_UP);
+    let response = AuthenticatorAssertionResponse {
+      client_data_json: get.as_slice(),
+      authenticator_data: up.as_slice(),
+      signature: signature.as_slice(),
+      user_handle: None,
+      attestation_object: None,
+    };
+    rp.verify_authentication(&CHALLENGE, &mut credential, &response).unwrap();
+    assert_eq!(credential.sign_count, 1);
+    assert!(matches!(
+      rp.verify_authentication(&CHALLENGE, &mut credential, &response),
+      Err(crate::Error::WebAuthnError(WebAuthnError::SignCountRegression))
+    ));
+  }
+
+  #[test]
+  fn registers_credentials_with_fido_u2f_attestation() {
+    let rp = RelyingParty::new("example.com", "https://example.com").unwrap();
+    let mut attestation_object = decode(FIDO_U2F);
+    let create = client_data("webauthn.create", &CHALLENGE, "https://example.com");
+    let credential = rp
+      .verify_registration(&CHALLENGE, &attestation_response(&attestation_object, &create))
+      .unwrap();
+    assert_eq!(credential.aaguid, [0; 16]);
+    assert_eq!(credential.id.as_slice(), &[7; 16]);
+    let idx = attestation_object.windows(16).position(|el| el == [7; 16]).unwrap();
+    attestation_object[idx] = 8;
+    assert!(
+      rp.verify_registration(&CHALLENGE, &attestation_response(&attestation_object, &create))
+        .is_err()
+    );
+  }
+
+  #[test]
+  fn registers_credentials_with_packed_attestation() {
+    let rp = RelyingParty::new("example.com", "https://example.com").unwrap();
+    let mut attestation_object = decode(PACKED);
+    let create = client_data("webauthn.create", &CHALLENGE, "https://example.com");
+    let credential = rp
+      .verify_registration(&CHALLENGE, &attestation_response(&attestation_object, &create))
+      .unwrap();
+    let aaguid: [u8; 16] = core::array::from_fn(|idx| idx.try_into().unwrap());
+    assert_eq!(credential.aaguid, aaguid);
+    assert_eq!(credential.alg, Alg::ES256);
+    let idx = attestation_object.windows(16).rposition(|el| el == aaguid).unwrap();
+    attestation_object[idx] = 1;
+    assert!(matches!(
+      rp.verify_registration(&CHALLENGE, &attestation_response(&attestation_object, &create)),
+      Err(crate::Error::WebAuthnError(WebAuthnError::InvalidAttestationStatement))
+    ));
+  }
+
+  #[test]
+  fn registers_credentials_without_attestation() {
+    let rp = RelyingParty::new("example.com", "https://example.com").unwrap();
+    let create = client_data("webauthn.create", &CHALLENGE, "https://example.com");
+    let mut auth_data = auth_data(FLAG_AT | FLAG_UP);
+    auth_data.extend([9; 16]);
+    auth_data.extend([0, 2, 5, 6]);
+    auth_data.extend([0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x58, 0x20]);
+    auth_data.extend([2; 32]);
+    auth_data.extend([0x22, 0x58, 0x20]);
+    auth_data.extend([3; 32]);
+    let mut attestation_object = Vec::new();
+    attestation_object.extend(b"\xa3\x63fmt\x64none\x67attStmt\xa0\x68authData\x59");
+    attestation_object.extend(u16::try_from(auth_data.len()).unwrap().to_be_bytes());
+    attestation_object.extend(&auth_data);
+    let response = |client_data_json| attestation_response(&attestation_object, client_data_json);
+    let credential = rp.verify_registration(&CHALLENGE, &response(&create)).unwrap();
+    assert_eq!(credential.aaguid, [9; 16]);
+    assert_eq!(credential.alg, Alg::ES256);
+    assert_eq!(credential.id.as_slice(), &[5, 6]);
+    assert_eq!(credential.public_key.len(), 65);
+    assert_eq!(credential.public_key.first(), Some(&4));
+    assert!(matches!(
+      rp.verify_registration(&[2; 32], &response(&create)),
+      Err(crate::Error::WebAuthnError(WebAuthnError::MismatchedChallenge))
+    ));
+    let other_origin = client_data("webauthn.create", &CHALLENGE, "https://evil.com");
+    assert!(matches!(
+      rp.verify_registration(&CHALLENGE, &response(&other_origin)),
+      Err(crate::Error::WebAuthnError(WebAuthnError::MismatchedOrigin))
+    ));
+  }
+
+  #[test]
+  fn rejects_invalid_assertions() {
+    let rp = RelyingParty::new("example.com", "https://example.com").unwrap();
+    let mut credential = WebAuthnCredential {
+      aaguid: [0; 16],
+      alg: Alg::ES256,
+      id: &[1][..],
+      public_key: &[4; 65][..],
+      sign_count: 0,
+    };
+    let not_present = auth_data(0);
+    let up = auth_data(FLAG_UP);
+    let response = |client_data_json, authenticator_data| AuthenticatorAssertionResponse {
+      client_data_json,
+      authenticator_data,
+      signature: &[0; 64][..],
+      user_handle: None,
+      attestation_object: None,
+    };
+    let create = client_data("webauthn.create", &CHALLENGE, "https://example.com");
+    assert!(matches!(
+      rp.verify_authentication(&CHALLENGE, &mut credential, &response(&create, &up)),
+      Err(crate::Error::WebAuthnError(WebAuthnError::MismatchedClientDataTy))
+    ));
+    let get = client_data("webauthn.get", &CHALLENGE, "https://example.com");
+    let other_rp = RelyingParty::new("other.com", "https://example.com").unwrap();
+    assert!(matches!(
+      other_rp.verify_authentication(&CHALLENGE, &mut credential, &response(&get, &up)),
+      Err(crate::Error::WebAuthnError(WebAuthnError::MismatchedRpId))
+    ));
+    assert!(matches!(
+      rp.verify_authentication(&CHALLENGE, &mut credential, &response(&get, &not_present)),
+      Err(crate::Error::WebAuthnError(WebAuthnError::UserNotPresent))
+    ));
+    assert!(rp.verify_authentication(&CHALLENGE, &mut credential, &response(&get, &up)).is_err());
+    assert_eq!(credential.sign_count, 0);
+  }
+
+  fn attestation_response<'any>(
+    attestation_object: &'any [u8],
+    client_data_json: &'any [u8],
+  ) -> AuthenticatorAttestationResponse<&'any [u8]> {
+    AuthenticatorAttestationResponse {
+      client_data_json,
+      attestation_object,
+      authenticator_data: None,
+      transports: None,
+      public_key: None,
+      public_key_algorithm: None,
+    }
+  }
+
+  fn auth_data(flags: u8) -> Vec<u8> {
+    let mut rslt = Vec::from(Sha256Global::digest([&b"example.com"[..]]));
+    rslt.extend([flags, 0, 0, 0, 1]);
+    rslt
+  }
+
+  fn decode(data: &str) -> Vec<u8> {
+    let mut buffer = vec![0; data.len()];
+    let len = base64_decode(Base64Alphabet::UrlNoPad, data.as_bytes(), &mut buffer).unwrap().len();
+    buffer.truncate(len);
+    buffer
+  }
+
+  fn client_data(ty: &str, challenge: &[u8], origin: &str) -> Vec<u8> {
+    let mut buffer = [0; 64];
+    let encoded = base64_encode(Base64Alphabet::UrlNoPad, challenge, &mut buffer).unwrap();
+    format!(r#"{{"type":"{ty}","challenge":"{encoded}","origin":"{origin}"}}"#).into_bytes()
+  }
+}
@@ -0,0 +1,19 @@
+use crate::http::web_authn::Alg;
+
+/// Public key credential created in a registration ceremony that must be persisted by the
+/// Relying Party to verify future authentication ceremonies.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebAuthnCredential<B> {
+  /// Identifies the model of the authenticator. All zeros when the model is unknown.
+  pub aaguid: [u8; 16],
+  /// See [`Alg`].
+  pub alg: Alg,
+  /// Credential identifier.
+  pub id: B,
+  /// Public key in the format expected by the validation methods of the crypto backends, i.e.,
+  /// uncompressed SEC1 points for ECDSA, raw bytes for `Ed25519` and PKCS#1 for RSA.
+  pub public_key: B,
+  /// Signature counter that must be updated after each successful authentication ceremony.
+  pub sign_count: u32,
+}
@@ -0,0 +1,32 @@
+/// `WebAuthn` error
+#[derive(Clone, Copy, Debug)]
+pub enum WebAuthnError {
+  /// The attestation statement doesn't match the requirements of its format.
+  InvalidAttestationStatement,
+  /// Authenticator data is malformed or truncated.
+  InvalidAuthenticatorData,
+  /// A CBOR item doesn't have the structure expected by `WebAuthn`.
+  InvalidCbor,
+  /// The credential public key is malformed or doesn't match its algorithm.
+  InvalidCoseKey,
+  /// The challenge of the client data differs from the expected challenge.
+  MismatchedChallenge,
+  /// The type of the client data differs from the type of the current ceremony.
+  MismatchedClientDataTy,
+  /// The origin of the client data isn't one of the allowed origins.
+  MismatchedOrigin,
+  /// The RP ID hash of the authenticator data doesn't match the RP ID.
+  MismatchedRpId,
+  /// Registration responses must contain attested credential data.
+  MissingAttestedCredential,
+  /// The signature counter didn't increase, which may indicate a cloned authenticator.
+  SignCountRegression,
+  /// The algorithm is not supported by the crypto backends.
+  UnsupportedAlg,
+  /// Only the `none`, `packed` and `fido-u2f` attestation formats are supported.
+  UnsupportedAttestationFormat,
+  /// The authenticator didn't confirm the presence of the user.
+  UserNotPresent,
+  /// User verification is required but the authenticator didn't verify the user.
+  UserNotVerified,
+}
@@ -1,9 +1,10 @@
-#![expect(clippy::infinite_loop, reason = "expected behavior of a server")]
-
 use crate::{
   collections::Vector,
   executor::{Executor, Runtime as _},
-  http::Router,
+  http::{
+    Router, ShutdownSignal,
+    shutdown_signal::{InFlight, InFlightGuard},
+  },
   net::{StreamReader, StreamWriter, TcpListener as _, TcpParams, Uri},
   rng::{CryptoRng, CryptoSeedableRng},
   sync::Arc,
@@ -11,7 +12,7 @@ use crate::{
   web_socket::{WebSocket, WebSocketAcceptor, WsCompression},
 };
 use alloc::string::String;
-use core::num::NonZeroUsize;
+use core::{num::NonZeroUsize, time::Duration};
 
 type LocalWebSocket<CO, EX, TCX> = WebSocket<
   <CO as WsCompression<false>>::NegotiatedCompression,
@@ -29,6 +30,8 @@ pub struct WebSocketServerFramework<CO, EC, EX, RC, RNG, TCX> {
   local_runtime_cb: RC,
   local_runtimes: Option<NonZeroUsize>,
   rng: RNG,
+  shutdown_signal: ShutdownSignal,
+  shutdown_timeout: Duration,
   tcp_params: TcpParams,
   tls_config: Arc<TlsConfig<TCX>>,
 }
@@ -58,6 +61,8 @@ where
       local_runtime_cb,
       local_runtimes: None,
       rng,
+      shutdown_signal: ShutdownSignal::new(),
+      shutdown_timeout: Duration::from_secs(30),
       tcp_params: TcpParams::default(),
       tls_config: tls_config.into(),
     })
@@ -102,6 +107,8 @@ impl<CO, EC, EX, RC, RNG, TCX> WebSocketServerFramework<CO, EC, EX, RC, RNG, TCX
       local_runtime_cb: self.local_runtime_cb,
       local_runtimes: self.local_runtimes,
       rng: self.rng,
+      shutdown_signal: self.shutdown_signal,
+      shutdown_timeout: self.shutdown_timeout,
       tcp_params: self.tcp_params,
       tls_config: self.tls_config,
     }
@@ -120,6 +127,8 @@ impl<CO, EC, EX, RC, RNG, TCX> WebSocketServerFramework<CO, EC, EX, RC, RNG, TCX
       local_runtime_cb: self.local_runtime_cb,
       local_runtimes: self.local_runtimes,
       rng: self.rng,
+      shutdown_signal: self.shutdown_signal,
+      shutdown_timeout: self.shutdown_timeout,
       tcp_params: self.tcp_params,
       tls_config: self.tls_config,
     }
@@ -140,11 +149,24 @@ impl<CO, EC, EX, RC, RNG, TCX> WebSocketServerFramework<CO, EC, EX, RC, RNG, TCX
       local_runtime_cb: value,
       local_runtimes: self.local_runtimes,
       rng: self.rng,
+      shutdown_signal: self.shutdown_signal,
+      shutdown_timeout: self.shutdown_timeout,
       tcp_params: self.tcp_params,
       tls_config: self.tls_config,
     }
   }
 
+  /// Handle that stops the server when triggered.
+  ///
+  /// New connections are no longer accepted and running methods return after all connections
+  /// are closed or after the duration specified by [`Self::set_shutdown_timeout`]. Accepted
+  /// WebSockets also receive this signal, which makes their reading operations send close frames
+  /// and return the replies of the peers. See [`WebSocket::shutdown_signal_mut`].
+  #[inline]
+  pub fn shutdown_signal(&self) -> ShutdownSignal {
+    self.shutdown_signal.clone()
+  }
+
   /// The number of spawned runtimes. Shouldn't be greater than the number of available threads.
   ///
   /// Only works when calling [`Self::run_in_threads`]. If [`None`], defaults to the number of threads.
@@ -155,6 +177,15 @@ impl<CO, EC, EX, RC, RNG, TCX> WebSocketServerFramework<CO, EC, EX, RC, RNG, TCX
     self
   }
 
+  /// Maximum amount of time spent waiting for open connections after a shutdown. Defaults to 30
+  /// seconds.
+  #[inline]
+  #[must_use]
+  pub const fn set_shutdown_timeout(mut self, value: Duration) -> Self {
+    self.shutdown_timeout = value;
+    self
+  }
+
   /// See [`TcpParams`].
   #[inline]
   #[must_use]
@@ -176,6 +207,8 @@ impl<CO, EC, EX, RC, RNG, TCX> WebSocketServerFramework<CO, EC, EX, RC, RNG, TCX
       local_runtime_cb: self.local_runtime_cb,
       local_runtimes: self.local_runtimes,
       rng: self.rng,
+      shutdown_signal: self.shutdown_signal,
+      shutdown_timeout: self.shutdown_timeout,
       tcp_params: self.tcp_params,
       tls_config: Arc::new(value),
     }
@@ -219,20 +252,25 @@ where
     let web_socket_router = Arc::new(wsr);
     let listener = EX::TcpListener::bind(uri.hostname_with_implied_port(), self.tcp_params).await?;
     let router = build_matcher(&*web_socket_router)?;
+    let in_flight = InFlight::new();
     loop {
-      let Ok(cp) = conn_params::<CO, EC, EX, RNG, TCX, WSR>(
+      let cp = match conn_params::<CO, EC, EX, RNG, TCX, WSR>(
         (&self.compression, &self.error_cb),
         (&mut self.rng, self.tcp_params, &self.tls_config),
-        &listener,
+        (&in_flight, &listener, &self.shutdown_signal),
         &router,
         &web_socket_router,
       )
       .await
-      else {
-        continue;
+      {
+        Ok(Some(elem)) => elem,
+        Ok(None) => break,
+        Err(_) => continue,
       };
       let _jh = self.executor.spawn(conn_fut(cp));
     }
+    let _is_idle = in_flight.wait_idle(self.shutdown_timeout).await?;
+    Ok(())
   }
 
   /// Starts the server using a runtime-per-thread architecture.
@@ -262,14 +300,18 @@ where
     };
     let web_socket_router = Arc::new(wsr);
     let router = build_matcher(&*web_socket_router)?;
+    let in_flight = InFlight::new();
     let mut join_handles = Vector::<std::thread::JoinHandle<Result<(), ER>>>::new();
     for _ in 0..runtimes {
       let thread_comp = self.compression.clone();
       let thread_error_cb = self.error_cb.clone();
       let thread_executor = self.executor.clone();
+      let thread_in_flight = in_flight.clone();
       let thread_local_runtime_cb = self.local_runtime_cb.clone();
       let mut thread_rng = RNG::from_crypto_rng(&mut self.rng)?;
       let thread_router = router.clone();
+      let thread_shutdown_signal = self.shutdown_signal.clone();
+      let thread_shutdown_timeout = self.shutdown_timeout;
       let thread_tcp_params = self.tcp_params;
       let thread_tls_config = self.tls_config.clone();
       let thread_uri = Uri::new(String::from(addr));
@@ -280,19 +322,23 @@ where
           let hostname = thread_uri.hostname_with_implied_port();
           let listener = EX::TcpListener::bind(hostname, thread_tcp_params).await?;
           loop {
-            let Ok(cp) = conn_params::<CO, EC, EX, RNG, TCX, WSR>(
+            let cp = match conn_params::<CO, EC, EX, RNG, TCX, WSR>(
               (&thread_comp, &thread_error_cb),
               (&mut thread_rng, thread_tcp_params, &thread_tls_config),
-              &listener,
+              (&thread_in_flight, &listener, &thread_shutdown_signal),
               &thread_router,
               &thread_web_socket_router,
             )
             .await
-            else {
-              continue;
+            {
+              Ok(Some(elem)) => elem,
+              Ok(None) => break,
+              Err(_) => continue,
             };
             let _jh = thread_executor.spawn_local(conn_fut(cp), &lc);
           }
+          let _is_idle = thread_in_flight.wait_idle(thread_shutdown_timeout).await?;
+          Ok(())
         })
       }))?;
     }
@@ -319,20 +365,25 @@ where
     let router = build_matcher(&*web_socket_router)?;
     let uri = Uri::new(addr);
     let listener = EX::TcpListener::bind(uri.hostname_with_implied_port(), self.tcp_params).await?;
+    let in_flight = InFlight::new();
     loop {
-      let Ok(cp) = conn_params::<CO, EC, EX, RNG, TCX, WSR>(
+      let cp = match conn_params::<CO, EC, EX, RNG, TCX, WSR>(
         (&self.compression, &self.error_cb),
         (&mut self.rng, self.tcp_params, &self.tls_config),
-        &listener,
+        (&in_flight, &listener, &self.shutdown_signal),
         &router,
         &web_socket_router,
       )
       .await
-      else {
-        continue;
+      {
+        Ok(Some(elem)) => elem,
+        Ok(None) => break,
+        Err(_) => continue,
       };
       let _jh = self.executor.spawn_local(conn_fut(cp), lc);
     }
+    let _is_idle = in_flight.wait_idle(self.shutdown_timeout).await?;
+    Ok(())
   }
 }
 
@@ -358,10 +409,12 @@ struct ConnParams<CO, EC, EX, RNG, TCX, WSR>
 where
   EX: Executor,
 {
+  _conn_guard: InFlightGuard,
   compression: CO,
   error_cb: EC,
   rng: RNG,
   router: Arc<Router<u8>>,
+  shutdown_signal: ShutdownSignal,
   stream: EX::TcpStream,
   tls_config: Arc<TlsConfig<TCX>>,
   web_socket_router: Arc<WSR>,
@@ -398,7 +451,7 @@ where
 {
   let fun = async {
     let mut path = String::new();
-    let web_socket = WebSocketAcceptor::default()
+    let mut web_socket = WebSocketAcceptor::default()
       .set_compression(conn_params.compression)
       .set_req(|req| {
         if let Some(elem) = req.path {
@@ -408,6 +461,7 @@ where
       })
       .accept(TlsAcceptor::new(&*conn_params.tls_config, conn_params.rng, conn_params.stream))
       .await?;
+    *web_socket.shutdown_signal_mut() = Some(conn_params.shutdown_signal);
     conn_params.web_socket_router.call(&conn_params.router, path, web_socket).await?;
     Ok::<_, ER>(())
   };
@@ -419,23 +473,28 @@ where
 async fn conn_params<CO, EC, EX, RNG, TCX, WSR>(
   (compression, error_cb): (&CO, &EC),
   (rng, tcp_params, tls_config): (&mut RNG, TcpParams, &Arc<TlsConfig<TCX>>),
-  listener: &EX::TcpListener,
+  (in_flight, listener, shutdown_signal): (&InFlight, &EX::TcpListener, &ShutdownSignal),
   router: &Arc<Router<u8>>,
   web_socket_router: &Arc<WSR>,
-) -> crate::Result<ConnParams<CO, EC, EX, RNG, TCX, WSR>>
+) -> crate::Result<Option<ConnParams<CO, EC, EX, RNG, TCX, WSR>>>
 where
   CO: Clone,
   EC: Clone,
   EX: Executor,
   RNG: CryptoRng + CryptoSeedableRng,
 {
-  Ok(ConnParams {
+  let Some(accept_rslt) = shutdown_signal.or_triggered(listener.accept(tcp_params)).await else {
+    return Ok(None);
+  };
+  Ok(Some(ConnParams {
+    _conn_guard: in_flight.guard(),
     compression: compression.clone(),
     error_cb: error_cb.clone(),
     rng: RNG::from_crypto_rng(rng)?,
     router: router.clone(),
-    stream: listener.accept(tcp_params).await?.0,
+    shutdown_signal: shutdown_signal.clone(),
+    stream: accept_rslt?.0,
     tls_config: tls_config.clone(),
     web_socket_router: web_socket_router.clone(),
-  })
+  }))
 }
@@ -2,7 +2,8 @@
 //! framework.
 //!
 //! 1. Does not support padded headers when writing.
-//! 2. Does not support push promises (Deprecated by major third-parties).
+//! 2. Only clients can receive push promises, which must be enabled through
+//!    [`crate::http::HttpRecvParams::enable_push`] (Deprecated by major third-parties).
 //! 3. Does not support prioritization (Deprecated by the RFC).
 
 #[macro_use]
@@ -14,8 +15,11 @@ mod common_stream;
 mod continuation_frame;
 mod data_frame;
 mod frame_init;
+mod frame_rates;
 mod frame_reader;
 mod go_away_frame;
+#[cfg(feature = "http2-h2c")]
+mod h2c;
 mod headers_frame;
 mod hpack_decoder;
 mod hpack_encoder;
@@ -30,10 +34,14 @@ mod http2_status;
 mod http_send_params;
 mod huffman;
 mod huffman_tables;
+mod initial_push_stream_remote;
 mod initial_server_stream_remote;
+mod keepalive;
 mod misc;
 mod ping_frame;
+mod priority_update_frame;
 mod process_receipt_frame_ty;
+mod push_promise_frame;
 mod reset_stream_frame;
 mod server_stream;
 mod settings_frame;
@@ -49,7 +57,9 @@ mod write_functions;
 
 use crate::{
   collections::SingleTypeStorage,
-  http::{DEFAULT_INITIAL_WINDOW_LEN, HttpRecvParams, MsgBufferString, Protocol, Request, U31},
+  http::{
+    DEFAULT_INITIAL_WINDOW_LEN, HttpRecvParams, MsgBufferString, Priority, Protocol, Request, U31,
+  },
   http2::settings_frame::SettingsFrame,
   misc::{Lease, LeaseMut, Usize},
   net::{ConnectionState, StreamReader, StreamWriter},
@@ -65,6 +75,7 @@ use core::{
   mem,
   pin::pin,
   task::{Poll, Waker},
+  time::Duration,
 };
 use hashbrown::HashMap;
 pub use http2_buffer::Http2Buffer;
@@ -74,10 +85,10 @@ pub use http2_error_code::Http2ErrorCode;
 pub use http2_status::{Http2RecvStatus, Http2SendStatus};
 pub use server_stream::ServerStream;
 #[cfg(feature = "web-socket")]
-pub use web_socket_over_stream::WebSocketOverStream;
+pub use web_socket_over_stream::{WebSocketOverClientStream, WebSocketOverStream};
 pub use window::{Window, Windows};
 
-const PREFACE: [u8; 24] = *b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+pub(crate) const PREFACE: [u8; 24] = *b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
 
 pub(crate) type Scorp = HashMap<U31, stream_receiver::StreamControlRecvParams>;
 pub(crate) type Sovrp = HashMap<U31, stream_receiver::StreamOverallRecvParams>;
@@ -99,8 +110,28 @@ where
     misc::connection_state(&self.inner.is_conn_open.connection_state)
   }
 
+  /// Round-trip time measured by the last acknowledged PING frame sent by this instance.
+  ///
+  /// PING frames are sent with [`Self::send_ping`] or periodically when
+  /// [`HttpRecvParams::keepalive_interval`] is set.
+  #[inline]
+  pub async fn rtt(&self) -> Option<Duration> {
+    self.inner.hd.lock().await.parts_mut().keepalive.rtt()
+  }
+
   send_go_away_method!();
 
+  /// Sends a PING frame whose acknowledgement will update [`Self::rtt`].
+  ///
+  /// Does nothing if there is already an unacknowledged PING frame.
+  #[inline]
+  pub async fn send_ping(&self) -> crate::Result<()> {
+    let Some(pf) = self.inner.hd.lock().await.parts_mut().keepalive.send_ping() else {
+      return Ok(());
+    };
+    misc::write_array([&pf.bytes()], &mut *self.inner.wd.lock().await).await
+  }
+
   #[cfg(all(feature = "http2-client-pool", feature = "tls"))]
   pub(crate) async fn swap_buffers(&mut self, hb: &mut Http2Buffer) {
     mem::swap(hb, self.inner.hd.lock().await.parts_mut().hb);
@@ -117,7 +148,7 @@ where
     SR: StreamReader,
   {
     let sf = SettingsFrame::from_hrp(hrp);
-    let sf_buffer = &mut [0; 45];
+    let sf_buffer = &mut [0; 51];
     let sf_bytes = sf.bytes(sf_buffer);
     if hrp.initial_window_len() == DEFAULT_INITIAL_WINDOW_LEN {
       if HAS_PREFACE {
@@ -140,16 +171,29 @@ where
     hb.hpack_dec.set_max_bytes(hrp.max_hpack_len().0);
     hb.hpack_enc.set_max_dyn_super_bytes(hrp.max_hpack_len().1);
     let nrb = mem::take(&mut hb.nrb);
-    let max_frame_len = hrp.max_frame_len();
     let inner = Arc::new(Http2Inner {
       hd: AsyncMutex::new(Http2Data::new(hb, hrp)),
       is_conn_open: stream_reader.common().clone(),
       wd: AsyncMutex::new(stream_writer),
     });
-    Ok((
-      frame_reader::frame_reader(inner.clone(), max_frame_len, nrb, stream_bridge, stream_reader),
-      Self { inner },
-    ))
+    let frame_reader =
+      frame_reader::frame_reader(hrp, inner.clone(), nrb, stream_bridge, stream_reader);
+    let keepalive = keepalive::keepalive(inner.clone());
+    let fut = async move {
+      let mut frame_reader_pin = pin!(frame_reader);
+      let mut keepalive_pin = pin!(keepalive);
+      let mut is_keepalive_finished = false;
+      // Closing a connection makes the frame reader finish, as such, there is no need to await
+      // the keepalive task.
+      poll_fn(|cx| {
+        if !is_keepalive_finished && keepalive_pin.as_mut().poll(cx).is_ready() {
+          is_keepalive_finished = true;
+        }
+        frame_reader_pin.as_mut().poll(cx)
+      })
+      .await;
+    };
+    Ok((fut, Self { inner }))
   }
 }
 
@@ -162,7 +206,7 @@ where
   #[inline]
   pub async fn accept<SR>(
     mut hb: Http2Buffer,
-    hrp: HttpRecvParams,
+    mut hrp: HttpRecvParams,
     (stream_bridge, mut stream_reader, mut stream_writer): (
       TlsStreamBridge<false>,
       TlsStreamReader<SR, TCX, false>,
@@ -173,6 +217,7 @@ where
     SR: StreamReader,
   {
     hb.clear();
+    hrp = hrp.set_enable_push(false);
     let mut buffer = [0; PREFACE.len()];
     let _read = stream_reader.read(buffer.as_mut_slice().into()).await?;
     if buffer != PREFACE {
@@ -187,6 +232,18 @@ where
       .await
   }
 
+  /// Sends a GOAWAY frame without an error that informs the peer about the last received stream.
+  ///
+  /// Contrary to [`Self::send_go_away`], ongoing streams are not affected and the connection
+  /// remains open. [`Self::stream`] yields the streams that were already received and then
+  /// returns [`None`], while subsequent streams initiated by the peer are refused.
+  ///
+  /// The connection should be closed with [`Self::send_go_away`] once all streams are finished.
+  #[inline]
+  pub async fn send_graceful_go_away(&self) {
+    misc::manage_graceful_go_away(&self.inner).await;
+  }
+
   /// Awaits for an initial header to create a stream.
   ///
   /// Returns [`None`] if the network connection has been closed, either locally or externally.
@@ -207,7 +264,7 @@ where
         return Poll::Ready(Ok(None));
       }
       let Some(lss) = hdpm.hb.initial_server_streams_remote.pop_front() else {
-        if is_registered {
+        if is_registered || *hdpm.is_graceful_drain {
           misc::frame_reader_rslt(hdpm.frame_reader_error)?;
           return Poll::Ready(Ok(None));
         }
@@ -221,6 +278,8 @@ where
         return Poll::Ready(Ok(None));
       };
       let (method, protocol, stream_id) = (lss.method, lss.protocol, lss.stream_id);
+      #[cfg(feature = "metrics")]
+      crate::metrics::MetricsRegistry::global().http2_streams_opened[1].inc();
       Poll::Ready(Ok(Some((
         ServerStream::new(
           inner.clone(),
@@ -280,7 +339,11 @@ where
     drop(hdpm.hb.scrps.insert(
       stream_id,
       stream_receiver::StreamControlRecvParams {
+        data_turn: 0,
+        is_deferred: false,
+        is_sending_data: false,
         is_stream_open: true,
+        priority: Priority::DEFAULT,
         stream_state: stream_state::StreamState::Idle,
         waker: Waker::noop().clone(),
         windows: Windows::initial(hdpm.hp, hdpm.hps),
@@ -288,8 +351,31 @@ where
     ));
     *hdpm.last_stream_id = hdpm.last_stream_id.wrapping_add(U31::TWO);
     drop(hd_guard);
+    #[cfg(feature = "metrics")]
+    crate::metrics::MetricsRegistry::global().http2_streams_opened[0].inc();
     Ok(ClientStream::new(inner.clone(), linger, span, stream_id))
   }
+
+  /// Number of local streams alongside the maximum number of concurrent streams allowed by the
+  /// peer.
+  ///
+  /// `waker`, if any, is awakened once a local stream is closed. Nothing is registered when there
+  /// are no local streams or when an equivalent waker is already registered.
+  #[cfg(feature = "http2-client-pool")]
+  pub(crate) async fn streams_usage(&self, waker: Option<&Waker>) -> crate::Result<(u32, u32)> {
+    let mut hd_guard = self.inner.hd.lock().await;
+    let hdpm = hd_guard.parts_mut();
+    // Streams that are awaiting responses or tunneling data are also active.
+    let len = hdpm.hb.scrps.len().wrapping_add(hdpm.hb.sorps.len());
+    if let Some(elem) = waker
+      && len > 0
+      && !hdpm.hb.closed_streams_local.iter().any(|el| el.will_wake(elem))
+    {
+      hdpm.hb.closed_streams_local.push_back(elem.clone())?;
+    }
+    let active = u32::try_from(len).unwrap_or(u32::MAX);
+    Ok((active, hdpm.hps.max_concurrent_streams_num))
+  }
 }
 
 impl<SW, TCX, const IS_CLIENT: bool> Lease<Http2<SW, TCX, IS_CLIENT>>
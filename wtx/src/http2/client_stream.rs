@@ -1,21 +1,30 @@
 use crate::{
-  collections::Vector,
-  http::{MsgBufferString, MsgData, Request, StatusCode, U31},
+  collections::{ArrayVectorU8, SingleTypeStorage, Vector},
+  http::{Headers, Method, MsgBufferString, MsgData, Priority, Protocol, Request, StatusCode, U31},
   http2::{
-    CommonStream, Http2Inner, Http2RecvStatus, Http2SendStatus,
+    CommonStream, Http2Error, Http2Inner, Http2RecvStatus, Http2SendStatus,
     hpack_static_headers::{HpackStaticRequestHeaders, HpackStaticResponseHeaders},
-    misc::{manage_recurrent_receiving_of_overall_stream, process_higher_operation_err},
+    misc::{
+      connection_state, frame_reader_rslt, manage_recurrent_receiving_of_overall_stream,
+      process_higher_operation_err, write_array,
+    },
+    priority_update_frame::PriorityUpdateFrame,
     stream_receiver::StreamOverallRecvParams,
     stream_state::StreamState,
     window::Windows,
-    write_functions::send_msg,
+    write_functions::{encode_headers, push_headers, send_msg, write_frames},
   },
-  misc::{Lease, span::Span},
-  net::StreamWriter,
+  misc::{Lease, LeaseMut, span::Span},
+  net::{StreamWriter, UriRef},
   sync::Arc,
   tls::TlsCtx,
 };
-use core::{future::poll_fn, pin::pin, task::Waker};
+use core::{
+  future::poll_fn,
+  mem,
+  pin::pin,
+  task::{Poll, Waker},
+};
 
 /// Groups the methods used by clients that connect to servers.
 #[derive(Debug)]
@@ -51,6 +60,68 @@ where
     CommonStream { inner, linger: *linger, span, stream_id: *stream_id }
   }
 
+  /// Receive push promise
+  ///
+  /// Awaits for a request promised by the server in the context of this stream. The returned
+  /// stream can then be used with [`Self::recv_res`] to receive the pushed response.
+  ///
+  /// Returns [`Option::None`] if the connection has been closed or if the response of this stream
+  /// was fully received without any pending promise.
+  ///
+  /// Promises are only received when [`crate::http::HttpRecvParams::enable_push`] is set and
+  /// should be awaited after [`Self::send_req`]. Promises that weren't received when this stream is
+  /// cleared are cancelled.
+  #[inline]
+  pub async fn recv_push_promise(
+    &mut self,
+  ) -> crate::Result<Option<(Request<MsgBufferString>, ClientStream<SW, TCX>)>> {
+    let Self { inner, linger, span, stream_id, windows: _ } = self;
+    let _e = span.enter();
+    _trace!(target: crate::_WTX_HTTP2, "Receiving push promise");
+    let mut lock_pin = pin!(inner.hd.lock());
+    poll_fn(|cx| {
+      let mut lock = lock_pin!(cx, inner.hd, lock_pin);
+      let hdpm = lock.parts_mut();
+      let pushes = &mut hdpm.hb.initial_push_streams_remote;
+      let mut push = None;
+      // Promises of other streams are rotated back in their original order.
+      for _ in 0..pushes.len() {
+        let Some(elem) = pushes.pop_front() else {
+          break;
+        };
+        if push.is_none() && elem.parent_stream_id == *stream_id {
+          push = Some(elem);
+        } else {
+          pushes.push_back(elem)?;
+        }
+      }
+      if let Some(elem) = push {
+        let promised_stream_id = elem.stream_id;
+        return Poll::Ready(Ok(Some((
+          Request::new(elem.method, elem.msg_buffer),
+          ClientStream::new(
+            inner.clone(),
+            *linger,
+            _trace_span!("New client push stream", stream_id = %promised_stream_id),
+            promised_stream_id,
+          ),
+        ))));
+      }
+      if connection_state(&inner.is_conn_open.connection_state).is_closed() {
+        frame_reader_rslt(hdpm.frame_reader_error)?;
+        return Poll::Ready(Ok(None));
+      }
+      match hdpm.hb.sorps.get_mut(stream_id) {
+        Some(elem) if !elem.stream_state.recv_eos() => {
+          elem.waker.clone_from(cx.waker());
+          Poll::Pending
+        }
+        _ => Poll::Ready(Ok(None)),
+      }
+    })
+    .await
+  }
+
   /// Receive response
   ///
   /// High-level operation that awaits for the data necessary to build a response and then closes the
@@ -78,7 +149,7 @@ where
         hdpm,
         &inner.is_conn_open.connection_state,
         *stream_id,
-        |_, status_code, _, _| status_code,
+        |_, _, status_code, _, _| status_code,
       )
     })
     .await;
@@ -88,6 +159,170 @@ where
     rslt
   }
 
+  /// Receive response headers
+  ///
+  /// Awaits the initial headers of a response without waiting for the end of the stream, which
+  /// is the case of tunnels opened by [`Self::send_extended_connect`].
+  ///
+  /// Returns [`Option::None`] if the network/stream connection has been closed, either locally
+  /// or externally.
+  #[inline]
+  pub async fn recv_res_headers(&mut self) -> crate::Result<Option<(Headers, StatusCode)>> {
+    let Self { inner, linger: _, span, stream_id, windows: _ } = self;
+    let _e = span.enter();
+    _trace!(target: crate::_WTX_HTTP2, "Receiving response headers");
+    let mut lock_pin = pin!(inner.hd.lock());
+    let rslt = poll_fn(|cx| {
+      let mut lock = lock_pin!(cx, inner.hd, lock_pin);
+      let hdpm = lock.parts_mut();
+      if connection_state(&inner.is_conn_open.connection_state).is_closed() {
+        frame_reader_rslt(hdpm.frame_reader_error)?;
+        return Poll::Ready(Ok(None));
+      }
+      let Some(sorp) = hdpm.hb.sorps.get_mut(stream_id) else {
+        return Poll::Ready(Ok(None));
+      };
+      if sorp.has_initial_header {
+        let headers = mem::take(&mut sorp.msg_buffer.headers);
+        return Poll::Ready(Ok(Some((headers, sorp.status_code))));
+      }
+      if !sorp.is_stream_open || sorp.stream_state.recv_eos() {
+        return Poll::Ready(Ok(None));
+      }
+      sorp.waker.clone_from(cx.waker());
+      Poll::Pending
+    })
+    .await;
+    if let Err(err) = &rslt {
+      process_higher_operation_err(err, inner).await;
+    }
+    rslt
+  }
+
+  /// Send extended CONNECT
+  ///
+  /// Opens a tunnel of `protocol` as defined in RFC 8441. Contrary to [`Self::send_req`], the
+  /// end-of-stream flag isn't sent, as such, DATA frames can be exchanged through [`Self::common`]
+  /// once [`Self::recv_res_headers`] returns a successful status code.
+  ///
+  /// Awaits the first SETTINGS frame of the server and returns an error if
+  /// `SETTINGS_ENABLE_CONNECT_PROTOCOL` isn't enabled. Shouldn't be called more than once or
+  /// alongside [`Self::send_req`].
+  #[inline]
+  pub async fn send_extended_connect(
+    &mut self,
+    enc_buffer: &mut Vector<u8>,
+    headers: &Headers,
+    protocol: Protocol,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<Http2SendStatus> {
+    let Self { inner, linger: _, span, stream_id, windows } = self;
+    let _e = span.enter();
+    _trace!(target: crate::_WTX_HTTP2, "Sending extended CONNECT");
+    let hss = (
+      HpackStaticRequestHeaders {
+        authority: uri.authority(),
+        method: Some(Method::Connect),
+        path: uri.relative_reference_slash(),
+        protocol: Some(protocol),
+        scheme: uri.scheme(),
+      },
+      HpackStaticResponseHeaders::EMPTY,
+    );
+    let fut = async {
+      let mut lock_pin = pin!(inner.hd.lock());
+      let opt = poll_fn(|cx| {
+        let mut lock = lock_pin!(cx, inner.hd, lock_pin);
+        let hdpm = lock.parts_mut();
+        if connection_state(&inner.is_conn_open.connection_state).is_closed() {
+          return Poll::Ready(Ok(Some(Http2SendStatus::ClosedConnection)));
+        }
+        if hdpm.hps.has_peer_settings {
+          if hdpm.hps.enable_connect_protocol == 0 {
+            return Poll::Ready(Err(crate::Error::Http2Error(
+              Http2Error::ExtendedConnectIsUnsupported,
+            )));
+          }
+          return Poll::Ready(Ok(None));
+        }
+        let Some(scrp) = hdpm.hb.scrps.get_mut(stream_id) else {
+          return Poll::Ready(Ok(Some(Http2SendStatus::InvalidState)));
+        };
+        scrp.waker.clone_from(cx.waker());
+        Poll::Pending
+      })
+      .await?;
+      if let Some(elem) = opt {
+        return Ok(elem);
+      }
+      let max_frame_len = {
+        let mut hd_guard = inner.hd.lock().await;
+        let hdpm = hd_guard.parts_mut();
+        if connection_state(&inner.is_conn_open.connection_state).is_closed() {
+          return Ok(Http2SendStatus::ClosedConnection);
+        }
+        let Some(scrp) = hdpm.hb.scrps.remove(stream_id) else {
+          return Ok(Http2SendStatus::InvalidState);
+        };
+        *windows = scrp.windows;
+        encode_headers::<true>(enc_buffer, headers, &mut hdpm.hb.hpack_enc, hss)?;
+        drop(hdpm.hb.sorps.insert(
+          *stream_id,
+          StreamOverallRecvParams {
+            body_len: 0,
+            content_length: None,
+            has_initial_header: false,
+            has_one_or_more_data_frames: false,
+            is_stream_open: true,
+            msg_buffer: MsgBufferString::default(),
+            priority: scrp.priority,
+            status_code: StatusCode::Ok,
+            stream_state: StreamState::Open,
+            waker: Waker::noop().clone(),
+            windows: *windows,
+          },
+        ));
+        hdpm.hps.max_frame_len
+      };
+      let mut frames = ArrayVectorU8::new();
+      let _ = push_headers::<true>(
+        enc_buffer,
+        &mut frames,
+        &mut 0,
+        hss,
+        false,
+        max_frame_len,
+        *stream_id,
+      )?;
+      write_frames((enc_buffer, &[]), &frames, &mut *inner.wd.lock().await).await?;
+      Ok(Http2SendStatus::Ok)
+    };
+    let rslt = fut.await;
+    enc_buffer.clear();
+    if let Err(err) = &rslt {
+      process_higher_operation_err(err, inner).await;
+    }
+    rslt
+  }
+
+  /// Send priority update
+  ///
+  /// Changes the priority of the response, which was initially stated by the `priority` header of
+  /// the request. Servers that don't support extensible priorities ignore such a change.
+  #[inline]
+  pub async fn send_priority_update(&mut self, priority: Priority) -> crate::Result<()> {
+    let Self { inner, linger: _, span, stream_id, windows: _ } = self;
+    let _e = span.enter();
+    _trace!(target: crate::_WTX_HTTP2, "Sending priority update");
+    let buffer = &mut [0; 19];
+    let frame = PriorityUpdateFrame::new(*stream_id, priority);
+    let rslt = write_array([frame.bytes(buffer)], &mut *inner.wd.lock().await).await;
+    if let Err(err) = &rslt {
+      process_higher_operation_err(err, inner).await;
+    }
+    rslt
+  }
+
   /// Send Request
   ///
   /// Sends all data related to a request.
@@ -139,6 +374,7 @@ where
             has_one_or_more_data_frames: false,
             is_stream_open: true,
             msg_buffer: MsgBufferString::default(),
+            priority: Priority::DEFAULT,
             status_code: StatusCode::Ok,
             stream_state: StreamState::HalfClosedLocal,
             // The possible future invocation of this waker by the reading task won't be a problem
@@ -152,3 +388,21 @@ where
     .await
   }
 }
+
+impl<SW, TCX> Lease<ClientStream<SW, TCX>> for ClientStream<SW, TCX> {
+  #[inline]
+  fn lease(&self) -> &ClientStream<SW, TCX> {
+    self
+  }
+}
+
+impl<SW, TCX> LeaseMut<ClientStream<SW, TCX>> for ClientStream<SW, TCX> {
+  #[inline]
+  fn lease_mut(&mut self) -> &mut ClientStream<SW, TCX> {
+    self
+  }
+}
+
+impl<SW, TCX> SingleTypeStorage for ClientStream<SW, TCX> {
+  type Item = (SW, TCX);
+}
@@ -48,6 +48,10 @@ impl CommonFlags {
     self.0 &= ACK;
   }
 
+  pub(crate) const fn only_eoh_pad(&mut self) {
+    self.0 &= EOH | PAD;
+  }
+
   pub(crate) const fn only_eoh_eos_pad_pri(&mut self) {
     self.0 &= EOH | EOS | PAD | PRI;
   }
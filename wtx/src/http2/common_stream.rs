@@ -4,11 +4,17 @@ use crate::{
   futures::{Sleep, TryJoinArrayVector},
   http::{Headers, StatusCode, U31},
   http2::{
-    Http2Error, Http2Inner, Http2RecvStatus, Http2SendStatus,
+    Http2Error, Http2ErrorCode, Http2Inner, Http2RecvStatus, Http2SendStatus,
     hpack_static_headers::{HpackStaticRequestHeaders, HpackStaticResponseHeaders},
-    misc::{frame_reader_rslt, protocol_err, sorp_mut, status_recv, status_send, write_array},
+    misc::{
+      frame_reader_rslt, protocol_err, sorp_mut, status_recv, status_send, take_unclaimed_push,
+      wake_closed_streams_local, wake_deferred_streams, write_array,
+    },
+    reset_stream_frame::ResetStreamFrame,
     window::WindowsPair,
-    write_functions::{encode_headers, push_data, push_headers, push_trailers, write_frames},
+    write_functions::{
+      encode_headers, push_data, push_eos_data, push_headers, push_trailers, write_frames,
+    },
   },
   misc::{Usize, span::Span},
   net::StreamWriter,
@@ -41,12 +47,41 @@ where
     }
     let mut hd_guard = inner.hd.lock().await;
     let hdpm = hd_guard.parts_mut();
-    if let Some(elem) = hdpm.hb.scrps.remove(stream_id) {
+    let scrp = hdpm.hb.scrps.remove(stream_id);
+    let sorp = hdpm.hb.sorps.remove(stream_id);
+    #[cfg(feature = "metrics")]
+    if (scrp.is_some() || sorp.is_some())
+      && let Some(elem) =
+        crate::metrics::MetricsRegistry::global().http2_streams_closed.get(usize::from(!IS_CLIENT))
+    {
+      elem.inc();
+    }
+    if scrp.is_some() || sorp.is_some() {
+      wake_closed_streams_local(hdpm.hb);
+    }
+    if let Some(elem) = scrp {
+      if elem.is_sending_data {
+        wake_deferred_streams(&mut hdpm.hb.scrps);
+      }
       elem.waker.wake();
     }
-    if let Some(elem) = hdpm.hb.sorps.remove(stream_id) {
+    if let Some(elem) = sorp {
       elem.waker.wake();
     }
+    drop(hd_guard);
+    if IS_CLIENT {
+      // Pushed streams that were never claimed would otherwise be kept until the end of the
+      // connection.
+      loop {
+        let push_stream_id = take_unclaimed_push(inner.hd.lock().await.parts_mut().hb, *stream_id)?;
+        let Some(elem) = push_stream_id else {
+          break;
+        };
+        let rsf = ResetStreamFrame::new(Http2ErrorCode::Cancel, elem);
+        // The connection might already be closed
+        let _rslt = write_array([&rsf.bytes()], &mut *inner.wd.lock().await).await;
+      }
+    }
     Ok(())
   }
 
@@ -165,6 +200,8 @@ where
       let opt = {
         frames.clear();
         let mut hd_pin = pin!(inner.hd.lock());
+        #[cfg(feature = "metrics")]
+        let mut is_stalled = false;
         poll_fn(|cx| {
           let mut hd_guard = lock_pin!(cx, inner.hd, hd_pin);
           let hdpm = hd_guard.parts_mut();
@@ -172,8 +209,19 @@ where
           if let Some(elem) = status_send::<false>(&inner.is_conn_open.connection_state, sorp) {
             return Poll::Ready(crate::Result::Ok(Some(elem)));
           }
+          if data.is_empty() {
+            if is_eos {
+              push_eos_data(&mut frames, *stream_id)?;
+            }
+            return Poll::Ready(Ok(None));
+          }
           let mut wp = WindowsPair::new(hdpm.windows, &mut sorp.windows);
           let Ok(available_send @ 1..=u32::MAX) = u32::try_from(wp.available_send()) else {
+            #[cfg(feature = "metrics")]
+            if !is_stalled {
+              crate::metrics::MetricsRegistry::global().http2_flow_control_stalls.inc();
+              is_stalled = true;
+            }
             sorp.waker.clone_from(cx.waker());
             return Poll::Pending;
           };
@@ -325,7 +373,7 @@ where
 
   /// Sends a reset frame to the peer, which cancels this stream.
   #[inline]
-  pub async fn send_reset(&self, error_code: crate::http2::Http2ErrorCode) {
+  pub async fn send_reset(&self, error_code: Http2ErrorCode) {
     let Self { inner, linger: _, span: _, stream_id } = self;
     let _ = crate::http2::misc::send_reset_stream(error_code, inner, *stream_id).await;
   }
@@ -13,6 +13,7 @@ create_enum! {
     GoAway = (7),
     WindowUpdate = (8),
     Continuation = (9),
+    PriorityUpdate = (16),
   }
 }
 
@@ -29,6 +30,7 @@ impl FrameInitTy {
       Self::GoAway => 7,
       Self::WindowUpdate => 8,
       Self::Continuation => 9,
+      Self::PriorityUpdate => 16,
     }
   }
 }
@@ -0,0 +1,91 @@
+use crate::{
+  calendar::Instant,
+  http::HttpRecvParams,
+  http2::{Http2Error, Http2ErrorCode},
+};
+use core::time::Duration;
+
+/// Per-connection budgets of frames that can be used to exhaust resources without opening new
+/// streams.
+#[derive(Debug)]
+pub(crate) struct FrameRates {
+  empty_data: FrameRate,
+  hrp: HttpRecvParams,
+  ping: FrameRate,
+  reset: FrameRate,
+  settings: FrameRate,
+}
+
+impl FrameRates {
+  pub(crate) fn new(hrp: HttpRecvParams) -> Self {
+    Self {
+      empty_data: FrameRate::new(),
+      hrp,
+      ping: FrameRate::new(),
+      reset: FrameRate::new(),
+      settings: FrameRate::new(),
+    }
+  }
+
+  pub(crate) fn empty_data(&mut self) -> crate::Result<()> {
+    manage(
+      self.empty_data.increment(self.hrp.max_empty_data_frames_rate()),
+      Http2Error::ExceedAmountOfEmptyDataFrames,
+    )
+  }
+
+  pub(crate) fn ping(&mut self) -> crate::Result<()> {
+    manage(
+      self.ping.increment(self.hrp.max_ping_frames_rate()),
+      Http2Error::ExceedAmountOfPingFrames,
+    )
+  }
+
+  pub(crate) fn reset(&mut self) -> crate::Result<()> {
+    manage(
+      self.reset.increment(self.hrp.max_reset_frames_rate()),
+      Http2Error::ExceedAmountOfResetFrames,
+    )
+  }
+
+  pub(crate) fn settings(&mut self) -> crate::Result<()> {
+    manage(
+      self.settings.increment(self.hrp.max_settings_frames_rate()),
+      Http2Error::ExceedAmountOfSettingsFrames,
+    )
+  }
+}
+
+#[derive(Debug)]
+struct FrameRate {
+  count: u32,
+  start: Instant,
+}
+
+impl FrameRate {
+  fn new() -> Self {
+    Self { count: 0, start: Instant::new() }
+  }
+
+  // Returns `false` if more than `max` frames were received within `interval`. The clock is only
+  // consulted when the threshold is reached.
+  fn increment(&mut self, (max, interval): (u32, Duration)) -> bool {
+    self.count = self.count.saturating_add(1);
+    if self.count <= max {
+      return true;
+    }
+    if self.start.elapsed().is_ok_and(|el| el >= interval) {
+      self.count = 1;
+      self.start = Instant::new();
+      return true;
+    }
+    false
+  }
+}
+
+fn manage(is_within_budget: bool, error: Http2Error) -> crate::Result<()> {
+  if is_within_budget {
+    return Ok(());
+  }
+  Err(crate::Error::Http2ErrorGoAway(Http2ErrorCode::EnhanceYourCalm, error))
+}
@@ -15,9 +15,11 @@ macro_rules! prft {
 }
 
 use crate::{
+  http::HttpRecvParams,
   http2::{
-    Http2Error, Http2Inner,
+    Http2Error, Http2ErrorCode, Http2Inner,
     frame_init::{FrameInit, FrameInitTy},
+    frame_rates::FrameRates,
     go_away_frame::GoAwayFrame,
     misc::{
       manage_termination, process_higher_operation_err, protocol_err, read_frame,
@@ -36,8 +38,8 @@ use crate::{
 use core::{future::poll_fn, hint::cold_path, mem, pin::pin, task::Poll};
 
 pub(crate) async fn frame_reader<SR, SW, TCX, const IS_CLIENT: bool>(
+  hrp: HttpRecvParams,
   inner: Arc<Http2Inner<SW, TCX, IS_CLIENT>>,
-  max_frame_len: u32,
   mut nrb: BufStreamReader,
   stream_bridge: TlsStreamBridge<IS_CLIENT>,
   mut stream_reader: TlsStreamReader<SR, TCX, IS_CLIENT>,
@@ -48,12 +50,24 @@ pub(crate) async fn frame_reader<SR, SW, TCX, const IS_CLIENT: bool>(
 {
   let span = _trace_span!("Starting the reading of frames");
   let _e = span.enter();
+  let has_idle_timeout = hrp.idle_timeout().is_some();
+  let max_frame_len = hrp.max_frame_len();
+  let mut frame_rates = FrameRates::new(hrp);
   let mut tls_fut = pin!(stream_bridge.listen());
 
   loop {
     if TCX::TY.is_plain_text() {
       let rslt = read_frame::<_, false>(max_frame_len, &mut nrb, &mut stream_reader).await;
-      if !manage_iteration(&inner, &mut nrb, rslt, &mut stream_reader).await {
+      if !manage_iteration(
+        &mut frame_rates,
+        has_idle_timeout,
+        &inner,
+        &mut nrb,
+        rslt,
+        &mut stream_reader,
+      )
+      .await
+      {
         break;
       }
       continue;
@@ -94,7 +108,16 @@ pub(crate) async fn frame_reader<SR, SW, TCX, const IS_CLIENT: bool>(
         }
       }
     };
-    if !manage_iteration(&inner, &mut nrb, http2, &mut stream_reader).await {
+    if !manage_iteration(
+      &mut frame_rates,
+      has_idle_timeout,
+      &inner,
+      &mut nrb,
+      http2,
+      &mut stream_reader,
+    )
+    .await
+    {
       break;
     }
   }
@@ -118,6 +141,8 @@ async fn finish<SW, TCX, const IS_CLIENT: bool>(
 
 // Returns `false` if the connection should be closed.
 async fn manage_iteration<SR, SW, TCX, const IS_CLIENT: bool>(
+  frame_rates: &mut FrameRates,
+  has_idle_timeout: bool,
   inner: &Http2Inner<SW, TCX, IS_CLIENT>,
   nrb: &mut BufStreamReader,
   rslt: crate::Result<Option<FrameInit>>,
@@ -140,7 +165,10 @@ where
     }
     Ok(Some(fi)) => fi,
   };
-  if let Err(err) = manage_fi(fi, inner, nrb, stream_reader).await {
+  if has_idle_timeout && !matches!(fi.ty, FrameInitTy::Ping) {
+    inner.hd.lock().await.parts_mut().keepalive.mark_activity();
+  }
+  if let Err(err) = manage_fi(fi, frame_rates, inner, nrb, stream_reader).await {
     process_higher_operation_err(&err, inner).await;
     finish(Some(err), inner, nrb).await;
     return false;
@@ -148,8 +176,10 @@ where
   true
 }
 
+#[expect(clippy::too_many_lines, reason = "one arm per frame type")]
 async fn manage_fi<SR, SW, TCX, const IS_CLIENT: bool>(
   fi: FrameInit,
+  frame_rates: &mut FrameRates,
   inner: &Http2Inner<SW, TCX, IS_CLIENT>,
   nrb: &mut BufStreamReader,
   stream_reader: &mut SR,
@@ -164,6 +194,9 @@ where
       return Err(protocol_err(Http2Error::InvalidContinuationFrame));
     }
     FrameInitTy::Data => {
+      if nrb.current().is_empty() && !fi.cf.has_eos() {
+        frame_rates.empty_data()?;
+      }
       let frame = {
         let mut hd_guard = inner.hd.lock().await;
         let mut hdpm = hd_guard.parts_mut();
@@ -188,6 +221,14 @@ where
       } else {
         let lss =
           prft!(fi, hdpm, inner, nrb, stream_reader).header_server_init(&mut hdpm.hb.sorps).await?;
+        // Streams initiated after a GOAWAY frame are refused so that clients can safely retry them
+        if *hdpm.is_graceful_drain {
+          drop(hdpm.hb.sorps.remove(&lss.stream_id));
+          drop(hd_guard);
+          let rsf = ResetStreamFrame::new(Http2ErrorCode::RefusedStream, lss.stream_id);
+          write_array([&rsf.bytes()], &mut *inner.wd.lock().await).await?;
+          return Ok(());
+        }
         hdpm.hb.initial_server_streams_remote.push_back(lss)?;
         if let Some(elem) = hdpm.hb.initial_server_streams_local.pop_front() {
           elem.wake();
@@ -196,16 +237,31 @@ where
     }
     FrameInitTy::Ping => {
       let mut pf = PingFrame::read(nrb.current(), fi)?;
-      if !pf.has_ack() {
+      if pf.has_ack() {
+        inner.hd.lock().await.parts_mut().keepalive.recv_ack(pf.payload());
+      } else {
+        frame_rates.ping()?;
         pf.set_ack();
         write_array([&pf.bytes()], &mut *inner.wd.lock().await).await?;
       }
     }
     FrameInitTy::PushPromise => {
-      return Err(protocol_err(Http2Error::PushPromiseIsUnsupported));
+      let mut hd_guard = inner.hd.lock().await;
+      let mut hdpm = hd_guard.parts_mut();
+      let ipsr = &mut hdpm.hb.initial_push_streams_remote;
+      prft!(fi, hdpm, inner, nrb, stream_reader)
+        .push_promise::<IS_CLIENT>(ipsr, hdpm.last_push_stream_id, &mut hdpm.hb.sorps)
+        .await?;
     }
     FrameInitTy::Priority => {}
+    FrameInitTy::PriorityUpdate => {
+      let mut hd_guard = inner.hd.lock().await;
+      let mut hdpm = hd_guard.parts_mut();
+      prft!(fi, hdpm, inner, nrb, stream_reader)
+        .priority_update::<IS_CLIENT>(&mut hdpm.hb.scrps, &mut hdpm.hb.sorps)?;
+    }
     FrameInitTy::Reset => {
+      frame_rates.reset()?;
       let rsf = ResetStreamFrame::read(nrb.current(), fi)?;
       if !send_reset_stream(rsf.error_code(), inner, fi.stream_id).await {
         return Err(protocol_err(Http2Error::UnknownResetStreamReceiver));
@@ -214,12 +270,13 @@ where
     FrameInitTy::Settings => {
       let sf = SettingsFrame::read(nrb.current(), fi)?;
       if !sf.has_ack() {
+        frame_rates.settings()?;
         {
           let mut hd_guard = inner.hd.lock().await;
           let hdpm = hd_guard.parts_mut();
           hdpm.hps.update(&mut hdpm.hb.hpack_enc, &mut hdpm.hb.scrps, &sf, &mut hdpm.hb.sorps)?;
         }
-        let buffer = &mut [0; 45];
+        let buffer = &mut [0; 51];
         write_array([SettingsFrame::ack().bytes(buffer)], &mut *inner.wd.lock().await).await?;
       }
     }
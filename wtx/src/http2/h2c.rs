@@ -0,0 +1,303 @@
+//! Cleartext HTTP/2 (`h2c`) connections that are initiated either with prior knowledge or with an
+//! HTTP/1.1 upgrade request.
+//!
+//! <https://datatracker.ietf.org/doc/html/rfc7540#section-3.2>
+
+use crate::{
+  codec::{Base64Alphabet, base64_decode, base64_encode},
+  http::{
+    Header, HttpRecvParams, KnownHeaderName, Method, MsgBufferString, Priority, StatusCode, U31,
+  },
+  http2::{
+    ClientStream, Http2, Http2Buffer, Http2Error, Http2ErrorCode, PREFACE,
+    common_flags::CommonFlags,
+    frame_init::{FrameInit, FrameInitTy},
+    go_away_frame::GoAwayFrame,
+    initial_server_stream_remote::InitialServerStreamRemote,
+    misc::{protocol_err, write_array},
+    settings_frame::SettingsFrame,
+    stream_receiver::StreamOverallRecvParams,
+    stream_state::StreamState,
+    window::Windows,
+  },
+  misc::{Lease, Usize, bytes_split1, from_utf8_basic},
+  net::{StreamReader, StreamWriter, Uri},
+  tls::{TlsCtx, TlsStreamBridge, TlsStreamReader, TlsStreamWriter},
+};
+use alloc::string::String;
+use core::task::Waker;
+use httparse::{EMPTY_HEADER, Request, Response, Status};
+
+const MAX_HEADERS: usize = 32;
+const READ_INCREMENT: usize = 1024;
+
+impl<SW, TCX> Http2<SW, TCX, false>
+where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  /// Accepts a cleartext connection that starts either with the connection preface (prior
+  /// knowledge) or with an HTTP/1.1 request that asks to upgrade to `h2c`.
+  ///
+  /// The upgrade request is assigned to the stream `1` and is yielded by [`Self::stream`] like any
+  /// other request. Upgrade requests with bodies are not supported.
+  #[inline]
+  pub async fn accept_h2c<SR>(
+    mut hb: Http2Buffer,
+    mut hrp: HttpRecvParams,
+    (stream_bridge, mut stream_reader, mut stream_writer): (
+      TlsStreamBridge<false>,
+      TlsStreamReader<SR, TCX, false>,
+      TlsStreamWriter<SW, TCX, false>,
+    ),
+  ) -> crate::Result<(impl Future<Output = ()>, Self)>
+  where
+    SR: StreamReader,
+  {
+    hb.clear();
+    hrp = hrp.set_enable_push(false);
+    let mut msg_buffer = MsgBufferString::default();
+    let upgrade = loop {
+      if hb.nrb.read_arbitrary(READ_INCREMENT, &mut stream_reader).await?.is_none() {
+        return Err(protocol_err(Http2Error::NoPreface));
+      }
+      let bytes = hb.nrb.current();
+      let prefix_len = bytes.len().min(PREFACE.len());
+      if bytes.get(..prefix_len) == PREFACE.get(..prefix_len) {
+        if prefix_len < PREFACE.len() {
+          continue;
+        }
+        hb.nrb.set_indices(0, 0);
+        break None;
+      }
+      let mut headers = [EMPTY_HEADER; MAX_HEADERS];
+      let mut req = Request::new(&mut headers);
+      let Status::Complete(len) = req.parse(bytes)? else {
+        if bytes.len() >= *Usize::from(hrp.max_headers_len()) {
+          let _rslt =
+            stream_writer.write_all(b"HTTP/1.1 431 Request Header Fields Too Large\r\n\r\n").await;
+          return Err(crate::Error::Http2Error(Http2Error::LargeH2cUpgradeRequest));
+        }
+        continue;
+      };
+      let rslt = upgrade_req(&mut msg_buffer, &req);
+      hb.nrb.set_indices(len, len);
+      match rslt {
+        Ok(elem) => {
+          stream_writer
+            .write_all(
+              b"HTTP/1.1 101 Switching Protocols\r\nConnection: Upgrade\r\nUpgrade: h2c\r\n\r\n",
+            )
+            .await?;
+          break Some(elem);
+        }
+        Err(err) => {
+          let _rslt = stream_writer.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n").await;
+          return Err(err);
+        }
+      }
+    };
+    if hb.nrb.read_header::<_, 24>(&mut stream_reader).await? != Some(PREFACE) {
+      let _rslt = stream_writer
+        .write_all(&GoAwayFrame::new(Http2ErrorCode::ProtocolError, U31::ZERO).bytes())
+        .await;
+      return Err(protocol_err(Http2Error::NoPreface));
+    }
+    let (fut, this) =
+      Self::manage_initial_params::<_, false>(hb, hrp, stream_bridge, stream_reader, stream_writer)
+        .await?;
+    if let Some((method, sf)) = upgrade {
+      let mut hd_guard = this.inner.hd.lock().await;
+      let hdpm = hd_guard.parts_mut();
+      hdpm.hps.update(&mut hdpm.hb.hpack_enc, &mut hdpm.hb.scrps, &sf, &mut hdpm.hb.sorps)?;
+      drop(hdpm.hb.sorps.insert(
+        U31::ONE,
+        StreamOverallRecvParams {
+          body_len: 0,
+          content_length: None,
+          has_initial_header: true,
+          has_one_or_more_data_frames: false,
+          is_stream_open: true,
+          priority: Priority::from_headers(&msg_buffer.headers),
+          msg_buffer,
+          status_code: StatusCode::Ok,
+          stream_state: StreamState::HalfClosedRemote,
+          waker: Waker::noop().clone(),
+          windows: Windows::initial(hdpm.hp, hdpm.hps),
+        },
+      ));
+      hdpm.hb.initial_server_streams_remote.push_back(InitialServerStreamRemote {
+        method,
+        protocol: None,
+        stream_id: U31::ONE,
+      })?;
+      *hdpm.last_stream_id = U31::ONE;
+      *hdpm.recv_streams_num = hdpm.recv_streams_num.wrapping_add(1);
+    }
+    Ok((fut, this))
+  }
+}
+
+impl<SW, TCX> Http2<SW, TCX, true>
+where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  /// Tries to connect to a cleartext server by upgrading a `GET` HTTP/1.1 request of `uri` to
+  /// `h2c`.
+  ///
+  /// The upgrade request is assigned to the stream `1`, whose response is received through the
+  /// returned [`ClientStream`]. Servers known to support HTTP/2 can be directly contacted with
+  /// [`Self::connect`] instead.
+  #[inline]
+  pub async fn connect_h2c<S, SR>(
+    mut hb: Http2Buffer,
+    mut hrp: HttpRecvParams,
+    (stream_bridge, mut stream_reader, mut stream_writer): (
+      TlsStreamBridge<true>,
+      TlsStreamReader<SR, TCX, true>,
+      TlsStreamWriter<SW, TCX, true>,
+    ),
+    uri: &Uri<S>,
+  ) -> crate::Result<(impl Future<Output = ()> + use<S, SR, SW, TCX>, Self, ClientStream<SW, TCX>)>
+  where
+    S: Lease<str>,
+    SR: StreamReader,
+  {
+    hb.clear();
+    hrp = hrp.set_enable_connect_protocol(false);
+    {
+      let (sf_buffer, settings_buffer) = (&mut [0; 51], &mut [0; 68]);
+      let sf_payload = SettingsFrame::from_hrp(hrp).bytes(sf_buffer).get(9..).unwrap_or_default();
+      let settings = base64_encode(Base64Alphabet::UrlNoPad, sf_payload, settings_buffer)?;
+      let array = [
+        b"GET ",
+        uri.relative_reference_slash().as_bytes(),
+        b" HTTP/1.1\r\nHost: ",
+        uri.host().as_bytes(),
+        b"\r\nConnection: Upgrade, HTTP2-Settings\r\nUpgrade: h2c\r\nHTTP2-Settings: ",
+        settings.as_bytes(),
+        b"\r\n\r\n",
+      ];
+      write_array(array, &mut stream_writer).await?;
+    }
+    loop {
+      if hb.nrb.read_arbitrary(READ_INCREMENT, &mut stream_reader).await?.is_none() {
+        return Err(crate::Error::Http2Error(Http2Error::UnexpectedH2cUpgradeStatus(None)));
+      }
+      let mut headers = [EMPTY_HEADER; MAX_HEADERS];
+      let mut res = Response::new(&mut headers);
+      let Status::Complete(len) = res.parse(hb.nrb.current())? else {
+        continue;
+      };
+      let has_upgrade = res
+        .headers
+        .iter()
+        .any(|header| is_name(header, KnownHeaderName::Upgrade) && has_token(header.value, b"h2c"));
+      if res.code != Some(101) || !has_upgrade {
+        return Err(crate::Error::Http2Error(Http2Error::UnexpectedH2cUpgradeStatus(res.code)));
+      }
+      // Frames sent by the server right after the response are kept in the buffer
+      hb.nrb.set_indices(len, len);
+      break;
+    }
+    let (fut, this) =
+      Self::manage_initial_params::<_, true>(hb, hrp, stream_bridge, stream_reader, stream_writer)
+        .await?;
+    let linger = {
+      let mut hd_guard = this.inner.hd.lock().await;
+      let hdpm = hd_guard.parts_mut();
+      drop(hdpm.hb.sorps.insert(
+        U31::ONE,
+        StreamOverallRecvParams {
+          body_len: 0,
+          content_length: None,
+          has_initial_header: false,
+          has_one_or_more_data_frames: false,
+          is_stream_open: true,
+          msg_buffer: MsgBufferString::default(),
+          priority: Priority::DEFAULT,
+          status_code: StatusCode::Ok,
+          stream_state: StreamState::HalfClosedLocal,
+          waker: Waker::noop().clone(),
+          windows: Windows::initial(hdpm.hp, hdpm.hps),
+        },
+      ));
+      *hdpm.last_stream_id = U31::ONE.wrapping_add(U31::TWO);
+      hdpm.hp.linger()
+    };
+    let span = _trace_span!("New client stream", stream_id = %U31::ONE);
+    let stream = ClientStream::new(this.inner.clone(), linger, span, U31::ONE);
+    Ok((fut, this, stream))
+  }
+}
+
+fn has_token(value: &[u8], token: &[u8]) -> bool {
+  bytes_split1(value, b',').any(|el| el.trim_ascii().eq_ignore_ascii_case(token))
+}
+
+fn is_name(header: &httparse::Header<'_>, name: KnownHeaderName) -> bool {
+  header.name.trim_ascii().as_bytes().eq_ignore_ascii_case(<&[u8]>::from(name))
+}
+
+// Validates an upgrade request and copies its content to `msg_buffer`. Connection-specific headers
+// are discarded.
+fn upgrade_req(
+  msg_buffer: &mut MsgBufferString,
+  req: &Request<'_, '_>,
+) -> crate::Result<(Method, SettingsFrame)> {
+  let invalid = || crate::Error::Http2Error(Http2Error::InvalidH2cUpgradeRequest);
+  let method = Method::try_from(req.method.unwrap_or_default().as_bytes())?;
+  let mut authority = "";
+  let (mut has_conn_settings, mut has_conn_upgrade, mut has_upgrade) = (false, false, false);
+  let mut name_buffer = String::new();
+  let mut settings_opt = None;
+  for header in req.headers.iter() {
+    let value = header.value.trim_ascii();
+    name_buffer.clear();
+    name_buffer.push_str(header.name.trim_ascii());
+    name_buffer.make_ascii_lowercase();
+    match KnownHeaderName::try_from(name_buffer.as_bytes()) {
+      Ok(KnownHeaderName::Connection) => {
+        has_conn_settings |= has_token(value, b"http2-settings");
+        has_conn_upgrade |= has_token(value, b"upgrade");
+      }
+      Ok(KnownHeaderName::ContentLength) if value != b"0" => return Err(invalid()),
+      Ok(KnownHeaderName::Host) => authority = from_utf8_basic(value)?,
+      Ok(KnownHeaderName::Http2Settings) => {
+        if settings_opt.replace(value).is_some() {
+          return Err(invalid());
+        }
+      }
+      Ok(KnownHeaderName::TransferEncoding) => return Err(invalid()),
+      Ok(KnownHeaderName::Upgrade) => has_upgrade |= has_token(value, b"h2c"),
+      Ok(
+        KnownHeaderName::ContentLength
+        | KnownHeaderName::KeepAlive
+        | KnownHeaderName::ProxyConnection,
+      ) => {}
+      _ => {
+        let local_value = from_utf8_basic(value)?;
+        msg_buffer
+          .headers
+          .push_from_iter(Header::from_name_and_value(&name_buffer, [local_value]))?;
+      }
+    }
+  }
+  let Some(settings) =
+    settings_opt.filter(|_| has_conn_settings && has_conn_upgrade && has_upgrade)
+  else {
+    return Err(invalid());
+  };
+  let sf_buffer = &mut [0; 128];
+  let sf_payload = base64_decode(Base64Alphabet::UrlNoPad, settings, sf_buffer)?;
+  let data_len = u32::try_from(sf_payload.len()).map_err(|_err| invalid())?;
+  let fi = FrameInit::new(CommonFlags::empty(), data_len, U31::ZERO, FrameInitTy::Settings);
+  let sf = SettingsFrame::read(sf_payload, fi)?;
+  let mut uri_buffer = msg_buffer.uri.reset();
+  uri_buffer.push_str("http://");
+  uri_buffer.push_str(authority);
+  uri_buffer.push_str(req.path.unwrap_or("/"));
+  drop(uri_buffer);
+  Ok((method, sf))
+}
@@ -81,8 +81,9 @@ impl HpackEncoder {
     buffer.reserve(bytes_len_hint / 2)?;
     self.manage_size_update(buffer)?;
     for (hhb, value) in pseudo_headers_iter {
-      let idx = self.encode_idx(("", value, false), hhb, Self::shi_pseudo((hhb, value)))?;
-      Self::manage_encode(buffer, ("", value), idx)?;
+      let name = hhb.name();
+      let idx = self.encode_idx((name, value, false), hhb, Self::shi_pseudo((hhb, value)))?;
+      Self::manage_encode(buffer, (name, value), idx)?;
     }
     for Header { is_sensitive, name, value, .. } in user_headers_iter {
       let idx = self.encode_idx(
@@ -26,6 +26,18 @@ impl HpackHeaderBasic {
       HpackHeaderBasic::StatusCode(_) => 7usize.wrapping_add(3).wrapping_add(32),
     }
   }
+
+  pub(crate) const fn name(self) -> &'static str {
+    match self {
+      HpackHeaderBasic::Authority => ":authority",
+      HpackHeaderBasic::Field => "",
+      HpackHeaderBasic::Method(_) => ":method",
+      HpackHeaderBasic::Path => ":path",
+      HpackHeaderBasic::Protocol(_) => ":protocol",
+      HpackHeaderBasic::Scheme => ":scheme",
+      HpackHeaderBasic::StatusCode(_) => ":status",
+    }
+  }
 }
 
 impl TryFrom<(HpackHeaderName, &str)> for HpackHeaderBasic {
@@ -2,6 +2,7 @@ use crate::{
   collections::Deque,
   http2::{
     Scorp, Sovrp, hpack_decoder::HpackDecoder, hpack_encoder::HpackEncoder,
+    initial_push_stream_remote::InitialPushStreamRemote,
     initial_server_stream_remote::InitialServerStreamRemote,
   },
   net::BufStreamReader,
@@ -13,8 +14,10 @@ use hashbrown::HashMap;
 /// Groups all intermediate structures necessary to perform HTTP/2 connections.
 #[derive(Debug)]
 pub struct Http2Buffer {
+  pub(crate) closed_streams_local: Deque<Waker>,
   pub(crate) hpack_dec: HpackDecoder,
   pub(crate) hpack_enc: HpackEncoder,
+  pub(crate) initial_push_streams_remote: Deque<InitialPushStreamRemote>,
   pub(crate) initial_server_streams_local: Deque<Waker>,
   pub(crate) initial_server_streams_remote: Deque<InitialServerStreamRemote>,
   pub(crate) nrb: BufStreamReader,
@@ -30,8 +33,10 @@ impl Http2Buffer {
     RNG: Rng,
   {
     Self {
+      closed_streams_local: Deque::new(),
       hpack_dec: HpackDecoder::new(),
       hpack_enc: HpackEncoder::new(rng),
+      initial_push_streams_remote: Deque::new(),
       initial_server_streams_local: Deque::new(),
       initial_server_streams_remote: Deque::new(),
       nrb: BufStreamReader::new(),
@@ -42,16 +47,20 @@ impl Http2Buffer {
 
   pub(crate) fn clear(&mut self) {
     let Self {
+      closed_streams_local,
       hpack_dec,
       hpack_enc,
+      initial_push_streams_remote,
       initial_server_streams_local,
       initial_server_streams_remote,
       nrb,
       scrps,
       sorps,
     } = self;
+    closed_streams_local.clear();
     hpack_dec.clear();
     hpack_enc.clear();
+    initial_push_streams_remote.clear();
     initial_server_streams_local.clear();
     initial_server_streams_remote.clear();
     nrb.clear();
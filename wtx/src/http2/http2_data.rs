@@ -1,6 +1,6 @@
 use crate::{
   http::{HttpRecvParams, U31},
-  http2::{Http2Buffer, http_send_params::HttpSendParams, window::Windows},
+  http2::{Http2Buffer, http_send_params::HttpSendParams, keepalive::Keepalive, window::Windows},
   misc::{Lease, LeaseMut},
 };
 
@@ -11,6 +11,9 @@ pub struct Http2Data<const IS_CLIENT: bool> {
   hb: Http2Buffer,
   hp: HttpRecvParams,
   hps: HttpSendParams,
+  is_graceful_drain: bool,
+  keepalive: Keepalive,
+  last_push_stream_id: U31,
   last_stream_id: U31,
   recv_streams_num: u32,
   windows: Windows,
@@ -25,6 +28,9 @@ impl<const IS_CLIENT: bool> Http2Data<IS_CLIENT> {
       hb,
       hp,
       hps,
+      is_graceful_drain: false,
+      keepalive: Keepalive::new(),
+      last_push_stream_id: U31::ZERO,
       last_stream_id: if IS_CLIENT { U31::ONE } else { U31::ZERO },
       recv_streams_num: 0,
       windows,
@@ -37,6 +43,9 @@ impl<const IS_CLIENT: bool> Http2Data<IS_CLIENT> {
       hb: &mut self.hb,
       hp: &mut self.hp,
       hps: &mut self.hps,
+      is_graceful_drain: &mut self.is_graceful_drain,
+      keepalive: &mut self.keepalive,
+      last_push_stream_id: &mut self.last_push_stream_id,
       last_stream_id: &mut self.last_stream_id,
       recv_streams_num: &mut self.recv_streams_num,
       windows: &mut self.windows,
@@ -63,6 +72,9 @@ pub(crate) struct Http2DataPartsMut<'instance, const IS_CLIENT: bool> {
   pub(crate) hb: &'instance mut Http2Buffer,
   pub(crate) hp: &'instance mut HttpRecvParams,
   pub(crate) hps: &'instance mut HttpSendParams,
+  pub(crate) is_graceful_drain: &'instance mut bool,
+  pub(crate) keepalive: &'instance mut Keepalive,
+  pub(crate) last_push_stream_id: &'instance mut U31,
   pub(crate) last_stream_id: &'instance mut U31,
   pub(crate) recv_streams_num: &'instance mut u32,
   pub(crate) windows: &'instance mut Windows,
@@ -27,6 +27,18 @@ pub enum Http2Error {
   ExceedAmountOfOpenedStreams,
   /// The number of active concurrent streams extrapolated the threshold
   ExceedAmountOfActiveConcurrentStreams,
+  /// The number of received empty DATA frames extrapolated the threshold
+  ExceedAmountOfEmptyDataFrames,
+  /// The number of concurrent pushed streams extrapolated the threshold
+  ExceedAmountOfPushedStreams,
+  /// The number of received PING frames extrapolated the threshold
+  ExceedAmountOfPingFrames,
+  /// The number of received RST_STREAM frames extrapolated the threshold
+  ExceedAmountOfResetFrames,
+  /// The number of received SETTINGS frames extrapolated the threshold
+  ExceedAmountOfSettingsFrames,
+  /// Extended CONNECT requests require the server to enable `SETTINGS_ENABLE_CONNECT_PROTOCOL`.
+  ExtendedConnectIsUnsupported,
   /// The system only supports 2 header frames when sending data
   HeadersOverflow,
   /// Couldn't decode a header into a hpack buffer
@@ -57,6 +69,8 @@ pub enum Http2Error {
   InvalidGoAwayFrameBytes,
   #[doc = stream_id_must_be_zero!()]
   InvalidGoAwayFrameNonZeroId,
+  /// HTTP/1.1 request isn't a valid `h2c` upgrade request or contains a body.
+  InvalidH2cUpgradeRequest,
   /// A container does not contain an element referred by the given idx
   InvalidHpackIdx(u32),
   /// Header frame has mal-formatted content
@@ -67,6 +81,12 @@ pub enum Http2Error {
   InvalidPingFrameBytes,
   #[doc = stream_id_must_be_zero!()]
   InvalidPingFrameNonZeroId,
+  /// `PRIORITY_UPDATE` frames must be sent by clients in the connection stream and must refer to
+  /// a non-zero stream ID.
+  InvalidPriorityUpdateFrame,
+  /// `PUSH_PROMISE` frames must be sent by servers in open streams and must promise a new
+  /// even stream ID.
+  InvalidPushPromiseFrame,
   /// Invalid frame after received EOS
   InvalidReceivedFrameAfterEos,
   #[doc = invalid_frame_bytes!()]
@@ -98,6 +118,9 @@ pub enum Http2Error {
   },
   /// Set of received data frames extrapolate delimited maximum length
   LargeBodyLen(Option<u32>),
+  /// The header section of an HTTP/1.1 upgrade request exceeded
+  /// [`crate::http::HttpRecvParams::max_headers_len`]
+  LargeH2cUpgradeRequest,
   /// Ignorable frames extrapolates delimited maximum length
   LargeIgnorableFrameLen,
   /// All trailer frames must include the EOS flag
@@ -110,12 +133,19 @@ pub enum Http2Error {
   OutOfBoundsMaxFrameSize,
   /// Window size must be within 0 and 2147483647
   OutOfBoundsWindowSize,
-  /// Browsers don't support `PUSH_PROMISE`.
+  /// The acknowledgement of a keepalive PING frame wasn't received in time
+  PingAckTimeout,
+  /// `PUSH_PROMISE` frames weren't enabled by [`crate::http::HttpRecvParams::enable_push`].
   PushPromiseIsUnsupported,
   /// Received frame should be a continuation frame with correct ID
   UnexpectedContinuationFrame,
   /// Decoding logic encountered an unexpected ending string signal.
   UnexpectedEndingHuffman,
+  /// Server didn't accept an extended CONNECT request and returned the provided status code
+  /// instead.
+  UnexpectedExtendedConnectStatus(u16),
+  /// Server didn't switch to `h2c` and returned the provided status code instead.
+  UnexpectedH2cUpgradeStatus(Option<u16>),
   /// Header frames must be received only once per block
   UnexpectedHeaderFrame,
   /// Received an Hpack index that does not adhere to the standard
@@ -156,6 +186,8 @@ pub enum Http2Error {
     " fetches of frames with mismatches IDs or mismatches types"
   )]
   VeryLargeAmountOfFrameMismatches,
+  /// The sum of the length of a HEADERS frame and its CONTINUATION frames is too large
+  VeryLargeHeaderBlockLen,
   /// Header integers must be equal or lesser than `u16::MAX`
   VeryLargeHeaderInteger,
   /// Received headers is too large to sent
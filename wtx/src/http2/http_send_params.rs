@@ -12,6 +12,8 @@ use core::cmp::Ordering;
 #[derive(Debug)]
 pub(crate) struct HttpSendParams {
   pub(crate) enable_connect_protocol: u32,
+  // If the first SETTINGS frame of the peer was received.
+  pub(crate) has_peer_settings: bool,
   pub(crate) initial_window_len: U31,
   pub(crate) max_concurrent_streams_num: u32,
   pub(crate) max_frame_len: u32,
@@ -30,6 +32,13 @@ impl HttpSendParams {
     if let Some(elem) = sf.enable_connect_protocol() {
       self.enable_connect_protocol = u32::from(elem);
     }
+    if !self.has_peer_settings {
+      self.has_peer_settings = true;
+      // Extended CONNECT requests await the first SETTINGS frame
+      for elem in scorp.values() {
+        elem.waker.wake_by_ref();
+      }
+    }
     'update: {
       if let Some(initial_window_size) = sf.initial_window_size() {
         let ordering = initial_window_size.cmp(&self.initial_window_len);
@@ -82,6 +91,7 @@ impl Default for HttpSendParams {
   fn default() -> Self {
     Self {
       enable_connect_protocol: 0,
+      has_peer_settings: false,
       initial_window_len: U31::from_u32(DEFAULT_INITIAL_WINDOW_LEN),
       max_hpack_len: DEFAULT_MAX_HPACK_LEN,
       max_headers_len: DEFAULT_MAX_HEADERS_LEN,
@@ -0,0 +1,9 @@
+use crate::http::{Method, MsgBufferString, U31};
+
+#[derive(Debug)]
+pub(crate) struct InitialPushStreamRemote {
+  pub(crate) method: Method,
+  pub(crate) msg_buffer: MsgBufferString,
+  pub(crate) parent_stream_id: U31,
+  pub(crate) stream_id: U31,
+}
@@ -0,0 +1,130 @@
+use crate::{
+  calendar::Instant,
+  futures::Sleep,
+  http2::{
+    Http2Error, Http2ErrorCode, Http2Inner,
+    common_flags::CommonFlags,
+    misc::{close, connection_state, write_array},
+    ping_frame::PingFrame,
+  },
+  net::StreamWriter,
+  sync::Arc,
+  tls::TlsCtx,
+};
+use core::time::Duration;
+
+/// Liveness information of a connection.
+#[derive(Debug)]
+pub(crate) struct Keepalive {
+  last_activity: Instant,
+  last_ping: Instant,
+  ping: Option<(Instant, [u8; 8])>,
+  ping_counter: u64,
+  rtt: Option<Duration>,
+}
+
+impl Keepalive {
+  pub(crate) fn new() -> Self {
+    let now = Instant::new();
+    Self { last_activity: now, last_ping: now, ping: None, ping_counter: 0, rtt: None }
+  }
+
+  /// Should be called for every received frame that isn't a PING frame.
+  pub(crate) fn mark_activity(&mut self) {
+    self.last_activity = Instant::new();
+  }
+
+  /// Updates the round-trip time if `payload` belongs to the outstanding PING frame.
+  pub(crate) fn recv_ack(&mut self, payload: [u8; 8]) {
+    if let Some((sent, local_payload)) = self.ping
+      && local_payload == payload
+    {
+      self.ping = None;
+      self.rtt = sent.elapsed().ok();
+    }
+  }
+
+  pub(crate) const fn rtt(&self) -> Option<Duration> {
+    self.rtt
+  }
+
+  /// Returns a new PING frame if there isn't an outstanding one.
+  pub(crate) fn send_ping(&mut self) -> Option<PingFrame> {
+    if self.ping.is_some() {
+      return None;
+    }
+    let payload = self.ping_counter.to_be_bytes();
+    self.ping_counter = self.ping_counter.wrapping_add(1);
+    self.last_ping = Instant::new();
+    self.ping = Some((self.last_ping, payload));
+    Some(PingFrame::new(CommonFlags::empty(), payload))
+  }
+}
+
+/// Periodically sends PING frames and closes the connection if their acknowledgements aren't
+/// received in time or if the connection is idle.
+///
+/// Never finishes if neither the keepalive interval nor the idle timeout are set.
+pub(crate) async fn keepalive<SW, TCX, const IS_CLIENT: bool>(
+  inner: Arc<Http2Inner<SW, TCX, IS_CLIENT>>,
+) where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  let (idle_timeout, keepalive_interval, keepalive_timeout) = {
+    let mut hd_guard = inner.hd.lock().await;
+    let hp = hd_guard.parts_mut().hp;
+    (hp.idle_timeout(), hp.keepalive_interval(), hp.keepalive_timeout())
+  };
+  let tick = match (idle_timeout, keepalive_interval) {
+    (None, None) => return core::future::pending().await,
+    (None, Some(interval)) => interval.min(keepalive_timeout),
+    (Some(idle), None) => idle,
+    (Some(idle), Some(interval)) => idle.min(interval).min(keepalive_timeout),
+  };
+  loop {
+    let Ok(sleep) = Sleep::new(tick) else {
+      return;
+    };
+    let _rslt = sleep.await;
+    if connection_state(&inner.is_conn_open.connection_state).is_closed() {
+      return;
+    }
+    let mut hd_guard = inner.hd.lock().await;
+    let hdpm = hd_guard.parts_mut();
+    let last_stream_id = *hdpm.last_stream_id;
+    let ka = &mut *hdpm.keepalive;
+    if let Some(elem) = idle_timeout
+      && hdpm.hb.scrps.is_empty()
+      && hdpm.hb.sorps.is_empty()
+      && ka.last_activity.elapsed().is_ok_and(|el| el >= elem)
+    {
+      drop(hd_guard);
+      _debug!("Closing idle connection");
+      close(Http2ErrorCode::NoError, &inner, last_stream_id).await;
+      return;
+    }
+    let Some(interval) = keepalive_interval else {
+      continue;
+    };
+    if let Some((sent, _)) = ka.ping {
+      if sent.elapsed().is_ok_and(|el| el >= keepalive_timeout) {
+        *hdpm.frame_reader_error = Some(crate::Error::Http2Error(Http2Error::PingAckTimeout));
+        drop(hd_guard);
+        _debug!("Closing unresponsive connection");
+        // A missing acknowledgement is a failure, not an orderly shutdown.
+        close(Http2ErrorCode::InternalError, &inner, last_stream_id).await;
+        return;
+      }
+      continue;
+    }
+    if ka.last_ping.elapsed().is_ok_and(|el| el >= interval)
+      && let Some(pf) = ka.send_ping()
+    {
+      drop(hd_guard);
+      if write_array([&pf.bytes()], &mut *inner.wd.lock().await).await.is_err() {
+        return;
+      }
+    }
+  }
+}
@@ -1,10 +1,10 @@
 use crate::{
   _AFTER_CLOSE_TIMEOUT_MS,
   futures::Sleep,
-  http::{HttpRecvParams, MsgBufferString, MsgDataMut as _, StatusCode, U31},
+  http::{HttpRecvParams, MsgBufferString, MsgDataMut as _, Priority, StatusCode, U31},
   http2::{
-    Http2Data, Http2Error, Http2ErrorCode, Http2Inner, Http2RecvStatus, Http2SendStatus, Scorp,
-    Sovrp, Windows,
+    Http2Buffer, Http2Data, Http2Error, Http2ErrorCode, Http2Inner, Http2RecvStatus,
+    Http2SendStatus, Scorp, Sovrp, Windows,
     common_flags::CommonFlags,
     frame_init::{FrameInit, FrameInitTy},
     go_away_frame::GoAwayFrame,
@@ -22,6 +22,7 @@ use crate::{
 };
 use core::{
   mem,
+  ops::Range,
   sync::atomic::Ordering,
   task::{Context, Poll},
   time::Duration,
@@ -40,6 +41,19 @@ pub(crate) fn check_content_length(
   Ok(())
 }
 
+// Returns the sum of `len` and the length of `frame`.
+fn check_header_block_len(len: u32, hp: &HttpRecvParams, frame: &[u8]) -> crate::Result<u32> {
+  let frame_len = u32::try_from(frame.len()).unwrap_or(u32::MAX);
+  let sum = len.saturating_add(frame_len);
+  if sum > hp.max_header_block_len() {
+    return Err(crate::Error::Http2ErrorGoAway(
+      Http2ErrorCode::EnhanceYourCalm,
+      Http2Error::VeryLargeHeaderBlockLen,
+    ));
+  }
+  Ok(sum)
+}
+
 pub(crate) fn frame_reader_rslt(err: &mut Option<crate::Error>) -> crate::Result<()> {
   match err.take() {
     Some(elem) => Err(elem),
@@ -79,18 +93,26 @@ pub(crate) fn manage_recurrent_receiving_of_overall_stream<EOS, const IS_CLIENT:
   mut hdpm: Http2DataPartsMut<'_, IS_CLIENT>,
   is_conn_open: &AtomicU8,
   stream_id: U31,
-  cb_eos: impl FnOnce(&mut Http2DataPartsMut<'_, IS_CLIENT>, StatusCode, StreamState, Windows) -> EOS,
+  cb_eos: impl FnOnce(
+    &mut Http2DataPartsMut<'_, IS_CLIENT>,
+    Priority,
+    StatusCode,
+    StreamState,
+    Windows,
+  ) -> EOS,
 ) -> Poll<crate::Result<(Http2RecvStatus<EOS, ()>, MsgBufferString)>> {
   macro_rules! eos {
     ($hdpm:expr, $hrs:ident, $sorp:expr, $stream_id:expr) => {{
       let content_length = $sorp.content_length;
       let msg_buffer = mem::take(&mut $sorp.msg_buffer);
+      let priority = $sorp.priority;
       let status_code = $sorp.status_code;
       let stream_state = $sorp.stream_state;
       let windows = $sorp.windows;
       drop($hdpm.hb.sorps.remove($stream_id));
+      wake_closed_streams_local($hdpm.hb);
       check_content_length(content_length, &msg_buffer)?;
-      let eos = cb_eos(&mut $hdpm, status_code, stream_state, windows);
+      let eos = cb_eos(&mut $hdpm, priority, status_code, stream_state, windows);
       Poll::Ready(Ok((Http2RecvStatus::$hrs(eos), msg_buffer)))
     }};
   }
@@ -100,6 +122,7 @@ pub(crate) fn manage_recurrent_receiving_of_overall_stream<EOS, const IS_CLIENT:
     (false, false | true) => {
       let msg_buffer = mem::take(&mut sorp.msg_buffer);
       drop(hdpm.hb.sorps.remove(&stream_id));
+      wake_closed_streams_local(hdpm.hb);
       frame_reader_rslt(hdpm.frame_reader_error)?;
       return Poll::Ready(Ok((Http2RecvStatus::ClosedConnection, msg_buffer)));
     }
@@ -113,52 +136,28 @@ pub(crate) fn manage_recurrent_receiving_of_overall_stream<EOS, const IS_CLIENT:
   Poll::Pending
 }
 
-pub(crate) async fn manage_termination<SW, TCX, const IS_CLIENT: bool, const IS_RECV: bool>(
+/// Sends a GOAWAY frame and then immediately closes the connection.
+pub(crate) async fn close<SW, TCX, const IS_CLIENT: bool>(
   error_code: Http2ErrorCode,
   inner: &Http2Inner<SW, TCX, IS_CLIENT>,
+  last_stream_id: U31,
 ) where
   SW: StreamWriter,
   TCX: TlsCtx,
 {
-  async fn close<SW, TCX, const IS_CLIENT: bool>(
-    error_code: Http2ErrorCode,
-    inner: &Http2Inner<SW, TCX, IS_CLIENT>,
-    last_stream_id: U31,
-  ) where
-    SW: StreamWriter,
-    TCX: TlsCtx,
-  {
-    let mut lock = inner.wd.lock().await;
-    do_send_go_away(error_code, last_stream_id, &mut *lock).await;
-    lock.close_abruptly();
-    wake_tasks(&mut *inner.hd.lock().await);
-  }
-
-  async fn do_send_go_away<SW, TCX, const IS_CLIENT: bool>(
-    error_code: Http2ErrorCode,
-    last_stream_id: U31,
-    stream_writer: &mut TlsStreamWriter<SW, TCX, IS_CLIENT>,
-  ) where
-    SW: StreamWriter,
-    TCX: TlsCtx,
-  {
-    let gaf = GoAwayFrame::new(error_code, last_stream_id);
-    let _rslt = stream_writer.write_all(&gaf.bytes()).await;
-  }
-
-  fn wake_tasks<const IS_CLIENT: bool>(hd: &mut Http2Data<IS_CLIENT>) {
-    let hdpm = hd.parts_mut();
-    while let Some(elem) = hdpm.hb.initial_server_streams_local.pop_front() {
-      elem.wake();
-    }
-    for (_, value) in hdpm.hb.scrps.drain() {
-      value.waker.wake();
-    }
-    for (_, value) in hdpm.hb.sorps.drain() {
-      value.waker.wake();
-    }
-  }
+  let mut lock = inner.wd.lock().await;
+  do_send_go_away(error_code, last_stream_id, &mut *lock).await;
+  lock.close_abruptly();
+  wake_tasks(&mut *inner.hd.lock().await);
+}
 
+pub(crate) async fn manage_termination<SW, TCX, const IS_CLIENT: bool, const IS_RECV: bool>(
+  error_code: Http2ErrorCode,
+  inner: &Http2Inner<SW, TCX, IS_CLIENT>,
+) where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
   if error_code.is_fatal() {
     if IS_RECV {
       inner.wd.lock().await.close_abruptly();
@@ -181,6 +180,41 @@ pub(crate) async fn manage_termination<SW, TCX, const IS_CLIENT: bool, const IS_
   close(Http2ErrorCode::ProtocolError, inner, last_stream_id).await;
 }
 
+async fn do_send_go_away<SW, TCX, const IS_CLIENT: bool>(
+  error_code: Http2ErrorCode,
+  last_stream_id: U31,
+  stream_writer: &mut TlsStreamWriter<SW, TCX, IS_CLIENT>,
+) where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  let gaf = GoAwayFrame::new(error_code, last_stream_id);
+  let _rslt = stream_writer.write_all(&gaf.bytes()).await;
+}
+
+pub(crate) async fn manage_graceful_go_away<SW, TCX, const IS_CLIENT: bool>(
+  inner: &Http2Inner<SW, TCX, IS_CLIENT>,
+) where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  let last_stream_id = {
+    let mut hd_guard = inner.hd.lock().await;
+    let hdpm = hd_guard.parts_mut();
+    if !connection_state(&inner.is_conn_open.connection_state).is_open()
+      || mem::replace(hdpm.is_graceful_drain, true)
+    {
+      return;
+    }
+    while let Some(elem) = hdpm.hb.initial_server_streams_local.pop_front() {
+      elem.wake();
+    }
+    *hdpm.last_stream_id
+  };
+  let gaf = GoAwayFrame::new(Http2ErrorCode::NoError, last_stream_id);
+  let _rslt = inner.wd.lock().await.write_all(&gaf.bytes()).await;
+}
+
 #[expect(clippy::wildcard_enum_match_arm, reason = "too many variants")]
 pub(crate) async fn process_higher_operation_err<SW, TCX, const IS_CLIENT: bool>(
   err: &crate::Error,
@@ -203,6 +237,27 @@ pub(crate) async fn process_higher_operation_err<SW, TCX, const IS_CLIENT: bool>
   }
 }
 
+/// Awakes the tasks waiting for available local streams.
+pub(crate) fn wake_closed_streams_local(hb: &mut Http2Buffer) {
+  while let Some(elem) = hb.closed_streams_local.pop_front() {
+    elem.wake();
+  }
+}
+
+fn wake_tasks<const IS_CLIENT: bool>(hd: &mut Http2Data<IS_CLIENT>) {
+  let hdpm = hd.parts_mut();
+  wake_closed_streams_local(hdpm.hb);
+  while let Some(elem) = hdpm.hb.initial_server_streams_local.pop_front() {
+    elem.wake();
+  }
+  for (_, value) in hdpm.hb.scrps.drain() {
+    value.waker.wake();
+  }
+  for (_, value) in hdpm.hb.sorps.drain() {
+    value.waker.wake();
+  }
+}
+
 pub(crate) const fn protocol_err(error: Http2Error) -> crate::Error {
   crate::Error::Http2ErrorGoAway(Http2ErrorCode::ProtocolError, error)
 }
@@ -256,6 +311,35 @@ pub(crate) async fn read_header_and_continuations<
   msg_buffer: &mut MsgBufferString,
   nrb: &mut BufStreamReader,
   stream_reader: &mut SR,
+  headers_cb: impl FnMut(&HeadersFrame<'_>) -> crate::Result<H>,
+) -> crate::Result<(Option<usize>, bool, H)>
+where
+  SR: StreamReader,
+{
+  let fragment = 0..nrb.current().len();
+  read_header_block::<_, _, IS_CLIENT, IS_TRAILER>(
+    fi,
+    fragment,
+    hp,
+    hpack_dec,
+    msg_buffer,
+    nrb,
+    stream_reader,
+    headers_cb,
+  )
+  .await
+}
+
+/// Reads a header block whose first `fragment` is located in the current frame. Subsequent
+/// fragments are expected to be sent by `CONTINUATION` frames.
+pub(crate) async fn read_header_block<H, SR, const IS_CLIENT: bool, const IS_TRAILER: bool>(
+  fi: FrameInit,
+  fragment: Range<usize>,
+  hp: &mut HttpRecvParams,
+  hpack_dec: &mut HpackDecoder,
+  msg_buffer: &mut MsgBufferString,
+  nrb: &mut BufStreamReader,
+  stream_reader: &mut SR,
   mut headers_cb: impl FnMut(&HeadersFrame<'_>) -> crate::Result<H>,
 ) -> crate::Result<(Option<usize>, bool, H)>
 where
@@ -272,9 +356,12 @@ where
     0
   };
 
+  let first_fragment = nrb.current().get(fragment).unwrap_or_default();
+
   if fi.cf.has_eoh() {
+    let _header_block_len = check_header_block_len(0, hp, first_fragment)?;
     let (content_length, hf) = HeadersFrame::read::<IS_CLIENT, IS_TRAILER>(
-      Some(nrb.current()),
+      Some(first_fragment),
       fi,
       hp,
       hpack_dec,
@@ -290,7 +377,8 @@ where
     return Ok((content_length, hf.has_eos(), headers_cb(&hf)?));
   }
 
-  msg_buffer.body.extend_from_copyable_slice(nrb.current())?;
+  let mut header_block_len = check_header_block_len(0, hp, first_fragment)?;
+  msg_buffer.body.extend_from_copyable_slice(first_fragment)?;
 
   'continuation_frames: {
     for _ in 0.._max_continuation_frames!() {
@@ -303,6 +391,7 @@ where
       if has_diff_id || is_not_continuation {
         return Err(protocol_err(Http2Error::UnexpectedContinuationFrame));
       }
+      header_block_len = check_header_block_len(header_block_len, hp, nrb.current())?;
       msg_buffer.body.extend_from_copyable_slice(nrb.current())?;
       if frame_fi.cf.has_eoh() {
         break 'continuation_frames;
@@ -318,11 +407,7 @@ where
     hpack_dec,
     (msg_buffer, rrb_body_start),
   )?;
-  if IS_TRAILER {
-    msg_buffer.body.truncate(rrb_body_start);
-  } else {
-    msg_buffer.clear();
-  }
+  msg_buffer.body.truncate(rrb_body_start);
   if hf.is_over_size() {
     return Err(crate::Error::Http2ErrorGoAway(
       Http2ErrorCode::FrameSizeError,
@@ -345,11 +430,15 @@ where
   let mut has_stored = false;
   let _rslt = inner.wd.lock().await.write_all(&rsf.bytes()).await;
   let mut hd_guard = inner.hd.lock().await;
-  if let Some(elem) = hd_guard.parts_mut().hb.scrps.get_mut(&stream_id) {
+  let scrps = &mut hd_guard.parts_mut().hb.scrps;
+  if let Some(elem) = scrps.get_mut(&stream_id) {
     has_stored = true;
     elem.is_stream_open = false;
     elem.stream_state = StreamState::Closed;
     elem.waker.wake_by_ref();
+    if mem::take(&mut elem.is_sending_data) {
+      wake_deferred_streams(scrps);
+    }
   }
   if let Some(elem) = hd_guard.parts_mut().hb.sorps.get_mut(&stream_id) {
     has_stored = true;
@@ -397,6 +486,27 @@ pub(crate) fn status_send<const IS_CLIENT: bool>(
   None
 }
 
+/// Removes the first promise of `parent_stream_id` that wasn't claimed through
+/// `ClientStream::recv_push_promise` as well as the associated stream.
+pub(crate) fn take_unclaimed_push(
+  hb: &mut Http2Buffer,
+  parent_stream_id: U31,
+) -> crate::Result<Option<U31>> {
+  let mut rslt = None;
+  for _ in 0..hb.initial_push_streams_remote.len() {
+    let Some(elem) = hb.initial_push_streams_remote.pop_front() else {
+      break;
+    };
+    if rslt.is_none() && elem.parent_stream_id == parent_stream_id {
+      drop(hb.sorps.remove(&elem.stream_id));
+      rslt = Some(elem.stream_id);
+    } else {
+      hb.initial_push_streams_remote.push_back(elem)?;
+    }
+  }
+  Ok(rslt)
+}
+
 pub(crate) fn trim_frame_pad(cf: CommonFlags, data: &mut &[u8]) -> crate::Result<Option<u8>> {
   let mut pad_len = None;
   if cf.has_pad() {
@@ -413,6 +523,16 @@ pub(crate) fn trim_frame_pad(cf: CommonFlags, data: &mut &[u8]) -> crate::Result
   Ok(pad_len)
 }
 
+/// Allows streams that were waiting for more urgent streams to try sending their DATA frames
+/// again.
+pub(crate) fn wake_deferred_streams(scrps: &mut Scorp) {
+  for elem in scrps.values_mut() {
+    if mem::take(&mut elem.is_deferred) {
+      elem.waker.wake_by_ref();
+    }
+  }
+}
+
 pub(crate) async fn write_array<SW, TCX, const N: usize, const IS_CLIENT: bool>(
   array: [&[u8]; N],
   stream_writer: &mut TlsStreamWriter<SW, TCX, IS_CLIENT>,
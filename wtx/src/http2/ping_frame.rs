@@ -46,6 +46,10 @@ impl PingFrame {
     self.cf.has_ack()
   }
 
+  pub(crate) const fn payload(&self) -> [u8; 8] {
+    self.payload
+  }
+
   pub(crate) const fn set_ack(&mut self) {
     self.cf.set_ack();
   }
@@ -0,0 +1,64 @@
+use crate::{
+  http::{Priority, U31},
+  http2::{
+    Http2Error, Http2ErrorCode,
+    common_flags::CommonFlags,
+    frame_init::{FrameInit, FrameInitTy},
+    misc::protocol_err,
+  },
+};
+
+/// Changes the priority of a stream after the reception of its request.
+///
+/// <https://www.rfc-editor.org/rfc/rfc9218#section-7.1>
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct PriorityUpdateFrame {
+  prioritized_stream_id: U31,
+  priority: Priority,
+}
+
+impl PriorityUpdateFrame {
+  pub(crate) const fn new(prioritized_stream_id: U31, priority: Priority) -> Self {
+    Self { prioritized_stream_id, priority }
+  }
+
+  pub(crate) fn read(bytes: &[u8], fi: FrameInit) -> crate::Result<Self> {
+    if !fi.stream_id.is_zero() {
+      return Err(protocol_err(Http2Error::InvalidPriorityUpdateFrame));
+    }
+    let [b0, b1, b2, b3, rest @ ..] = bytes else {
+      return Err(crate::Error::Http2ErrorGoAway(
+        Http2ErrorCode::FrameSizeError,
+        Http2Error::InvalidPriorityUpdateFrame,
+      ));
+    };
+    let prioritized_stream_id = U31::from_u32(u32::from_be_bytes([*b0, *b1, *b2, *b3]));
+    if prioritized_stream_id.is_zero() {
+      return Err(protocol_err(Http2Error::InvalidPriorityUpdateFrame));
+    }
+    Ok(Self { prioritized_stream_id, priority: Priority::parse(rest) })
+  }
+
+  pub(crate) fn bytes<'buffer>(&self, buffer: &'buffer mut [u8; 19]) -> &'buffer [u8] {
+    let field_value = self.priority.field_value().as_bytes();
+    let data_len = 4u32.wrapping_add(u32::try_from(field_value.len()).unwrap_or_default());
+    let fi = FrameInit::new(CommonFlags::empty(), data_len, U31::ZERO, FrameInitTy::PriorityUpdate);
+    let mut idx: usize = 0;
+    for elem in [&fi.bytes()[..], &self.prioritized_stream_id.to_be_bytes(), field_value] {
+      let next_idx = idx.wrapping_add(elem.len());
+      if let Some(slice) = buffer.get_mut(idx..next_idx) {
+        slice.copy_from_slice(elem);
+      }
+      idx = next_idx;
+    }
+    buffer.get(..idx).unwrap_or_default()
+  }
+
+  pub(crate) const fn prioritized_stream_id(&self) -> U31 {
+    self.prioritized_stream_id
+  }
+
+  pub(crate) const fn priority(&self) -> Priority {
+    self.priority
+  }
+}
@@ -1,22 +1,27 @@
 use crate::{
-  collections::ArrayVectorCopy,
-  http::{HttpError, HttpRecvParams, MsgBufferString, StatusCode, U31},
+  collections::{ArrayVectorCopy, Deque},
+  http::{HttpError, HttpRecvParams, Method, MsgBufferString, Priority, StatusCode, U31},
   http2::{
     Http2Error, Http2ErrorCode, Scorp, Sovrp,
+    common_flags::CommonFlags,
     data_frame::DataFrame,
-    frame_init::FrameInit,
+    frame_init::{FrameInit, FrameInitTy},
     hpack_decoder::HpackDecoder,
     http_send_params::HttpSendParams,
+    initial_push_stream_remote::InitialPushStreamRemote,
     initial_server_stream_remote::InitialServerStreamRemote,
     misc::{
-      check_content_length, protocol_err, read_header_and_continuations,
-      server_header_stream_state, sorp_mut,
+      check_content_length, protocol_err, read_header_and_continuations, read_header_block,
+      server_header_stream_state, sorp_mut, wake_deferred_streams,
     },
+    priority_update_frame::PriorityUpdateFrame,
+    push_promise_frame::PushPromiseFrame,
     stream_receiver::StreamOverallRecvParams,
     stream_state::StreamState,
     window::{Windows, WindowsPair},
     window_update_frame::WindowUpdateFrame,
   },
+  misc::Usize,
   net::{BufStreamReader, StreamReader},
 };
 use core::task::Waker;
@@ -101,6 +106,8 @@ where
       .await?;
       elem.has_initial_header = true;
       elem.status_code = status_code;
+      // Tunnels await the initial headers of responses that don't finish the stream.
+      elem.waker.wake_by_ref();
       has_eos
     };
     if has_eos {
@@ -143,6 +150,7 @@ where
         has_initial_header: true,
         has_one_or_more_data_frames: false,
         is_stream_open: true,
+        priority: Priority::from_headers(&msg_buffer.headers),
         msg_buffer,
         status_code: StatusCode::Ok,
         stream_state,
@@ -177,6 +185,103 @@ where
     Ok(())
   }
 
+  pub(crate) async fn push_promise<const IS_CLIENT: bool>(
+    self,
+    initial_push_streams_remote: &mut Deque<InitialPushStreamRemote>,
+    last_push_stream_id: &mut U31,
+    sorp: &mut Sovrp,
+  ) -> crate::Result<()> {
+    if !IS_CLIENT || !self.hp.enable_push() {
+      return Err(protocol_err(Http2Error::PushPromiseIsUnsupported));
+    }
+    let ppf = PushPromiseFrame::read(self.nrb.current(), self.fi)?;
+    let promised_stream_id = ppf.promised_stream_id();
+    let is_parent_open = sorp.get(&self.fi.stream_id).is_some_and(|el| !el.stream_state.recv_eos());
+    if !is_parent_open
+      || promised_stream_id <= *last_push_stream_id
+      || !promised_stream_id.u32().is_multiple_of(2)
+    {
+      return Err(protocol_err(Http2Error::InvalidPushPromiseFrame));
+    }
+    let pushes_num = sorp.keys().filter(|el| el.u32().is_multiple_of(2)).count();
+    if pushes_num >= *Usize::from(self.hp.max_concurrent_pushes_num()) {
+      return Err(protocol_err(Http2Error::ExceedAmountOfPushedStreams));
+    }
+    *last_push_stream_id = promised_stream_id;
+    // The header block of a promised request is decoded like the header block of a `HEADERS`
+    // frame without padding or priority.
+    let mut cf = CommonFlags::empty();
+    if ppf.has_eoh() {
+      cf.set_eoh();
+    }
+    let fi = FrameInit::new(cf, self.fi.data_len, self.fi.stream_id, FrameInitTy::Headers);
+    let mut msg_buffer = MsgBufferString::default();
+    let (_, _, method) = read_header_block::<_, _, false, false>(
+      fi,
+      ppf.fragment(),
+      self.hp,
+      self.hpack_dec,
+      &mut msg_buffer,
+      self.nrb,
+      self.stream_reader,
+      |hf| hf.hsreqh().method.ok_or_else(|| HttpError::MissingRequestMethod.into()),
+    )
+    .await?;
+    // Promised requests must be safe and cacheable
+    if !matches!(method, Method::Get | Method::Head) {
+      return Err(protocol_err(Http2Error::InvalidPushPromiseFrame));
+    }
+    drop(sorp.insert(
+      promised_stream_id,
+      StreamOverallRecvParams {
+        body_len: 0,
+        content_length: None,
+        has_initial_header: false,
+        has_one_or_more_data_frames: false,
+        is_stream_open: true,
+        msg_buffer: MsgBufferString::default(),
+        priority: Priority::DEFAULT,
+        status_code: StatusCode::Ok,
+        stream_state: StreamState::HalfClosedLocal,
+        waker: Waker::noop().clone(),
+        windows: Windows::initial(self.hp, self.hps),
+      },
+    ));
+    initial_push_streams_remote.push_back(InitialPushStreamRemote {
+      method,
+      msg_buffer,
+      parent_stream_id: self.fi.stream_id,
+      stream_id: promised_stream_id,
+    })?;
+    if let Some(elem) = sorp.get(&self.fi.stream_id) {
+      elem.waker.wake_by_ref();
+    }
+    Ok(())
+  }
+
+  pub(crate) fn priority_update<const IS_CLIENT: bool>(
+    self,
+    scorp: &mut Scorp,
+    sovrp: &mut Sovrp,
+  ) -> crate::Result<()> {
+    if IS_CLIENT {
+      return Err(protocol_err(Http2Error::InvalidPriorityUpdateFrame));
+    }
+    let puf = PriorityUpdateFrame::read(self.nrb.current(), self.fi)?;
+    let stream_id = puf.prioritized_stream_id();
+    if stream_id.u32().is_multiple_of(2) {
+      return Err(protocol_err(Http2Error::InvalidPriorityUpdateFrame));
+    }
+    // Updates of closed or unknown streams are ignored
+    if let Some(elem) = scorp.get_mut(&stream_id) {
+      elem.priority = puf.priority();
+      wake_deferred_streams(scorp);
+    } else if let Some(elem) = sovrp.get_mut(&stream_id) {
+      elem.priority = puf.priority();
+    }
+    Ok(())
+  }
+
   pub(crate) fn window_update(self, scorp: &mut Scorp, sovrp: &mut Sovrp) -> crate::Result<()> {
     if let Some(elem) = scorp.get_mut(&self.fi.stream_id) {
       self.do_window_update(&mut elem.windows, &elem.waker)?;
@@ -0,0 +1,50 @@
+use crate::{
+  http::U31,
+  http2::{
+    Http2Error,
+    common_flags::CommonFlags,
+    frame_init::FrameInit,
+    misc::{protocol_err, trim_frame_pad},
+  },
+};
+use core::ops::Range;
+
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct PushPromiseFrame {
+  cf: CommonFlags,
+  // Location of the header block fragment in the frame payload.
+  fragment: Range<usize>,
+  promised_stream_id: U31,
+}
+
+impl PushPromiseFrame {
+  pub(crate) fn fragment(&self) -> Range<usize> {
+    self.fragment.clone()
+  }
+
+  pub(crate) const fn has_eoh(&self) -> bool {
+    self.cf.has_eoh()
+  }
+
+  pub(crate) const fn promised_stream_id(&self) -> U31 {
+    self.promised_stream_id
+  }
+
+  pub(crate) fn read(data: &[u8], mut fi: FrameInit) -> crate::Result<Self> {
+    if fi.stream_id.is_zero() {
+      return Err(protocol_err(Http2Error::InvalidPushPromiseFrame));
+    }
+    fi.cf.only_eoh_pad();
+    let mut local_data = data;
+    let pad_len = trim_frame_pad(fi.cf, &mut local_data)?;
+    let [b0, b1, b2, b3, rest @ ..] = local_data else {
+      return Err(protocol_err(Http2Error::InvalidPushPromiseFrame));
+    };
+    let start = if pad_len.is_some() { 5 } else { 4 };
+    Ok(Self {
+      cf: fi.cf,
+      fragment: start..start.wrapping_add(rest.len()),
+      promised_stream_id: U31::from_u32(u32::from_be_bytes([*b0, *b1, *b2, *b3])),
+    })
+  }
+}
@@ -83,11 +83,15 @@ where
           lock.parts_mut(),
           &inner.is_conn_open.connection_state,
           *stream_id,
-          |hdpm, _, stream_state, windows| {
+          |hdpm, priority, _, stream_state, windows| {
             drop(hdpm.hb.scrps.insert(
               *stream_id,
               StreamControlRecvParams {
+                data_turn: 0,
+                is_deferred: false,
+                is_sending_data: false,
                 is_stream_open: true,
+                priority,
                 stream_state,
                 waker: Waker::noop().clone(),
                 windows,
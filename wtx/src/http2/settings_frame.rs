@@ -12,6 +12,7 @@ use crate::{
 pub(crate) struct SettingsFrame {
   cf: CommonFlags,
   enable_connect_protocol: Option<bool>,
+  enable_push: Option<bool>,
   header_table_size: Option<u32>,
   initial_window_size: Option<U31>,
   len: u8,
@@ -29,6 +30,7 @@ impl SettingsFrame {
     Self {
       cf: CommonFlags::empty(),
       enable_connect_protocol: None,
+      enable_push: None,
       header_table_size: None,
       initial_window_size: None,
       len: 0,
@@ -40,6 +42,7 @@ impl SettingsFrame {
   pub(crate) fn from_hrp(hrp: HttpRecvParams) -> Self {
     let mut settings_frame = SettingsFrame::empty();
     settings_frame.set_enable_connect_protocol(Some(hrp.enable_connect_protocol()));
+    settings_frame.set_enable_push(Some(hrp.enable_push()));
     settings_frame.set_header_table_size(Some(hrp.max_hpack_len().0));
     settings_frame.set_initial_window_size(Some(U31::from_u32(hrp.initial_window_len())));
     settings_frame.set_max_concurrent_streams(Some(hrp.max_concurrent_streams_num()));
@@ -48,7 +51,7 @@ impl SettingsFrame {
     settings_frame
   }
 
-  pub(crate) fn bytes<'buffer>(&self, buffer: &'buffer mut [u8; 45]) -> &'buffer [u8] {
+  pub(crate) fn bytes<'buffer>(&self, buffer: &'buffer mut [u8; 51]) -> &'buffer [u8] {
     macro_rules! copy_bytes {
       ($buffer:expr, $bytes:expr, $idx:expr) => {{
         let next_idx = $idx.wrapping_add(6);
@@ -91,6 +94,7 @@ impl SettingsFrame {
     let Self {
       cf: _,
       enable_connect_protocol,
+      enable_push,
       header_table_size,
       initial_window_size,
       len: _,
@@ -100,6 +104,7 @@ impl SettingsFrame {
     } = self;
     let mut idx: usize = 9;
     copy_bytes!(buffer, header_table_size.map(|el| bytes(1, el)), idx);
+    copy_bytes!(buffer, enable_push.map(|el| bytes(2, u32::from(el))), idx);
     copy_bytes!(buffer, max_concurrent_streams.map(|el| bytes(3, el)), idx);
     copy_bytes!(buffer, initial_window_size.map(|el| bytes(4, el.u32())), idx);
     copy_bytes!(buffer, max_frame_size.map(|el| bytes(5, el)), idx);
@@ -164,6 +169,7 @@ impl SettingsFrame {
     let Self {
       cf: _,
       enable_connect_protocol,
+      enable_push: _,
       header_table_size,
       initial_window_size,
       len,
@@ -231,6 +237,11 @@ impl SettingsFrame {
     self.enable_connect_protocol = elem;
   }
 
+  pub(crate) fn set_enable_push(&mut self, elem: Option<bool>) {
+    Self::update_len(&mut self.len, self.enable_push, elem);
+    self.enable_push = elem;
+  }
+
   pub(crate) fn set_header_table_size(&mut self, elem: Option<u32>) {
     Self::update_len(&mut self.len, self.header_table_size, elem);
     self.header_table_size = elem;
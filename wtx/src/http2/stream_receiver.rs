@@ -1,5 +1,5 @@
 use crate::{
-  http::{MsgBufferString, StatusCode},
+  http::{MsgBufferString, Priority, StatusCode},
   http2::{stream_state::StreamState, window::Windows},
 };
 use core::task::Waker;
@@ -10,7 +10,15 @@ use core::task::Waker;
 /// Used only by unidirectional streams when they are sending data or when the state is closed.
 #[derive(Debug)]
 pub(crate) struct StreamControlRecvParams {
+  /// Ordinal of the last sent DATA frame among all the DATA frames of the connection. Streams of
+  /// the same urgency that waited longer go first.
+  pub(crate) data_turn: u64,
+  /// Waiting for the DATA frames of more urgent streams or of streams of the same urgency.
+  pub(crate) is_deferred: bool,
+  /// DATA frames are being sent, including while waiting for a `WINDOW_UPDATE`.
+  pub(crate) is_sending_data: bool,
   pub(crate) is_stream_open: bool,
+  pub(crate) priority: Priority,
   pub(crate) stream_state: StreamState,
   pub(crate) waker: Waker,
   pub(crate) windows: Windows,
@@ -28,6 +36,7 @@ pub(crate) struct StreamOverallRecvParams {
   pub(crate) has_one_or_more_data_frames: bool,
   pub(crate) is_stream_open: bool,
   pub(crate) msg_buffer: MsgBufferString,
+  pub(crate) priority: Priority,
   pub(crate) status_code: StatusCode,
   pub(crate) stream_state: StreamState,
   pub(crate) windows: Windows,
@@ -1,3 +1,11 @@
 mod connections;
+mod frame_rates;
+#[cfg(feature = "http2-h2c")]
+mod h2c;
 #[cfg(all(feature = "_integration-tests", feature = "serde_json"))]
 mod hpack;
+mod keepalive;
+mod priority;
+mod push;
+#[cfg(feature = "web-socket")]
+mod web_socket;
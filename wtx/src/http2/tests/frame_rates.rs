@@ -0,0 +1,168 @@
+use crate::{
+  executor::StdRuntime,
+  futures::Sleep,
+  http::HttpRecvParams,
+  http2::{Http2, Http2Buffer, PREFACE},
+  net::Stream as _,
+  rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+  tests::_uri,
+  tls::{TlsAcceptor, TlsConfig},
+};
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::{
+  io::{Read, Write},
+  net::{TcpListener, TcpStream},
+};
+
+const CANCEL: [u8; 4] = 8u32.to_be_bytes();
+const CONTINUATION: u8 = 9;
+const DATA: u8 = 0;
+const ENHANCE_YOUR_CALM: Option<u32> = Some(11);
+const END_HEADERS: u8 = 4;
+const END_STREAM: u8 = 1;
+const GET: &[u8] = b"\x82\x86\x84\x41\x09localhost";
+const HEADERS: u8 = 1;
+const PING: u8 = 6;
+const PROTOCOL_ERROR: Option<u32> = Some(1);
+const PUSH_PROMISE: u8 = 5;
+const RST_STREAM: u8 = 3;
+const SETTINGS: u8 = 4;
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn continuation_flood() {
+  let hrp = HttpRecvParams::with_optioned_params().set_max_header_block_len(1024);
+  let mut frames = frame(HEADERS, 0, 1, &[0; 512]);
+  frames.extend(frame(CONTINUATION, 0, 1, &[0; 256]));
+  assert_eq!(go_away_code(hrp, &push_promise(frames.clone())), PROTOCOL_ERROR);
+  frames.extend(frame(CONTINUATION, 0, 1, &[0; 512]));
+  assert_eq!(go_away_code(hrp, &frames), ENHANCE_YOUR_CALM);
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn empty_data_flood() {
+  let hrp = HttpRecvParams::with_optioned_params().set_max_empty_data_frames_rate((4, minute()));
+  let mut frames = frame(HEADERS, END_HEADERS, 1, GET);
+  for _ in 0..4 {
+    frames.extend(frame(DATA, 0, 1, &[]));
+  }
+  frames.extend(frame(DATA, END_STREAM, 1, &[]));
+  assert_eq!(go_away_code(hrp, &push_promise(frames.clone())), PROTOCOL_ERROR);
+  frames.extend(frame(DATA, 0, 1, &[]));
+  assert_eq!(go_away_code(hrp, &frames), ENHANCE_YOUR_CALM);
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn ping_flood() {
+  let hrp = HttpRecvParams::with_optioned_params().set_max_ping_frames_rate((4, minute()));
+  let mut frames = Vec::new();
+  for _ in 0..4 {
+    frames.extend(frame(PING, 0, 0, &[0; 8]));
+  }
+  frames.extend(frame(PING, 1, 0, &[0; 8]));
+  assert_eq!(go_away_code(hrp, &push_promise(frames.clone())), PROTOCOL_ERROR);
+  frames.extend(frame(PING, 0, 0, &[0; 8]));
+  assert_eq!(go_away_code(hrp, &frames), ENHANCE_YOUR_CALM);
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn rapid_reset() {
+  let hrp = HttpRecvParams::with_optioned_params().set_max_reset_frames_rate((4, minute()));
+  let mut frames = Vec::new();
+  for stream_id in [1, 3, 5, 7] {
+    frames.extend(frame(HEADERS, END_HEADERS, stream_id, GET));
+    frames.extend(frame(RST_STREAM, 0, stream_id, &CANCEL));
+  }
+  assert_eq!(go_away_code(hrp, &push_promise(frames.clone())), PROTOCOL_ERROR);
+  frames.extend(frame(HEADERS, END_HEADERS, 9, GET));
+  frames.extend(frame(RST_STREAM, 0, 9, &CANCEL));
+  assert_eq!(go_away_code(hrp, &frames), ENHANCE_YOUR_CALM);
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn settings_flood() {
+  let hrp = HttpRecvParams::with_optioned_params().set_max_settings_frames_rate((4, minute()));
+  // The connection preface already contains a SETTINGS frame
+  let mut frames = Vec::new();
+  for _ in 0..3 {
+    frames.extend(frame(SETTINGS, 0, 0, &[]));
+  }
+  assert_eq!(go_away_code(hrp, &push_promise(frames.clone())), PROTOCOL_ERROR);
+  frames.extend(frame(SETTINGS, 0, 0, &[]));
+  assert_eq!(go_away_code(hrp, &frames), ENHANCE_YOUR_CALM);
+}
+
+fn frame(ty: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+  let [_, len0, len1, len2] = u32::try_from(payload.len()).unwrap().to_be_bytes();
+  let mut rslt = Vec::from([len0, len1, len2, ty, flags]);
+  rslt.extend(stream_id.to_be_bytes());
+  rslt.extend(payload);
+  rslt
+}
+
+// Sends `frames` to a server and returns the error code of the received GOAWAY frame.
+fn go_away_code(hrp: HttpRecvParams, frames: &[u8]) -> Option<u32> {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let runtime_fut = runtime;
+  let _server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let tls_stream =
+        TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+          .accept()
+          .await
+          .unwrap()
+          .tls_stream;
+      let (frame_reader, http2) = Http2::accept(
+        Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+        hrp,
+        tls_stream.into_split().unwrap(),
+      )
+      .await
+      .unwrap();
+      let _jh = runtime_fut.spawn(frame_reader);
+      while !http2.connection_state().is_closed() {
+        Sleep::new(Duration::from_millis(10)).unwrap().await.unwrap();
+      }
+    })
+    .unwrap();
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).unwrap();
+  stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+  let _rslt = stream.write_all(&[&PREFACE[..], &frame(SETTINGS, 0, 0, &[]), frames].concat());
+  let mut header = [0; 9];
+  loop {
+    stream.read_exact(&mut header).ok()?;
+    let [len0, len1, len2, ty, ..] = header;
+    let len = u32::from_be_bytes([0, len0, len1, len2]);
+    let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+    stream.read_exact(&mut payload).ok()?;
+    if ty == 7 {
+      let [_, _, _, _, c0, c1, c2, c3, ..] = payload.as_slice() else {
+        return None;
+      };
+      return Some(u32::from_be_bytes([*c0, *c1, *c2, *c3]));
+    }
+  }
+}
+
+const fn minute() -> Duration {
+  Duration::from_secs(60)
+}
+
+// Appends an unsupported frame that always triggers a protocol error.
+fn push_promise(mut frames: Vec<u8>) -> Vec<u8> {
+  frames.extend(frame(PUSH_PROMISE, END_HEADERS, 1, &[0, 0, 0, 2]));
+  frames
+}
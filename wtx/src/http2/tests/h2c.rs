@@ -0,0 +1,191 @@
+use crate::{
+  collections::Vector,
+  executor::StdRuntime,
+  http::{HttpRecvParams, Method, MsgBufferString, ReqBuilder, StatusCode},
+  http2::{Http2, Http2Buffer, Http2Error, Http2ErrorCode, Http2RecvStatus},
+  net::{Stream as _, UriString},
+  rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+  tests::_uri,
+  tls::{PlaintextCtx, TlsAcceptor, TlsConfig, TlsConnectorBuilder},
+};
+use core::time::Duration;
+use std::{
+  io::{Read, Write},
+  net::{TcpListener, TcpStream},
+};
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn invalid_upgrade() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).unwrap();
+  stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+  stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: h2c\r\n\r\n").unwrap();
+  let rslt = runtime.block_on(accept(HttpRecvParams::with_optioned_params(), listener));
+  assert!(matches!(rslt, Err(crate::Error::Http2Error(Http2Error::InvalidH2cUpgradeRequest))));
+  let mut res = [0; 12];
+  stream.read_exact(&mut res).unwrap();
+  assert_eq!(&res, b"HTTP/1.1 400");
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn large_upgrade() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).unwrap();
+  stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+  stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nUpgrade: h2c\r\nFoo: ").unwrap();
+  stream.write_all(&[b'a'; 4096]).unwrap();
+  let hrp = HttpRecvParams::with_optioned_params().set_max_headers_len(1024);
+  let rslt = runtime.block_on(accept(hrp, listener));
+  assert!(matches!(rslt, Err(crate::Error::Http2Error(Http2Error::LargeH2cUpgradeRequest))));
+  let mut res = [0; 12];
+  stream.read_exact(&mut res).unwrap();
+  assert_eq!(&res, b"HTTP/1.1 431");
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn prior_knowledge() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  server(&runtime, &uri, false);
+  runtime.block_on(async {
+    let tls_stream = connector(&uri).await;
+    let (frame_reader, http2) = Http2::connect(
+      Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+      HttpRecvParams::with_optioned_params(),
+      tls_stream.into_split().unwrap(),
+    )
+    .await
+    .unwrap();
+    let _jh = runtime.spawn(frame_reader).unwrap();
+    assert_eq!(req(&http2, &uri).await, b"/");
+    http2.send_go_away(Http2ErrorCode::NoError).await;
+  });
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn refused_upgrade() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let _jh = std::thread::spawn(move || {
+    let (mut stream, _) = listener.accept().unwrap();
+    let mut buffer = [0; 256];
+    let _len = stream.read(&mut buffer).unwrap();
+    stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+  });
+  runtime.block_on(async {
+    let tls_stream = connector(&uri).await;
+    let rslt = Http2::connect_h2c(
+      Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+      HttpRecvParams::with_optioned_params(),
+      tls_stream.into_split().unwrap(),
+      &uri,
+    )
+    .await;
+    assert!(matches!(
+      rslt,
+      Err(crate::Error::Http2Error(Http2Error::UnexpectedH2cUpgradeStatus(Some(200))))
+    ));
+  });
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn upgrade() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  server(&runtime, &uri, true);
+  runtime.block_on(async {
+    let tls_stream = connector(&uri).await;
+    let (frame_reader, http2, mut stream) = Http2::connect_h2c(
+      Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+      HttpRecvParams::with_optioned_params(),
+      tls_stream.into_split().unwrap(),
+      &uri,
+    )
+    .await
+    .unwrap();
+    let _jh = runtime.spawn(frame_reader).unwrap();
+    let (status, res_buffer) = stream.recv_res().await.unwrap();
+    assert!(matches!(status, Http2RecvStatus::Eos(StatusCode::Ok)));
+    assert_eq!(res_buffer.body.as_slice(), b"/");
+    assert_eq!(req(&http2, &uri).await, b"/");
+    http2.send_go_away(Http2ErrorCode::NoError).await;
+  });
+}
+
+async fn accept(
+  hrp: HttpRecvParams,
+  listener: TcpListener,
+) -> crate::Result<Http2<TcpStream, PlaintextCtx, false>> {
+  let (stream, _) = listener.accept().unwrap();
+  let tls_stream =
+    TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+      .accept()
+      .await
+      .unwrap()
+      .tls_stream;
+  let (frame_reader, http2) = Http2::accept_h2c(
+    Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+    hrp,
+    tls_stream.into_split().unwrap(),
+  )
+  .await?;
+  let _jh = StdRuntime::new().spawn(frame_reader).unwrap();
+  Ok(http2)
+}
+
+async fn connector(uri: &UriString) -> crate::tls::TlsStream<TcpStream, PlaintextCtx, true> {
+  TlsConnectorBuilder::std(uri)
+    .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+    .await
+    .unwrap()
+    .connect()
+    .await
+    .unwrap()
+    .tls_stream
+}
+
+// Sends a request through a new stream and returns the body of the response.
+async fn req(http2: &Http2<TcpStream, PlaintextCtx, true>, uri: &UriString) -> Vector<u8> {
+  let mut stream = http2.stream().await.unwrap();
+  let msg_buffer = MsgBufferString::default();
+  let rb = ReqBuilder::get((msg_buffer.body.as_ref(), &msg_buffer.headers, uri.to_ref()));
+  let _ = stream.send_req(&mut Vector::new(), rb.into_request()).await.unwrap();
+  let (status, res_buffer) = stream.recv_res().await.unwrap();
+  assert!(matches!(status, Http2RecvStatus::Eos(StatusCode::Ok)));
+  res_buffer.body
+}
+
+// Responds each request with its path. Upgrades are also followed by a regular request.
+fn server(runtime: &StdRuntime, uri: &UriString, is_upgrade: bool) {
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let _server_jh = runtime
+    .spawn(async move {
+      let http2 = accept(HttpRecvParams::with_optioned_params(), listener).await.unwrap();
+      for _ in 0..if is_upgrade { 2 } else { 1 } {
+        let (mut stream, _) = http2.stream(|_, _| {}).await.unwrap().unwrap();
+        let (_, mut msg_buffer) = stream.recv_req().await.unwrap();
+        assert_eq!(stream.method(), Method::Get);
+        let path = msg_buffer.uri.path().as_bytes();
+        msg_buffer.body.extend_from_copyable_slice(path).unwrap();
+        msg_buffer.headers.clear();
+        let res = msg_buffer.as_response(StatusCode::Ok);
+        let _ = stream.send_res(&mut Vector::new(), res).await.unwrap();
+      }
+    })
+    .unwrap();
+}
@@ -0,0 +1,154 @@
+use crate::http2::keepalive::Keepalive;
+#[cfg(feature = "tokio")]
+use {
+  crate::{http::HttpRecvParams, http2::Http2ErrorCode},
+  alloc::vec::Vec,
+  core::time::Duration,
+};
+
+#[test]
+fn outstanding_ping() {
+  let mut keepalive = Keepalive::new();
+  let first = keepalive.send_ping().unwrap();
+  assert!(!first.has_ack());
+  assert!(keepalive.send_ping().is_none());
+  keepalive.recv_ack([9; 8]);
+  assert!(keepalive.send_ping().is_none());
+  assert_eq!(keepalive.rtt(), None);
+  keepalive.recv_ack(first.payload());
+  assert!(keepalive.rtt().is_some());
+  let second = keepalive.send_ping().unwrap();
+  assert_ne!(first.payload(), second.payload());
+}
+
+#[test]
+fn unsolicited_ack() {
+  let mut keepalive = Keepalive::new();
+  keepalive.recv_ack([0; 8]);
+  assert_eq!(keepalive.rtt(), None);
+  assert!(keepalive.send_ping().is_some());
+}
+
+// FIXME(MIRI): socket support
+#[cfg(feature = "tokio")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn idle_connections_are_closed() {
+  let frames =
+    connection(HttpRecvParams::with_optioned_params().set_idle_timeout(Some(ms(50))), false);
+  assert!(frames.iter().all(|(ty, ..)| *ty != 6));
+  assert_eq!(go_away_code(&frames), Some(0));
+}
+
+// FIXME(MIRI): socket support
+#[cfg(feature = "tokio")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn pings_are_periodically_sent() {
+  let frames =
+    connection(HttpRecvParams::with_optioned_params().set_keepalive_interval(Some(ms(20))), true);
+  let pings: Vec<_> = frames.iter().filter(|(ty, ..)| *ty == 6).collect();
+  assert_eq!(pings.len(), 2);
+  assert!(pings.iter().all(|(_, flags, _)| *flags == 0));
+  assert_ne!(pings[0].2, pings[1].2);
+}
+
+// FIXME(MIRI): socket support
+#[cfg(feature = "tokio")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn unacknowledged_pings_close_the_connection() {
+  let hrp = HttpRecvParams::with_optioned_params()
+    .set_keepalive_interval(Some(ms(20)))
+    .set_keepalive_timeout(ms(50));
+  let frames = connection(hrp, false);
+  assert!(frames.iter().any(|(ty, ..)| *ty == 6));
+  assert_eq!(go_away_code(&frames), Some(u32::from(Http2ErrorCode::InternalError)));
+}
+
+// Connects a client to a raw peer that records every received frame until a GOAWAY shows up or,
+// when `ack_pings` is set, until two PINGs were acknowledged.
+#[cfg(feature = "tokio")]
+fn connection(hrp: HttpRecvParams, ack_pings: bool) -> Vec<(u8, u8, Vec<u8>)> {
+  use crate::{
+    executor::Runtime,
+    http2::{Http2, Http2Buffer, PREFACE},
+    net::Stream as _,
+    rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+    tests::_uri,
+    tls::{TlsConfig, TlsConnectorBuilder},
+  };
+  use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    thread,
+  };
+
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let peer_jh = thread::spawn(move || {
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut preface = [0; 24];
+    stream.read_exact(&mut preface).unwrap();
+    assert_eq!(preface, PREFACE);
+    let mut frames = Vec::new();
+    let mut header = [0; 9];
+    while stream.read_exact(&mut header).is_ok() {
+      let [len0, len1, len2, ty, flags, ..] = header;
+      let len = u32::from_be_bytes([0, len0, len1, len2]);
+      let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+      stream.read_exact(&mut payload).unwrap();
+      if ack_pings && ty == 6 && flags == 0 {
+        let ack = [&[0, 0, 8, 6, 1, 0, 0, 0, 0][..], &payload].concat();
+        stream.write_all(&ack).unwrap();
+      }
+      frames.push((ty, flags, payload));
+      let pings = frames.iter().filter(|(local_ty, ..)| *local_ty == 6).count();
+      if ty == 7 || (ack_pings && pings >= 2) {
+        break;
+      }
+    }
+    frames
+  });
+
+  let lr = <tokio::runtime::LocalRuntime as Runtime>::new().unwrap();
+  lr.block_on(async {
+    let tls_stream = TlsConnectorBuilder::tokio(&uri)
+      .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+      .await
+      .unwrap()
+      .connect()
+      .await
+      .unwrap()
+      .tls_stream;
+    let (frame_reader, http2) = Http2::connect(
+      Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+      hrp,
+      tls_stream.into_split().unwrap(),
+    )
+    .await
+    .unwrap();
+    frame_reader.await;
+    if ack_pings {
+      assert!(http2.rtt().await.is_some());
+    } else {
+      assert!(http2.connection_state().is_closed());
+    }
+  });
+  peer_jh.join().unwrap()
+}
+
+#[cfg(feature = "tokio")]
+fn go_away_code(frames: &[(u8, u8, Vec<u8>)]) -> Option<u32> {
+  let (_, _, payload) = frames.iter().find(|(ty, ..)| *ty == 7)?;
+  let [_, _, _, _, b0, b1, b2, b3, ..] = *payload.as_slice() else {
+    return None;
+  };
+  Some(u32::from_be_bytes([b0, b1, b2, b3]))
+}
+
+#[cfg(feature = "tokio")]
+const fn ms(value: u64) -> Duration {
+  Duration::from_millis(value)
+}
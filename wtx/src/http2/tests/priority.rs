@@ -0,0 +1,268 @@
+use crate::{
+  collections::Vector,
+  executor::StdRuntime,
+  futures::Sleep,
+  http::{HttpRecvParams, Priority, StatusCode, U31},
+  http2::{
+    Http2, Http2Buffer, PREFACE,
+    common_flags::CommonFlags,
+    frame_init::{FrameInit, FrameInitTy},
+    priority_update_frame::PriorityUpdateFrame,
+  },
+  net::{Stream as _, UriString},
+  rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+  tests::_uri,
+  tls::{TlsAcceptor, TlsConfig},
+};
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::{
+  io::{Read, Write},
+  net::{TcpListener, TcpStream},
+};
+
+const BODY_LEN: usize = 1024 * 1024;
+const DATA: u8 = 0;
+const END_HEADERS: u8 = 4;
+const END_STREAM: u8 = 1;
+const GET: &[u8] = b"\x82\x86\x84\x41\x09localhost";
+const HEADERS: u8 = 1;
+const PRIORITY_UPDATE: u8 = 16;
+const PROTOCOL_ERROR: Option<u32> = Some(1);
+const SETTINGS: u8 = 4;
+const WINDOW_UPDATE: u8 = 8;
+
+#[test]
+fn encodes_and_decodes_frame() {
+  let buffer = &mut [0; 19];
+  let frame = PriorityUpdateFrame::new(U31::from_u32(5), Priority::new(true, 1));
+  let bytes = frame.bytes(buffer);
+  let [header @ .., _, _, _, _, _, _, _, _, _, _] = bytes else {
+    panic!();
+  };
+  let (Some(fi), 10) = FrameInit::from_array(header.try_into().unwrap()) else {
+    panic!();
+  };
+  assert_eq!(fi.ty, FrameInitTy::PriorityUpdate);
+  assert_eq!(PriorityUpdateFrame::read(bytes.get(9..).unwrap(), fi).unwrap(), frame);
+  let fi = FrameInit::new(CommonFlags::empty(), 3, U31::ZERO, FrameInitTy::PriorityUpdate);
+  assert!(PriorityUpdateFrame::read(&[0, 0, 5], fi).is_err());
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn invalid_priority_update() {
+  let payload = [&5u32.to_be_bytes()[..], b"u=0"].concat();
+  assert_eq!(go_away_code(&frame(PRIORITY_UPDATE, 0, 1, &payload)), PROTOCOL_ERROR);
+  let payload = [&0u32.to_be_bytes()[..], b"u=0"].concat();
+  assert_eq!(go_away_code(&frame(PRIORITY_UPDATE, 0, 0, &payload)), PROTOCOL_ERROR);
+  let payload = [&2u32.to_be_bytes()[..], b"u=0"].concat();
+  assert_eq!(go_away_code(&frame(PRIORITY_UPDATE, 0, 0, &payload)), PROTOCOL_ERROR);
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn urgent_response_first() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  server(&runtime, &uri);
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).unwrap();
+  stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+  stream
+    .write_all(
+      &[
+        &PREFACE[..],
+        &frame(SETTINGS, 0, 0, &[]),
+        &frame(HEADERS, END_HEADERS | END_STREAM, 1, &[GET, b"\x00\x08priority\x03u=7"].concat()),
+        &frame(HEADERS, END_HEADERS | END_STREAM, 3, &[GET, b"\x00\x08priority\x03u=0"].concat()),
+      ]
+      .concat(),
+    )
+    .unwrap();
+  let increment = (1u32 << 30).to_be_bytes();
+  let mut data_len: u32 = 0;
+  let mut ends = Vec::new();
+  let mut headers_num: u8 = 0;
+  let mut header = [0; 9];
+  let mut is_low_resumed = false;
+  let mut is_urgent_resumed = false;
+  while ends.len() < 2 {
+    stream.read_exact(&mut header).unwrap();
+    let [len0, len1, len2, ty, flags, s0, s1, s2, s3] = header;
+    let len = u32::from_be_bytes([0, len0, len1, len2]);
+    let stream_id = u32::from_be_bytes([s0, s1, s2, s3]);
+    let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+    stream.read_exact(&mut payload).unwrap();
+    if ty == HEADERS {
+      headers_num = headers_num.wrapping_add(1);
+    } else if ty == DATA {
+      if is_low_resumed && stream_id == 1 {
+        // The less urgent response must wait until the urgent response is finished
+        assert_eq!(ends, [3]);
+      }
+      if flags & END_STREAM == END_STREAM {
+        ends.push(stream_id);
+      }
+      data_len = data_len.wrapping_add(len);
+    } else {
+      continue;
+    }
+    // Both responses are only resumed after their initial windows are exhausted
+    if !is_urgent_resumed && headers_num == 2 && data_len == 2 * 65_535 {
+      is_urgent_resumed = true;
+      let frames = [frame(WINDOW_UPDATE, 0, 0, &increment), frame(WINDOW_UPDATE, 0, 3, &increment)];
+      stream.write_all(&frames.concat()).unwrap();
+    } else if is_urgent_resumed && !is_low_resumed && ty == DATA && ends.is_empty() {
+      is_low_resumed = true;
+      stream.write_all(&frame(WINDOW_UPDATE, 0, 1, &increment)).unwrap();
+    }
+  }
+  assert_eq!(ends, [3, 1]);
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn same_urgency_responses_alternate() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  server(&runtime, &uri);
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).unwrap();
+  stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+  stream
+    .write_all(
+      &[
+        &PREFACE[..],
+        &frame(SETTINGS, 0, 0, &[]),
+        &frame(HEADERS, END_HEADERS | END_STREAM, 1, GET),
+        &frame(HEADERS, END_HEADERS | END_STREAM, 3, GET),
+      ]
+      .concat(),
+    )
+    .unwrap();
+  let increment = (1u32 << 30).to_be_bytes();
+  let mut data_lens: [u32; 2] = [0, 0];
+  let mut headers_num: u8 = 0;
+  let mut header = [0; 9];
+  let mut is_resumed = false;
+  loop {
+    stream.read_exact(&mut header).unwrap();
+    let [len0, len1, len2, ty, flags, s0, s1, s2, s3] = header;
+    let len = u32::from_be_bytes([0, len0, len1, len2]);
+    let stream_id = u32::from_be_bytes([s0, s1, s2, s3]);
+    let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+    stream.read_exact(&mut payload).unwrap();
+    if ty == HEADERS {
+      headers_num = headers_num.wrapping_add(1);
+    } else if ty == DATA {
+      let [first, second] = &mut data_lens;
+      let (this, other) = if stream_id == 1 { (first, second) } else { (second, first) };
+      *this = this.wrapping_add(len);
+      if flags & END_STREAM == END_STREAM {
+        // Both responses took turns, so the other one is also almost finished
+        assert!(*other > u32::try_from(BODY_LEN / 2).unwrap());
+        break;
+      }
+    } else {
+      continue;
+    }
+    // Both responses are resumed at the same time by a new initial window after their initial
+    // windows are exhausted
+    if !is_resumed && headers_num == 2 && data_lens == [65_535, 65_535] {
+      is_resumed = true;
+      let initial_window_size = [&[0, 4][..], &increment].concat();
+      let frames =
+        [frame(WINDOW_UPDATE, 0, 0, &increment), frame(SETTINGS, 0, 0, &initial_window_size)];
+      stream.write_all(&frames.concat()).unwrap();
+    }
+  }
+  assert!(is_resumed);
+}
+
+async fn accept(listener: TcpListener) -> Http2<TcpStream, crate::tls::PlaintextCtx, false> {
+  let (stream, _) = listener.accept().unwrap();
+  let tls_stream =
+    TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+      .accept()
+      .await
+      .unwrap()
+      .tls_stream;
+  let (frame_reader, http2) = Http2::accept(
+    Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+    HttpRecvParams::with_optioned_params(),
+    tls_stream.into_split().unwrap(),
+  )
+  .await
+  .unwrap();
+  let _jh = StdRuntime::new().spawn(frame_reader).unwrap();
+  http2
+}
+
+fn frame(ty: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+  let [_, len0, len1, len2] = u32::try_from(payload.len()).unwrap().to_be_bytes();
+  let mut rslt = Vec::from([len0, len1, len2, ty, flags]);
+  rslt.extend(stream_id.to_be_bytes());
+  rslt.extend(payload);
+  rslt
+}
+
+// Sends `frames` to a server and returns the error code of the received GOAWAY frame.
+fn go_away_code(frames: &[u8]) -> Option<u32> {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let _server_jh = runtime
+    .spawn(async move {
+      let http2 = accept(listener).await;
+      while !http2.connection_state().is_closed() {
+        Sleep::new(Duration::from_millis(10)).unwrap().await.unwrap();
+      }
+    })
+    .unwrap();
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).unwrap();
+  stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+  let _rslt = stream.write_all(&[&PREFACE[..], &frame(SETTINGS, 0, 0, &[]), frames].concat());
+  let mut header = [0; 9];
+  loop {
+    stream.read_exact(&mut header).ok()?;
+    let [len0, len1, len2, ty, ..] = header;
+    let len = u32::from_be_bytes([0, len0, len1, len2]);
+    let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+    stream.read_exact(&mut payload).ok()?;
+    if ty == 7 {
+      let [_, _, _, _, c0, c1, c2, c3, ..] = payload.as_slice() else {
+        return None;
+      };
+      return Some(u32::from_be_bytes([*c0, *c1, *c2, *c3]));
+    }
+  }
+}
+
+// Concurrently responds each one of the two received requests with a large body.
+fn server(runtime: &StdRuntime, uri: &UriString) {
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let _server_jh = runtime
+    .spawn(async move {
+      let http2 = accept(listener).await;
+      let mut jhs = Vec::new();
+      for _ in 0..2 {
+        let (mut stream, _) = http2.stream(|_, _| {}).await.unwrap().unwrap();
+        jhs.push(
+          StdRuntime::new()
+            .spawn(async move {
+              let (_, mut msg_buffer) = stream.recv_req().await.unwrap();
+              msg_buffer.body.extend_from_copyable_slice(&alloc::vec![0; BODY_LEN]).unwrap();
+              let res = msg_buffer.as_response(StatusCode::Ok);
+              let _ = stream.send_res(&mut Vector::new(), res).await.unwrap();
+            })
+            .unwrap(),
+        );
+      }
+      for jh in jhs {
+        jh.await;
+      }
+    })
+    .unwrap();
+}
@@ -0,0 +1,229 @@
+use crate::{
+  collections::Vector,
+  executor::StdRuntime,
+  futures::Sleep,
+  http::{HttpRecvParams, Method, MsgBufferString, ReqBuilder, StatusCode},
+  http2::{Http2, Http2Buffer, Http2ErrorCode, Http2RecvStatus, PREFACE},
+  net::{Stream as _, UriString},
+  rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+  tests::_uri,
+  tls::{PlaintextCtx, TlsConfig, TlsConnectorBuilder},
+};
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::{
+  io::{Read, Write},
+  net::{TcpListener, TcpStream},
+  thread::JoinHandle,
+};
+
+const END_HEADERS: u8 = 4;
+const END_STREAM: u8 = 1;
+const GET: &[u8] = b"\x82\x86\x84\x41\x09localhost";
+const HEADERS: u8 = 1;
+const PROTOCOL_ERROR: Option<u32> = Some(1);
+const PUSH_PROMISE: u8 = 5;
+const SETTINGS: u8 = 4;
+const STATUS_200: &[u8] = b"\x88";
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn disabled_push() {
+  let frames = push_promise(1, 2);
+  assert_eq!(go_away_code(HttpRecvParams::with_optioned_params(), frames), (false, PROTOCOL_ERROR));
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn exceeded_pushes() {
+  let hrp = HttpRecvParams::with_optioned_params().set_enable_push(true);
+  let mut frames = push_promise(1, 2);
+  frames.extend(push_promise(1, 4));
+  let local_hrp = hrp.set_max_concurrent_pushes_num(2);
+  assert_eq!(go_away_code(local_hrp, [frames.clone(), response(1)].concat()), (true, Some(0)));
+  let local_hrp = hrp.set_max_concurrent_pushes_num(1);
+  assert_eq!(go_away_code(local_hrp, frames), (true, PROTOCOL_ERROR));
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn invalid_promised_stream_id() {
+  let hrp = HttpRecvParams::with_optioned_params().set_enable_push(true);
+  assert_eq!(go_away_code(hrp, push_promise(1, 3)), (true, PROTOCOL_ERROR));
+  let mut frames = push_promise(1, 4);
+  frames.extend(push_promise(1, 2));
+  assert_eq!(go_away_code(hrp, frames), (true, PROTOCOL_ERROR));
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn pushed_response() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let frames = [push_promise(1, 2), response(1), response(2)].concat();
+  let server_jh = server(&uri, frames);
+  runtime.block_on(async {
+    let hrp = HttpRecvParams::with_optioned_params().set_enable_push(true);
+    let http2 = client(hrp, &runtime, &uri).await;
+    let mut stream = http2.stream().await.unwrap();
+    let msg_buffer = MsgBufferString::default();
+    let rb = ReqBuilder::get((msg_buffer.body.as_ref(), &msg_buffer.headers, uri.to_ref()));
+    let _ = stream.send_req(&mut Vector::new(), rb.into_request()).await.unwrap();
+    let (req, mut push_stream) = stream.recv_push_promise().await.unwrap().unwrap();
+    assert_eq!(req.method, Method::Get);
+    assert_eq!(req.msg_data.uri.path(), "/");
+    assert!(matches!(stream.recv_res().await.unwrap().0, Http2RecvStatus::Eos(StatusCode::Ok)));
+    assert!(stream.recv_push_promise().await.unwrap().is_none());
+    let (status, res_buffer) = push_stream.recv_res().await.unwrap();
+    assert!(matches!(status, Http2RecvStatus::Eos(StatusCode::Ok)));
+    assert!(res_buffer.body.is_empty());
+    http2.send_go_away(Http2ErrorCode::NoError).await;
+  });
+  assert_eq!(server_jh.join().unwrap(), (true, Some(0), Vec::new()));
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn unclaimed_pushes_are_reset() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let frames = [push_promise(1, 2), push_promise(1, 4), response(1)].concat();
+  let server_jh = server(&uri, frames);
+  runtime.block_on(async {
+    let hrp = HttpRecvParams::with_optioned_params().set_enable_push(true);
+    let http2 = client(hrp, &runtime, &uri).await;
+    let mut stream = http2.stream().await.unwrap();
+    let msg_buffer = MsgBufferString::default();
+    let rb = ReqBuilder::get((msg_buffer.body.as_ref(), &msg_buffer.headers, uri.to_ref()));
+    let _ = stream.send_req(&mut Vector::new(), rb.into_request()).await.unwrap();
+    let (_, mut push_stream) = stream.recv_push_promise().await.unwrap().unwrap();
+    assert!(matches!(stream.recv_res().await.unwrap().0, Http2RecvStatus::Eos(StatusCode::Ok)));
+    stream.common().clear().await.unwrap();
+    push_stream.common().clear().await.unwrap();
+    http2.send_go_away(Http2ErrorCode::NoError).await;
+  });
+  // Only the second promise wasn't claimed. `CANCEL` has the code 8.
+  assert_eq!(server_jh.join().unwrap(), (true, Some(0), Vec::from([(4, 8)])));
+}
+
+async fn client(
+  hrp: HttpRecvParams,
+  runtime: &StdRuntime,
+  uri: &UriString,
+) -> Http2<TcpStream, PlaintextCtx, true> {
+  let tls_stream = TlsConnectorBuilder::std(uri)
+    .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+    .await
+    .unwrap()
+    .connect()
+    .await
+    .unwrap()
+    .tls_stream;
+  let (frame_reader, http2) = Http2::connect(
+    Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+    hrp,
+    tls_stream.into_split().unwrap(),
+  )
+  .await
+  .unwrap();
+  let _jh = runtime.spawn(frame_reader).unwrap();
+  http2
+}
+
+fn frame(ty: u8, flags: u8, stream_id: u32, payload: &[u8]) -> Vec<u8> {
+  let [_, len0, len1, len2] = u32::try_from(payload.len()).unwrap().to_be_bytes();
+  let mut rslt = Vec::from([len0, len1, len2, ty, flags]);
+  rslt.extend(stream_id.to_be_bytes());
+  rslt.extend(payload);
+  rslt
+}
+
+// Sends `frames` to a client that issued a request and returns the `ENABLE_PUSH` setting as well
+// as the error code of the received GOAWAY frame.
+fn go_away_code(hrp: HttpRecvParams, frames: Vec<u8>) -> (bool, Option<u32>) {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let server_jh = server(&uri, frames);
+  runtime.block_on(async {
+    let http2 = client(hrp, &runtime, &uri).await;
+    let mut stream = http2.stream().await.unwrap();
+    let msg_buffer = MsgBufferString::default();
+    let rb = ReqBuilder::get((msg_buffer.body.as_ref(), &msg_buffer.headers, uri.to_ref()));
+    let _ = stream.send_req(&mut Vector::new(), rb.into_request()).await.unwrap();
+    if stream.recv_res().await.is_ok_and(|el| matches!(el.0, Http2RecvStatus::Eos(_))) {
+      http2.send_go_away(Http2ErrorCode::NoError).await;
+    }
+    while !http2.connection_state().is_closed() {
+      Sleep::new(Duration::from_millis(10)).unwrap().await.unwrap();
+    }
+  });
+  let (enable_push, go_away_code, _) = server_jh.join().unwrap();
+  (enable_push, go_away_code)
+}
+
+fn push_promise(stream_id: u32, promised_stream_id: u32) -> Vec<u8> {
+  frame(
+    PUSH_PROMISE,
+    END_HEADERS,
+    stream_id,
+    &[&promised_stream_id.to_be_bytes()[..], GET].concat(),
+  )
+}
+
+fn response(stream_id: u32) -> Vec<u8> {
+  frame(HEADERS, END_HEADERS | END_STREAM, stream_id, STATUS_200)
+}
+
+// Raw server that waits for the first request before sending `frames`. Returns the `ENABLE_PUSH`
+// setting, the error code of the received GOAWAY frame and the received stream resets.
+fn server(uri: &UriString, frames: Vec<u8>) -> JoinHandle<(bool, Option<u32>, Vec<(u32, u32)>)> {
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  std::thread::spawn(move || {
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut preface = [0; 24];
+    stream.read_exact(&mut preface).unwrap();
+    assert_eq!(preface, PREFACE);
+    let mut enable_push = false;
+    let mut header = [0; 9];
+    let mut resets = Vec::new();
+    loop {
+      if stream.read_exact(&mut header).is_err() {
+        return (enable_push, None, resets);
+      }
+      let [len0, len1, len2, ty, flags, s0, s1, s2, s3] = header;
+      let len = u32::from_be_bytes([0, len0, len1, len2]);
+      let mut payload = alloc::vec![0; usize::try_from(len).unwrap()];
+      if stream.read_exact(&mut payload).is_err() {
+        return (enable_push, None, resets);
+      }
+      match ty {
+        HEADERS => {
+          let _rslt = stream.write_all(&[&frame(SETTINGS, 0, 0, &[]), frames.as_slice()].concat());
+        }
+        SETTINGS if flags == 0 => {
+          enable_push = payload.as_chunks::<6>().0.contains(&[0, 2, 0, 0, 0, 1]);
+        }
+        3 => {
+          let [c0, c1, c2, c3] = payload.as_slice() else {
+            return (enable_push, None, resets);
+          };
+          let stream_id = u32::from_be_bytes([s0, s1, s2, s3]);
+          resets.push((stream_id, u32::from_be_bytes([*c0, *c1, *c2, *c3])));
+        }
+        7 => {
+          let [_, _, _, _, c0, c1, c2, c3, ..] = payload.as_slice() else {
+            return (enable_push, None, resets);
+          };
+          return (enable_push, Some(u32::from_be_bytes([*c0, *c1, *c2, *c3])), resets);
+        }
+        _ => {}
+      }
+    }
+  })
+}
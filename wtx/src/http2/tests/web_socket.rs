@@ -0,0 +1,147 @@
+use crate::{
+  collections::Vector,
+  executor::StdRuntime,
+  http::{Headers, HttpRecvParams, Protocol},
+  http2::{
+    Http2, Http2Buffer, Http2Error, Http2ErrorCode, WebSocketOverClientStream, WebSocketOverStream,
+  },
+  net::{Stream as _, UriString},
+  rng::{ChaCha20, CryptoSeedableRng, SeedableRng, Xorshift64},
+  tests::_uri,
+  tls::{PlaintextCtx, TlsAcceptor, TlsConfig, TlsConnectorBuilder},
+  web_socket::{Frame, GenericWebSocket, OpCode},
+};
+use core::time::Duration;
+use std::net::{TcpListener, TcpStream};
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn web_socket() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  server(&uri, &runtime, true);
+  let client_fut = client(&uri, &runtime);
+  runtime.block_on(client_fut);
+}
+
+// FIXME(MIRI): socket support
+#[cfg_attr(miri, ignore)]
+#[test]
+fn web_socket_without_connect_protocol() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  server(&uri, &runtime, false);
+  runtime.block_on(async {
+    let (frame_header, http2) = http2_client(&uri).await;
+    let _jh = runtime.spawn(frame_header).unwrap();
+    let rslt = WebSocketOverClientStream::connect(
+      &mut Vector::new(),
+      &mut Headers::new(),
+      false,
+      Xorshift64::from_simple_seed().unwrap(),
+      http2.stream().await.unwrap(),
+      &uri.to_ref(),
+    )
+    .await;
+    assert!(matches!(
+      rslt,
+      Err(crate::Error::Http2Error(Http2Error::ExtendedConnectIsUnsupported))
+    ));
+    http2.send_go_away(Http2ErrorCode::NoError).await;
+  });
+}
+
+async fn client(uri: &UriString, runtime: &StdRuntime) {
+  let (frame_header, http2) = http2_client(uri).await;
+  let _jh = runtime.spawn(frame_header).unwrap();
+  let mut ws = WebSocketOverClientStream::connect(
+    &mut Vector::new(),
+    &mut Headers::new(),
+    false,
+    Xorshift64::from_simple_seed().unwrap(),
+    http2.stream().await.unwrap(),
+    &uri.to_ref(),
+  )
+  .await
+  .unwrap();
+  let mut buffer = Vector::new();
+  echo(&mut ws, &mut buffer, b"Hello").await;
+  echo(&mut ws, &mut buffer, &[7; 300]).await;
+  ws.close().await.unwrap();
+  http2.send_go_away(Http2ErrorCode::NoError).await;
+  crate::futures::Sleep::new(Duration::from_millis(100)).unwrap().await.unwrap();
+}
+
+async fn echo(ws: &mut impl GenericWebSocket, buffer: &mut Vector<u8>, payload: &[u8]) {
+  let mut data = Vector::from_copyable_slice(payload).unwrap();
+  ws.write_frame(&mut Frame::new_fin(OpCode::Binary, data.as_slice_mut()).unwrap()).await.unwrap();
+  let frame = ws.read_frame(buffer).await.unwrap();
+  assert_eq!(frame.op_code(), OpCode::Binary);
+  assert_eq!(frame.payload(), &payload);
+}
+
+async fn http2_client(
+  uri: &UriString,
+) -> (impl Future<Output = ()> + use<>, Http2<TcpStream, PlaintextCtx, true>) {
+  let tls_stream = TlsConnectorBuilder::std(uri)
+    .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+    .await
+    .unwrap()
+    .connect()
+    .await
+    .unwrap()
+    .tls_stream;
+  Http2::connect(
+    Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+    HttpRecvParams::with_optioned_params(),
+    tls_stream.into_split().unwrap(),
+  )
+  .await
+  .unwrap()
+}
+
+fn server(uri: &UriString, runtime: &StdRuntime, enable_connect_protocol: bool) {
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let runtime_fut = *runtime;
+  let _server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let tls_stream =
+        TlsAcceptor::new(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap(), stream)
+          .accept()
+          .await
+          .unwrap()
+          .tls_stream;
+      let (frame_header, http2) = Http2::accept(
+        Http2Buffer::new(&mut Xorshift64::from_simple_seed().unwrap()),
+        HttpRecvParams::with_optioned_params().set_enable_connect_protocol(enable_connect_protocol),
+        tls_stream.into_split().unwrap(),
+      )
+      .await
+      .unwrap();
+      let _jh = runtime_fut.spawn(frame_header);
+      if !enable_connect_protocol {
+        assert!(http2.stream(|_, protocol| protocol).await.unwrap().is_none());
+        return;
+      }
+      let (stream, protocol) = http2.stream(|_, protocol| protocol).await.unwrap().unwrap();
+      assert_eq!(protocol, Some(Protocol::WebSocket));
+      let mut ws = WebSocketOverStream::new(
+        &Headers::new(),
+        false,
+        Xorshift64::from_simple_seed().unwrap(),
+        stream,
+      )
+      .await
+      .unwrap();
+      let mut buffer = Vector::new();
+      for _ in 0..2 {
+        let mut frame = ws.read_frame(&mut buffer).await.unwrap();
+        ws.write_frame(&mut Frame::new_fin(frame.op_code(), frame.payload_mut()).unwrap())
+          .await
+          .unwrap();
+      }
+    })
+    .unwrap();
+}
@@ -1,25 +1,31 @@
 //! Tools to manage WebSocket connections in HTTP/2 streams
 
 use crate::{
-  collections::{ArrayVectorU16, SingleTypeStorage, Vector},
-  futures::JoinArrayVector,
-  http::{Headers, StatusCode},
-  http2::{Http2Error, Http2ErrorCode, Http2RecvStatus, ServerStream, misc::protocol_err},
+  _MAX_PAYLOAD_LEN,
+  collections::{SingleTypeStorage, Vector},
+  http::{Header, Headers, KnownHeaderName, Protocol, StatusCode, has_web_socket_no_masking},
+  http2::{
+    ClientStream, CommonStream, Http2Error, Http2ErrorCode, Http2RecvStatus, ServerStream,
+    misc::protocol_err,
+  },
   misc::LeaseMut,
-  net::StreamWriter,
+  net::{StreamWriter, UriRef},
   rng::Xorshift64,
   tls::{TlsCtx, TlsStreamBridge},
   web_socket::{
-    Frame, FrameMut, OpCode, WebSocketBridge,
+    Frame, FrameMut, GenericWebSocket, MASK_MASK, OpCode, PAYLOAD_MASK, WebSocketBridge,
+    WebSocketError,
     read_frame::{manage_auto_reply, manage_op_code_of_first_final_frame, unmask_nb},
     read_frame_info::ReadFrameInfo,
     write_frame::mask_frame,
   },
 };
 
-/// WebSocket tunneling
+/// WebSocket tunneling of servers
 #[derive(Debug)]
 pub struct WebSocketOverStream<S> {
+  max_payload_len: usize,
+  network_buffer: Vector<u8>,
   no_masking: bool,
   rng: Xorshift64,
   stream: S,
@@ -47,15 +53,26 @@ where
     if hss.is_closed() {
       return Err(crate::Error::ClosedHttpConnection);
     }
-    Ok(Self { no_masking, rng, stream })
+    Ok(Self {
+      max_payload_len: _MAX_PAYLOAD_LEN,
+      network_buffer: Vector::new(),
+      no_masking,
+      rng,
+      stream,
+    })
   }
 
   /// Closes the stream as well as the WebSocket connection.
   #[inline]
   pub async fn close(&mut self) -> crate::Result<()> {
-    self.write_frame(&mut Frame::new(true, OpCode::Close, &mut [], 0)).await?;
-    self.stream.lease_mut().common().send_reset(Http2ErrorCode::NoError).await;
-    Ok(())
+    close(self.no_masking, &mut self.rng, &self.stream.lease_mut().common()).await
+  }
+
+  /// Sets whether to automatically close the connection when a received frame payload length
+  /// exceeds `max_payload_len`. Defaults to `64 * 1024 * 1024` bytes (64 MiB).
+  #[inline]
+  pub const fn max_payload_len_mut(&mut self) -> &mut usize {
+    &mut self.max_payload_len
   }
 
   /// Reads a frame from the stream.
@@ -67,26 +84,133 @@ where
     &mut self,
     buffer: &'buffer mut Vector<u8>,
   ) -> crate::Result<FrameMut<'buffer>> {
-    buffer.clear();
-    let (rfi, is_eos) = recv_data(buffer, self.no_masking, self.stream.lease_mut()).await?;
-    if rfi.fin {
-      let _is_control_frame = manage_auto_reply::<_, _, true, false>(
-        self.stream.lease_mut(),
-        self.no_masking,
-        rfi.op_code,
-        buffer,
-        &mut self.rng,
-        &WebSocketBridge::new(TlsStreamBridge::new()),
-        write_control_frame_cb,
-      )
-      .await?;
-      manage_op_code_of_first_final_frame(rfi.op_code, buffer)?;
-      return Ok(FrameMut::new(true, rfi.op_code, buffer, 0));
+    let Self { max_payload_len, network_buffer, no_masking, rng, stream } = self;
+    let common = &mut stream.lease_mut().common();
+    read_frame(buffer, *max_payload_len, network_buffer, *no_masking, rng, common).await
+  }
+
+  /// Writes a frame to the stream.
+  #[inline]
+  pub async fn write_frame<P>(&mut self, frame: &mut Frame<P>) -> crate::Result<()>
+  where
+    P: LeaseMut<[u8]>,
+  {
+    write_frame(frame, self.no_masking, &mut self.rng, &self.stream.lease_mut().common()).await
+  }
+}
+
+impl<S, SW, TCX> GenericWebSocket for WebSocketOverStream<S>
+where
+  S: LeaseMut<ServerStream<SW, TCX>> + SingleTypeStorage<Item = (SW, TCX)>,
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  #[inline]
+  async fn read_frame<'buffer>(
+    &mut self,
+    buffer: &'buffer mut Vector<u8>,
+  ) -> crate::Result<FrameMut<'buffer>> {
+    self.read_frame(buffer).await
+  }
+
+  #[inline]
+  async fn write_frame<P>(&mut self, frame: &mut Frame<P>) -> crate::Result<()>
+  where
+    P: LeaseMut<[u8]>,
+  {
+    self.write_frame(frame).await
+  }
+}
+
+/// WebSocket tunneling of clients
+#[derive(Debug)]
+pub struct WebSocketOverClientStream<S> {
+  max_payload_len: usize,
+  network_buffer: Vector<u8>,
+  no_masking: bool,
+  rng: Xorshift64,
+  stream: S,
+}
+
+impl<S, SW, TCX> WebSocketOverClientStream<S>
+where
+  S: LeaseMut<ClientStream<SW, TCX>> + SingleTypeStorage<Item = (SW, TCX)>,
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  /// Sends an extended CONNECT request with the WebSocket protocol and then awaits an `Ok` status
+  /// code that confirms the handshake.
+  ///
+  /// `headers` can contain additional fields like `sec-websocket-protocol`. The `no-masking`
+  /// parameter is only used if the server also agrees with it.
+  #[inline]
+  pub async fn connect(
+    enc_buffer: &mut Vector<u8>,
+    headers: &mut Headers,
+    no_masking: bool,
+    rng: Xorshift64,
+    mut stream: S,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<Self> {
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::SecWebsocketVersion.into(),
+      ["13"],
+    ))?;
+    if no_masking {
+      headers.push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::SecWebsocketExtensions.into(),
+        ["no-masking"],
+      ))?;
     }
-    if is_eos {
+    let client_stream = stream.lease_mut();
+    let hss =
+      client_stream.send_extended_connect(enc_buffer, headers, Protocol::WebSocket, uri).await?;
+    if hss.is_closed() {
+      return Err(crate::Error::ClosedHttpConnection);
+    }
+    let Some((res_headers, status_code)) = client_stream.recv_res_headers().await? else {
       return Err(crate::Error::ClosedHttpConnection);
+    };
+    if status_code != StatusCode::Ok {
+      client_stream.common().send_reset(Http2ErrorCode::Cancel).await;
+      let status = u16::from(status_code);
+      return Err(crate::Error::Http2Error(Http2Error::UnexpectedExtendedConnectStatus(status)));
     }
-    Err(protocol_err(Http2Error::WebSocketContinuationFrame))
+    let has_no_masking = has_web_socket_no_masking(&res_headers);
+    Ok(Self {
+      max_payload_len: _MAX_PAYLOAD_LEN,
+      network_buffer: Vector::new(),
+      no_masking: no_masking && has_no_masking,
+      rng,
+      stream,
+    })
+  }
+
+  /// Closes the stream as well as the WebSocket connection.
+  #[inline]
+  pub async fn close(&mut self) -> crate::Result<()> {
+    close(self.no_masking, &mut self.rng, &self.stream.lease_mut().common()).await
+  }
+
+  /// Sets whether to automatically close the connection when a received frame payload length
+  /// exceeds `max_payload_len`. Defaults to `64 * 1024 * 1024` bytes (64 MiB).
+  #[inline]
+  pub const fn max_payload_len_mut(&mut self) -> &mut usize {
+    &mut self.max_payload_len
+  }
+
+  /// Reads a frame from the stream.
+  ///
+  /// If a frame is made up of other sub-frames or continuations, then everything is collected
+  /// until all fragments are received.
+  #[inline]
+  pub async fn read_frame<'buffer>(
+    &mut self,
+    buffer: &'buffer mut Vector<u8>,
+  ) -> crate::Result<FrameMut<'buffer>> {
+    let Self { max_payload_len, network_buffer, no_masking, rng, stream } = self;
+    let common = &mut stream.lease_mut().common();
+    read_frame(buffer, *max_payload_len, network_buffer, *no_masking, rng, common).await
   }
 
   /// Writes a frame to the stream.
@@ -95,63 +219,129 @@ where
   where
     P: LeaseMut<[u8]>,
   {
-    mask_frame::<_, _, false>(frame, self.no_masking, &mut self.rng);
-    let (header, payload) = frame.header_and_payload();
-    let common_stream = self.stream.lease_mut().common();
-    let results = JoinArrayVector::new(ArrayVectorU16::<_, 2>::from_array([
-      common_stream.send_data(header, false),
-      common_stream.send_data(payload.lease(), false),
-    ]))
-    .await;
-    for result in results {
-      if result?.is_closed() {
-        return Err(crate::Error::ClosedHttpConnection);
-      }
-    }
-    Ok(())
+    write_frame(frame, self.no_masking, &mut self.rng, &self.stream.lease_mut().common()).await
+  }
+}
+
+impl<S, SW, TCX> GenericWebSocket for WebSocketOverClientStream<S>
+where
+  S: LeaseMut<ClientStream<SW, TCX>> + SingleTypeStorage<Item = (SW, TCX)>,
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  #[inline]
+  async fn read_frame<'buffer>(
+    &mut self,
+    buffer: &'buffer mut Vector<u8>,
+  ) -> crate::Result<FrameMut<'buffer>> {
+    self.read_frame(buffer).await
+  }
+
+  #[inline]
+  async fn write_frame<P>(&mut self, frame: &mut Frame<P>) -> crate::Result<()>
+  where
+    P: LeaseMut<[u8]>,
+  {
+    self.write_frame(frame).await
   }
 }
 
-fn extend_buffer(
-  buffer: &mut Vector<u8>,
+async fn close<SW, TCX, const IS_CLIENT: bool>(
   no_masking: bool,
-  mut slice: &[u8],
-) -> crate::Result<(ReadFrameInfo, usize)> {
-  let rfi = ReadFrameInfo::from_bytes::<(), false>(&mut slice, usize::MAX, 0, no_masking)?;
-  let before = buffer.len();
-  buffer.extend_from_copyable_slice(slice)?;
-  Ok((rfi, before))
+  rng: &mut Xorshift64,
+  stream: &CommonStream<'_, SW, TCX, IS_CLIENT>,
+) -> crate::Result<()>
+where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  write_frame(&mut Frame::new(true, OpCode::Close, &mut [], 0), no_masking, rng, stream).await?;
+  stream.send_reset(Http2ErrorCode::NoError).await;
+  Ok(())
+}
+
+// Length of the header that starts `bytes` or `None` if more bytes are needed.
+fn header_len(bytes: &[u8]) -> Option<usize> {
+  let [_, b1, ..] = bytes else {
+    return None;
+  };
+  let len_bytes: usize = match b1 & PAYLOAD_MASK {
+    126 => 2,
+    127 => 8,
+    _ => 0,
+  };
+  let mask_bytes: usize = if b1 & MASK_MASK == 0 { 0 } else { 4 };
+  let len = len_bytes.wrapping_add(mask_bytes).wrapping_add(2);
+  (bytes.len() >= len).then_some(len)
 }
 
-async fn recv_data<SW, TCX>(
-  buffer: &mut Vector<u8>,
+// HTTP/2 doesn't preserve the boundaries of DATA frames, as such, frames are assembled from
+// `network_buffer`, which also stores the bytes of subsequent frames.
+async fn read_frame<'buffer, SW, TCX, const IS_CLIENT: bool>(
+  buffer: &'buffer mut Vector<u8>,
+  max_payload_len: usize,
+  network_buffer: &mut Vector<u8>,
   no_masking: bool,
-  stream: &mut ServerStream<SW, TCX>,
-) -> crate::Result<(ReadFrameInfo, bool)>
+  rng: &mut Xorshift64,
+  stream: &mut CommonStream<'_, SW, TCX, IS_CLIENT>,
+) -> crate::Result<FrameMut<'buffer>>
 where
   SW: StreamWriter,
   TCX: TlsCtx,
 {
-  let (before, is_eos, rfi) = match stream
-    .common()
-    .recv_data(|ongoing_data| extend_buffer(buffer, no_masking, ongoing_data))
-    .await?
-  {
-    Http2RecvStatus::ClosedConnection | Http2RecvStatus::ClosedStream(_) => {
+  let mut is_eos = false;
+  let (rfi, frame_len) = loop {
+    if let Some(len) = header_len(network_buffer) {
+      let mut header = network_buffer.get(..len).unwrap_or_default();
+      let rfi =
+        ReadFrameInfo::from_bytes::<(), IS_CLIENT>(&mut header, max_payload_len, 0, no_masking)?;
+      let frame_len = len
+        .checked_add(rfi.payload_len)
+        .ok_or(crate::Error::from(WebSocketError::VeryLargePayload))?;
+      if network_buffer.len() >= frame_len {
+        buffer.clear();
+        buffer
+          .extend_from_copyable_slice(network_buffer.get(len..frame_len).unwrap_or_default())?;
+        break (rfi, frame_len);
+      }
+    }
+    if is_eos {
       return Err(crate::Error::ClosedHttpConnection);
     }
-    Http2RecvStatus::Eos(data) => {
-      let (rfi, before) = extend_buffer(buffer, no_masking, &data)?;
-      (before, true, rfi)
+    match stream.recv_data(|data| network_buffer.extend_from_copyable_slice(data)).await? {
+      Http2RecvStatus::ClosedConnection | Http2RecvStatus::ClosedStream(_) => {
+        return Err(crate::Error::ClosedHttpConnection);
+      }
+      Http2RecvStatus::Eos(data) => {
+        network_buffer.extend_from_copyable_slice(&data)?;
+        is_eos = true;
+      }
+      Http2RecvStatus::Ongoing(()) => {}
     }
-    Http2RecvStatus::Ongoing((rfi, before)) => (before, false, rfi),
   };
-  unmask_nb::<false>(rfi.mask, buffer.get_mut(before..).unwrap_or_default(), no_masking)?;
-  Ok((rfi, is_eos))
+  let rest_len = network_buffer.len().wrapping_sub(frame_len);
+  network_buffer.as_slice_mut().copy_within(frame_len.., 0);
+  network_buffer.truncate(rest_len);
+  unmask_nb::<IS_CLIENT>(rfi.mask, buffer, no_masking)?;
+  if !rfi.fin {
+    return Err(protocol_err(Http2Error::WebSocketContinuationFrame));
+  }
+  let _is_control_frame = manage_auto_reply::<_, _, true, IS_CLIENT>(
+    &*stream,
+    no_masking,
+    rfi.op_code,
+    buffer,
+    rng,
+    &WebSocketBridge::new(TlsStreamBridge::new()),
+    write_control_frame_cb,
+  )
+  .await?;
+  manage_op_code_of_first_final_frame(rfi.op_code, buffer)?;
+  Ok(FrameMut::new(true, rfi.op_code, buffer, 0))
 }
 
-async fn write_control_frame_cb<SW, TCX>(
-  stream: &mut ServerStream<SW, TCX>,
+async fn send_data<SW, TCX, const IS_CLIENT: bool>(
+  stream: &CommonStream<'_, SW, TCX, IS_CLIENT>,
   header: &[u8],
   payload: &[u8],
 ) -> crate::Result<()>
@@ -159,16 +349,42 @@ where
   SW: StreamWriter,
   TCX: TlsCtx,
 {
-  let common_stream = stream.common();
-  let results = JoinArrayVector::new(ArrayVectorU16::<_, 2>::from_array([
-    common_stream.send_data(header, false),
-    common_stream.send_data(payload, false),
-  ]))
-  .await;
-  for result in results {
-    if result?.is_closed() {
+  // Sequential writes because the header must precede the payload.
+  for data in [header, payload] {
+    if data.is_empty() {
+      continue;
+    }
+    if stream.send_data(data, false).await?.is_closed() {
       return Err(crate::Error::ClosedHttpConnection);
     }
   }
   Ok(())
 }
+
+async fn write_control_frame_cb<SW, TCX, const IS_CLIENT: bool>(
+  stream: &CommonStream<'_, SW, TCX, IS_CLIENT>,
+  header: &[u8],
+  payload: &[u8],
+) -> crate::Result<()>
+where
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  send_data(stream, header, payload).await
+}
+
+async fn write_frame<P, SW, TCX, const IS_CLIENT: bool>(
+  frame: &mut Frame<P>,
+  no_masking: bool,
+  rng: &mut Xorshift64,
+  stream: &CommonStream<'_, SW, TCX, IS_CLIENT>,
+) -> crate::Result<()>
+where
+  P: LeaseMut<[u8]>,
+  SW: StreamWriter,
+  TCX: TlsCtx,
+{
+  mask_frame::<_, _, IS_CLIENT>(frame, no_masking, rng);
+  let (header, payload) = frame.header_and_payload();
+  send_data(stream, header, payload.lease()).await
+}
@@ -28,16 +28,18 @@ macro_rules! frame_params {
 
 use crate::{
   collections::{ArrayVectorU8, Vector},
-  http::{Headers, Trailers, U31},
+  http::{Headers, Priority, Trailers, U31},
   http2::{
-    Http2Data, Http2Error, Http2Inner, Http2SendStatus,
+    Http2Data, Http2Error, Http2Inner, Http2SendStatus, Scorp,
     continuation_frame::ContinuationFrame,
     data_frame::DataFrame,
     headers_frame::HeadersFrame,
     hpack_encoder::HpackEncoder,
     hpack_static_headers::{HpackStaticRequestHeaders, HpackStaticResponseHeaders},
     http2_data::Http2DataPartsMut,
-    misc::{process_higher_operation_err, protocol_err, scrp_mut, write_array},
+    misc::{
+      process_higher_operation_err, protocol_err, scrp_mut, wake_deferred_streams, write_array,
+    },
     stream_state::StreamState,
     window::WindowsPair,
   },
@@ -47,6 +49,7 @@ use crate::{
 };
 use core::{
   future::poll_fn,
+  mem,
   pin::pin,
   task::{Poll, Waker},
 };
@@ -150,6 +153,17 @@ pub(crate) fn push_data(
   }
 }
 
+// Empty DATA frames don't withdraw windows and are only useful to finish streams.
+pub(crate) fn push_eos_data(
+  frames: &mut ArrayVectorU8<FrameParams, 4>,
+  stream_id: U31,
+) -> crate::Result<()> {
+  let mut frame = DataFrame::new(U31::ZERO, stream_id);
+  frame.set_eos();
+  frames.push(frame_params!(&mut 0u32, frame, 0u32, true))?;
+  Ok(())
+}
+
 pub(crate) fn push_headers<const IS_CLIENT: bool>(
   enc_buffer: &[u8],
   frames: &mut ArrayVectorU8<FrameParams, 4>,
@@ -216,6 +230,7 @@ pub(crate) fn push_trailers(
 ///
 /// * Control frames like Settings or `WindowUpdate` are out of scope.
 /// * At most one continuation frame can be sent
+/// * DATA frames wait for the DATA frames of more urgent streams, as stated by [`Priority`].
 pub(crate) async fn send_msg<SW, TCX, const IS_CLIENT: bool>(
   data: &[u8],
   enc_buffer: &mut Vector<u8>,
@@ -233,8 +248,11 @@ where
   let fut = async {
     let mut data_idx = 0;
     let mut frames = ArrayVectorU8::new();
+    let mut sdg = SendingDataGuard { inner, is_armed: false, stream_id };
     let mut hd_guard_pin = pin!(inner.hd.lock());
     let mut hpack_idx = 0;
+    #[cfg(feature = "metrics")]
+    let mut is_stalled = false;
     let hss = loop {
       let state = poll_fn(|cx| {
         let state = do_send_msg::<IS_CLIENT>(
@@ -250,7 +268,17 @@ where
           cx.waker(),
           &mut cb,
         )?;
+        // A stream that keeps waiting for the same window update is only counted once
+        #[cfg(feature = "metrics")]
         if let SendMsgState::NeedsMoreWindow = state {
+          if !is_stalled {
+            crate::metrics::MetricsRegistry::global().http2_flow_control_stalls.inc();
+            is_stalled = true;
+          }
+        } else {
+          is_stalled = false;
+        }
+        if let SendMsgState::Deferred | SendMsgState::NeedsMoreWindow = state {
           return Poll::Pending;
         }
         Poll::Ready(Ok::<_, crate::Error>(state))
@@ -258,12 +286,16 @@ where
       .await?;
       let should_stop = match state {
         SendMsgState::ClosedStream => break Http2SendStatus::ClosedStream,
-        SendMsgState::Finished | SendMsgState::NeedsMoreWindow => break Http2SendStatus::Ok,
-        SendMsgState::GeneratedData(should_stop) | SendMsgState::GeneratedHeaders(should_stop) => {
+        SendMsgState::Deferred | SendMsgState::Finished | SendMsgState::NeedsMoreWindow => {
+          break Http2SendStatus::Ok;
+        }
+        SendMsgState::GeneratedData(should_stop)
+        | SendMsgState::GeneratedHeadersAndData(should_stop) => {
+          sdg.is_armed = true;
           should_stop
         }
         SendMsgState::GeneratedFastPath | SendMsgState::GeneratedTrailers => true,
-        SendMsgState::GeneratedHeadersAndData(should_stop) => should_stop,
+        SendMsgState::GeneratedHeaders(should_stop) => should_stop,
       };
       write_frames::<_, _, IS_CLIENT>(
         (enc_buffer.as_ref(), data),
@@ -275,6 +307,15 @@ where
         break Http2SendStatus::Ok;
       }
     };
+    // Less urgent streams only resume after the last DATA frames are written
+    if mem::take(&mut sdg.is_armed) {
+      let mut hd_guard = inner.hd.lock().await;
+      let scrps = &mut hd_guard.parts_mut().hb.scrps;
+      if let Some(elem) = scrps.get_mut(&stream_id) {
+        elem.is_sending_data = false;
+      }
+      wake_deferred_streams(scrps);
+    }
     _trace!(target: crate::_WTX_HTTP2, "Message has been sent");
     Ok(hss)
   };
@@ -371,13 +412,21 @@ fn do_send_msg<const IS_CLIENT: bool>(
 ) -> crate::Result<SendMsgState> {
   frames.clear();
   let hdpm = hd.parts_mut();
-  let scrp = scrp_mut(&mut hdpm.hb.scrps, stream_id)?;
-  if !scrp.is_stream_open {
-    return Ok(SendMsgState::ClosedStream);
-  }
-  if !scrp.stream_state.can_send::<IS_CLIENT>() {
-    return Err(protocol_err(Http2Error::InvalidSendStreamState));
+  {
+    let local_scrp = scrp_mut(&mut hdpm.hb.scrps, stream_id)?;
+    if !local_scrp.is_stream_open {
+      return Ok(SendMsgState::ClosedStream);
+    }
+    if !local_scrp.stream_state.can_send::<IS_CLIENT>() {
+      return Err(protocol_err(Http2Error::InvalidSendStreamState));
+    }
+    if IS_CLIENT && enc_buffer.is_empty() {
+      local_scrp.priority = Priority::from_headers(headers);
+    }
   }
+  let must_defer = *Usize::from(*data_idx) < data.len() && must_defer(&hdpm.hb.scrps, stream_id);
+  let data_turn = next_data_turn(&hdpm.hb.scrps);
+  let scrp = scrp_mut(&mut hdpm.hb.scrps, stream_id)?;
   let mut wp = WindowsPair::new(hdpm.windows, &mut scrp.windows);
 
   let Ok(available_send @ 1..=u32::MAX) = u32::try_from(wp.available_send()) else {
@@ -401,6 +450,10 @@ fn do_send_msg<const IS_CLIENT: bool>(
       return Ok(SendMsgState::GeneratedHeaders(should_stop));
     }
     scrp.waker.clone_from(waker);
+    // Streams without window don't hold other streams back
+    if scrp.is_sending_data {
+      wake_deferred_streams(&mut hdpm.hb.scrps);
+    }
     return Ok(SendMsgState::NeedsMoreWindow);
   };
 
@@ -437,6 +490,10 @@ fn do_send_msg<const IS_CLIENT: bool>(
       return Ok(SendMsgState::GeneratedHeaders(true));
     }
     change_initial_stream_state::<IS_CLIENT>(&mut scrp.stream_state);
+    if must_defer {
+      scrp.waker.clone_from(waker);
+      return Ok(SendMsgState::GeneratedHeaders(false));
+    }
     let should_stop_from_data = push_data(
       available_send,
       data,
@@ -450,15 +507,24 @@ fn do_send_msg<const IS_CLIENT: bool>(
     if should_stop_from_data {
       final_generic_calls!(cb, hdpm, scrp);
     } else {
+      scrp.data_turn = data_turn;
+      scrp.is_sending_data = true;
       scrp.waker.clone_from(waker);
       if wp.available_send() > 0 {
         waker.wake_by_ref();
       }
+      // Streams of the same urgency take turns
+      wake_deferred_streams(&mut hdpm.hb.scrps);
     }
     return Ok(SendMsgState::GeneratedHeadersAndData(should_stop_from_data));
   }
 
   if *Usize::from(*data_idx) < data.len() {
+    if must_defer {
+      scrp.is_deferred = true;
+      scrp.waker.clone_from(waker);
+      return Ok(SendMsgState::Deferred);
+    }
     let should_stop_from_data = push_data(
       available_send,
       data,
@@ -472,10 +538,14 @@ fn do_send_msg<const IS_CLIENT: bool>(
     if should_stop_from_data {
       final_generic_calls!(cb, hdpm, scrp);
     } else {
+      scrp.data_turn = data_turn;
+      scrp.is_sending_data = true;
       scrp.waker.clone_from(waker);
       if wp.available_send() > 0 {
         waker.wake_by_ref();
       }
+      // Streams of the same urgency take turns
+      wake_deferred_streams(&mut hdpm.hb.scrps);
     }
     return Ok(SendMsgState::GeneratedData(should_stop_from_data));
   }
@@ -532,6 +602,27 @@ fn is_eos(data: &[u8], data_idx: u32, headers: &Headers) -> bool {
   *Usize::from(data_idx) >= data.len() && !headers.trailers().has_any()
 }
 
+// If another stream with precedence over `stream_id` is sending DATA frames and has window to do
+// so. Among streams of the same urgency, the one that waited the longest has precedence.
+fn must_defer(scrps: &Scorp, stream_id: U31) -> bool {
+  let Some((data_turn, priority)) = scrps.get(&stream_id).map(|el| (el.data_turn, el.priority))
+  else {
+    return false;
+  };
+  scrps.iter().any(|(id, el)| {
+    *id != stream_id
+      && el.is_sending_data
+      && el.is_stream_open
+      && el.windows.send().available() > 0
+      && (priority.yields_to(el.priority)
+        || (el.priority.urgency() == priority.urgency() && el.data_turn < data_turn))
+  })
+}
+
+fn next_data_turn(scrps: &Scorp) -> u64 {
+  scrps.values().map(|el| el.data_turn).max().unwrap_or_default().wrapping_add(1)
+}
+
 fn push_fast_path<const IS_CLIENT: bool>(
   available_send: u32,
   data: &[u8],
@@ -640,6 +731,7 @@ fn split_bytes(bytes: &[u8], begin: u32, len: u32) -> (u32, &[u8]) {
 #[derive(Clone, Copy, Debug)]
 enum SendMsgState {
   ClosedStream,
+  Deferred,
   Finished,
   GeneratedData(bool),
   GeneratedFastPath,
@@ -649,6 +741,33 @@ enum SendMsgState {
   NeedsMoreWindow,
 }
 
+// Clears the sending status of a stream whose message was interrupted by an error or by the
+// cancellation of its future, otherwise less urgent streams would wait forever.
+struct SendingDataGuard<'inner, SW, TCX, const IS_CLIENT: bool> {
+  inner: &'inner Http2Inner<SW, TCX, IS_CLIENT>,
+  is_armed: bool,
+  stream_id: U31,
+}
+
+impl<SW, TCX, const IS_CLIENT: bool> Drop for SendingDataGuard<'_, SW, TCX, IS_CLIENT> {
+  #[inline]
+  fn drop(&mut self) {
+    if !self.is_armed {
+      return;
+    }
+    // `Drop` can't await the lock. If contended, the status is cleared when the stream is
+    // cleared or reset.
+    let Some(mut hd_guard) = self.inner.hd.try_lock() else {
+      return;
+    };
+    let scrps = &mut hd_guard.parts_mut().hb.scrps;
+    if let Some(elem) = scrps.get_mut(&self.stream_id) {
+      elem.is_sending_data = false;
+    }
+    wake_deferred_streams(scrps);
+  }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub(crate) struct FrameParams {
   header: [u8; 9],
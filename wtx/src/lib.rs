@@ -39,6 +39,8 @@ pub mod grpc;
 pub mod http;
 #[cfg(feature = "http2")]
 pub mod http2;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod misc;
 pub mod net;
 pub mod pool;
@@ -60,6 +62,7 @@ pub use wtx_macros::*;
 const _WTX_CAF: &str = "wtx-caf";
 const _WTX_HTTP_SM: &str = "wtx-http-sm";
 const _WTX_HTTP: &str = "wtx-http";
+const _WTX_HTTP_ACCESS: &str = "wtx-http-access";
 const _WTX_HTTP2: &str = "wtx-http2";
 const _WTX_TLS_HS: &str = "wtx-tls-hs";
 const _WTX_TLS: &str = "wtx-tls";
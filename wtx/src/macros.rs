@@ -219,6 +219,13 @@ macro_rules! doc_single_elem_cap_overflow {
   };
 }
 
+macro_rules! _info {
+  ($($tt:tt)+) => {
+    #[cfg(feature = "tracing")]
+    tracing::info!($($tt)+);
+  };
+}
+
 macro_rules! _internal_buffer_doc {
   () => {
     "Buffer used for internal operations."
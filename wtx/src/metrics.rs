@@ -0,0 +1,16 @@
+//! Prometheus-style metrics
+//!
+//! Built-in instrumentation is recorded into [`MetricsRegistry::global`], which can be encoded
+//! using the text exposition format.
+
+mod counter;
+mod gauge;
+mod histogram;
+mod metrics_encoder;
+mod metrics_registry;
+
+pub use counter::Counter;
+pub use gauge::Gauge;
+pub use histogram::Histogram;
+pub use metrics_encoder::{MetricTy, MetricsEncoder};
+pub use metrics_registry::MetricsRegistry;
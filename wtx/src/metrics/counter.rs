@@ -0,0 +1,39 @@
+use crate::sync::AtomicU64;
+use core::sync::atomic::Ordering;
+
+/// Value that only increases.
+#[derive(Debug)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+  /// Instance starting at zero.
+  #[inline]
+  pub const fn new() -> Self {
+    Self(AtomicU64::new(0))
+  }
+
+  /// Increases the current value by `value`.
+  #[inline]
+  pub fn add(&self, value: u64) {
+    let _ = self.0.fetch_add(value, Ordering::Relaxed);
+  }
+
+  /// Current value
+  #[inline]
+  pub fn get(&self) -> u64 {
+    self.0.load(Ordering::Relaxed)
+  }
+
+  /// Increases the current value by one.
+  #[inline]
+  pub fn inc(&self) {
+    self.add(1);
+  }
+}
+
+impl Default for Counter {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,52 @@
+use crate::sync::AtomicU64;
+use core::sync::atomic::Ordering;
+
+/// Value that can increase or decrease. Never goes below zero.
+#[derive(Debug)]
+pub struct Gauge(AtomicU64);
+
+impl Gauge {
+  /// Instance starting at zero.
+  #[inline]
+  pub const fn new() -> Self {
+    Self(AtomicU64::new(0))
+  }
+
+  /// Increases the current value by `value`.
+  #[inline]
+  pub fn add(&self, value: u64) {
+    let _ = self.0.fetch_add(value, Ordering::Relaxed);
+  }
+
+  /// Current value
+  #[inline]
+  pub fn get(&self) -> u64 {
+    self.0.load(Ordering::Relaxed)
+  }
+
+  /// Replaces the current value.
+  #[inline]
+  pub fn set(&self, value: u64) {
+    self.0.store(value, Ordering::Relaxed);
+  }
+
+  /// Decreases the current value by `value`.
+  #[inline]
+  pub fn sub(&self, value: u64) {
+    let mut current = self.0.load(Ordering::Relaxed);
+    loop {
+      let new = current.saturating_sub(value);
+      match self.0.compare_exchange_weak(current, new, Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => break,
+        Err(elem) => current = elem,
+      }
+    }
+  }
+}
+
+impl Default for Gauge {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,109 @@
+use crate::{calendar::Instant, sync::AtomicU64};
+use core::{sync::atomic::Ordering, time::Duration};
+
+const BUCKETS_LEN: usize = 13;
+
+// Upper bounds in microseconds alongside their representation in seconds.
+pub(crate) static BUCKETS: [(u64, &str); BUCKETS_LEN] = [
+  (1_000, "0.001"),
+  (2_500, "0.0025"),
+  (5_000, "0.005"),
+  (10_000, "0.01"),
+  (25_000, "0.025"),
+  (50_000, "0.05"),
+  (100_000, "0.1"),
+  (250_000, "0.25"),
+  (500_000, "0.5"),
+  (1_000_000, "1"),
+  (2_500_000, "2.5"),
+  (5_000_000, "5"),
+  (10_000_000, "10"),
+];
+
+/// Distribution of durations, in seconds, grouped by fixed buckets that range from 1 millisecond
+/// to 10 seconds.
+#[derive(Debug)]
+pub struct Histogram {
+  buckets: [AtomicU64; BUCKETS_LEN],
+  count: AtomicU64,
+  sum_us: AtomicU64,
+}
+
+impl Histogram {
+  /// Empty instance
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      buckets: [const { AtomicU64::new(0) }; BUCKETS_LEN],
+      count: AtomicU64::new(0),
+      sum_us: AtomicU64::new(0),
+    }
+  }
+
+  /// Number of observations
+  #[inline]
+  pub fn count(&self) -> u64 {
+    self.count.load(Ordering::Relaxed)
+  }
+
+  /// Records a single duration.
+  #[inline]
+  pub fn observe(&self, duration: Duration) {
+    let us = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+    let idx = BUCKETS.iter().position(|(bound, _)| us <= *bound);
+    if let Some(bucket) = idx.and_then(|el| self.buckets.get(el)) {
+      let _ = bucket.fetch_add(1, Ordering::Relaxed);
+    }
+    let _ = self.count.fetch_add(1, Ordering::Relaxed);
+    let _ = self.sum_us.fetch_add(us, Ordering::Relaxed);
+  }
+
+  /// Records the time elapsed since `instant`. Does nothing if there is no time backend.
+  #[inline]
+  pub fn observe_elapsed(&self, instant: Instant) {
+    if let Ok(elem) = instant.elapsed() {
+      self.observe(elem);
+    }
+  }
+
+  /// Sum of all observations
+  #[inline]
+  pub fn sum(&self) -> Duration {
+    Duration::from_micros(self.sum_us.load(Ordering::Relaxed))
+  }
+
+  // Early returns and errors are also measured because the observation happens on drop.
+  #[cfg(feature = "postgres")]
+  pub(crate) fn start_timer(&self) -> HistogramTimer<'_> {
+    HistogramTimer { histogram: self, instant: Instant::new() }
+  }
+
+  pub(crate) fn cumulative_buckets(&self) -> impl Iterator<Item = (&'static str, u64)> {
+    BUCKETS.iter().zip(&self.buckets).scan(0u64, |acc, ((_, bound), bucket)| {
+      *acc = acc.wrapping_add(bucket.load(Ordering::Relaxed));
+      Some((*bound, *acc))
+    })
+  }
+}
+
+impl Default for Histogram {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+pub(crate) struct HistogramTimer<'histogram> {
+  histogram: &'histogram Histogram,
+  instant: Instant,
+}
+
+#[cfg(feature = "postgres")]
+impl Drop for HistogramTimer<'_> {
+  #[inline]
+  fn drop(&mut self) {
+    self.histogram.observe_elapsed(self.instant);
+  }
+}
@@ -0,0 +1,199 @@
+use crate::{
+  codec::{u32_string_pad, u64_string},
+  collections::{ArrayStringU8, Vector},
+  metrics::{Counter, Gauge, Histogram},
+  misc::{AsciiGraphic, const_ok},
+};
+
+const ZERO: AsciiGraphic = const { const_ok(AsciiGraphic::new(b'0')).unwrap() };
+
+/// Type of a metric family
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetricTy {
+  /// See [`Counter`].
+  Counter,
+  /// See [`Gauge`].
+  Gauge,
+  /// See [`Histogram`].
+  Histogram,
+}
+
+impl MetricTy {
+  const fn as_str(self) -> &'static str {
+    match self {
+      Self::Counter => "counter",
+      Self::Gauge => "gauge",
+      Self::Histogram => "histogram",
+    }
+  }
+}
+
+/// Writes metrics using the Prometheus text exposition format (version `0.0.4`).
+///
+/// Each family must be declared through [`Self::family`] before its samples.
+#[derive(Debug)]
+pub struct MetricsEncoder<'any> {
+  buffer: &'any mut Vector<u8>,
+}
+
+impl<'any> MetricsEncoder<'any> {
+  /// Appends data to `buffer`.
+  #[inline]
+  pub const fn new(buffer: &'any mut Vector<u8>) -> Self {
+    Self { buffer }
+  }
+
+  /// Writes a single counter sample.
+  #[inline]
+  pub fn counter(
+    &mut self,
+    name: &str,
+    labels: &[(&str, &str)],
+    counter: &Counter,
+  ) -> crate::Result<()> {
+    self.sample(name, "", labels, None, u64_string(counter.get()).as_str())
+  }
+
+  /// Writes the `HELP` and `TYPE` lines of a family.
+  #[inline]
+  pub fn family(&mut self, name: &str, help: &str, ty: MetricTy) -> crate::Result<()> {
+    let _ = self.buffer.extend_from_copyable_slices([
+      "# HELP ",
+      name,
+      " ",
+      help,
+      "\n# TYPE ",
+      name,
+      " ",
+      ty.as_str(),
+      "\n",
+    ])?;
+    Ok(())
+  }
+
+  /// Writes a single gauge sample.
+  #[inline]
+  pub fn gauge(&mut self, name: &str, labels: &[(&str, &str)], gauge: &Gauge) -> crate::Result<()> {
+    self.sample(name, "", labels, None, u64_string(gauge.get()).as_str())
+  }
+
+  /// Writes the cumulative buckets, the sum and the count of a histogram.
+  #[inline]
+  pub fn histogram(
+    &mut self,
+    name: &str,
+    labels: &[(&str, &str)],
+    histogram: &Histogram,
+  ) -> crate::Result<()> {
+    let count = u64_string(histogram.count());
+    for (bound, value) in histogram.cumulative_buckets() {
+      self.sample(name, "_bucket", labels, Some(bound), u64_string(value).as_str())?;
+    }
+    self.sample(name, "_bucket", labels, Some("+Inf"), count.as_str())?;
+    let sum = histogram.sum();
+    let micros = u32_string_pad(sum.subsec_micros(), ZERO, 6);
+    let mut sum_str = ArrayStringU8::<32>::new();
+    let _ = sum_str.push_strs([u64_string(sum.as_secs()).as_str(), ".", micros.as_str()])?;
+    self.sample(name, "_sum", labels, None, sum_str.as_str())?;
+    self.sample(name, "_count", labels, None, count.as_str())?;
+    Ok(())
+  }
+
+  fn sample(
+    &mut self,
+    name: &str,
+    suffix: &str,
+    labels: &[(&str, &str)],
+    le: Option<&str>,
+    value: &str,
+  ) -> crate::Result<()> {
+    let _ = self.buffer.extend_from_copyable_slices([name, suffix])?;
+    let mut iter = labels.iter().copied().chain(le.map(|el| ("le", el)));
+    if let Some((key, label_value)) = iter.next() {
+      self.buffer.push(b'{')?;
+      self.label(key, label_value)?;
+      for (local_key, local_value) in iter {
+        self.buffer.push(b',')?;
+        self.label(local_key, local_value)?;
+      }
+      self.buffer.push(b'}')?;
+    }
+    let _ = self.buffer.extend_from_copyable_slices([" ", value, "\n"])?;
+    Ok(())
+  }
+
+  fn label(&mut self, key: &str, value: &str) -> crate::Result<()> {
+    let _ = self.buffer.extend_from_copyable_slices([key, "=\""])?;
+    for byte in value.bytes() {
+      match byte {
+        b'"' => self.buffer.extend_from_copyable_slice(br#"\""#)?,
+        b'\\' => self.buffer.extend_from_copyable_slice(br"\\")?,
+        b'\n' => self.buffer.extend_from_copyable_slice(br"\n")?,
+        _ => self.buffer.push(byte)?,
+      }
+    }
+    self.buffer.push(b'"')?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    metrics::{Counter, Histogram, MetricTy, MetricsEncoder},
+  };
+  use core::time::Duration;
+
+  #[test]
+  fn encodes_counters() {
+    let counter = Counter::new();
+    counter.add(3);
+    let mut buffer = Vector::new();
+    let mut encoder = MetricsEncoder::new(&mut buffer);
+    encoder.family("foo_total", "Foo", MetricTy::Counter).unwrap();
+    encoder.counter("foo_total", &[("a", "b\"c"), ("d", "e")], &counter).unwrap();
+    encoder.counter("foo_total", &[], &counter).unwrap();
+    assert_eq!(
+      buffer.as_slice(),
+      concat!(
+        "# HELP foo_total Foo\n# TYPE foo_total counter\n",
+        "foo_total{a=\"b\\\"c\",d=\"e\"} 3\n",
+        "foo_total 3\n"
+      )
+      .as_bytes()
+    );
+  }
+
+  #[test]
+  fn encodes_histograms() {
+    let histogram = Histogram::new();
+    histogram.observe(Duration::from_micros(1_500));
+    histogram.observe(Duration::from_millis(3));
+    histogram.observe(Duration::from_secs(20));
+    let mut buffer = Vector::new();
+    MetricsEncoder::new(&mut buffer).histogram("bar", &[("x", "y")], &histogram).unwrap();
+    assert_eq!(
+      buffer.as_slice(),
+      concat!(
+        "bar_bucket{x=\"y\",le=\"0.001\"} 0\n",
+        "bar_bucket{x=\"y\",le=\"0.0025\"} 1\n",
+        "bar_bucket{x=\"y\",le=\"0.005\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"0.01\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"0.025\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"0.05\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"0.1\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"0.25\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"0.5\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"1\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"2.5\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"5\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"10\"} 2\n",
+        "bar_bucket{x=\"y\",le=\"+Inf\"} 3\n",
+        "bar_sum{x=\"y\"} 20.004500\n",
+        "bar_count{x=\"y\"} 3\n",
+      )
+      .as_bytes()
+    );
+  }
+}
@@ -0,0 +1,266 @@
+use crate::{
+  codec::u16_string,
+  collections::Vector,
+  metrics::{Counter, Gauge, Histogram, MetricTy, MetricsEncoder},
+  sync::{AtomicUsize, CachePadded, SyncMutex},
+};
+use alloc::string::String;
+use core::{hash::BuildHasher as _, sync::atomic::Ordering, time::Duration};
+use foldhash::fast::FixedState;
+use hashbrown::HashMap;
+
+const MAX_ROUTES: usize = 256;
+const OTHER_ROUTE: &str = "_other";
+const SHARDS_LEN: usize = 16;
+const SIDES: [&str; 2] = ["client", "server"];
+const TLS_PATHS: [&str; 4] = ["full", "full_hrr", "resumed", "resumed_hrr"];
+
+static GLOBAL: MetricsRegistry = MetricsRegistry::new();
+
+/// Collection of all built-in metrics.
+///
+/// Instrumented structures always record into [`Self::global`].
+#[derive(Debug)]
+pub struct MetricsRegistry {
+  pub(crate) http2_flow_control_stalls: Counter,
+  pub(crate) http2_streams_closed: [Counter; 2],
+  pub(crate) http2_streams_opened: [Counter; 2],
+  pub(crate) http_server_request_duration: Histogram,
+  pub(crate) pool_resources: Gauge,
+  pub(crate) pool_wait: Histogram,
+  pub(crate) postgres_query_duration: Histogram,
+  pub(crate) tls_handshake_duration: [Histogram; 4],
+  http_server_requests: [CachePadded<SyncMutex<RequestsShard>>; SHARDS_LEN],
+  http_server_routes: AtomicUsize,
+}
+
+impl MetricsRegistry {
+  const fn new() -> Self {
+    Self {
+      http2_flow_control_stalls: Counter::new(),
+      http2_streams_closed: [Counter::new(), Counter::new()],
+      http2_streams_opened: [Counter::new(), Counter::new()],
+      http_server_request_duration: Histogram::new(),
+      pool_resources: Gauge::new(),
+      pool_wait: Histogram::new(),
+      postgres_query_duration: Histogram::new(),
+      tls_handshake_duration: [
+        Histogram::new(),
+        Histogram::new(),
+        Histogram::new(),
+        Histogram::new(),
+      ],
+      http_server_requests: [const {
+        CachePadded(SyncMutex::new(HashMap::with_hasher(FixedState::with_seed(0))))
+      }; SHARDS_LEN],
+      http_server_routes: AtomicUsize::new(0),
+    }
+  }
+
+  /// Process-wide instance
+  #[inline]
+  pub fn global() -> &'static Self {
+    &GLOBAL
+  }
+
+  /// Writes all metrics using the Prometheus text exposition format.
+  #[inline]
+  pub fn encode_text(&self, buffer: &mut Vector<u8>) -> crate::Result<()> {
+    let mut enc = MetricsEncoder::new(buffer);
+    self.encode_http_server(&mut enc)?;
+    self.encode_http2(&mut enc)?;
+    enc.family(
+      "wtx_pool_resources",
+      "Number of pool resources currently held by callers",
+      MetricTy::Gauge,
+    )?;
+    enc.gauge("wtx_pool_resources", &[], &self.pool_resources)?;
+    enc.family(
+      "wtx_pool_wait_seconds",
+      "Time spent waiting for an available resource",
+      MetricTy::Histogram,
+    )?;
+    enc.histogram("wtx_pool_wait_seconds", &[], &self.pool_wait)?;
+    enc.family(
+      "wtx_postgres_query_duration_seconds",
+      "Latency of PostgreSQL queries",
+      MetricTy::Histogram,
+    )?;
+    enc.histogram("wtx_postgres_query_duration_seconds", &[], &self.postgres_query_duration)?;
+    enc.family(
+      "wtx_tls_handshake_duration_seconds",
+      "Duration of successful TLS handshakes",
+      MetricTy::Histogram,
+    )?;
+    for (path, histogram) in TLS_PATHS.into_iter().zip(&self.tls_handshake_duration) {
+      enc.histogram("wtx_tls_handshake_duration_seconds", &[("path", path)], histogram)?;
+    }
+    Ok(())
+  }
+
+  /// Records a handled HTTP request. Useful for servers that don't use
+  /// `MetricsMiddleware`.
+  ///
+  /// `route` should be a pattern like `/user/{id}` instead of a path to avoid an unbounded
+  /// number of series. Regardless, after a certain amount of distinct routes, new values are
+  /// grouped under `_other`.
+  #[inline]
+  pub fn record_http_server_request(
+    &self,
+    duration: Duration,
+    method: &'static str,
+    route: &str,
+    status: u16,
+  ) {
+    self.http_server_request_duration.observe(duration);
+    if !self.increment_http_server_request(method, route, status, false) {
+      let _ = self.increment_http_server_request(method, OTHER_ROUTE, status, true);
+    }
+  }
+
+  // Returns `false` if `route` is new and the limit of routes was reached.
+  fn increment_http_server_request(
+    &self,
+    method: &'static str,
+    route: &str,
+    status: u16,
+    is_other: bool,
+  ) -> bool {
+    let shard_idx = usize::try_from(FixedState::with_seed(0).hash_one(route)).unwrap_or_default();
+    let Some(shard) = self.http_server_requests.get(shard_idx % SHARDS_LEN) else {
+      return true;
+    };
+    let mut map = shard.lock();
+    let entries = if let Some(elem) = map.get_mut(route) {
+      elem
+    } else {
+      if !is_other && self.http_server_routes.fetch_add(1, Ordering::Relaxed) >= MAX_ROUTES {
+        let _ = self.http_server_routes.fetch_sub(1, Ordering::Relaxed);
+        return false;
+      }
+      map.entry(String::from(route)).or_default()
+    };
+    if let Some(entry) = entries.iter().find(|el| el.method == method && el.status == status) {
+      entry.counter.inc();
+    } else {
+      let counter = Counter::new();
+      counter.inc();
+      let _rslt = entries.push(RequestsEntry { counter, method, status });
+    }
+    true
+  }
+
+  fn encode_http2(&self, enc: &mut MetricsEncoder<'_>) -> crate::Result<()> {
+    enc.family(
+      "wtx_http2_flow_control_stalls_total",
+      "Number of times a sender waited for a window update",
+      MetricTy::Counter,
+    )?;
+    enc.counter("wtx_http2_flow_control_stalls_total", &[], &self.http2_flow_control_stalls)?;
+    enc.family(
+      "wtx_http2_streams_opened_total",
+      "Number of opened HTTP/2 streams",
+      MetricTy::Counter,
+    )?;
+    for (side, counter) in SIDES.into_iter().zip(&self.http2_streams_opened) {
+      enc.counter("wtx_http2_streams_opened_total", &[("side", side)], counter)?;
+    }
+    enc.family("wtx_http2_streams_active", "Number of active HTTP/2 streams", MetricTy::Gauge)?;
+    for (side, (opened, closed)) in
+      SIDES.into_iter().zip(self.http2_streams_opened.iter().zip(&self.http2_streams_closed))
+    {
+      let gauge = Gauge::new();
+      gauge.set(opened.get().saturating_sub(closed.get()));
+      enc.gauge("wtx_http2_streams_active", &[("side", side)], &gauge)?;
+    }
+    Ok(())
+  }
+
+  fn encode_http_server(&self, enc: &mut MetricsEncoder<'_>) -> crate::Result<()> {
+    enc.family(
+      "wtx_http_server_requests_total",
+      "Number of handled HTTP requests",
+      MetricTy::Counter,
+    )?;
+    for shard in &self.http_server_requests {
+      for (route, entries) in shard.lock().iter() {
+        for entry in entries {
+          let status = u16_string(entry.status);
+          let labels =
+            [("method", entry.method), ("route", route.as_str()), ("status", status.as_str())];
+          enc.counter("wtx_http_server_requests_total", &labels, &entry.counter)?;
+        }
+      }
+    }
+    enc.family(
+      "wtx_http_server_request_duration_seconds",
+      "Time spent handling HTTP requests",
+      MetricTy::Histogram,
+    )?;
+    enc.histogram(
+      "wtx_http_server_request_duration_seconds",
+      &[],
+      &self.http_server_request_duration,
+    )
+  }
+}
+
+// Methods and status codes of a route are few so their entries are linearly searched.
+type RequestsShard = HashMap<String, Vector<RequestsEntry>, FixedState>;
+
+#[derive(Debug)]
+struct RequestsEntry {
+  counter: Counter,
+  method: &'static str,
+  status: u16,
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    metrics::{MetricsRegistry, metrics_registry::MAX_ROUTES},
+  };
+  use alloc::format;
+  use core::time::Duration;
+
+  #[test]
+  fn records_requests() {
+    let registry = MetricsRegistry::new();
+    registry.record_http_server_request(Duration::from_millis(1), "GET", "/a", 200);
+    registry.record_http_server_request(Duration::from_millis(1), "GET", "/a", 200);
+    registry.record_http_server_request(Duration::from_millis(1), "POST", "/a", 404);
+    registry.http2_streams_opened[1].add(3);
+    registry.http2_streams_closed[1].inc();
+    let mut buffer = Vector::new();
+    registry.encode_text(&mut buffer).unwrap();
+    let contains = |needle: &[u8]| buffer.windows(needle.len()).any(|el| el == needle);
+    assert!(contains(
+      b"wtx_http_server_requests_total{method=\"GET\",route=\"/a\",status=\"200\"} 2\n"
+    ));
+    assert!(contains(
+      b"wtx_http_server_requests_total{method=\"POST\",route=\"/a\",status=\"404\"} 1\n"
+    ));
+    assert!(contains(b"wtx_http_server_request_duration_seconds_count 3\n"));
+    assert!(contains(b"wtx_http2_streams_active{side=\"server\"} 2\n"));
+  }
+
+  #[test]
+  fn groups_excessive_routes() {
+    let registry = MetricsRegistry::new();
+    for idx in 0..=MAX_ROUTES {
+      registry.record_http_server_request(Duration::ZERO, "GET", &format!("/{idx}"), 200);
+    }
+    registry.record_http_server_request(Duration::ZERO, "GET", "/0", 200);
+    registry.record_http_server_request(Duration::ZERO, "GET", "/foo", 200);
+    let mut buffer = Vector::new();
+    registry.encode_text(&mut buffer).unwrap();
+    let contains = |needle: &[u8]| buffer.windows(needle.len()).any(|el| el == needle);
+    assert!(contains(
+      b"wtx_http_server_requests_total{method=\"GET\",route=\"/0\",status=\"200\"} 2\n"
+    ));
+    assert!(contains(
+      b"wtx_http_server_requests_total{method=\"GET\",route=\"_other\",status=\"200\"} 2\n"
+    ));
+  }
+}
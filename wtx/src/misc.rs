@@ -4,7 +4,7 @@
 pub(crate) mod bytes_transfer;
 #[cfg(any(feature = "postgres", feature = "tls"))]
 pub(crate) mod counter_writer;
-#[cfg(feature = "http2")]
+#[cfg(any(feature = "http2", feature = "postgres"))]
 pub(crate) mod span;
 
 mod ascii;
@@ -30,4 +30,19 @@ impl Span {
       _elem: &self._elem,
     }
   }
+
+  /// Enters the span on every poll of `future` without holding the guard across suspension
+  /// points.
+  #[cfg(feature = "postgres")]
+  pub(crate) async fn instrument<F>(&self, future: F) -> F::Output
+  where
+    F: Future,
+  {
+    let mut future_pin = core::pin::pin!(future);
+    core::future::poll_fn(|cx| {
+      let _e = self.enter();
+      future_pin.as_mut().poll(cx)
+    })
+    .await
+  }
 }
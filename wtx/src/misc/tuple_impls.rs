@@ -112,6 +112,11 @@ macro_rules! impl_tuples {
             })*
             Ok(ControlFlow::Continue(()))
           }
+
+          #[inline]
+          fn route(&self, _mw_aux: &mut Self::Aux, _route: &[RouteMatch]) {
+            $( self.$T13.route(&mut _mw_aux.$T13, _route); )*
+          }
         }
 
         impl<$($T,)* DATA, ERR, STREAM> Endpoint<DATA, ERR, STREAM> for ($(PathParams<$T>,)*)
@@ -6,10 +6,13 @@ mod macros;
 mod buf_stream_reader;
 mod bytes_stream;
 mod connection_state;
+#[cfg(feature = "dns")]
+pub mod dns;
 #[cfg(feature = "embassy-net")]
 mod embassy_net;
 mod misc;
 mod net_error;
+pub mod proxy;
 mod role;
 #[cfg(feature = "std")]
 mod std;
@@ -246,7 +246,7 @@ impl BufStreamReader {
   /// number of filled elements.
   ///
   /// `reserve_len` is only used to create a buffer to allow external reads.
-  #[cfg(feature = "web-socket")]
+  #[cfg(any(feature = "http2-h2c", feature = "web-socket"))]
   pub(crate) async fn read_arbitrary<SR>(
     &mut self,
     reserve_len: usize,
@@ -270,8 +270,32 @@ impl BufStreamReader {
     Ok(Some(len))
   }
 
-  /// Both indices will be capped to avoid data corruption.
+  /// Fetches data into the following region without modifying indices, which means that dropping
+  /// the returned future doesn't lose any received bytes.
   #[cfg(feature = "web-socket")]
+  pub(crate) async fn read_following<SR>(
+    &mut self,
+    stream_reader: &mut SR,
+  ) -> crate::Result<Option<core::num::NonZeroUsize>>
+  where
+    SR: StreamReader,
+  {
+    self.manage_capacity(crate::web_socket::MAX_HEADER_LEN)?;
+    let (init, uninit) = self.buffer.split_at_spare_mut();
+    let Some(len) = stream_reader.read(uninit.into()).await? else {
+      cold_path();
+      return Ok(None);
+    };
+    let new_len = init.len().wrapping_add(len.get());
+    // SAFETY: `stream_reader.read` just initialized `len` buffer
+    unsafe {
+      self.buffer.set_len(new_len);
+    }
+    Ok(Some(len))
+  }
+
+  /// Both indices will be capped to avoid data corruption.
+  #[cfg(any(feature = "http2-h2c", feature = "web-socket"))]
   pub(crate) fn set_indices(&mut self, antecedent_end_idx: usize, current_end_idx: usize) {
     self.current_end_idx = current_end_idx.min(self.buffer.len());
     self.antecedent_end_idx = antecedent_end_idx.min(self.current_end_idx);
@@ -0,0 +1,36 @@
+//! Asynchronous DNS stub resolver with a TTL-aware cache and "Happy Eyeballs" connections.
+//!
+//! Queries are sent over UDP and retried over TCP when responses are truncated. Alternatively,
+//! DNS-over-TLS and DNS-over-HTTPS are supported through [`DnsTransport`].
+
+mod dns_cache;
+mod dns_config;
+mod dns_error;
+pub(crate) mod dns_message;
+mod dns_resolver;
+mod dns_transport;
+#[cfg(feature = "http")]
+mod doh_transport;
+mod dot_transport;
+mod happy_eyeballs;
+
+pub use dns_cache::DnsCache;
+pub use dns_config::DnsConfig;
+pub use dns_error::DnsError;
+pub use dns_resolver::DnsResolver;
+pub use dns_transport::DnsTransport;
+#[cfg(feature = "http")]
+pub use doh_transport::DohTransport;
+pub use dot_transport::{DOT_PORT, DotTransport};
+pub use happy_eyeballs::{CONNECTION_ATTEMPT_DELAY, connect_happy_eyeballs};
+
+create_enum! {
+  /// Types of resource records that can be queried.
+  #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+  pub enum DnsRecordTy<u16> {
+    /// `A` record, IPv4 address.
+    Ipv4 = (1),
+    /// `AAAA` record, IPv6 address.
+    Ipv6 = (28),
+  }
+}
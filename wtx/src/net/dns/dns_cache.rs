@@ -0,0 +1,91 @@
+use crate::collections::Vector;
+use alloc::string::String;
+use core::{net::IpAddr, time::Duration};
+use hashbrown::HashMap;
+
+/// Addresses of previously resolved names that are stored until their TTL expires.
+#[derive(Debug)]
+pub struct DnsCache {
+  entries: HashMap<String, DnsCacheEntry>,
+  max_len: usize,
+}
+
+impl DnsCache {
+  pub(crate) fn new(max_len: usize) -> Self {
+    Self { entries: HashMap::new(), max_len }
+  }
+
+  /// Removes all entries.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.entries.clear();
+  }
+
+  /// If there are no entries.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// Number of entries, including expired ones that weren't removed yet.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  /// Returns the addresses of `name` if they are not expired. `now` is the current number of
+  /// seconds since the UNIX epoch.
+  #[inline]
+  pub fn get(&self, name: &str, now: Duration) -> Option<&[IpAddr]> {
+    let entry = self.entries.get(name)?;
+    (entry.expires > now.as_secs()).then_some(entry.addrs.as_slice())
+  }
+
+  pub(crate) fn insert(&mut self, name: &str, addrs: Vector<IpAddr>, now: Duration, ttl: u32) {
+    if self.max_len == 0 || ttl == 0 {
+      return;
+    }
+    if self.entries.len() >= self.max_len && !self.entries.contains_key(name) {
+      self.entries.retain(|_, el| el.expires > now.as_secs());
+      if self.entries.len() >= self.max_len {
+        let oldest = self.entries.iter().min_by_key(|el| el.1.expires).map(|el| el.0.clone());
+        if let Some(elem) = oldest {
+          let _entry = self.entries.remove(&elem);
+        }
+      }
+    }
+    let expires = now.as_secs().saturating_add(ttl.into());
+    let _prev = self.entries.insert(String::from(name), DnsCacheEntry { addrs, expires });
+  }
+}
+
+#[derive(Debug)]
+struct DnsCacheEntry {
+  addrs: Vector<IpAddr>,
+  expires: u64,
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{collections::Vector, net::dns::DnsCache};
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+  };
+
+  #[test]
+  fn respects_ttl_and_capacity() {
+    let addrs = || Vector::from_iterator([IpAddr::V4(Ipv4Addr::LOCALHOST)]).unwrap();
+    let now = Duration::from_secs(100);
+    let mut cache = DnsCache::new(2);
+    cache.insert("a.com", addrs(), now, 10);
+    cache.insert("b.com", addrs(), now, 20);
+    cache.insert("c.com", addrs(), now, 30);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("a.com", now).is_none());
+    assert!(cache.get("b.com", now).is_some());
+    assert!(cache.get("b.com", now.saturating_add(Duration::from_secs(20))).is_none());
+    cache.insert("d.com", addrs(), now, 0);
+    assert!(cache.get("d.com", now).is_none());
+  }
+}
@@ -0,0 +1,186 @@
+use crate::collections::Vector;
+use alloc::string::String;
+use core::{
+  net::{IpAddr, SocketAddr},
+  time::Duration,
+};
+
+/// Parameters of [`crate::net::dns::DnsResolver`].
+#[derive(Debug)]
+pub struct DnsConfig {
+  attempts: u8,
+  hosts: Vector<(String, IpAddr)>,
+  max_cache_len: usize,
+  nameservers: Vector<SocketAddr>,
+  timeout: Duration,
+}
+
+impl DnsConfig {
+  /// Instance without hosts or name servers.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      attempts: 2,
+      hosts: Vector::new(),
+      max_cache_len: 1024,
+      nameservers: Vector::new(),
+      timeout: Duration::from_secs(5),
+    }
+  }
+
+  /// Constructs itself using the contents of a `resolv.conf` and a `hosts` file.
+  ///
+  /// Only the `nameserver` entries and the `attempts` and `timeout` options are considered.
+  /// Search lists are ignored.
+  #[inline]
+  pub fn from_files_data(resolv_conf: &str, hosts: &str) -> crate::Result<Self> {
+    let mut this = Self::new();
+    for line in lines(resolv_conf) {
+      let mut parts = line.split_ascii_whitespace();
+      match parts.next() {
+        Some("nameserver") => {
+          if let Some(elem) = parts.next().and_then(|el| el.parse::<IpAddr>().ok()) {
+            this.nameservers.push(SocketAddr::new(elem, 53))?;
+          }
+        }
+        Some("options") => {
+          for option in parts {
+            if let Some(elem) = option.strip_prefix("attempts:").and_then(|el| el.parse().ok()) {
+              this.attempts = elem;
+            } else if let Some(elem) =
+              option.strip_prefix("timeout:").and_then(|el| el.parse().ok())
+            {
+              this.timeout = Duration::from_secs(elem);
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+    for line in lines(hosts) {
+      let mut parts = line.split_ascii_whitespace();
+      let Some(ip) = parts.next().and_then(|el| el.parse::<IpAddr>().ok()) else {
+        continue;
+      };
+      for name in parts {
+        let mut lowercase = String::from(name);
+        lowercase.make_ascii_lowercase();
+        this.hosts.push((lowercase, ip))?;
+      }
+    }
+    Ok(this)
+  }
+
+  /// Constructs itself using the `/etc/resolv.conf` and `/etc/hosts` files. Missing files are
+  /// treated as empty files.
+  #[cfg(feature = "std")]
+  #[inline]
+  pub fn from_system() -> crate::Result<Self> {
+    let resolv_conf = std::fs::read_to_string("/etc/resolv.conf").unwrap_or_default();
+    let hosts = std::fs::read_to_string("/etc/hosts").unwrap_or_default();
+    Self::from_files_data(&resolv_conf, &hosts)
+  }
+
+  /// Number of times that all name servers are queried before giving up.
+  #[inline]
+  pub const fn attempts(&self) -> u8 {
+    self.attempts
+  }
+
+  /// Static mapping of lowercase names to addresses that take precedence over name servers.
+  #[inline]
+  pub fn hosts(&self) -> &[(String, IpAddr)] {
+    &self.hosts
+  }
+
+  /// Mutable version of [`Self::hosts`].
+  #[inline]
+  pub const fn hosts_mut(&mut self) -> &mut Vector<(String, IpAddr)> {
+    &mut self.hosts
+  }
+
+  /// Maximum number of cached names.
+  #[inline]
+  pub const fn max_cache_len(&self) -> usize {
+    self.max_cache_len
+  }
+
+  /// Name servers that are queried in order.
+  #[inline]
+  pub fn nameservers(&self) -> &[SocketAddr] {
+    &self.nameservers
+  }
+
+  /// Mutable version of [`Self::nameservers`].
+  #[inline]
+  pub const fn nameservers_mut(&mut self) -> &mut Vector<SocketAddr> {
+    &mut self.nameservers
+  }
+
+  /// Sets the number of times that all name servers are queried before giving up.
+  #[inline]
+  #[must_use]
+  pub const fn set_attempts(mut self, value: u8) -> Self {
+    self.attempts = value;
+    self
+  }
+
+  /// Sets the maximum number of cached names.
+  #[inline]
+  #[must_use]
+  pub const fn set_max_cache_len(mut self, value: usize) -> Self {
+    self.max_cache_len = value;
+    self
+  }
+
+  /// Sets the maximum duration of each query.
+  #[inline]
+  #[must_use]
+  pub const fn set_timeout(mut self, value: Duration) -> Self {
+    self.timeout = value;
+    self
+  }
+
+  /// Maximum duration of each query.
+  #[inline]
+  pub const fn timeout(&self) -> Duration {
+    self.timeout
+  }
+}
+
+impl Default for DnsConfig {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+fn lines(data: &str) -> impl Iterator<Item = &str> {
+  data.lines().map(|el| el.split(['#', ';']).next().unwrap_or_default().trim())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::net::dns::DnsConfig;
+  use core::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+  };
+
+  #[test]
+  fn parses_files() {
+    let config = DnsConfig::from_files_data(
+      "# Comment\nnameserver 10.0.0.1\nnameserver foo\nsearch foo.com\noptions timeout:2 attempts:3",
+      "127.0.0.1 localhost Foo.local # Comment\n::1 localhost\ninvalid line",
+    )
+    .unwrap();
+    assert_eq!(config.attempts(), 3);
+    assert_eq!(config.hosts().len(), 3);
+    assert_eq!(config.hosts()[1], ("foo.local".into(), IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    assert_eq!(
+      config.nameservers(),
+      &[SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 53)]
+    );
+    assert_eq!(config.timeout(), Duration::from_secs(2));
+  }
+}
@@ -0,0 +1,22 @@
+/// DNS error
+#[derive(Clone, Copy, Debug)]
+pub enum DnsError {
+  /// Names must have non-empty labels of at most 63 bytes and a total length of at most 253 bytes.
+  InvalidName,
+  /// Received a message that doesn't conform to the DNS wire format.
+  InvalidMessage,
+  /// There are no configured name servers.
+  NoNameservers,
+  /// The name server didn't return any `A` or `AAAA` record.
+  NoRecords,
+  /// The queried domain doesn't exist.
+  NonExistentDomain,
+  /// The name server returned an error with the contained response code.
+  ServerFailure(u8),
+  /// A DNS-over-HTTPS server returned a response with the contained non-successful status code.
+  UnexpectedHttpStatus(u16),
+  /// Received a response that doesn't belong to the sent query.
+  UnexpectedId,
+  /// Received a response whose question doesn't match the sent query.
+  UnexpectedQuestion,
+}
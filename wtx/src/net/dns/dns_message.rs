@@ -0,0 +1,253 @@
+// https://datatracker.ietf.org/doc/html/rfc1035#section-4
+
+use crate::{
+  collections::Vector,
+  net::dns::{DnsError, DnsRecordTy},
+};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+const CLASS_IN: u16 = 1;
+const FLAG_QR: u16 = 0b1000_0000_0000_0000;
+const FLAG_RD: u16 = 0b0000_0001_0000_0000;
+const FLAG_TC: u16 = 0b0000_0010_0000_0000;
+const HEADER_LEN: usize = 12;
+const MAX_NAME_LEN: usize = 255;
+const MAX_LABEL_LEN: usize = 63;
+const RCODE_NXDOMAIN: u8 = 3;
+
+/// Addresses of a response
+#[derive(Debug)]
+pub(crate) struct DnsAnswer {
+  pub(crate) addrs: Vector<IpAddr>,
+  pub(crate) is_truncated: bool,
+  pub(crate) min_ttl: u32,
+}
+
+/// Writes a recursive query of `name` with the record type `ty`.
+pub(crate) fn encode_query(
+  buffer: &mut Vector<u8>,
+  id: u16,
+  name: &str,
+  ty: DnsRecordTy,
+) -> crate::Result<()> {
+  let name_trimmed = name.strip_suffix('.').unwrap_or(name);
+  if name_trimmed.is_empty() || name_trimmed.len() > MAX_NAME_LEN.wrapping_sub(2) {
+    return Err(DnsError::InvalidName.into());
+  }
+  let [id0, id1] = id.to_be_bytes();
+  let [flags0, flags1] = FLAG_RD.to_be_bytes();
+  buffer.extend_from_copyable_slice(&[id0, id1, flags0, flags1, 0, 1, 0, 0, 0, 0, 0, 0])?;
+  for label in name_trimmed.split('.') {
+    if label.is_empty() || label.len() > MAX_LABEL_LEN {
+      return Err(DnsError::InvalidName.into());
+    }
+    buffer.push(u8::try_from(label.len()).map_err(|_err| DnsError::InvalidName)?)?;
+    buffer.extend_from_copyable_slice(label.as_bytes())?;
+  }
+  let [ty0, ty1] = u16::from(ty).to_be_bytes();
+  let [class0, class1] = CLASS_IN.to_be_bytes();
+  buffer.extend_from_copyable_slice(&[0, ty0, ty1, class0, class1])?;
+  Ok(())
+}
+
+/// Extracts all `A` and `AAAA` records of a response to the query of `name` with the record type
+/// `ty` that was identified by `id`.
+///
+/// Truncated responses are returned without records.
+pub(crate) fn decode_answer(
+  bytes: &[u8],
+  id: u16,
+  name: &str,
+  ty: DnsRecordTy,
+) -> crate::Result<DnsAnswer> {
+  let [id0, id1, flags0, flags1, qd0, qd1, an0, an1, _, _, _, _, after_header @ ..] = bytes else {
+    return Err(DnsError::InvalidMessage.into());
+  };
+  if u16::from_be_bytes([*id0, *id1]) != id {
+    return Err(DnsError::UnexpectedId.into());
+  }
+  let flags = u16::from_be_bytes([*flags0, *flags1]);
+  if flags & FLAG_QR == 0 {
+    return Err(DnsError::InvalidMessage.into());
+  }
+  if u16::from_be_bytes([*qd0, *qd1]) != 1 {
+    return Err(DnsError::UnexpectedQuestion.into());
+  }
+  let mut idx = HEADER_LEN.wrapping_add(question_len(after_header, name, ty)?);
+  let mut answer = DnsAnswer { addrs: Vector::new(), is_truncated: false, min_ttl: u32::MAX };
+  if flags & FLAG_TC != 0 {
+    answer.is_truncated = true;
+    return Ok(answer);
+  }
+  let [_, rcode] = flags.to_be_bytes();
+  match rcode & 0b1111 {
+    0 => {}
+    RCODE_NXDOMAIN => return Err(DnsError::NonExistentDomain.into()),
+    other => return Err(DnsError::ServerFailure(other).into()),
+  }
+  for _ in 0..u16::from_be_bytes([*an0, *an1]) {
+    idx = skip_name(bytes, idx)?;
+    let Some([ty0, ty1, class0, class1, ttl0, ttl1, ttl2, ttl3, len0, len1, after_fixed @ ..]) =
+      bytes.get(idx..)
+    else {
+      return Err(DnsError::InvalidMessage.into());
+    };
+    let len = usize::from(u16::from_be_bytes([*len0, *len1]));
+    let Some(data) = after_fixed.get(..len) else {
+      return Err(DnsError::InvalidMessage.into());
+    };
+    idx = idx.wrapping_add(10).wrapping_add(len);
+    if u16::from_be_bytes([*class0, *class1]) != CLASS_IN {
+      continue;
+    }
+    let addr = match DnsRecordTy::try_from(u16::from_be_bytes([*ty0, *ty1])) {
+      Ok(DnsRecordTy::Ipv4) => match <[u8; 4]>::try_from(data) {
+        Ok(elem) => IpAddr::V4(Ipv4Addr::from(elem)),
+        Err(_) => continue,
+      },
+      Ok(DnsRecordTy::Ipv6) => match <[u8; 16]>::try_from(data) {
+        Ok(elem) => IpAddr::V6(Ipv6Addr::from(elem)),
+        Err(_) => continue,
+      },
+      Err(_) => continue,
+    };
+    answer.min_ttl = answer.min_ttl.min(u32::from_be_bytes([*ttl0, *ttl1, *ttl2, *ttl3]));
+    answer.addrs.push(addr)?;
+  }
+  Ok(answer)
+}
+
+// Compares the echoed question with the sent query, returning the number of bytes it occupies.
+fn question_len(bytes: &[u8], name: &str, ty: DnsRecordTy) -> crate::Result<usize> {
+  let name_trimmed = name.strip_suffix('.').unwrap_or(name);
+  let mut idx: usize = 0;
+  for label in name_trimmed.split('.') {
+    let Some([len, rest @ ..]) = bytes.get(idx..) else {
+      return Err(DnsError::InvalidMessage.into());
+    };
+    let received = rest.get(..usize::from(*len)).unwrap_or_default();
+    if usize::from(*len) != label.len() || !received.eq_ignore_ascii_case(label.as_bytes()) {
+      return Err(DnsError::UnexpectedQuestion.into());
+    }
+    idx = idx.wrapping_add(label.len()).wrapping_add(1);
+  }
+  let Some([0, ty0, ty1, class0, class1, ..]) = bytes.get(idx..) else {
+    return Err(DnsError::UnexpectedQuestion.into());
+  };
+  if u16::from_be_bytes([*ty0, *ty1]) != u16::from(ty)
+    || u16::from_be_bytes([*class0, *class1]) != CLASS_IN
+  {
+    return Err(DnsError::UnexpectedQuestion.into());
+  }
+  Ok(idx.wrapping_add(5))
+}
+
+// Returns the index after the name that starts at `idx`.
+fn skip_name(bytes: &[u8], mut idx: usize) -> crate::Result<usize> {
+  loop {
+    let Some(len) = bytes.get(idx) else {
+      return Err(DnsError::InvalidMessage.into());
+    };
+    match len {
+      0 => return Ok(idx.wrapping_add(1)),
+      0b1100_0000..=u8::MAX => return Ok(idx.wrapping_add(2)),
+      1..=0b0011_1111 => idx = idx.wrapping_add(usize::from(*len)).wrapping_add(1),
+      _ => return Err(DnsError::InvalidMessage.into()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    net::dns::{
+      DnsError, DnsRecordTy,
+      dns_message::{decode_answer, encode_query},
+    },
+  };
+  use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+  #[test]
+  fn decodes_answers() {
+    let mut bytes = Vector::new();
+    encode_query(&mut bytes, 7, "foo.com", DnsRecordTy::Ipv4).unwrap();
+    bytes[2] = 0b1000_0001;
+    bytes[3] = 0b1000_0000;
+    bytes[7] = 2;
+    let a_record: [u8; 16] = [0b1100_0000, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 127, 0, 0, 1];
+    let aaaa_record: [u8; 28] = [
+      0b1100_0000,
+      12,
+      0,
+      28,
+      0,
+      1,
+      0,
+      0,
+      0,
+      30,
+      0,
+      16,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      0,
+      1,
+    ];
+    bytes.extend_from_copyable_slices([a_record.as_slice(), aaaa_record.as_slice()]).unwrap();
+    let answer = decode_answer(&bytes, 7, "FOO.com", DnsRecordTy::Ipv4).unwrap();
+    assert_eq!(
+      answer.addrs.as_slice(),
+      &[IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::LOCALHOST)]
+    );
+    assert_eq!(answer.min_ttl, 30);
+    assert!(decode_answer(&bytes, 8, "foo.com", DnsRecordTy::Ipv4).is_err());
+  }
+
+  #[test]
+  fn rejects_answers_of_other_questions() {
+    let mut bytes = Vector::new();
+    encode_query(&mut bytes, 7, "foo.com", DnsRecordTy::Ipv4).unwrap();
+    bytes[2] = 0b1000_0001;
+    bytes[3] = 0b1000_0000;
+    let is_unexpected = |local_bytes: &[u8], name, ty| {
+      matches!(
+        decode_answer(local_bytes, 7, name, ty),
+        Err(crate::Error::DnsError(DnsError::UnexpectedQuestion))
+      )
+    };
+    assert!(is_unexpected(&bytes, "bar.com", DnsRecordTy::Ipv4));
+    assert!(is_unexpected(&bytes, "foo.co", DnsRecordTy::Ipv4));
+    assert!(is_unexpected(&bytes, "foo.com.br", DnsRecordTy::Ipv4));
+    assert!(is_unexpected(&bytes, "foo.com", DnsRecordTy::Ipv6));
+    assert!(!is_unexpected(&bytes, "foo.com", DnsRecordTy::Ipv4));
+    bytes[5] = 2;
+    assert!(is_unexpected(&bytes, "foo.com", DnsRecordTy::Ipv4));
+  }
+
+  #[test]
+  fn encodes_queries() {
+    let mut bytes = Vector::new();
+    encode_query(&mut bytes, 258, "foo.com.", DnsRecordTy::Ipv6).unwrap();
+    assert_eq!(
+      bytes.as_slice(),
+      &[
+        1, 2, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 3, b'f', b'o', b'o', 3, b'c', b'o', b'm', 0, 0, 28, 0,
+        1
+      ]
+    );
+    assert!(encode_query(&mut bytes, 1, "foo..com", DnsRecordTy::Ipv4).is_err());
+  }
+}
@@ -0,0 +1,362 @@
+use crate::{
+  calendar::Instant,
+  collections::Vector,
+  executor::Executor,
+  futures::Timeout,
+  misc::Either,
+  net::{
+    TcpParams, TcpStream, UdpStream,
+    dns::{
+      DnsCache, DnsConfig, DnsError, DnsRecordTy, DnsTransport, DotTransport,
+      dns_message::{DnsAnswer, decode_answer, encode_query},
+    },
+  },
+  rng::Rng,
+  sync::SyncMutex,
+};
+use alloc::string::String;
+use core::{
+  net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+  time::Duration,
+};
+
+// Maximum size of DNS messages transported over UDP without extensions.
+const MAX_UDP_LEN: usize = 512;
+
+/// Asynchronous stub resolver that delegates recursion to the configured name servers.
+///
+/// Names are looked up in the static hosts, then in the cache and only then queried over UDP or
+/// through a [`DnsTransport`]. Truncated UDP responses are retried over TCP.
+#[derive(Debug)]
+pub struct DnsResolver<RNG> {
+  cache: SyncMutex<DnsCache>,
+  config: DnsConfig,
+  rng: SyncMutex<RNG>,
+}
+
+impl<RNG> DnsResolver<RNG>
+where
+  RNG: Rng,
+{
+  /// New instance. `rng` generates the identifiers of the queries.
+  #[inline]
+  pub fn new(config: DnsConfig, rng: RNG) -> Self {
+    Self {
+      cache: SyncMutex::new(DnsCache::new(config.max_cache_len())),
+      config,
+      rng: SyncMutex::new(rng),
+    }
+  }
+
+  /// See [`DnsCache`].
+  #[inline]
+  pub const fn cache(&self) -> &SyncMutex<DnsCache> {
+    &self.cache
+  }
+
+  /// See [`DnsConfig`].
+  #[inline]
+  pub const fn config(&self) -> &DnsConfig {
+    &self.config
+  }
+
+  /// Resolves `name` into IPv6 and IPv4 addresses. IP literals are returned as is.
+  ///
+  /// `udp` must be a socket bound to an address of the same family of the name servers. `TS` is
+  /// only used when responses are truncated.
+  #[inline]
+  pub async fn resolve<TS, US>(&self, name: &str, udp: &mut US) -> crate::Result<Vector<IpAddr>>
+  where
+    TS: TcpStream,
+    US: UdpStream,
+  {
+    let now = Instant::now_timestamp()?;
+    let lowercase = match self.local_addrs(name, now)? {
+      Either::Left(elem) => return Ok(elem),
+      Either::Right(elem) => elem,
+    };
+    self.query_nameservers::<TS, US>(&lowercase, now, udp).await
+  }
+
+  /// Similar to [`Self::resolve`] but the UDP socket is created by `EX` and bound to an
+  /// unspecified address of the same family of the first name server.
+  ///
+  /// Sockets are only created when names need to be queried.
+  #[inline]
+  pub async fn resolve_with_executor<EX>(&self, name: &str) -> crate::Result<Vector<IpAddr>>
+  where
+    EX: Executor,
+  {
+    let now = Instant::now_timestamp()?;
+    let lowercase = match self.local_addrs(name, now)? {
+      Either::Left(elem) => return Ok(elem),
+      Either::Right(elem) => elem,
+    };
+    let Some(nameserver) = self.config.nameservers().first() else {
+      return Err(DnsError::NoNameservers.into());
+    };
+    let ip = if nameserver.is_ipv4() {
+      IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+      IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+    };
+    let mut udp = EX::bind_udp(SocketAddr::new(ip, 0)).await?;
+    self.query_nameservers::<EX::TcpStream, _>(&lowercase, now, &mut udp).await
+  }
+
+  /// Similar to [`Self::resolve`] but queries are sent through `transport` instead of the
+  /// configured name servers, which allows the usage of encrypted protocols like DNS-over-TLS or
+  /// DNS-over-HTTPS.
+  #[inline]
+  pub async fn resolve_with_transport<T>(
+    &self,
+    name: &str,
+    transport: &mut T,
+  ) -> crate::Result<Vector<IpAddr>>
+  where
+    T: DnsTransport,
+  {
+    let now = Instant::now_timestamp()?;
+    let lowercase = match self.local_addrs(name, now)? {
+      Either::Left(elem) => return Ok(elem),
+      Either::Right(elem) => elem,
+    };
+    let mut last_err = None;
+    for _ in 0..self.config.attempts().max(1) {
+      let future = query_transport(&lowercase, transport);
+      let rslt = Timeout::new(future, self.config.timeout())?.await?;
+      if let Some(elem) = self.manage_rslt(&lowercase, now, rslt, &mut last_err)? {
+        return Ok(elem);
+      }
+    }
+    Err(last_err.unwrap_or(crate::Error::ExpiredFuture))
+  }
+
+  // Returns the addresses of IP literals, static hosts or cached names. Otherwise, returns the
+  // normalized name that should be queried.
+  fn local_addrs(
+    &self,
+    name: &str,
+    now: Duration,
+  ) -> crate::Result<Either<Vector<IpAddr>, String>> {
+    if let Ok(elem) = name.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>() {
+      return Ok(Either::Left(Vector::from_iterator([elem])?));
+    }
+    let mut lowercase = String::from(name.strip_suffix('.').unwrap_or(name));
+    lowercase.make_ascii_lowercase();
+    let hosts = Vector::from_iterator(
+      self.config.hosts().iter().filter(|el| el.0 == lowercase).map(|el| el.1),
+    )?;
+    if !hosts.is_empty() {
+      return Ok(Either::Left(hosts));
+    }
+    if let Some(elem) = self.cache.lock().get(&lowercase, now) {
+      return Ok(Either::Left(Vector::from_cloneable_slice(elem)?));
+    }
+    Ok(Either::Right(lowercase))
+  }
+
+  // Caches successful results, returns conclusive errors and stores the others in `last_err`.
+  fn manage_rslt(
+    &self,
+    name: &str,
+    now: Duration,
+    rslt: Option<crate::Result<(Vector<IpAddr>, u32)>>,
+    last_err: &mut Option<crate::Error>,
+  ) -> crate::Result<Option<Vector<IpAddr>>> {
+    match rslt {
+      Some(Ok((addrs, ttl))) => {
+        self.cache.lock().insert(name, addrs.clone(), now, ttl);
+        Ok(Some(addrs))
+      }
+      Some(Err(
+        err @ crate::Error::DnsError(DnsError::NoRecords | DnsError::NonExistentDomain),
+      )) => Err(err),
+      Some(Err(err)) => {
+        *last_err = Some(err);
+        Ok(None)
+      }
+      None => {
+        *last_err = Some(crate::Error::ExpiredFuture);
+        Ok(None)
+      }
+    }
+  }
+
+  // Sends `AAAA` and `A` queries at the same time.
+  async fn query<TS, US>(
+    &self,
+    name: &str,
+    nameserver: SocketAddr,
+    udp: &mut US,
+  ) -> crate::Result<(Vector<IpAddr>, u32)>
+  where
+    TS: TcpStream,
+    US: UdpStream,
+  {
+    let [id0, id1, id2, id3] = self.rng.lock().u8_4();
+    let id6 = u16::from_be_bytes([id0, id1]);
+    let mut id4 = u16::from_be_bytes([id2, id3]);
+    if id4 == id6 {
+      id4 = id4.wrapping_add(1);
+    }
+    let mut pending = [(id6, DnsRecordTy::Ipv6), (id4, DnsRecordTy::Ipv4)];
+    let mut buffer = Vector::new();
+    for (id, ty) in pending {
+      buffer.clear();
+      encode_query(&mut buffer, id, name, ty)?;
+      let _len = udp.send_to(&mut buffer, nameserver).await?;
+    }
+    let mut addrs = Vector::new();
+    let mut remaining: u8 = 2;
+    let mut ttl = u32::MAX;
+    let mut udp_buffer = [0; MAX_UDP_LEN];
+    while remaining > 0 {
+      let (len, addr) = udp.recv_from(&mut udp_buffer).await?;
+      if addr != nameserver {
+        continue;
+      }
+      let bytes = udp_buffer.get(..len).unwrap_or_default();
+      for (id, ty) in &mut pending {
+        if *id == 0 {
+          continue;
+        }
+        let answer = match decode_answer(bytes, *id, name, *ty) {
+          Ok(elem) => elem,
+          Err(crate::Error::DnsError(DnsError::UnexpectedId | DnsError::UnexpectedQuestion)) => {
+            continue;
+          }
+          Err(err) => return Err(err),
+        };
+        let DnsAnswer { addrs: local_addrs, min_ttl, .. } = if answer.is_truncated {
+          query_tcp::<TS>(*id, name, nameserver, *ty).await?
+        } else {
+          answer
+        };
+        addrs.extend_from_copyable_slice(&local_addrs)?;
+        if !local_addrs.is_empty() {
+          ttl = ttl.min(min_ttl);
+        }
+        *id = 0;
+        remaining = remaining.wrapping_sub(1);
+        break;
+      }
+    }
+    if addrs.is_empty() {
+      return Err(DnsError::NoRecords.into());
+    }
+    Ok((addrs, ttl))
+  }
+
+  // Tries all name servers until one of them returns a conclusive result.
+  async fn query_nameservers<TS, US>(
+    &self,
+    name: &str,
+    now: Duration,
+    udp: &mut US,
+  ) -> crate::Result<Vector<IpAddr>>
+  where
+    TS: TcpStream,
+    US: UdpStream,
+  {
+    if self.config.nameservers().is_empty() {
+      return Err(DnsError::NoNameservers.into());
+    }
+    let mut last_err = None;
+    for _ in 0..self.config.attempts().max(1) {
+      for nameserver in self.config.nameservers() {
+        let future = self.query::<TS, US>(name, *nameserver, udp);
+        let rslt = Timeout::new(future, self.config.timeout())?.await?;
+        if let Some(elem) = self.manage_rslt(name, now, rslt, &mut last_err)? {
+          return Ok(elem);
+        }
+      }
+    }
+    Err(last_err.unwrap_or_else(|| DnsError::NoNameservers.into()))
+  }
+}
+
+// Streams don't need to match responses with identifiers so queries are sent one after another
+// with an identifier of zero, as recommended by RFC 8484.
+async fn query_transport<T>(name: &str, transport: &mut T) -> crate::Result<(Vector<IpAddr>, u32)>
+where
+  T: DnsTransport,
+{
+  let mut addrs = Vector::new();
+  let mut bytes = Vector::new();
+  let mut ttl = u32::MAX;
+  for ty in [DnsRecordTy::Ipv6, DnsRecordTy::Ipv4] {
+    bytes.clear();
+    encode_query(&mut bytes, 0, name, ty)?;
+    transport.exchange(&mut bytes).await?;
+    let answer = decode_answer(&bytes, 0, name, ty)?;
+    addrs.extend_from_copyable_slice(&answer.addrs)?;
+    if !answer.addrs.is_empty() {
+      ttl = ttl.min(answer.min_ttl);
+    }
+  }
+  if addrs.is_empty() {
+    return Err(DnsError::NoRecords.into());
+  }
+  Ok((addrs, ttl))
+}
+
+// https://datatracker.ietf.org/doc/html/rfc7766
+async fn query_tcp<TS>(
+  id: u16,
+  name: &str,
+  nameserver: SocketAddr,
+  ty: DnsRecordTy,
+) -> crate::Result<DnsAnswer>
+where
+  TS: TcpStream,
+{
+  let stream = TS::connect(nameserver, TcpParams::default()).await?;
+  let mut bytes = Vector::new();
+  encode_query(&mut bytes, id, name, ty)?;
+  DotTransport::new(stream).exchange(&mut bytes).await?;
+  decode_answer(&bytes, id, name, ty)
+}
+
+#[cfg(test)]
+mod tests {
+  // FIXME(MIRI): socket support
+  #[cfg(not(feature = "tokio"))]
+  #[cfg_attr(miri, ignore)]
+  #[wtx::test]
+  async fn resolves_with_executor_sockets() {
+    use crate::{
+      executor::StdExecutor,
+      net::dns::{DnsConfig, DnsResolver},
+      rng::{SeedableRng as _, Xorshift64},
+    };
+    use core::net::{IpAddr, Ipv4Addr};
+    use std::{net::UdpSocket, thread};
+
+    let nameserver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let mut config = DnsConfig::new();
+    config.nameservers_mut().push(nameserver.local_addr().unwrap()).unwrap();
+    let jh = thread::spawn(move || {
+      for _ in 0..2 {
+        let mut buffer = [0; 512];
+        let (len, addr) = nameserver.recv_from(&mut buffer).unwrap();
+        let mut res = buffer[..len].to_vec();
+        res[2] |= 0b1000_0000;
+        res[3] = 0b1000_0000;
+        if res[len - 3] == 1 {
+          res[7] = 1;
+          res.extend_from_slice(&[0b1100_0000, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 127, 0, 0, 1]);
+        }
+        let _ = nameserver.send_to(&res, addr).unwrap();
+      }
+    });
+    let resolver = DnsResolver::new(config, Xorshift64::from_simple_seed().unwrap());
+    let addrs = resolver.resolve_with_executor::<StdExecutor>("foo.com").await.unwrap();
+    assert_eq!(addrs.as_slice(), &[IpAddr::V4(Ipv4Addr::LOCALHOST)]);
+    jh.join().unwrap();
+    assert_eq!(
+      resolver.resolve_with_executor::<StdExecutor>("FOO.com").await.unwrap().as_slice(),
+      &[IpAddr::V4(Ipv4Addr::LOCALHOST)]
+    );
+  }
+}
@@ -0,0 +1,18 @@
+use crate::collections::Vector;
+
+/// Reliable exchange of encoded DNS messages with a name server, for example, through
+/// [`crate::net::dns::DotTransport`].
+pub trait DnsTransport {
+  /// Sends the encoded query contained in `bytes` and replaces it with the encoded response.
+  fn exchange(&mut self, bytes: &mut Vector<u8>) -> impl Future<Output = crate::Result<()>>;
+}
+
+impl<T> DnsTransport for &mut T
+where
+  T: DnsTransport,
+{
+  #[inline]
+  async fn exchange(&mut self, bytes: &mut Vector<u8>) -> crate::Result<()> {
+    (**self).exchange(bytes).await
+  }
+}
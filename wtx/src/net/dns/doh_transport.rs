@@ -0,0 +1,148 @@
+use crate::{
+  codec::{Base64Alphabet, base64_encode, base64_encoded_len},
+  collections::Vector,
+  http::{Header, Headers, HttpClient, KnownHeaderName, Method, Mime, ReqBuilder, StatusCode},
+  net::{
+    UriString,
+    dns::{DnsError, DnsTransport},
+  },
+};
+use alloc::vec;
+
+/// DNS-over-HTTPS (RFC 8484) transport that works with any [`HttpClient`], for example, HTTP/2
+/// connections or pools.
+///
+/// Queries are sent with `POST` requests by default. `GET` requests place queries in the `dns`
+/// parameter of the URI, which must not have a query of its own.
+#[derive(Debug)]
+pub struct DohTransport<C> {
+  client: C,
+  enc_buffer: Vector<u8>,
+  is_get: bool,
+  uri: UriString,
+}
+
+impl<C> DohTransport<C>
+where
+  C: HttpClient,
+{
+  /// New instance that sends queries to `uri`, for example, `https://dns.example/dns-query`.
+  #[inline]
+  pub const fn new(client: C, uri: UriString) -> Self {
+    Self { client, enc_buffer: Vector::new(), is_get: false, uri }
+  }
+
+  /// If queries should be sent with `GET` requests, which are more friendly to HTTP caches.
+  #[inline]
+  #[must_use]
+  pub const fn set_is_get(mut self, value: bool) -> Self {
+    self.is_get = value;
+    self
+  }
+}
+
+impl<C> DnsTransport for DohTransport<C>
+where
+  C: HttpClient,
+{
+  #[inline]
+  async fn exchange(&mut self, bytes: &mut Vector<u8>) -> crate::Result<()> {
+    let mut headers = Headers::new();
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::Accept.into(),
+      [Mime::ApplicationDnsMessage.as_str()],
+    ))?;
+    let res = if self.is_get {
+      let len = base64_encoded_len(bytes.len(), false).unwrap_or_default();
+      let mut buffer = vec![0; len];
+      let encoded = base64_encode(Base64Alphabet::UrlNoPad, bytes, &mut buffer)?;
+      self.uri.truncate_with_initial_len();
+      let _ = self.uri.query_writer("dns", encoded)?;
+      let req = ReqBuilder::new(Method::Get, (&[][..], &headers, self.uri.to_ref()));
+      let rslt = self.client.send_req_recv_res(&mut self.enc_buffer, req.into_request()).await;
+      self.uri.truncate_with_initial_len();
+      rslt?
+    } else {
+      headers.push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::ContentType.into(),
+        [Mime::ApplicationDnsMessage.as_str()],
+      ))?;
+      let req = ReqBuilder::new(Method::Post, (bytes.as_slice(), &headers, self.uri.to_ref()));
+      self.client.send_req_recv_res(&mut self.enc_buffer, req.into_request()).await?
+    };
+    if res.status_code != StatusCode::Ok {
+      return Err(DnsError::UnexpectedHttpStatus(res.status_code.into()).into());
+    }
+    bytes.clear();
+    bytes.extend_from_copyable_slice(&res.msg_data.body)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    http::{
+      HttpClient, KnownHeaderName, Method, MsgBufferString, MsgData, Request, Response, StatusCode,
+    },
+    misc::Lease,
+    net::{
+      UriString,
+      dns::{DnsTransport as _, DohTransport},
+    },
+  };
+  use alloc::string::String;
+
+  #[wtx::test]
+  async fn sends_get_and_post_requests() {
+    let mut post = DohTransport::new(Client, UriString::new("https://dns.example/q".into()));
+    let mut bytes = Vector::from_copyable_slice(&[1, 2]).unwrap();
+    post.exchange(&mut bytes).await.unwrap();
+    assert_eq!(bytes.as_slice(), b"POST https://dns.example/q \x01\x02");
+    let mut get =
+      DohTransport::new(Client, UriString::new("https://dns.example/q".into())).set_is_get(true);
+    let mut bytes = Vector::from_copyable_slice(&[1, 2]).unwrap();
+    get.exchange(&mut bytes).await.unwrap();
+    assert_eq!(bytes.as_slice(), b"GET https://dns.example/q?dns=AQI ");
+    get.exchange(&mut bytes).await.unwrap();
+    assert_eq!(
+      bytes.as_slice(),
+      b"GET https://dns.example/q?dns=R0VUIGh0dHBzOi8vZG5zLmV4YW1wbGUvcT9kbnM9QVFJIA "
+    );
+  }
+
+  // Replies with the method, the URI and the body of the request.
+  struct Client;
+
+  impl HttpClient for Client {
+    type ReqId = MsgBufferString;
+
+    async fn recv_res(&self, req_id: Self::ReqId) -> crate::Result<Response<MsgBufferString>> {
+      Ok(Response::new(req_id, StatusCode::Ok))
+    }
+
+    async fn send_req<MD>(&self, _: &mut Vector<u8>, req: Request<MD>) -> crate::Result<Self::ReqId>
+    where
+      MD: MsgData,
+      MD::Body: Lease<[u8]>,
+    {
+      let headers = req.msg_data.headers();
+      assert!(headers.get_by_name(KnownHeaderName::Accept.into()).is_some());
+      assert_eq!(
+        headers.get_by_name(KnownHeaderName::ContentType.into()).is_some(),
+        req.method == Method::Post
+      );
+      let mut msg_buffer = MsgBufferString::default();
+      let method = if req.method == Method::Get { "GET " } else { "POST " };
+      let mut uri = String::from(req.msg_data.uri().as_str());
+      uri.push(' ');
+      let _ = msg_buffer.body.extend_from_copyable_slices([
+        method.as_bytes(),
+        uri.as_bytes(),
+        req.msg_data.body().lease(),
+      ])?;
+      Ok(msg_buffer)
+    }
+  }
+}
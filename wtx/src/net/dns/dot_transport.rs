@@ -0,0 +1,72 @@
+use crate::{
+  collections::{ExpansionTy, Vector},
+  net::{
+    StreamReader, StreamWriter,
+    dns::{DnsError, DnsTransport},
+    misc::read_exact,
+  },
+};
+
+/// Default port of DNS-over-TLS servers.
+pub const DOT_PORT: u16 = 853;
+
+/// DNS-over-TLS (RFC 7858) transport.
+///
+/// Messages are prefixed with their two-byte length, which is also the framing used by DNS over
+/// plain TCP. As such, any stream can be used but `S` is expected to be a TLS stream connected to
+/// [`DOT_PORT`].
+#[derive(Debug)]
+pub struct DotTransport<S> {
+  stream: S,
+}
+
+impl<S> DotTransport<S> {
+  /// New instance
+  #[inline]
+  pub const fn new(stream: S) -> Self {
+    Self { stream }
+  }
+
+  /// Returns the underlying stream.
+  #[inline]
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+}
+
+impl<S> DnsTransport for DotTransport<S>
+where
+  S: StreamReader + StreamWriter,
+{
+  #[inline]
+  async fn exchange(&mut self, bytes: &mut Vector<u8>) -> crate::Result<()> {
+    let len = u16::try_from(bytes.len()).map_err(|_err| DnsError::InvalidName)?;
+    self.stream.write_all_vectored(&[&len.to_be_bytes(), bytes]).await?;
+    let mut len_bytes = [0; 2];
+    read_exact(&mut self.stream, &mut len_bytes).await?;
+    bytes.clear();
+    bytes.expand(ExpansionTy::Len(u16::from_be_bytes(len_bytes).into()), 0)?;
+    read_exact(&mut self.stream, bytes).await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    collections::Vector,
+    net::{
+      BytesStream, StreamWriter as _,
+      dns::{DnsTransport as _, DotTransport},
+    },
+  };
+
+  #[wtx::test]
+  async fn frames_messages() {
+    let mut stream = BytesStream::default();
+    stream.write_all(&[0, 3, 4, 5, 6]).await.unwrap();
+    let mut transport = DotTransport::new(stream);
+    let mut bytes = Vector::from_copyable_slice(&[1, 2]).unwrap();
+    transport.exchange(&mut bytes).await.unwrap();
+    assert_eq!(bytes.as_slice(), &[4, 5, 6]);
+  }
+}
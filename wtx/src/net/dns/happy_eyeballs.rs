@@ -0,0 +1,149 @@
+use crate::{
+  collections::Vector,
+  executor::ExecutorError,
+  futures::Sleep,
+  net::{TcpParams, TcpStream},
+};
+use alloc::boxed::Box;
+use core::{
+  future::poll_fn,
+  net::{IpAddr, SocketAddr},
+  pin::Pin,
+  task::Poll,
+  time::Duration,
+};
+
+/// Delay between the beginnings of connection attempts recommended by RFC 8305.
+pub const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Connects to one of the `addrs` according to the "Happy Eyeballs" algorithm of RFC 8305.
+///
+/// Addresses are interleaved by family starting with IPv6 and a new attempt begins whenever the
+/// previous one fails or after [`CONNECTION_ATTEMPT_DELAY`]. The first established connection is
+/// returned and all other attempts are dropped.
+///
+/// Executors that block on connections perform attempts sequentially.
+#[inline]
+pub async fn connect_happy_eyeballs<TS>(
+  addrs: &[IpAddr],
+  port: u16,
+  tcp_params: TcpParams,
+) -> crate::Result<TS>
+where
+  TS: TcpStream,
+{
+  let sorted = interleave(addrs, port)?;
+  race(&sorted, CONNECTION_ATTEMPT_DELAY, |addr| TS::connect(addr, tcp_params)).await
+}
+
+fn interleave(addrs: &[IpAddr], port: u16) -> crate::Result<Vector<SocketAddr>> {
+  let mut v4 = addrs.iter().filter(|el| el.is_ipv4());
+  let mut v6 = addrs.iter().filter(|el| el.is_ipv6());
+  let mut rslt = Vector::with_capacity(addrs.len())?;
+  loop {
+    let (first, second) = (v6.next(), v4.next());
+    if first.is_none() && second.is_none() {
+      break;
+    }
+    for elem in first.into_iter().chain(second) {
+      rslt.push(SocketAddr::new(*elem, port))?;
+    }
+  }
+  Ok(rslt)
+}
+
+async fn race<F, T>(
+  addrs: &[SocketAddr],
+  delay: Duration,
+  mut cb: impl FnMut(SocketAddr) -> F,
+) -> crate::Result<T>
+where
+  F: Future<Output = crate::Result<T>>,
+{
+  let mut attempts: Vector<Pin<Box<F>>> = Vector::new();
+  let mut iter = addrs.iter().copied();
+  let mut last_err = None;
+  let mut should_start = true;
+  let mut timer: Option<Pin<Box<Sleep>>> = None;
+  poll_fn(|cx| {
+    loop {
+      if should_start {
+        should_start = false;
+        if let Some(addr) = iter.next() {
+          attempts.push(Box::pin(cb(addr)))?;
+          timer = Some(Box::pin(Sleep::new(delay)?));
+        } else {
+          timer = None;
+        }
+      }
+      let mut idx: usize = 0;
+      while let Some(attempt) = attempts.get_mut(idx) {
+        match attempt.as_mut().poll(cx) {
+          Poll::Ready(Ok(elem)) => return Poll::Ready(Ok(elem)),
+          Poll::Ready(Err(err)) => {
+            last_err = Some(err);
+            should_start = true;
+            drop(attempts.remove(idx));
+          }
+          Poll::Pending => idx = idx.wrapping_add(1),
+        }
+      }
+      if let Some(elem) = &mut timer
+        && elem.as_mut().poll(cx).is_ready()
+      {
+        should_start = true;
+      }
+      if should_start {
+        continue;
+      }
+      if attempts.is_empty() {
+        let err = last_err.take().unwrap_or_else(|| ExecutorError::InvalidResolvedAddress.into());
+        return Poll::Ready(Err(err));
+      }
+      return Poll::Pending;
+    }
+  })
+  .await
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::net::dns::happy_eyeballs::interleave;
+  use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+  #[test]
+  fn interleaves_families() {
+    let v4 = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let v6 = IpAddr::V6(Ipv6Addr::LOCALHOST);
+    let addrs = interleave(&[v4, v4, v6], 80).unwrap();
+    assert_eq!(
+      addrs.as_slice(),
+      &[SocketAddr::new(v6, 80), SocketAddr::new(v4, 80), SocketAddr::new(v4, 80)]
+    );
+  }
+
+  #[cfg(not(feature = "tokio"))]
+  #[wtx::test]
+  async fn starts_new_attempts_on_failures_and_delays() {
+    use crate::{futures::Sleep, net::dns::happy_eyeballs::race};
+    use core::time::Duration;
+
+    let addrs = [
+      SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 1)), 1),
+      SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 2)), 2),
+      SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 3)), 3),
+    ];
+    let rslt = race(&addrs, Duration::from_millis(10), |addr| async move {
+      match addr.port() {
+        1 => Err(crate::Error::ExpiredFuture),
+        2 => {
+          Sleep::new(Duration::from_secs(10))?.await?;
+          Ok(2)
+        }
+        _ => Ok(addr.port()),
+      }
+    })
+    .await;
+    assert_eq!(rslt.unwrap(), 3);
+  }
+}
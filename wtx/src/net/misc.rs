@@ -11,3 +11,21 @@ pub(crate) fn convert_to_io_slices<'buffer, 'bytes>(
   }
   Ok(buffer.get_mut(..elems.len()).unwrap_or_default())
 }
+
+// Fills all `bytes`, returning an error if the stream is closed before that.
+pub(crate) async fn read_exact<S>(stream: &mut S, bytes: &mut [u8]) -> crate::Result<()>
+where
+  S: crate::net::StreamReader,
+{
+  let mut idx: usize = 0;
+  while let Some(slice) = bytes.get_mut(idx..) {
+    if slice.is_empty() {
+      break;
+    }
+    let Some(read) = stream.read(slice.into()).await? else {
+      return Err(crate::net::NetError::UnexpectedStreamReadEOF.into());
+    };
+    idx = idx.wrapping_add(read.get());
+  }
+  Ok(())
+}
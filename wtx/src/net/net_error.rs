@@ -7,12 +7,26 @@ pub enum NetError {
   CapacityOverflow,
   /// The instance is configured to prevent the removal of contents
   ForbiddenClear,
+  /// HTTP proxy refused to open a tunnel with the contained status code.
+  HttpProxyRejected(u16),
+  /// Proxy sent a response that doesn't conform to its protocol.
+  InvalidProxyResponse,
   /// Nothing can resolve a domain into sockets.
   NoResolutionBackend,
+  /// SOCKS5 proxy rejected the provided credentials.
+  Socks5AuthFailed,
+  /// SOCKS5 proxy doesn't support any of the offered authentication methods.
+  Socks5NoAcceptableMethod,
+  /// Host names, usernames and passwords sent to SOCKS5 proxies can't have more than 255 bytes.
+  Socks5OversizedField,
+  /// SOCKS5 proxy refused to connect with the contained reply code.
+  Socks5Rejected(u8),
   /// Unexpected end of file when reading from a stream.
   UnexpectedStreamReadEOF,
   /// Unexpected end of file when writing to a stream.
   UnexpectedStreamWriteEOF,
+  /// Proxies can only be specified with the `http`, `socks5` or `socks5h` schemes.
+  UnsupportedProxyScheme,
   /// It is not possible to write more than 8 slices at once.
   VectoredWriteOverflow,
 }
@@ -0,0 +1,89 @@
+use crate::{
+  collections::{MaybeUninitSlice, Vector},
+  net::{Stream, StreamCommon, StreamReader, StreamWriter},
+};
+use core::num::NonZeroUsize;
+
+/// Stream tunneled through a [`crate::net::proxy::Proxy`].
+///
+/// Bytes that were received alongside the handshake response are returned before any other
+/// data of the underlying stream.
+#[derive(Debug)]
+pub struct ProxyStream<S> {
+  leftover: Vector<u8>,
+  stream: S,
+}
+
+impl<S> ProxyStream<S> {
+  pub(crate) const fn new(leftover: Vector<u8>, stream: S) -> Self {
+    Self { leftover, stream }
+  }
+
+  /// Returns the underlying stream, discarding unread bytes of the handshake response.
+  #[inline]
+  pub fn into_inner(self) -> S {
+    self.stream
+  }
+
+  /// Underlying stream
+  #[inline]
+  pub const fn stream_mut(&mut self) -> &mut S {
+    &mut self.stream
+  }
+}
+
+impl<S> Stream for ProxyStream<S>
+where
+  S: Stream,
+{
+  type BridgeOwned = S::BridgeOwned;
+  type ReadHalfOwned = ProxyStream<S::ReadHalfOwned>;
+  type WriteHalfOwned = S::WriteHalfOwned;
+
+  #[inline]
+  fn into_split(
+    self,
+  ) -> crate::Result<(Self::BridgeOwned, Self::ReadHalfOwned, Self::WriteHalfOwned)> {
+    let (bridge, read, write) = self.stream.into_split()?;
+    Ok((bridge, ProxyStream::new(self.leftover, read), write))
+  }
+}
+
+impl<S> StreamCommon for ProxyStream<S> {}
+
+impl<S> StreamReader for ProxyStream<S>
+where
+  S: StreamReader,
+{
+  #[inline]
+  async fn read(
+    &mut self,
+    mut bytes: MaybeUninitSlice<'_, u8>,
+  ) -> crate::Result<Option<NonZeroUsize>> {
+    if self.leftover.is_empty() {
+      return self.stream.read(bytes).await;
+    }
+    let initialized = bytes.initialize_all_bytes();
+    let len = initialized.len().min(self.leftover.len());
+    if let (Some(to), Some(from)) = (initialized.get_mut(..len), self.leftover.get(..len)) {
+      to.copy_from_slice(from);
+    }
+    self.leftover.vec_mut().drain(..len).for_each(drop);
+    Ok(NonZeroUsize::new(len))
+  }
+}
+
+impl<S> StreamWriter for ProxyStream<S>
+where
+  S: StreamWriter,
+{
+  #[inline]
+  async fn write_all(&mut self, bytes: &[u8]) -> crate::Result<()> {
+    self.stream.write_all(bytes).await
+  }
+
+  #[inline]
+  async fn write_all_vectored(&mut self, bytes: &[&[u8]]) -> crate::Result<()> {
+    self.stream.write_all_vectored(bytes).await
+  }
+}
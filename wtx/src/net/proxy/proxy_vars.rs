@@ -0,0 +1,146 @@
+use crate::{
+  misc::FromVars,
+  net::{UriRef, UriString, proxy::Proxy},
+};
+use alloc::string::String;
+
+/// Proxy settings of the `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment variables. Their
+/// lowercase versions take precedence.
+///
+/// Can be constructed through [`crate::misc::EnvVars`].
+#[derive(Debug, Default)]
+pub struct ProxyVars {
+  /// Proxy of `http` and `ws` URIs.
+  pub http_proxy: Option<String>,
+  /// Proxy of all other URIs.
+  pub https_proxy: Option<String>,
+  /// Comma-separated list of hosts, domain suffixes or `*` that should be reached directly.
+  /// Entries can have ports.
+  pub no_proxy: Option<String>,
+}
+
+impl ProxyVars {
+  /// If `uri` matches one of the entries of [`Self::no_proxy`].
+  #[inline]
+  pub fn is_excluded(&self, uri: &UriRef<'_>) -> bool {
+    let Some(no_proxy) = self.no_proxy.as_deref() else {
+      return false;
+    };
+    let host = uri.hostname().trim_start_matches('[').trim_end_matches(']');
+    let port = uri.port().unwrap_or_else(|| default_port(uri.scheme()));
+    for entry in no_proxy.split([',', ' ']).map(str::trim).filter(|el| !el.is_empty()) {
+      if entry == "*" {
+        return true;
+      }
+      let (entry_host, entry_port) = match entry.rsplit_once(':') {
+        Some((lhs, rhs)) if !lhs.is_empty() && !lhs.ends_with(':') => match rhs.parse::<u16>() {
+          Ok(elem) => (lhs, Some(elem)),
+          Err(_err) => (entry, None),
+        },
+        _ => (entry, None),
+      };
+      if entry_port.is_some_and(|el| el != port) {
+        continue;
+      }
+      let suffix = entry_host
+        .trim_start_matches('*')
+        .trim_start_matches('.')
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+      if host.eq_ignore_ascii_case(suffix) {
+        return true;
+      }
+      let Some(idx) = host.len().checked_sub(suffix.len()) else {
+        continue;
+      };
+      let (prefix, rest) = host.split_at_checked(idx).unwrap_or_default();
+      if prefix.ends_with('.') && rest.eq_ignore_ascii_case(suffix) {
+        return true;
+      }
+    }
+    false
+  }
+
+  /// Returns the proxy that should be used to reach `uri`, if any. Values without a scheme are
+  /// treated as `http` proxies.
+  #[inline]
+  pub fn proxy(&self, uri: &UriRef<'_>) -> crate::Result<Option<Proxy>> {
+    let scheme = uri.scheme();
+    let value = if scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("ws") {
+      self.http_proxy.as_deref()
+    } else {
+      self.https_proxy.as_deref()
+    };
+    let Some(elem) = value.filter(|el| !el.is_empty()) else {
+      return Ok(None);
+    };
+    if self.is_excluded(uri) {
+      return Ok(None);
+    }
+    let proxy_uri = if elem.contains("://") {
+      String::from(elem)
+    } else {
+      let mut rslt = String::from("http://");
+      rslt.push_str(elem);
+      rslt
+    };
+    Ok(Some(Proxy::new(UriString::new(proxy_uri))?))
+  }
+}
+
+impl FromVars for ProxyVars {
+  #[inline]
+  fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> crate::Result<Self> {
+    let mut rslt = Self::default();
+    let mut is_lowercase = [false; 3];
+    for (key, value) in vars {
+      let (idx, opt) = match key.as_str() {
+        "HTTP_PROXY" | "http_proxy" => (0, &mut rslt.http_proxy),
+        "HTTPS_PROXY" | "https_proxy" => (1, &mut rslt.https_proxy),
+        "NO_PROXY" | "no_proxy" => (2, &mut rslt.no_proxy),
+        _ => continue,
+      };
+      let Some(elem) = is_lowercase.get_mut(idx) else {
+        continue;
+      };
+      let is_key_lowercase = key.bytes().all(|el| !el.is_ascii_uppercase());
+      if is_key_lowercase || !*elem {
+        *opt = Some(value);
+        *elem = is_key_lowercase;
+      }
+    }
+    Ok(rslt)
+  }
+}
+
+fn default_port(scheme: &str) -> u16 {
+  if scheme.eq_ignore_ascii_case("http") || scheme.eq_ignore_ascii_case("ws") { 80 } else { 443 }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    misc::FromVars as _,
+    net::{UriRef, proxy::ProxyVars},
+  };
+  use alloc::string::String;
+
+  #[test]
+  fn excludes_hosts() {
+    let vars = ProxyVars::from_vars([
+      (String::from("https_proxy"), String::from("socks5://localhost")),
+      (String::from("HTTPS_PROXY"), String::from("http://other")),
+      (String::from("NO_PROXY"), String::from("localhost, .foo.com,bar.com:8443")),
+    ])
+    .unwrap();
+    let proxy = vars.proxy(&UriRef::new("https://baz.com")).unwrap().unwrap();
+    assert_eq!(proxy.uri().as_str(), "socks5://localhost");
+    assert!(vars.proxy(&UriRef::new("http://baz.com")).unwrap().is_none());
+    assert!(vars.is_excluded(&UriRef::new("https://LOCALHOST:1234")));
+    assert!(vars.is_excluded(&UriRef::new("https://a.foo.com")));
+    assert!(vars.is_excluded(&UriRef::new("https://foo.com")));
+    assert!(!vars.is_excluded(&UriRef::new("https://afoo.com")));
+    assert!(vars.is_excluded(&UriRef::new("https://bar.com:8443")));
+    assert!(!vars.is_excluded(&UriRef::new("https://bar.com")));
+  }
+}
@@ -1,8 +1,9 @@
 use crate::{
   collections::MaybeUninitSlice,
-  net::{Stream, StreamCommon, StreamReader, StreamWriter},
+  net::{Stream, StreamCommon, StreamReader, StreamWriter, UdpStream},
 };
 use core::{
+  net::SocketAddr,
   num::NonZeroUsize,
   pin::Pin,
   task::{Context, Poll, ready},
@@ -10,7 +11,7 @@ use core::{
 use tokio::{
   io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf, ReadHalf, WriteHalf},
   net::{
-    TcpStream,
+    TcpStream, UdpSocket,
     tcp::{OwnedReadHalf, OwnedWriteHalf},
   },
 };
@@ -134,6 +135,18 @@ where
   }
 }
 
+impl UdpStream for UdpSocket {
+  #[inline]
+  async fn recv_from(&mut self, buffer: &mut [u8]) -> crate::Result<(usize, SocketAddr)> {
+    Ok(UdpSocket::recv_from(self, buffer).await?)
+  }
+
+  #[inline]
+  async fn send_to(&mut self, bytes: &mut [u8], addr: SocketAddr) -> crate::Result<usize> {
+    Ok(UdpSocket::send_to(self, bytes, addr).await?)
+  }
+}
+
 struct ReadFut<'any, R: ?Sized> {
   read_buf: ReadBuf<'any>,
   reader: &'any mut R,
@@ -114,11 +114,14 @@ pub(crate) mod database {
     },
     executor::Executor,
     misc::{Secret, SecretContext},
-    net::TcpParams,
+    net::{
+      TcpParams, UriRef,
+      proxy::{Proxy, ProxyStream},
+    },
     pool::ResourceManager,
     rng::ChaCha20,
     sync::{Arc, AtomicCell},
-    tls::{TlsConfig, TlsConnectorBuilder, TlsCtx},
+    tls::{TlsConfig, TlsConnector, TlsConnectorBuilder, TlsCtx},
   };
   use core::{marker::PhantomData, mem};
 
@@ -126,8 +129,11 @@ pub(crate) mod database {
   #[derive(Debug)]
   pub struct PostgresRM<ER, EX, TCX> {
     _executor: EX,
+    #[cfg(feature = "dns")]
+    dns_resolver: Option<crate::net::dns::DnsResolver<ChaCha20>>,
     max_stmts: usize,
     phantom: PhantomData<fn() -> ER>,
+    proxy: Option<Proxy>,
     rng: AtomicCell<ChaCha20>,
     secret: Secret,
     tcp_params: TcpParams,
@@ -161,14 +167,62 @@ pub(crate) mod database {
       let secret = Secret::new(uri, &mut rng, secret_context)?;
       Ok(Self {
         _executor: executor,
+        #[cfg(feature = "dns")]
+        dns_resolver: None,
         max_stmts: DEFAULT_MAX_STMTS,
         phantom: PhantomData,
+        proxy: None,
         rng: AtomicCell::new(rng),
         secret,
         tcp_params: TcpParams::default(),
         tls_config: tls_config.into(),
       })
     }
+
+    /// Resolver of the host of direct connections, which are then established with "Happy
+    /// Eyeballs". Defaults to `None`, i.e., the host is resolved by the executor.
+    #[cfg(feature = "dns")]
+    #[inline]
+    pub const fn dns_resolver_mut(
+      &mut self,
+    ) -> &mut Option<crate::net::dns::DnsResolver<ChaCha20>> {
+      &mut self.dns_resolver
+    }
+
+    /// Proxy that tunnels all connections. Defaults to `None`.
+    #[inline]
+    pub const fn proxy_mut(&mut self) -> &mut Option<Proxy> {
+      &mut self.proxy
+    }
+  }
+
+  impl<ER, EX, TCX> PostgresRM<ER, EX, TCX>
+  where
+    EX: Executor,
+  {
+    async fn tls_connector<'any, RNG>(
+      &'any self,
+      rng: RNG,
+      tls_config: &'any TlsConfig<TCX>,
+      uri: UriRef<'any>,
+    ) -> crate::Result<
+      TlsConnector<RNG, ProxyStream<EX::TcpStream>, &'any TlsConfig<TCX>, UriRef<'any>>,
+    > {
+      let builder = TlsConnectorBuilder::new(EX::default(), uri).set_tcp_params(self.tcp_params);
+      cfg_select! {
+        feature = "dns" => {
+          builder
+            .build_with_proxy_and_resolver(
+              tls_config,
+              self.proxy.as_ref(),
+              self.dns_resolver.as_ref(),
+              rng,
+            )
+            .await
+        }
+        _ => builder.build_with_proxy(tls_config, self.proxy.as_ref(), rng).await,
+      }
+    }
   }
 
   impl<ER, EX, TCX> ResourceManager for PostgresRM<ER, EX, TCX>
@@ -180,7 +234,7 @@ pub(crate) mod database {
     type CreateAux = ();
     type Error = ER;
     type RecycleAux = ();
-    type Resource = PostgresClient<ER, EX::TcpStream, TCX>;
+    type Resource = PostgresClient<ER, ProxyStream<EX::TcpStream>, TCX>;
 
     #[inline]
     async fn create(&self, _: &Self::CreateAux) -> Result<Self::Resource, Self::Error> {
@@ -188,10 +242,7 @@ pub(crate) mod database {
       let rng = &mut &self.rng;
       let tls_config = &*self.tls_config;
       Ok(_executor!(&self.secret, |postgres_config, uri| {
-        let tls_connector = TlsConnectorBuilder::new(EX::default(), uri)
-          .set_tcp_params(self.tcp_params)
-          .build(tls_config, rng)
-          .await?;
+        let tls_connector = self.tls_connector(rng, tls_config, uri).await?;
         PostgresClient::connect(client_buffer, &postgres_config, tls_connector)
       }))
     }
@@ -212,10 +263,7 @@ pub(crate) mod database {
       let tls_config = &*self.tls_config;
       mem::swap(&mut client_buffer, &mut resource.cb);
       *resource = _executor!(&self.secret, |postgres_config, uri| {
-        let tls_connector = TlsConnectorBuilder::new(EX::default(), uri)
-          .set_tcp_params(self.tcp_params)
-          .build(tls_config, rng)
-          .await?;
+        let tls_connector = self.tls_connector(rng, tls_config, uri).await?;
         PostgresClient::connect(client_buffer, &postgres_config, tls_connector)
       });
       Ok(())
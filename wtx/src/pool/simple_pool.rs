@@ -61,6 +61,8 @@ where
   where
     'this: 'guard,
   {
+    #[cfg(feature = "metrics")]
+    let instant = crate::calendar::Instant::new();
     let idx = poll_fn(|cx| {
       if let Some(mut elem) = self.state.try_lock() {
         if let Some(idx) = elem.available.pop() {
@@ -75,6 +77,12 @@ where
       }
     })
     .await;
+    #[cfg(feature = "metrics")]
+    {
+      let registry = crate::metrics::MetricsRegistry::global();
+      registry.pool_resources.add(1);
+      registry.pool_wait.observe_elapsed(instant);
+    }
     let mut drop_guard = SimplePoolGetDropGuard { state: &self.state, idx: Some(idx) };
     // SAFETY: `idx` is guaranteed to be within bounds as defined in the constructor
     let lock = unsafe { self.resources.locks.get(idx).unwrap_unchecked() };
@@ -237,6 +245,8 @@ impl Drop for SimplePoolGetDropGuard<'_> {
 }
 
 fn push_available(idx: usize, state: &SyncMutex<PoolState>) {
+  #[cfg(feature = "metrics")]
+  crate::metrics::MetricsRegistry::global().pool_resources.sub(1);
   let mut state_guard = state.lock();
   state_guard.available.push(idx);
   if let Some(waker) = state_guard.wakers.pop() {
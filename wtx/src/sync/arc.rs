@@ -38,6 +38,12 @@ impl<T> Arc<T> {
     LocalTy::into_inner(this.0)
   }
 
+  /// Returns `true` if the two instances point to the same allocation.
+  #[inline]
+  pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+    LocalTy::ptr_eq(&this.0, &other.0)
+  }
+
   /// Gets the number of strong pointers to this allocation.
   #[inline]
   pub fn strong_count(this: &Self) -> usize {
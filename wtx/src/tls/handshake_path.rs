@@ -10,3 +10,15 @@ pub enum HandshakePath {
   /// Normal handshake
   Full,
 }
+
+impl HandshakePath {
+  #[cfg(feature = "metrics")]
+  pub(crate) const fn metrics_idx(self) -> usize {
+    match self {
+      Self::Full => 0,
+      Self::FullWithHelloRetryRequest => 1,
+      Self::Resumed => 2,
+      Self::ResumedWithHelloRetryRequest => 3,
+    }
+  }
+}
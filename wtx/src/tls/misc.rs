@@ -240,6 +240,19 @@ where
   }
 }
 
+#[cfg(feature = "metrics")]
+pub(crate) fn observe_handshake(
+  handshake_path: crate::tls::HandshakePath,
+  instant: crate::calendar::Instant,
+) {
+  if let Some(elem) = crate::metrics::MetricsRegistry::global()
+    .tls_handshake_duration
+    .get(handshake_path.metrics_idx())
+  {
+    elem.observe_elapsed(instant);
+  }
+}
+
 pub(crate) fn manage_user_canceled(warning_alerts: &mut u8) -> crate::Result<bool> {
   *warning_alerts = warning_alerts.wrapping_add(1);
   if usize::from(*warning_alerts) >= MAX_WARNING_ALERTS {
@@ -140,6 +140,8 @@ where
       });
     }
     _trace!(target: crate::_WTX_TLS_HS, "Start");
+    #[cfg(feature = "metrics")]
+    let instant = crate::calendar::Instant::new();
     let fut = async {
       let first_rri = self.fetch_rec_from_stream::<false, true>(false).await?;
       _trace!(target: crate::_WTX_TLS_HS, "Read ClientHello: {:?}", &first_rri);
@@ -180,6 +182,8 @@ where
     let kss = self.key_schedule.write_mut().state_mut();
     manage_err_handshake(true, kss, rslt, &mut self.stream).await?;
     _trace!(target: crate::_WTX_TLS_HS, "Successful handshake");
+    #[cfg(feature = "metrics")]
+    crate::tls::misc::observe_handshake(self.handshake_path, instant);
     Ok(TlsAcceptOutput {
       handshake_path: self.handshake_path,
       named_group: self.named_group,
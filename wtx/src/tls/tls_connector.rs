@@ -185,7 +185,8 @@ where
         uri: self.uri,
       });
     }
-
+    #[cfg(feature = "metrics")]
+    let instant = crate::calendar::Instant::new();
     let fut = async {
       let mut secrets = self.write_client_hello()?;
       self.stream.write_all(&self.buffer.writer_buffer).await?;
@@ -229,6 +230,8 @@ where
     let tls_server_end_point =
       manage_err_handshake(self.has_sent_ccs, kss, rslt, &mut self.stream).await?;
     _trace!(target: crate::_WTX_TLS_HS, "Successful handshake");
+    #[cfg(feature = "metrics")]
+    crate::tls::misc::observe_handshake(self.handshake_path, instant);
     Ok(TlsConnectOutput {
       handshake_path: self.handshake_path,
       named_group: self.named_group,
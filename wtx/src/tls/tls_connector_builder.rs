@@ -1,8 +1,11 @@
 use crate::{
-  collections::SingleTypeStorage,
+  collections::{SingleTypeStorage, Vector},
   executor::Executor,
   misc::Lease,
-  net::{TcpParams, TcpStream as _, Uri},
+  net::{
+    TcpParams, TcpStream as _, Uri,
+    proxy::{Proxy, ProxyStream},
+  },
   tls::{TlsConfig, TlsConnector},
 };
 
@@ -67,6 +70,102 @@ where
     Ok(TlsConnector::new(config, rng, stream, self.uri))
   }
 
+  /// Similar to [`Self::build`] but the connection is tunneled through `proxy`. If `proxy` is
+  /// `None`, then the host of the URI is directly reached.
+  #[inline]
+  pub async fn build_with_proxy<RNG, TCG, TCX>(
+    self,
+    config: TCG,
+    proxy: Option<&Proxy>,
+    rng: RNG,
+  ) -> crate::Result<TlsConnector<RNG, ProxyStream<EX::TcpStream>, TCG, U>>
+  where
+    TCG: Lease<TlsConfig<TCX>> + SingleTypeStorage<Item = TCX>,
+  {
+    let addr = self.uri.lease().hostname_with_implied_port();
+    let stream = match proxy {
+      Some(elem) => elem.connect(addr, self.tcp_params).await?,
+      None => ProxyStream::new(Vector::new(), EX::TcpStream::connect(addr, self.tcp_params).await?),
+    };
+    Ok(TlsConnector::new(config, rng, stream, self.uri))
+  }
+
+  /// Similar to [`Self::build_with_proxy`] but direct connections resolve the host with
+  /// `resolver` and are established with [`crate::net::dns::connect_happy_eyeballs`]. Proxies
+  /// resolve tunneled hosts by themselves. If `resolver` is `None`, then this method behaves like
+  /// [`Self::build_with_proxy`].
+  #[cfg(feature = "dns")]
+  #[inline]
+  pub async fn build_with_proxy_and_resolver<DRNG, RNG, TCG, TCX>(
+    self,
+    config: TCG,
+    proxy: Option<&Proxy>,
+    resolver: Option<&crate::net::dns::DnsResolver<DRNG>>,
+    rng: RNG,
+  ) -> crate::Result<TlsConnector<RNG, ProxyStream<EX::TcpStream>, TCG, U>>
+  where
+    DRNG: crate::rng::Rng,
+    TCG: Lease<TlsConfig<TCX>> + SingleTypeStorage<Item = TCX>,
+  {
+    let (None, Some(local_resolver)) = (proxy, resolver) else {
+      return self.build_with_proxy(config, proxy, rng).await;
+    };
+    let (host, port) = self.uri.lease().hostname_with_implied_port();
+    let addrs = local_resolver.resolve_with_executor::<EX>(host).await?;
+    let stream =
+      crate::net::dns::connect_happy_eyeballs::<EX::TcpStream>(&addrs, port, self.tcp_params)
+        .await?;
+    Ok(TlsConnector::new(config, rng, ProxyStream::new(Vector::new(), stream), self.uri))
+  }
+
+  /// Similar to [`Self::build`] but the host is resolved by `resolver` and the connection is
+  /// established with [`crate::net::dns::connect_happy_eyeballs`].
+  #[cfg(feature = "dns")]
+  #[inline]
+  pub async fn build_with_resolver<DRNG, RNG, TCG, TCX, US>(
+    self,
+    config: TCG,
+    resolver: &crate::net::dns::DnsResolver<DRNG>,
+    rng: RNG,
+    udp: &mut US,
+  ) -> crate::Result<TlsConnector<RNG, EX::TcpStream, TCG, U>>
+  where
+    DRNG: crate::rng::Rng,
+    TCG: Lease<TlsConfig<TCX>> + SingleTypeStorage<Item = TCX>,
+    US: crate::net::UdpStream,
+  {
+    let (host, port) = self.uri.lease().hostname_with_implied_port();
+    let addrs = resolver.resolve::<EX::TcpStream, US>(host, udp).await?;
+    let stream =
+      crate::net::dns::connect_happy_eyeballs::<EX::TcpStream>(&addrs, port, self.tcp_params)
+        .await?;
+    Ok(TlsConnector::new(config, rng, stream, self.uri))
+  }
+
+  /// Similar to [`Self::build_with_resolver`] but queries are sent through `transport`, which
+  /// can be a [`crate::net::dns::DotTransport`] or a [`crate::net::dns::DohTransport`].
+  #[cfg(feature = "dns")]
+  #[inline]
+  pub async fn build_with_dns_transport<DRNG, DT, RNG, TCG, TCX>(
+    self,
+    config: TCG,
+    resolver: &crate::net::dns::DnsResolver<DRNG>,
+    rng: RNG,
+    transport: &mut DT,
+  ) -> crate::Result<TlsConnector<RNG, EX::TcpStream, TCG, U>>
+  where
+    DRNG: crate::rng::Rng,
+    DT: crate::net::dns::DnsTransport,
+    TCG: Lease<TlsConfig<TCX>> + SingleTypeStorage<Item = TCX>,
+  {
+    let (host, port) = self.uri.lease().hostname_with_implied_port();
+    let addrs = resolver.resolve_with_transport(host, transport).await?;
+    let stream =
+      crate::net::dns::connect_happy_eyeballs::<EX::TcpStream>(&addrs, port, self.tcp_params)
+        .await?;
+    Ok(TlsConnector::new(config, rng, stream, self.uri))
+  }
+
   /// Owned version of [`Self::tcp_params`].
   #[inline]
   #[must_use]
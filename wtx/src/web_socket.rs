@@ -3,10 +3,13 @@
 
 mod close_code;
 mod frame;
+mod generic_web_socket;
 mod handshake;
+mod heartbeat;
 mod is_in_continuation_frame;
 mod mask_op;
 mod misc;
+mod msg_fragmenter;
 mod op_code;
 pub(crate) mod read_frame;
 pub(crate) mod read_frame_info;
@@ -23,18 +26,22 @@ pub(crate) mod write_frame;
 
 use crate::{
   _MAX_PAYLOAD_LEN,
-  collections::Vector,
+  calendar::Instant,
+  collections::{ArrayString, ArrayStringU8, ArrayVectorU8, Vector},
+  http::ShutdownSignal,
   misc::LeaseMut,
   net::{ConnectionState, Stream},
   rng::{SeedableRng as _, Xorshift64},
   tls::{TlsCtx, TlsStream, TlsStreamBridge},
-  web_socket::web_socket_compression::NegotiatedWsCompression,
+  web_socket::{heartbeat::Heartbeat, web_socket_compression::NegotiatedWsCompression},
 };
 pub use close_code::CloseCode;
-use core::marker::PhantomData;
+use core::{marker::PhantomData, time::Duration};
 pub use frame::{
   Frame, FrameControlArray, FrameMut, FrameRef, FrameVector, FrameVectorMut, FrameVectorRef,
 };
+pub use generic_web_socket::GenericWebSocket;
+pub use msg_fragmenter::MsgFragmenter;
 pub use op_code::OpCode;
 pub use web_socket_acceptor::WebSocketAcceptor;
 pub use web_socket_bridge::{WebSocketBridge, WebSocketBridgeData};
@@ -47,11 +54,13 @@ pub use web_socket_owned::{WebSocketReaderOwned, WebSocketWriterOwned};
 pub use web_socket_payload_origin::WebSocketPayloadOrigin;
 
 const FIN_MASK: u8 = 0b1000_0000;
-const MASK_MASK: u8 = 0b1000_0000;
+pub(crate) const MASK_MASK: u8 = 0b1000_0000;
 const MAX_CONTROL_PAYLOAD_LEN: usize = 125;
-const MAX_HEADER_LEN: usize = 14;
+pub(crate) const MAX_HEADER_LEN: usize = 14;
+const MAX_SUBPROTOCOL_LEN: usize = 32;
+const MAX_SUBPROTOCOLS: usize = 8;
 const OP_CODE_MASK: u8 = 0b0000_1111;
-const PAYLOAD_MASK: u8 = 0b0111_1111;
+pub(crate) const PAYLOAD_MASK: u8 = 0b0111_1111;
 const RSV1_MASK: u8 = 0b0100_0000;
 const RSV2_MASK: u8 = 0b0010_0000;
 const RSV3_MASK: u8 = 0b0001_0000;
@@ -71,19 +80,24 @@ type IntoSplitTy<NC, S, TCX, const IS_CLIENT: bool> = (
     IS_CLIENT,
   >,
 );
+type Subprotocol = ArrayStringU8<MAX_SUBPROTOCOL_LEN>;
+type Subprotocols = ArrayVectorU8<Subprotocol, MAX_SUBPROTOCOLS>;
 
 /// Full-duplex communication over an asynchronous stream.
 ///
 /// <https://tools.ietf.org/html/rfc6455>
 #[derive(Debug)]
 pub struct WebSocket<NC, S, TCX, const IS_CLIENT: bool> {
+  heartbeat: Heartbeat,
   is_in_continuation_frame: Option<is_in_continuation_frame::IsInContinuationFrame>,
   max_payload_len: usize,
   nc: NC,
   nc_rsv1: u8,
   no_masking: bool,
   rng: Xorshift64,
+  shutdown_signal: Option<ShutdownSignal>,
   stream: TlsStream<S, TCX, IS_CLIENT>,
+  subprotocol: Option<Subprotocol>,
   wsb: WebSocketBuffer,
 }
 
@@ -94,6 +108,30 @@ impl<NC, S, TCX, const IS_CLIENT: bool> WebSocket<NC, S, TCX, IS_CLIENT> {
   pub const fn max_payload_len_mut(&mut self) -> &mut usize {
     &mut self.max_payload_len
   }
+
+  /// Round-trip time measured by the last answered heartbeat PING frame.
+  ///
+  /// Heartbeats are enabled with [`WebSocketConnector::set_heartbeat_interval`] or
+  /// [`WebSocketAcceptor::set_heartbeat_interval`].
+  #[inline]
+  pub const fn rtt(&self) -> Option<Duration> {
+    self.heartbeat.rtt()
+  }
+
+  /// Once triggered, a close frame with [`CloseCode::Away`] is sent by the next reading operation
+  /// or by [`WebSocketBridge::listen`] and the reply of the peer is then returned as usual.
+  #[inline]
+  pub const fn shutdown_signal_mut(&mut self) -> &mut Option<ShutdownSignal> {
+    &mut self.shutdown_signal
+  }
+
+  /// Subprotocol negotiated in the handshake, if any.
+  ///
+  /// See [`WebSocketConnector::set_subprotocols`] and [`WebSocketAcceptor::set_subprotocols`].
+  #[inline]
+  pub fn subprotocol(&self) -> Option<&str> {
+    self.subprotocol.as_ref().map(ArrayString::as_str)
+  }
 }
 
 impl<NC, S, TCX, const IS_CLIENT: bool> WebSocket<NC, S, TCX, IS_CLIENT>
@@ -113,13 +151,16 @@ where
   ) -> Self {
     let nc_rsv1 = nc.rsv1();
     Self {
+      heartbeat: Heartbeat::default(),
       is_in_continuation_frame: None,
       max_payload_len: _MAX_PAYLOAD_LEN,
       nc,
       nc_rsv1,
       no_masking,
       rng,
+      shutdown_signal: None,
       stream,
+      subprotocol: None,
       wsb,
     }
   }
@@ -128,6 +169,9 @@ where
   ///
   /// If a frame is made up of other sub-frames or continuations, then everything is collected
   /// until all fragments are received.
+  ///
+  /// When heartbeats are enabled, PING frames are sent while the next frame is awaited and the
+  /// whole message must be received before the outstanding or the next PING frame times out.
   #[inline]
   pub async fn read_frame<'buffer, 'frame, 'this>(
     &'this mut self,
@@ -139,17 +183,32 @@ where
     'this: 'frame,
   {
     let WebSocket {
+      heartbeat,
       is_in_continuation_frame,
       max_payload_len,
       nc,
       nc_rsv1,
       no_masking,
       rng,
+      shutdown_signal,
       stream,
+      subprotocol: _,
       wsb,
     } = self;
     let WebSocketBuffer { network_buffer, reader_buffer, .. } = wsb;
-    read_frame::read_frame::<_, _, _, _, _, true, IS_CLIENT>(
+    heartbeat::manage_heartbeat::<_, _, IS_CLIENT>(
+      heartbeat,
+      stream.connection_state.is_open(),
+      network_buffer,
+      *no_masking,
+      rng,
+      shutdown_signal,
+      stream,
+      |el, state| el.connection_state = state,
+    )
+    .await?;
+    let bridge = WebSocketBridge::new(TlsStreamBridge::new());
+    let read = read_frame::read_frame::<_, _, _, _, _, true, IS_CLIENT>(
       is_in_continuation_frame,
       *max_payload_len,
       nc,
@@ -160,13 +219,100 @@ where
       reader_buffer,
       rng,
       stream,
-      &WebSocketBridge::new(TlsStreamBridge::new()),
+      &bridge,
       buffer,
       |el| el.connection_state = ConnectionState::ClosedGracefully,
       |local_stream| local_stream,
       |local_stream| local_stream,
+    );
+    let Some(frame) = heartbeat::read_within_deadline(heartbeat, read).await? else {
+      return heartbeat::close_unresponsive::<_, _, _, IS_CLIENT>(
+        *no_masking,
+        rng,
+        stream,
+        |el, state| el.connection_state = state,
+      )
+      .await;
+    };
+    heartbeat.recv(Instant::new(), frame.op_code(), frame.payload());
+    Ok(frame)
+  }
+
+  /// Reads the next fragment of a message or a control frame.
+  ///
+  /// Contrary to [`Self::read_frame`], continuation frames are not concatenated, which allows the
+  /// processing of large messages as they arrive. The first fragment carries the [`OpCode`] of the
+  /// message and the subsequent fragments are [`OpCode::Continuation`] frames, the last one being
+  /// final. Control frames can be returned between fragments.
+  ///
+  /// Text fragments are validated as they arrive. A character split across frames is only
+  /// delivered with the next fragment, so every returned payload is valid UTF-8.
+  ///
+  /// Must not be mixed with [`Self::read_frame`] while a message is being received.
+  #[inline]
+  pub async fn read_msg_fragment<'buffer, 'frame, 'this>(
+    &'this mut self,
+    buffer: &'buffer mut Vector<u8>,
+    payload_origin: WebSocketPayloadOrigin,
+  ) -> crate::Result<FrameMut<'frame>>
+  where
+    'buffer: 'frame,
+    'this: 'frame,
+  {
+    let WebSocket {
+      heartbeat,
+      is_in_continuation_frame,
+      max_payload_len,
+      nc,
+      nc_rsv1,
+      no_masking,
+      rng,
+      shutdown_signal,
+      stream,
+      subprotocol: _,
+      wsb,
+    } = self;
+    let WebSocketBuffer { network_buffer, reader_buffer, .. } = wsb;
+    heartbeat::manage_heartbeat::<_, _, IS_CLIENT>(
+      heartbeat,
+      stream.connection_state.is_open(),
+      network_buffer,
+      *no_masking,
+      rng,
+      shutdown_signal,
+      stream,
+      |el, state| el.connection_state = state,
     )
-    .await
+    .await?;
+    let bridge = WebSocketBridge::new(TlsStreamBridge::new());
+    let read = read_frame::read_msg_fragment::<_, _, _, _, _, true, IS_CLIENT>(
+      is_in_continuation_frame,
+      *max_payload_len,
+      nc,
+      *nc_rsv1,
+      network_buffer,
+      *no_masking,
+      payload_origin,
+      reader_buffer,
+      rng,
+      stream,
+      &bridge,
+      buffer,
+      |el| el.connection_state = ConnectionState::ClosedGracefully,
+      |local_stream| local_stream,
+      |local_stream| local_stream,
+    );
+    let Some(frame) = heartbeat::read_within_deadline(heartbeat, read).await? else {
+      return heartbeat::close_unresponsive::<_, _, _, IS_CLIENT>(
+        *no_masking,
+        rng,
+        stream,
+        |el, state| el.connection_state = state,
+      )
+      .await;
+    };
+    heartbeat.recv(Instant::new(), frame.op_code(), frame.payload());
+    Ok(frame)
   }
 
   /// Different mutable parts that allow sending received frames using common elements.
@@ -179,12 +325,15 @@ where
     WebSocketWriterMut<'_, NC, S, TCX, IS_CLIENT>,
   ) {
     let WebSocket {
+      heartbeat,
       is_in_continuation_frame,
       nc,
       nc_rsv1,
       no_masking,
       rng,
+      shutdown_signal,
       stream,
+      subprotocol: _,
       wsb,
       max_payload_len,
     } = self;
@@ -192,12 +341,14 @@ where
     (
       WebSocketCommonMut { nc, nc_rsv1: *nc_rsv1, rng, stream },
       WebSocketReaderMut {
+        heartbeat,
         is_in_continuation_frame,
         max_payload_len: *max_payload_len,
         network_buffer,
         no_masking: *no_masking,
         phantom: PhantomData,
         reader_buffer,
+        shutdown_signal,
       },
       WebSocketWriterMut { no_masking: *no_masking, phantom: PhantomData, writer_buffer },
     )
@@ -230,22 +381,32 @@ where
   TCX: TlsCtx,
 {
   /// Splits this instance into owned parts that can be used in concurrent scenarios.
+  ///
+  /// Heartbeats, if enabled, are then driven by [`WebSocketBridge::listen`].
   #[inline]
   pub fn into_split(self) -> crate::Result<IntoSplitTy<NC, S, TCX, IS_CLIENT>> {
     let WebSocket {
+      heartbeat,
       is_in_continuation_frame,
       nc,
       nc_rsv1,
       no_masking,
       mut rng,
+      shutdown_signal,
       stream,
+      subprotocol: _,
       wsb,
       max_payload_len,
     } = self;
     let (compression, decompression) = nc.into_split();
     let WebSocketBuffer { network_buffer, reader_buffer, writer_buffer } = wsb;
+    let is_open = stream.connection_state.is_open();
     let (stream_bridge_tls, stream_reader, stream_writer) = stream.into_split()?;
-    let stream_bridge_ws = WebSocketBridge::new(stream_bridge_tls);
+    let mut stream_bridge_ws = WebSocketBridge::new(stream_bridge_tls);
+    if is_open {
+      stream_bridge_ws.set_shutdown_signal(shutdown_signal);
+    }
+    stream_bridge_ws.set_heartbeat(heartbeat);
     Ok((
       stream_bridge_ws.clone(),
       WebSocketReaderOwned {
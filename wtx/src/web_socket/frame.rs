@@ -146,6 +146,7 @@ where
       // * Continuation frames (No decompression): Whole payload is verified when concatenated.
       // * Single `FIN` frame (With decompression): Whole payload is verified.
       // * Continuation frames (With decompression): Whole payload is verified when concatenated.
+      // * Message fragments: Incomplete characters are only delivered with the next fragment.
       unsafe { str::from_utf8_unchecked(self.payload.lease()) }
     })
   }
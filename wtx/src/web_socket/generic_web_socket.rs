@@ -0,0 +1,75 @@
+use crate::{
+  collections::Vector,
+  misc::LeaseMut,
+  net::Stream,
+  tls::TlsCtx,
+  web_socket::{
+    Frame, FrameMut, WebSocket, WebSocketPayloadOrigin,
+    web_socket_compression::NegotiatedWsCompression,
+  },
+};
+
+/// WebSocket connection regardless of the procedure used in the handshake.
+///
+/// Allows the writing of handlers that work with HTTP/1.1 upgrades as well as with HTTP/2
+/// extended CONNECT streams.
+pub trait GenericWebSocket {
+  /// Reads a frame whose payload is always stored in `buffer`.
+  ///
+  /// If a frame is made up of other sub-frames or continuations, then everything is collected
+  /// until all fragments are received.
+  fn read_frame<'buffer>(
+    &mut self,
+    buffer: &'buffer mut Vector<u8>,
+  ) -> impl Future<Output = crate::Result<FrameMut<'buffer>>>;
+
+  /// Writes a frame to the stream.
+  fn write_frame<P>(&mut self, frame: &mut Frame<P>) -> impl Future<Output = crate::Result<()>>
+  where
+    P: LeaseMut<[u8]>;
+}
+
+impl<T> GenericWebSocket for &mut T
+where
+  T: GenericWebSocket,
+{
+  #[inline]
+  async fn read_frame<'buffer>(
+    &mut self,
+    buffer: &'buffer mut Vector<u8>,
+  ) -> crate::Result<FrameMut<'buffer>> {
+    (**self).read_frame(buffer).await
+  }
+
+  #[inline]
+  async fn write_frame<P>(&mut self, frame: &mut Frame<P>) -> crate::Result<()>
+  where
+    P: LeaseMut<[u8]>,
+  {
+    (**self).write_frame(frame).await
+  }
+}
+
+impl<NC, S, TCX, const IS_CLIENT: bool> GenericWebSocket for WebSocket<NC, S, TCX, IS_CLIENT>
+where
+  NC: NegotiatedWsCompression,
+  S: Stream,
+  TCX: TlsCtx,
+{
+  #[inline]
+  async fn read_frame<'buffer>(
+    &mut self,
+    buffer: &'buffer mut Vector<u8>,
+  ) -> crate::Result<FrameMut<'buffer>> {
+    let op_code = self.read_frame(buffer, WebSocketPayloadOrigin::Consistent).await?.op_code();
+    Ok(FrameMut::new(true, op_code, buffer, self.nc_rsv1))
+  }
+
+  #[inline]
+  async fn write_frame<P>(&mut self, frame: &mut Frame<P>) -> crate::Result<()>
+  where
+    P: LeaseMut<[u8]>,
+  {
+    self.write_frame(frame).await
+  }
+}
@@ -18,8 +18,9 @@ macro_rules! check_headers {
 }
 
 use crate::{
+  calendar::Instant,
   codec::{Base64Alphabet, base64_encode, base64_encoded_len},
-  collections::{SingleTypeStorage, SuffixGuardVectorMut},
+  collections::{SingleTypeStorage, SuffixGuardVectorMut, Vector},
   crypto::{Hash as _, Sha1Global},
   http::{GenericHeader as _, GenericRequest as _, HttpError, KnownHeaderName, Method},
   misc::{Lease, bytes_split1},
@@ -27,8 +28,8 @@ use crate::{
   rng::{CryptoRng, Rng, SeedableRng as _, Xorshift64},
   tls::{TlsAcceptor, TlsConfig, TlsConnector, TlsCtx, TlsCtxSk},
   web_socket::{
-    WebSocket, WebSocketAcceptor, WebSocketConnector, WebSocketError, WsCompression,
-    web_socket_compression::NegotiatedWsCompression,
+    Subprotocol, WebSocket, WebSocketAcceptor, WebSocketConnector, WebSocketError, WsCompression,
+    heartbeat::Heartbeat, web_socket_compression::NegotiatedWsCompression,
   },
 };
 use httparse::{EMPTY_HEADER, Header, Request, Response, Status};
@@ -37,11 +38,12 @@ const MAX_HEADERS: usize = 16;
 const READ_INCREMENT: usize = 1024;
 const VERSION: &str = "13";
 
-impl<C, E, R> WebSocketAcceptor<C, R>
+impl<C, E, R, X> WebSocketAcceptor<C, R, X>
 where
   C: WsCompression<false>,
   E: From<crate::Error>,
   R: FnOnce(&Request<'_, '_>) -> Result<bool, E>,
+  X: FnOnce(&Request<'_, '_>, &mut Vector<u8>) -> Result<(), E>,
 {
   /// Reads external data to establish an WebSocket connection.
   #[inline]
@@ -80,7 +82,12 @@ where
             );
           }
           let mut key_buffer = [0; 30];
-          let swa = match Self::check_req_headers(&mut self.no_masking, &req, &mut key_buffer) {
+          let (swa, subprotocol) = match Self::check_req_headers(
+            &mut self.no_masking,
+            &req,
+            &mut key_buffer,
+            &self.subprotocols,
+          ) {
             Ok(el) => el,
             Err(err) => {
               build_and_send_res400(&mut tls_stream).await?;
@@ -88,10 +95,17 @@ where
             }
           };
           let nc = self.compression.negotiate(req.headers.iter())?;
-          build_and_send_res101(swa, &nc, self.no_masking, &mut tls_stream).await?;
+          let wb = &mut self.wsb.writer_buffer;
+          extra_res_headers(self.extensions_cb, &req, subprotocol, wb)?;
+          build_and_send_res101(swa, wb, &nc, self.no_masking, &mut tls_stream).await?;
+          wb.clear();
           self.wsb.network_buffer.set_indices(len, len);
           let rng = Xorshift64::from_simple_seed()?;
-          return Ok(WebSocket::new(nc, self.no_masking, rng, tls_stream, self.wsb));
+          let mut ws = WebSocket::new(nc, self.no_masking, rng, tls_stream, self.wsb);
+          ws.heartbeat =
+            Heartbeat::new(self.heartbeat_interval, Instant::new(), self.heartbeat_timeout);
+          ws.subprotocol = subprotocol;
+          return Ok(ws);
         }
         Status::Partial => {}
       }
@@ -102,17 +116,21 @@ where
     no_masking: &mut bool,
     req: &Request<'_, '_>,
     key_buffer: &'kb mut [u8; 30],
-  ) -> crate::Result<&'kb [u8]> {
-    let [_, _, b2, b3, b4] = check_headers!(
+    subprotocols: &[Subprotocol],
+  ) -> crate::Result<(&'kb [u8], Option<Subprotocol>)> {
+    let [_, _, b2, b3, b4, b5] = check_headers!(
       req.headers,
       (KnownHeaderName::SecWebsocketExtensions, None),
       (KnownHeaderName::SecWebsocketKey, None),
+      (KnownHeaderName::SecWebsocketProtocol, None),
       (KnownHeaderName::SecWebsocketVersion, Some(VERSION.as_bytes()))
     );
     *no_masking &= check_header_value(b2).is_ok_and(has_no_masking);
     let key = check_header_value(b3)?;
-    let _ = check_header_value(b4)?;
-    Ok(derived_key(key_buffer, key))
+    let _ = check_header_value(b5)?;
+    let subprotocol =
+      select_subprotocol(b4.1, subprotocols).map(Subprotocol::try_from).transpose()?;
+    Ok((derived_key(key_buffer, key), subprotocol))
   }
 }
 
@@ -124,6 +142,10 @@ where
   R: FnOnce(&Response<'_, '_>) -> Result<(), E>,
 {
   /// Sends data to establish an WebSocket connection.
+  ///
+  /// Connections can be tunneled through proxies with
+  /// [`crate::tls::TlsConnectorBuilder::build_with_proxy`]. With the `dns` feature, hosts can be
+  /// resolved with `DnsResolver` through `TlsConnectorBuilder::build_with_resolver`.
   #[inline]
   pub async fn connect<RNG, S, STR, TCG, TCX, U>(
     mut self,
@@ -149,12 +171,13 @@ where
         key_buffer,
         self.no_masking,
         &mut out.rng,
+        &self.subprotocols,
         out.uri.lease(),
       )?;
       out.tls_stream.write_all(sw.curr()).await?;
       key
     };
-    let (nc, len) = loop {
+    let (nc, len, subprotocol) = loop {
       let nb = &mut self.wsb.network_buffer;
       let _ = nb
         .read_arbitrary(READ_INCREMENT, &mut out.tls_stream)
@@ -174,18 +197,24 @@ where
         );
       }
       (self.res_cb)(&res)?;
-      let [_, _, b2, b3] = check_headers!(
+      let [_, _, b2, b3, b4] = check_headers!(
         res.headers,
         (KnownHeaderName::SecWebsocketAccept, Some(derived_key(&mut [0; 30], key))),
-        (KnownHeaderName::SecWebsocketExtensions, None)
+        (KnownHeaderName::SecWebsocketExtensions, None),
+        (KnownHeaderName::SecWebsocketProtocol, None)
       );
       drop(check_header_value(b2));
       self.no_masking &= check_header_value(b3).is_ok_and(has_no_masking);
-      break (self.compression.negotiate(res.headers.iter())?, len);
+      let subprotocol =
+        verify_subprotocol(b4.1, &self.subprotocols)?.map(Subprotocol::try_from).transpose()?;
+      break (self.compression.negotiate(res.headers.iter())?, len, subprotocol);
     };
     self.wsb.network_buffer.set_indices(len, len);
     let rng = Xorshift64::from_simple_seed()?;
-    Ok(WebSocket::new(nc, self.no_masking, rng, out.tls_stream, self.wsb))
+    let mut ws = WebSocket::new(nc, self.no_masking, rng, out.tls_stream, self.wsb);
+    ws.heartbeat = Heartbeat::new(self.heartbeat_interval, Instant::new(), self.heartbeat_timeout);
+    ws.subprotocol = subprotocol;
+    Ok(ws)
   }
 }
 
@@ -208,6 +237,7 @@ fn build_req<'headers, 'kb, C, RNG, STR>(
   key_buffer: &'kb mut [u8; 26],
   no_masking: bool,
   rng: &mut RNG,
+  subprotocols: &[Subprotocol],
   uri: &Uri<STR>,
 ) -> crate::Result<&'kb [u8]>
 where
@@ -236,6 +266,16 @@ where
     b"Upgrade: websocket\r\n",
     compression.req_headers().as_ref(),
   ])?;
+  if let [first, rest @ ..] = subprotocols {
+    let _ = sw
+      .inner_mut()
+      .extend_from_copyable_slices(&[b"Sec-WebSocket-Protocol: ", first.as_str().as_bytes()])?;
+    for subprotocol in rest {
+      let _ =
+        sw.inner_mut().extend_from_copyable_slices(&[b", ", subprotocol.as_str().as_bytes()])?;
+    }
+    sw.inner_mut().extend_from_copyable_slice(b"\r\n")?;
+  }
   for (name, value) in headers {
     let _ = sw.inner_mut().extend_from_copyable_slices(&[
       name.as_bytes(),
@@ -259,6 +299,7 @@ where
 /// Server response
 async fn build_and_send_res101<NC, S>(
   key: &[u8],
+  extra_headers: &[u8],
   nc: &NC,
   no_masking: bool,
   stream: &mut S,
@@ -275,6 +316,7 @@ where
       begin,
       no_masking_bytes(no_masking),
       nc.res_headers().as_slice(),
+      extra_headers,
       middle,
       key,
       end,
@@ -325,6 +367,37 @@ fn derived_key<'buffer>(buffer: &'buffer mut [u8; 30], key: &[u8]) -> &'buffer [
   base64_from_array(&array, buffer)
 }
 
+/// Headers of the server response that depend on the user, i.e., the extensions written by
+/// `extensions_cb` and the selected subprotocol.
+fn extra_res_headers<E, X>(
+  extensions_cb: X,
+  req: &Request<'_, '_>,
+  subprotocol: Option<Subprotocol>,
+  wb: &mut Vector<u8>,
+) -> Result<(), E>
+where
+  E: From<crate::Error>,
+  X: FnOnce(&Request<'_, '_>, &mut Vector<u8>) -> Result<(), E>,
+{
+  let prefix = b"Sec-WebSocket-Extensions: ";
+  wb.clear();
+  wb.extend_from_copyable_slice(prefix)?;
+  extensions_cb(req, wb)?;
+  if wb.len() == prefix.len() {
+    wb.clear();
+  } else {
+    wb.extend_from_copyable_slice(b"\r\n")?;
+  }
+  if let Some(elem) = subprotocol {
+    let _ = wb.extend_from_copyable_slices([
+      b"Sec-WebSocket-Protocol: ",
+      elem.as_str().as_bytes(),
+      b"\r\n",
+    ])?;
+  }
+  Ok(())
+}
+
 fn gen_key<'buffer>(buffer: &'buffer mut [u8; 26], rng: &mut impl Rng) -> &'buffer [u8] {
   base64_from_array(&rng.u8_16(), buffer)
 }
@@ -336,3 +409,31 @@ const fn has_no_masking(el: &[u8]) -> bool {
 const fn no_masking_bytes(no_masking: bool) -> &'static [u8] {
   if no_masking { b"Sec-WebSocket-Extensions: no-masking\r\n" } else { &[] }
 }
+
+/// Returns the first supported subprotocol that was also offered by the client.
+fn select_subprotocol<'sp, SP>(offered: Option<&[u8]>, supported: &'sp [SP]) -> Option<&'sp str>
+where
+  SP: Lease<str>,
+{
+  let bytes = offered?;
+  supported.iter().map(Lease::lease).find(|subprotocol| {
+    bytes_split1(bytes, b',').any(|elem| elem.trim_ascii() == subprotocol.as_bytes())
+  })
+}
+
+/// The subprotocol selected by the server must be one of the offered subprotocols.
+fn verify_subprotocol<'sp, SP>(
+  selected: Option<&[u8]>,
+  offered: &'sp [SP],
+) -> crate::Result<Option<&'sp str>>
+where
+  SP: Lease<str>,
+{
+  let Some(elem) = selected else {
+    return Ok(None);
+  };
+  match offered.iter().map(Lease::lease).find(|subprotocol| subprotocol.as_bytes() == elem) {
+    Some(subprotocol) => Ok(Some(subprotocol)),
+    None => Err(WebSocketError::InvalidSubprotocol.into()),
+  }
+}
@@ -11,17 +11,27 @@ use crate::{
   collections::Vector,
   executor::StdRuntime,
   futures::Sleep,
+  http::ShutdownSignal,
+  misc::bytes_split1,
+  net::{UriRef, UriString, proxy::Proxy},
   rng::{ChaCha20, CryptoSeedableRng},
   sync::{Arc, AtomicBool},
   tests::_uri,
   tls::{PlaintextCtx, TlsAcceptor, TlsConfig, TlsConnectorBuilder},
   web_socket::{
-    Frame, OpCode, WebSocket, WebSocketAcceptor, WebSocketConnector, WebSocketPayloadOrigin,
-    WsCompression, web_socket_compression::NegotiatedWsCompression,
+    CloseCode, Frame, MsgFragmenter, OpCode, WebSocket, WebSocketAcceptor, WebSocketConnector,
+    WebSocketPayloadOrigin, WsCompression,
+    handshake::{select_subprotocol, verify_subprotocol},
+    web_socket_compression::NegotiatedWsCompression,
   },
 };
+use alloc::string::String;
 use core::{sync::atomic::Ordering, time::Duration};
-use std::net::{TcpListener, TcpStream};
+use httparse::Response;
+use std::{
+  io::{Read as _, Write as _},
+  net::{TcpListener, TcpStream},
+};
 
 static HAS_SERVER_FINISHED: AtomicBool = AtomicBool::new(false);
 
@@ -50,6 +60,329 @@ fn compressed() {
   });
 }
 
+// The listener acts as an HTTP proxy that forwards the tunnel to itself.
+// Only the extensions accepted by the callback of the server are sent back.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn extensions_cb() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let _ws = WebSocketAcceptor::default()
+        .set_extensions_cb(|req, buffer| {
+          let has_foo = req.headers.iter().any(|header| {
+            header.name.eq_ignore_ascii_case("sec-websocket-extensions")
+              && bytes_split1(header.value, b',').any(|el| el.trim_ascii() == b"foo")
+          });
+          if has_foo {
+            buffer.extend_from_copyable_slice(b"foo")?;
+          }
+          crate::Result::Ok(())
+        })
+        .accept(TlsAcceptor::new(
+          &TlsConfig::plaintext(),
+          &mut ChaCha20::from_std_random().unwrap(),
+          stream,
+        ))
+        .await
+        .unwrap();
+    })
+    .unwrap();
+  runtime.block_on(async {
+    let mut has_foo = false;
+    let _ws = WebSocketConnector::default()
+      .set_headers([("Sec-WebSocket-Extensions", "bar, foo")])
+      .set_res_cb(|res: &Response<'_, '_>| {
+        has_foo = res.headers.iter().any(|header| {
+          header.name.eq_ignore_ascii_case("sec-websocket-extensions") && header.value == b"foo"
+        });
+        crate::Result::Ok(())
+      })
+      .connect(
+        TlsConnectorBuilder::std(uri)
+          .build(&TlsConfig::plaintext(), &mut ChaCha20::from_std_random().unwrap())
+          .await
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+    server_jh.await;
+    assert!(has_foo);
+  });
+}
+
+// A raw peer completes the handshake but never answers the `Ping` of the client.
+#[cfg(feature = "tokio")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn heartbeat_timeout() {
+  use crate::{
+    Error,
+    executor::Runtime,
+    web_socket::{WebSocketError, handshake::derived_key},
+  };
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let peer_jh = std::thread::spawn(move || {
+    let (mut stream, _) = listener.accept().unwrap();
+    let mut req = Vector::new();
+    while !req.ends_with(b"\r\n\r\n") {
+      let mut byte = [0];
+      stream.read_exact(&mut byte).unwrap();
+      req.push(byte[0]).unwrap();
+    }
+    let key = bytes_split1(&req, b'\n')
+      .find_map(|el| el.strip_prefix(b"Sec-WebSocket-Key: "))
+      .unwrap()
+      .trim_ascii();
+    stream.write_all(b"HTTP/1.1 101 Switching Protocols\r\nConnection: Upgrade\r\n").unwrap();
+    stream.write_all(b"Sec-WebSocket-Accept: ").unwrap();
+    stream.write_all(derived_key(&mut [0; 30], key)).unwrap();
+    stream.write_all(b"\r\nUpgrade: websocket\r\n\r\n").unwrap();
+    let mut frames = Vector::new();
+    for _ in 0..2 {
+      let mut header = [0; 6];
+      stream.read_exact(&mut header).unwrap();
+      let [first, second, mask @ ..] = header;
+      let mut payload = std::vec![0; usize::from(second & 0b0111_1111)];
+      stream.read_exact(&mut payload).unwrap();
+      for (byte, mask_byte) in payload.iter_mut().zip(mask.iter().cycle()) {
+        *byte ^= mask_byte;
+      }
+      frames.push((first, payload)).unwrap();
+    }
+    frames
+  });
+  let lr = <tokio::runtime::LocalRuntime as Runtime>::new().unwrap();
+  lr.block_on(async {
+    let mut ws = WebSocketConnector::default()
+      .set_heartbeat_interval(Some(Duration::from_millis(20)))
+      .set_heartbeat_timeout(Duration::from_millis(50))
+      .connect(
+        TlsConnectorBuilder::tokio(&uri)
+          .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+          .await
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+    let mut buffer = Vector::new();
+    let err = ws.read_frame(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap_err();
+    assert!(matches!(err, Error::WebSocketError(WebSocketError::HeartbeatTimeout)));
+  });
+  let frames = peer_jh.join().unwrap();
+  assert_eq!(frames[0].0, 0b1000_0000 | u8::from(OpCode::Ping));
+  assert_eq!(frames[1].0, 0b1000_0000 | u8::from(OpCode::Close));
+  assert_eq!(frames[1].1.as_slice(), &CloseCode::Away.bytes());
+}
+
+// A raw peer completes the handshake but trickles the bytes of a frame that is never finished
+// in time.
+#[cfg(feature = "tokio")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn heartbeat_timeout_of_trickled_frames() {
+  use crate::{
+    Error,
+    executor::Runtime,
+    web_socket::{WebSocketError, handshake::derived_key},
+  };
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let peer_jh = std::thread::spawn(move || {
+    let (mut stream, _) = listener.accept().unwrap();
+    let mut req = Vector::new();
+    while !req.ends_with(b"\r\n\r\n") {
+      let mut byte = [0];
+      stream.read_exact(&mut byte).unwrap();
+      req.push(byte[0]).unwrap();
+    }
+    let key = bytes_split1(&req, b'\n')
+      .find_map(|el| el.strip_prefix(b"Sec-WebSocket-Key: "))
+      .unwrap()
+      .trim_ascii();
+    stream.write_all(b"HTTP/1.1 101 Switching Protocols\r\nConnection: Upgrade\r\n").unwrap();
+    stream.write_all(b"Sec-WebSocket-Accept: ").unwrap();
+    stream.write_all(derived_key(&mut [0; 30], key)).unwrap();
+    stream.write_all(b"\r\nUpgrade: websocket\r\n\r\n").unwrap();
+    stream.write_all(&[0b1000_0000 | u8::from(OpCode::Text), 100]).unwrap();
+    for _ in 0..100 {
+      std::thread::sleep(Duration::from_millis(10));
+      let _rslt = stream.write_all(b"a");
+    }
+    let mut header = [0; 6];
+    stream.read_exact(&mut header).unwrap();
+    header[0]
+  });
+  let lr = <tokio::runtime::LocalRuntime as Runtime>::new().unwrap();
+  lr.block_on(async {
+    let mut ws = WebSocketConnector::default()
+      .set_heartbeat_interval(Some(Duration::from_millis(20)))
+      .set_heartbeat_timeout(Duration::from_millis(50))
+      .connect(
+        TlsConnectorBuilder::tokio(&uri)
+          .build(&TlsConfig::plaintext(), ChaCha20::from_std_random().unwrap())
+          .await
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+    let mut buffer = Vector::new();
+    let err = ws.read_frame(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap_err();
+    assert!(matches!(err, Error::WebSocketError(WebSocketError::HeartbeatTimeout)));
+  });
+  assert_eq!(peer_jh.join().unwrap(), 0b1000_0000 | u8::from(OpCode::Close));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn proxy() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let server_jh = runtime
+    .spawn(async move {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut req = Vector::new();
+      while !req.ends_with(b"\r\n\r\n") {
+        let mut byte = [0];
+        stream.read_exact(&mut byte).unwrap();
+        req.push(byte[0]).unwrap();
+      }
+      stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap();
+      let mut ws = WebSocketAcceptor::default()
+        .accept(TlsAcceptor::new(
+          &TlsConfig::plaintext(),
+          &mut ChaCha20::from_std_random().unwrap(),
+          stream,
+        ))
+        .await
+        .unwrap();
+      let mut buffer = Vector::new();
+      let frame = ws.read_frame(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap();
+      (req, Vector::from_iterator(frame.payload().iter().copied()).unwrap())
+    })
+    .unwrap();
+  runtime.block_on(async {
+    let proxy = Proxy::new(UriString::new(uri.as_str().into())).unwrap();
+    let mut ws = WebSocketConnector::default()
+      .connect(
+        TlsConnectorBuilder::std(UriRef::new("ws://foo.com/chat"))
+          .build_with_proxy(
+            &TlsConfig::plaintext(),
+            Some(&proxy),
+            &mut ChaCha20::from_std_random().unwrap(),
+          )
+          .await
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+    ws.write_frame(&mut Frame::new_fin(OpCode::Text, *b"Hello!").unwrap()).await.unwrap();
+    let (req, payload) = server_jh.await;
+    assert_eq!(req.as_slice(), b"CONNECT foo.com:80 HTTP/1.1\r\nHost: foo.com:80\r\n\r\n");
+    assert_eq!(payload.as_slice(), b"Hello!");
+  });
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn shutdown_signal() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let mut ws = WebSocketAcceptor::default()
+        .accept(TlsAcceptor::new(
+          &TlsConfig::plaintext(),
+          &mut ChaCha20::from_std_random().unwrap(),
+          stream,
+        ))
+        .await
+        .unwrap();
+      let signal = ShutdownSignal::new();
+      signal.trigger();
+      *ws.shutdown_signal_mut() = Some(signal);
+      let mut buffer = Vector::new();
+      let frame = ws.read_frame(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap();
+      (frame.op_code(), Vector::from_iterator(frame.payload().iter().copied()).unwrap())
+    })
+    .unwrap();
+  runtime.block_on(async {
+    let mut ws = WebSocketConnector::default()
+      .connect(
+        TlsConnectorBuilder::std(uri)
+          .build(&TlsConfig::plaintext(), &mut ChaCha20::from_std_random().unwrap())
+          .await
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+    let mut buffer = Vector::new();
+    let frame = ws.read_frame(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap();
+    assert_eq!(frame.op_code(), OpCode::Close);
+    assert_eq!(&**frame.payload(), &CloseCode::Away.bytes());
+    let (op_code, payload) = server_jh.await;
+    assert_eq!(op_code, OpCode::Close);
+    assert_eq!(payload.as_slice(), &CloseCode::Away.bytes());
+  });
+}
+
+#[test]
+fn subprotocol_selection() {
+  let supported = ["stomp", "graphql-transport-ws"].as_slice();
+  assert_eq!(select_subprotocol(Some(b"v12.stomp, stomp"), supported), Some("stomp"));
+  assert_eq!(select_subprotocol(Some(b"graphql-ws"), supported), None);
+  assert_eq!(select_subprotocol(None, supported), None);
+  assert_eq!(verify_subprotocol(Some(b"stomp"), supported).unwrap(), Some("stomp"));
+  assert_eq!(verify_subprotocol(None, supported).unwrap(), None);
+  assert!(verify_subprotocol(Some(b"graphql-ws"), supported).is_err());
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn subprotocols() {
+  let runtime = StdRuntime::new();
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).unwrap();
+  let server_jh = runtime
+    .spawn(async move {
+      let (stream, _) = listener.accept().unwrap();
+      let ws = WebSocketAcceptor::default()
+        .set_subprotocols(["stomp", "graphql-transport-ws"])
+        .unwrap()
+        .accept(TlsAcceptor::new(
+          &TlsConfig::plaintext(),
+          &mut ChaCha20::from_std_random().unwrap(),
+          stream,
+        ))
+        .await
+        .unwrap();
+      ws.subprotocol().map(String::from)
+    })
+    .unwrap();
+  runtime.block_on(async {
+    let ws = WebSocketConnector::default()
+      .set_subprotocols(["graphql-transport-ws", "v12.stomp"].map(String::from))
+      .unwrap()
+      .connect(
+        TlsConnectorBuilder::std(uri)
+          .build(&TlsConfig::plaintext(), &mut ChaCha20::from_std_random().unwrap())
+          .await
+          .unwrap(),
+      )
+      .await
+      .unwrap();
+    assert_eq!(ws.subprotocol(), Some("graphql-transport-ws"));
+    assert_eq!(server_jh.await.as_deref(), Some("graphql-transport-ws"));
+  });
+}
+
 #[cfg_attr(miri, ignore)]
 #[test]
 fn uncompressed() {
@@ -97,6 +430,7 @@ async fn do_test_client_and_server_frames<CC, SC>(
         (server, &mut ws),
         FragmentedText,
         LargeFragmentedText,
+        MsgFragments,
         PingAndText,
         PingBetweenFragmentedText,
         TwoPings,
@@ -122,6 +456,7 @@ async fn do_test_client_and_server_frames<CC, SC>(
     (client, &mut ws),
     FragmentedText,
     LargeFragmentedText,
+    MsgFragments,
     PingAndText,
     PingBetweenFragmentedText,
     TwoPings,
@@ -155,10 +490,8 @@ where
   NC: NegotiatedWsCompression,
 {
   async fn client(ws: &mut LocalWebSocket<NC, true>) {
-    ws.write_frame(&mut Frame::new_unfin(OpCode::Text, &mut [b'1']).unwrap()).await.unwrap();
-    ws.write_frame(&mut Frame::new_fin(OpCode::Continuation, &mut [b'2', b'3']).unwrap())
-      .await
-      .unwrap();
+    ws.write_frame(&mut Frame::new_unfin(OpCode::Text, *b"1").unwrap()).await.unwrap();
+    ws.write_frame(&mut Frame::new_fin(OpCode::Continuation, *b"23").unwrap()).await.unwrap();
   }
 
   async fn server(ws: &mut LocalWebSocket<NC, false>) {
@@ -245,6 +578,47 @@ where
   }
 }
 
+struct MsgFragments;
+impl<NC> Test<NC> for MsgFragments
+where
+  NC: NegotiatedWsCompression,
+{
+  async fn client(ws: &mut LocalWebSocket<NC, true>) {
+    let mut buffer = Vector::new();
+    let mut mf = MsgFragmenter::new(OpCode::Binary).unwrap();
+    ws.write_frame(&mut mf.frame(false, *b"12").unwrap()).await.unwrap();
+    ws.write_frame(&mut Frame::new_fin(OpCode::Ping, *b"9").unwrap()).await.unwrap();
+    ws.write_frame(&mut mf.frame(false, *b"34").unwrap()).await.unwrap();
+    ws.write_frame(&mut mf.frame(true, *b"5").unwrap()).await.unwrap();
+    assert!(mf.frame(true, *b"6").is_err());
+    ws.write_frame(&mut Frame::new_unfin(OpCode::Text, *b"a").unwrap()).await.unwrap();
+    ws.write_frame(&mut Frame::new_unfin(OpCode::Continuation, [0xC3]).unwrap()).await.unwrap();
+    ws.write_frame(&mut Frame::new_fin(OpCode::Continuation, [0xA9, b'b']).unwrap()).await.unwrap();
+    let pong = ws.read_frame(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap();
+    assert_eq!((OpCode::Pong, "9".as_bytes()), (pong.op_code(), &**pong.payload()));
+  }
+
+  async fn server(ws: &mut LocalWebSocket<NC, false>) {
+    let mut buffer = Vector::new();
+    for (fin, op_code, payload) in [
+      (false, OpCode::Binary, "12"),
+      (true, OpCode::Ping, "9"),
+      (false, OpCode::Continuation, "34"),
+      (true, OpCode::Continuation, "5"),
+      (false, OpCode::Text, "a"),
+      (false, OpCode::Continuation, ""),
+      (true, OpCode::Continuation, "éb"),
+    ] {
+      let frame =
+        ws.read_msg_fragment(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap();
+      assert_eq!(
+        (fin, op_code, payload.as_bytes()),
+        (frame.fin(), frame.op_code(), &**frame.payload())
+      );
+    }
+  }
+}
+
 struct PingAndText;
 impl<NC> Test<NC> for PingAndText
 where
@@ -276,11 +650,9 @@ where
 {
   async fn client(ws: &mut LocalWebSocket<NC, true>) {
     let mut buffer = Vector::new();
-    ws.write_frame(&mut Frame::new_unfin(OpCode::Text, &mut [b'1']).unwrap()).await.unwrap();
-    ws.write_frame(&mut Frame::new_fin(OpCode::Ping, &mut [b'9']).unwrap()).await.unwrap();
-    ws.write_frame(&mut Frame::new_fin(OpCode::Continuation, &mut [b'2', b'3']).unwrap())
-      .await
-      .unwrap();
+    ws.write_frame(&mut Frame::new_unfin(OpCode::Text, *b"1").unwrap()).await.unwrap();
+    ws.write_frame(&mut Frame::new_fin(OpCode::Ping, *b"9").unwrap()).await.unwrap();
+    ws.write_frame(&mut Frame::new_fin(OpCode::Continuation, *b"23").unwrap()).await.unwrap();
     assert_eq!(
       OpCode::Pong,
       ws.read_frame(&mut buffer, WebSocketPayloadOrigin::Adaptive).await.unwrap().op_code()
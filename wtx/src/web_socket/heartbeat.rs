@@ -0,0 +1,372 @@
+use crate::{
+  calendar::Instant,
+  futures::Sleep,
+  http::ShutdownSignal,
+  net::{BufStreamReader, ConnectionState, StreamReader, StreamWriter},
+  rng::Rng,
+  web_socket::{
+    CloseCode, OpCode, WebSocketError,
+    misc::{write_control_frame, write_control_frame_cb},
+  },
+};
+use core::{
+  future::{pending, poll_fn},
+  pin::pin,
+  task::Poll,
+  time::Duration,
+};
+
+pub(crate) const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// What should be done to keep a connection alive.
+#[derive(Debug)]
+pub(crate) enum HeartbeatAction {
+  /// The peer didn't send anything after the last PING frame in time.
+  Close,
+  /// Heartbeats aren't enabled.
+  Disabled,
+  /// A PING frame with the given payload should be sent.
+  Ping([u8; 8]),
+  /// Nothing should be done within the given duration.
+  Wait(Duration),
+}
+
+/// Liveness information of a connection.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Heartbeat {
+  interval: Option<Duration>,
+  is_awaiting_reply: bool,
+  last_ping: Instant,
+  ping: Option<(Instant, [u8; 8])>,
+  ping_counter: u64,
+  rtt: Option<Duration>,
+  timeout: Duration,
+}
+
+// Instants are provided by callers so that time can be controlled.
+impl Heartbeat {
+  pub(crate) const fn new(interval: Option<Duration>, now: Instant, timeout: Duration) -> Self {
+    Self {
+      interval,
+      is_awaiting_reply: false,
+      last_ping: now,
+      ping: None,
+      ping_counter: 0,
+      rtt: None,
+      timeout,
+    }
+  }
+
+  /// A new PING frame is only sent if the previous one was answered by any other frame.
+  pub(crate) fn next_action(&mut self, now: Instant) -> HeartbeatAction {
+    let Some(interval) = self.interval else {
+      return HeartbeatAction::Disabled;
+    };
+    if self.is_awaiting_reply
+      && let Some((sent, _)) = self.ping
+    {
+      let elapsed = now.duration_since(sent).unwrap_or_default();
+      if elapsed >= self.timeout {
+        return HeartbeatAction::Close;
+      }
+      return HeartbeatAction::Wait(self.timeout.saturating_sub(elapsed));
+    }
+    let elapsed = now.duration_since(self.last_ping).unwrap_or_default();
+    if elapsed < interval {
+      return HeartbeatAction::Wait(interval.saturating_sub(elapsed));
+    }
+    let payload = self.ping_counter.to_be_bytes();
+    self.ping_counter = self.ping_counter.wrapping_add(1);
+    self.last_ping = now;
+    self.is_awaiting_reply = true;
+    self.ping = Some((now, payload));
+    HeartbeatAction::Ping(payload)
+  }
+
+  /// Time left for the peer to finish the frame that is being received. Frames only count as
+  /// replies after being fully received and PING frames can't be sent in the meantime, as such,
+  /// the current or the next PING frame must be answered within the timeout.
+  pub(crate) fn read_deadline(&self, now: Instant) -> Option<Duration> {
+    let interval = self.interval?;
+    let limit = if self.is_awaiting_reply
+      && let Some((sent, _)) = self.ping
+    {
+      self.timeout.saturating_sub(now.duration_since(sent).unwrap_or_default())
+    } else {
+      let elapsed = now.duration_since(self.last_ping).unwrap_or_default();
+      interval.saturating_add(self.timeout).saturating_sub(elapsed)
+    };
+    Some(limit)
+  }
+
+  /// Should be called for every received frame. Updates the round-trip time if `payload` belongs
+  /// to the outstanding PING frame.
+  pub(crate) fn recv(&mut self, now: Instant, op_code: OpCode, payload: &[u8]) {
+    self.is_awaiting_reply = false;
+    if let (OpCode::Pong, Some((sent, local_payload))) = (op_code, self.ping)
+      && local_payload == payload
+    {
+      self.ping = None;
+      self.rtt = now.duration_since(sent).ok();
+    }
+  }
+
+  pub(crate) const fn rtt(&self) -> Option<Duration> {
+    self.rtt
+  }
+}
+
+impl Default for Heartbeat {
+  #[inline]
+  fn default() -> Self {
+    Self::new(None, Instant::new(), DEFAULT_HEARTBEAT_TIMEOUT)
+  }
+}
+
+/// Sends a close frame with [`CloseCode::Away`] to an unresponsive peer.
+pub(crate) async fn close_unresponsive<RNG, S, T, const IS_CLIENT: bool>(
+  no_masking: bool,
+  rng: &mut RNG,
+  stream: &mut S,
+  mut connection_state_cb: impl FnMut(&mut S, ConnectionState),
+) -> crate::Result<T>
+where
+  RNG: Rng,
+  S: StreamWriter,
+{
+  write_control_frame::<_, _, IS_CLIENT>(
+    &mut *stream,
+    no_masking,
+    OpCode::Close,
+    &mut CloseCode::Away.bytes(),
+    rng,
+    write_control_frame_cb,
+  )
+  .await?;
+  connection_state_cb(stream, ConnectionState::ClosedGracefully);
+  Err(WebSocketError::HeartbeatTimeout.into())
+}
+
+/// Sends PING frames while the first bytes of the next frame are awaited. If the peer is
+/// unresponsive, then the connection is closed with [`CloseCode::Away`].
+///
+/// The remaining bytes should be read through [`read_within_deadline`].
+///
+/// A triggered `shutdown_signal` of an open connection also sends a close frame with
+/// [`CloseCode::Away`] but the reply of the peer is still awaited by the subsequent reading.
+///
+/// Bytes are only fetched into the following region of the network buffer, as such, nothing is
+/// lost when the reading is interrupted by a timer.
+pub(crate) async fn manage_heartbeat<RNG, S, const IS_CLIENT: bool>(
+  heartbeat: &mut Heartbeat,
+  is_open: bool,
+  network_buffer: &mut BufStreamReader,
+  no_masking: bool,
+  rng: &mut RNG,
+  shutdown_signal: &mut Option<ShutdownSignal>,
+  stream: &mut S,
+  mut connection_state_cb: impl FnMut(&mut S, ConnectionState),
+) -> crate::Result<()>
+where
+  RNG: Rng,
+  S: StreamReader + StreamWriter,
+{
+  if !is_open {
+    *shutdown_signal = None;
+  }
+  if !network_buffer.following().is_empty() {
+    return Ok(());
+  }
+  loop {
+    let mut close_payload;
+    let mut ping_payload;
+    let is_triggered = shutdown_signal.as_ref().is_some_and(ShutdownSignal::is_triggered);
+    let action =
+      if is_triggered { HeartbeatAction::Close } else { heartbeat.next_action(Instant::new()) };
+    let (op_code, payload): (_, &mut [u8]) = match action {
+      HeartbeatAction::Close if !is_triggered => {
+        return close_unresponsive::<_, _, _, IS_CLIENT>(
+          no_masking,
+          rng,
+          stream,
+          connection_state_cb,
+        )
+        .await;
+      }
+      HeartbeatAction::Close => {
+        close_payload = CloseCode::Away.bytes();
+        (OpCode::Close, &mut close_payload)
+      }
+      HeartbeatAction::Disabled => {
+        let Some(elem) = shutdown_signal.as_ref() else {
+          return Ok(());
+        };
+        if wait_following(network_buffer, None, Some(elem), stream).await? {
+          return Ok(());
+        }
+        continue;
+      }
+      HeartbeatAction::Ping(elem) => {
+        ping_payload = elem;
+        (OpCode::Ping, &mut ping_payload)
+      }
+      HeartbeatAction::Wait(duration) => {
+        let signal = shutdown_signal.as_ref();
+        if wait_following(network_buffer, Some(duration), signal, stream).await? {
+          return Ok(());
+        }
+        continue;
+      }
+    };
+    write_control_frame::<_, _, IS_CLIENT>(
+      &mut *stream,
+      no_masking,
+      op_code,
+      payload,
+      rng,
+      write_control_frame_cb,
+    )
+    .await?;
+    if is_triggered {
+      *shutdown_signal = None;
+      connection_state_cb(stream, ConnectionState::WriteClosed);
+      return Ok(());
+    }
+  }
+}
+
+/// Awaits `read` until the deadline of [`Heartbeat::read_deadline`], which keeps peers that
+/// trickle the bytes of a frame from staying alive. Returns `None` if the deadline expired.
+pub(crate) async fn read_within_deadline<T>(
+  heartbeat: &Heartbeat,
+  read: impl Future<Output = crate::Result<T>>,
+) -> crate::Result<Option<T>> {
+  let Some(duration) = heartbeat.read_deadline(Instant::new()) else {
+    return read.await.map(Some);
+  };
+  let mut read_pin = pin!(read);
+  let mut sleep = pin!(Sleep::new(duration)?);
+  poll_fn(|cx| {
+    if let Poll::Ready(rslt) = read_pin.as_mut().poll(cx) {
+      return Poll::Ready(rslt.map(Some));
+    }
+    sleep.as_mut().poll(cx).map(|rslt| rslt.map(|()| None))
+  })
+  .await
+}
+
+// Returns `true` if the first bytes of the next frame were received before the expiration of
+// `duration` or before the triggering of `shutdown_signal`.
+async fn wait_following<S>(
+  network_buffer: &mut BufStreamReader,
+  duration: Option<Duration>,
+  shutdown_signal: Option<&ShutdownSignal>,
+  stream: &mut S,
+) -> crate::Result<bool>
+where
+  S: StreamReader,
+{
+  let mut read_fut = pin!(network_buffer.read_following(stream));
+  let mut sleep = pin!(duration.map(Sleep::new).transpose()?);
+  let mut triggered = pin!(async {
+    match shutdown_signal {
+      Some(elem) => elem.triggered().await,
+      None => pending().await,
+    }
+  });
+  poll_fn(|cx| {
+    if let Poll::Ready(rslt) = read_fut.as_mut().poll(cx) {
+      return Poll::Ready(rslt.map(|_| true));
+    }
+    if triggered.as_mut().poll(cx).is_ready() {
+      return Poll::Ready(Ok(false));
+    }
+    match sleep.as_mut().as_pin_mut() {
+      Some(elem) => elem.poll(cx).map(|rslt| rslt.map(|()| false)),
+      None => Poll::Pending,
+    }
+  })
+  .await
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    calendar::Instant,
+    web_socket::{
+      OpCode,
+      heartbeat::{Heartbeat, HeartbeatAction},
+    },
+  };
+  use core::time::Duration;
+
+  #[test]
+  fn data_answers_ping() {
+    let start = Instant::new();
+    let mut heartbeat = Heartbeat::new(Some(Duration::ZERO), start, ms(20));
+    assert!(matches!(heartbeat.next_action(start), HeartbeatAction::Ping(_)));
+    heartbeat.recv(start.add(ms(1)).unwrap(), OpCode::Text, b"hello");
+    assert!(matches!(heartbeat.next_action(start.add(ms(30)).unwrap()), HeartbeatAction::Ping(_)));
+    assert_eq!(heartbeat.rtt(), None);
+  }
+
+  #[test]
+  fn disabled() {
+    let mut heartbeat = Heartbeat::default();
+    assert!(matches!(heartbeat.next_action(Instant::new()), HeartbeatAction::Disabled));
+  }
+
+  #[test]
+  fn interval() {
+    let start = Instant::new();
+    let mut heartbeat = Heartbeat::new(Some(ms(10)), start, ms(20));
+    let HeartbeatAction::Wait(duration) = heartbeat.next_action(start.add(ms(4)).unwrap()) else {
+      panic!();
+    };
+    assert_eq!(duration, ms(6));
+    assert!(matches!(heartbeat.next_action(start.add(ms(10)).unwrap()), HeartbeatAction::Ping(_)));
+  }
+
+  #[test]
+  fn outstanding_ping() {
+    let start = Instant::new();
+    let mut heartbeat = Heartbeat::new(Some(Duration::ZERO), start, ms(20));
+    let HeartbeatAction::Ping(first) = heartbeat.next_action(start) else { panic!() };
+    let HeartbeatAction::Wait(duration) = heartbeat.next_action(start.add(ms(5)).unwrap()) else {
+      panic!();
+    };
+    assert_eq!(duration, ms(15));
+    heartbeat.recv(start.add(ms(6)).unwrap(), OpCode::Pong, &[9; 8]);
+    assert_eq!(heartbeat.rtt(), None);
+    heartbeat.recv(start.add(ms(7)).unwrap(), OpCode::Pong, &first);
+    assert_eq!(heartbeat.rtt(), Some(ms(7)));
+    let HeartbeatAction::Ping(second) = heartbeat.next_action(start.add(ms(7)).unwrap()) else {
+      panic!()
+    };
+    assert_ne!(first, second);
+  }
+
+  #[test]
+  fn read_deadline() {
+    let start = Instant::new();
+    assert_eq!(Heartbeat::default().read_deadline(start), None);
+    let mut heartbeat = Heartbeat::new(Some(ms(10)), start, ms(20));
+    assert_eq!(heartbeat.read_deadline(start.add(ms(4)).unwrap()), Some(ms(26)));
+    assert!(matches!(heartbeat.next_action(start.add(ms(10)).unwrap()), HeartbeatAction::Ping(_)));
+    assert_eq!(heartbeat.read_deadline(start.add(ms(15)).unwrap()), Some(ms(15)));
+    assert_eq!(heartbeat.read_deadline(start.add(ms(40)).unwrap()), Some(Duration::ZERO));
+  }
+
+  #[test]
+  fn unresponsive_peer() {
+    let start = Instant::new();
+    let mut heartbeat = Heartbeat::new(Some(Duration::ZERO), start, ms(20));
+    assert!(matches!(heartbeat.next_action(start), HeartbeatAction::Ping(_)));
+    let after_timeout = start.add(ms(20)).unwrap();
+    assert!(matches!(heartbeat.next_action(after_timeout), HeartbeatAction::Close));
+  }
+
+  const fn ms(value: u64) -> Duration {
+    Duration::from_millis(value)
+  }
+}
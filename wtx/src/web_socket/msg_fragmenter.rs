@@ -0,0 +1,52 @@
+use crate::{
+  misc::{Lease, from_utf8_basic},
+  web_socket::{Frame, OpCode, WebSocketError},
+};
+
+/// Creates the frames of a message that is sent in chunks, which avoids the buffering of the
+/// whole message before writing.
+///
+/// ```ignore,rust
+/// let mut mf = MsgFragmenter::new(OpCode::Binary)?;
+/// ws.write_frame(&mut mf.frame(false, first_chunk)?).await?;
+/// ws.write_frame(&mut mf.frame(true, last_chunk)?).await?;
+/// ```
+///
+/// Control frames can be written between fragments.
+#[derive(Clone, Copy, Debug)]
+pub struct MsgFragmenter {
+  is_finished: bool,
+  is_first: bool,
+  op_code: OpCode,
+}
+
+impl MsgFragmenter {
+  /// `op_code` must be [`OpCode::Binary`] or [`OpCode::Text`].
+  #[inline]
+  pub fn new(op_code: OpCode) -> crate::Result<Self> {
+    if !matches!(op_code, OpCode::Binary | OpCode::Text) {
+      return Err(WebSocketError::UnexpectedFrame.into());
+    }
+    Ok(Self { is_finished: false, is_first: true, op_code })
+  }
+
+  /// Creates the frame of the next chunk, where `fin` indicates the last chunk of the message.
+  ///
+  /// Chunks of text messages must be valid UTF-8 on their own.
+  #[inline]
+  pub fn frame<P>(&mut self, fin: bool, payload: P) -> crate::Result<Frame<P>>
+  where
+    P: Lease<[u8]>,
+  {
+    if self.is_finished {
+      return Err(WebSocketError::UnexpectedFrame.into());
+    }
+    if self.op_code.is_text() {
+      let _str = from_utf8_basic(payload.lease())?;
+    }
+    let op_code = if self.is_first { self.op_code } else { OpCode::Continuation };
+    self.is_finished = fin;
+    self.is_first = false;
+    Ok(Frame::new(fin, op_code, payload, 0))
+  }
+}
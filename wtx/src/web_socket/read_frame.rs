@@ -301,6 +301,124 @@ where
   Ok(Frame::new(true, op_code, payload, nc_rsv1))
 }
 
+/// Reads a single frame of a message without concatenating continuation frames.
+pub(crate) async fn read_msg_fragment<
+  'frame,
+  'nb,
+  'ub,
+  D,
+  R,
+  S,
+  SR,
+  SW,
+  const HAS_AUTO_REPLY: bool,
+  const IS_CLIENT: bool,
+>(
+  is_in_continuation_frame_opt: &mut Option<IsInContinuationFrame>,
+  max_payload_len: usize,
+  nc: &mut D,
+  nc_rsv1: u8,
+  network_buffer: &'nb mut BufStreamReader,
+  no_masking: bool,
+  payload_origin: WebSocketPayloadOrigin,
+  reader_buffer: &mut Vector<u8>,
+  rng: &mut R,
+  stream: &mut S,
+  stream_bridge: &WebSocketBridge<IS_CLIENT>,
+  user_buffer: &'ub mut Vector<u8>,
+  mut closed_conn_cb: impl FnMut(&mut S),
+  mut stream_reader_cb: impl FnMut(&mut S) -> &mut SR,
+  mut stream_writer_cb: impl FnMut(&mut S) -> &mut SW,
+) -> crate::Result<FrameMut<'frame>>
+where
+  'nb: 'frame,
+  'ub: 'frame,
+  D: WebSocketDecompression,
+  R: Rng,
+  SR: StreamReader,
+  SW: StreamWriter,
+{
+  user_buffer.clear();
+  let rfi = fetch_frame_from_stream::<D, _, IS_CLIENT>(
+    max_payload_len,
+    nc_rsv1,
+    network_buffer,
+    no_masking,
+    stream_reader_cb(&mut *stream),
+  )
+  .await?;
+  unmask_nb::<IS_CLIENT>(rfi.mask, network_buffer.current_mut(), no_masking)?;
+  if rfi.op_code.is_control() {
+    if rfi.op_code.is_close() {
+      cold_path();
+      closed_conn_cb(&mut *stream);
+    }
+    let payload = payload_origin.manage_payload(network_buffer.current_mut(), user_buffer)?;
+    let _is_control_frame = manage_auto_reply::<_, _, HAS_AUTO_REPLY, IS_CLIENT>(
+      stream_writer_cb(stream),
+      no_masking,
+      rfi.op_code,
+      payload,
+      rng,
+      stream_bridge,
+      write_control_frame_cb,
+    )
+    .await?;
+    return Ok(Frame::new(true, rfi.op_code, payload, nc_rsv1));
+  }
+  let is_in_continuation_frame = if let Some(elem) = is_in_continuation_frame_opt {
+    if rfi.op_code != OpCode::Continuation {
+      return Err(WebSocketError::UnexpectedFrame.into());
+    }
+    elem
+  } else {
+    if rfi.op_code == OpCode::Continuation {
+      return Err(WebSocketError::UnexpectedFrame.into());
+    }
+    is_in_continuation_frame_opt.insert(IsInContinuationFrame {
+      iuc: None,
+      op_code: rfi.op_code,
+      should_decompress: rfi.should_decompress,
+    })
+  };
+  let is_text = is_in_continuation_frame.op_code.is_text();
+  let payload = if !D::IS_NOOP && is_in_continuation_frame.should_decompress {
+    let buffer = if is_text {
+      reader_buffer.clear();
+      &mut *reader_buffer
+    } else {
+      &mut *user_buffer
+    };
+    let _ = nc.decompress(DecompressionFlush::NoFlush, network_buffer.current(), buffer)?;
+    if rfi.fin {
+      let _ = nc.decompress(DecompressionFlush::SyncFlush, &DECOMPRESSION_SUFFIX, buffer)?;
+      if nc.no_context_takeover() {
+        nc.reset();
+      }
+    }
+    if is_text {
+      manage_text_of_msg_fragment(
+        reader_buffer,
+        rfi.fin,
+        &mut is_in_continuation_frame.iuc,
+        user_buffer,
+      )?
+    } else {
+      user_buffer.as_slice_mut()
+    }
+  } else if is_text {
+    let bytes = network_buffer.current();
+    manage_text_of_msg_fragment(bytes, rfi.fin, &mut is_in_continuation_frame.iuc, user_buffer)?
+  } else {
+    payload_origin.manage_payload(network_buffer.current_mut(), user_buffer)?
+  };
+  if rfi.fin {
+    *is_in_continuation_frame_opt = None;
+  }
+  let rsv1 = if rfi.op_code == OpCode::Continuation { 0 } else { nc_rsv1 };
+  Ok(Frame::new(rfi.fin, rfi.op_code, payload, rsv1))
+}
+
 pub(crate) fn unmask_nb<const IS_CLIENT: bool>(
   mask: Option<[u8; 4]>,
   network_buffer: &mut [u8],
@@ -474,6 +592,22 @@ where
   }))
 }
 
+// Incomplete characters at the end of `bytes` are only delivered with the next fragment, which
+// means that every returned payload is valid UTF-8.
+fn manage_text_of_msg_fragment<'ub>(
+  bytes: &[u8],
+  fin: bool,
+  iuc: &mut Option<PartialChar>,
+  user_buffer: &'ub mut Vector<u8>,
+) -> crate::Result<&'ub mut [u8]> {
+  let (lhs, rhs) = process_utf8_stream(iuc, bytes)?;
+  if fin && iuc.is_some() {
+    return Err(crate::Error::InvalidUTF8);
+  }
+  let _ = user_buffer.extend_from_copyable_slices([lhs.as_bytes(), rhs.as_bytes()])?;
+  Ok(user_buffer.as_slice_mut())
+}
+
 async fn read_continuation_frames<
   D,
   R,
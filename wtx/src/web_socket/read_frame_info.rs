@@ -184,6 +184,10 @@ impl ReadFrameInfo {
     let length_code = b1 & PAYLOAD_MASK;
     let masked = has_masked_frame(b1);
     let op_code = op_code(b0)?;
+    // Only the first frame of a message indicates that the message is compressed.
+    if rsv1 != 0 && (op_code == OpCode::Continuation || op_code.is_control()) {
+      return Err(WebSocketError::InvalidCompressionHeaderParameter.into());
+    }
     Ok((fin, length_code, masked, op_code, should_decompress))
   }
 
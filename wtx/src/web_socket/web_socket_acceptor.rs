@@ -1,60 +1,171 @@
-use crate::web_socket::WebSocketBuffer;
+use crate::{
+  collections::Vector,
+  misc::Lease,
+  web_socket::{Subprotocols, WebSocketBuffer, heartbeat::DEFAULT_HEARTBEAT_TIMEOUT},
+};
+use core::time::Duration;
 use httparse::Request;
 
 /// WebSocket acceptor
 #[derive(Debug)]
-pub struct WebSocketAcceptor<C, R> {
+pub struct WebSocketAcceptor<C, R, X> {
   pub(crate) compression: C,
+  pub(crate) extensions_cb: X,
+  pub(crate) heartbeat_interval: Option<Duration>,
+  pub(crate) heartbeat_timeout: Duration,
   pub(crate) no_masking: bool,
   pub(crate) req: R,
+  pub(crate) subprotocols: Subprotocols,
   pub(crate) wsb: WebSocketBuffer,
 }
 
-impl<C, R> WebSocketAcceptor<C, R> {
+impl<C, R, X> WebSocketAcceptor<C, R, X> {
   /// Defaults to no compression.
   #[inline]
-  pub fn set_compression<_C>(self, elem: _C) -> WebSocketAcceptor<_C, R> {
+  pub fn set_compression<_C>(self, elem: _C) -> WebSocketAcceptor<_C, R, X> {
     WebSocketAcceptor {
       compression: elem,
+      extensions_cb: self.extensions_cb,
+      heartbeat_interval: self.heartbeat_interval,
+      heartbeat_timeout: self.heartbeat_timeout,
       no_masking: self.no_masking,
       req: self.req,
+      subprotocols: self.subprotocols,
       wsb: self.wsb,
     }
   }
 
+  /// Extensions callback.
+  ///
+  /// Called with the request of each connection after [`Self::set_req`] to negotiate extensions
+  /// that aren't handled internally. Everything written into the provided buffer is sent back as
+  /// the value of an additional `Sec-WebSocket-Extensions` header, which is omitted when nothing
+  /// is written. Frames with reserved bits that weren't negotiated through [`Self::set_compression`]
+  /// are still rejected.
+  #[inline]
+  pub fn set_extensions_cb<_E, _X>(self, elem: _X) -> WebSocketAcceptor<C, R, _X>
+  where
+    _X: FnOnce(&Request<'_, '_>, &mut Vector<u8>) -> Result<(), _E>,
+  {
+    WebSocketAcceptor {
+      compression: self.compression,
+      extensions_cb: elem,
+      heartbeat_interval: self.heartbeat_interval,
+      heartbeat_timeout: self.heartbeat_timeout,
+      no_masking: self.no_masking,
+      req: self.req,
+      subprotocols: self.subprotocols,
+      wsb: self.wsb,
+    }
+  }
+
+  /// Heartbeat interval
+  ///
+  /// Periodically sends PING frames while frames are awaited to detect dead connections and to
+  /// prevent intermediaries from discarding quiet connections. Round-trip times are measured
+  /// with the received PONG frames. Defaults to `None`.
+  ///
+  /// Pending reads are interrupted by timers, as such, the underlying stream must not block the
+  /// current thread.
+  #[inline]
+  #[must_use]
+  pub const fn set_heartbeat_interval(
+    mut self,
+    elem: Option<Duration>,
+  ) -> WebSocketAcceptor<C, R, X> {
+    self.heartbeat_interval = elem;
+    self
+  }
+
+  /// Heartbeat timeout
+  ///
+  /// If no frame is received within the specified duration after a PING frame, then the
+  /// connection is closed with [`crate::web_socket::CloseCode::Away`]. Only used when
+  /// [`Self::set_heartbeat_interval`] is set. Defaults to 20 seconds.
+  #[inline]
+  #[must_use]
+  pub const fn set_heartbeat_timeout(mut self, elem: Duration) -> WebSocketAcceptor<C, R, X> {
+    self.heartbeat_timeout = elem;
+    self
+  }
+
   /// If possible, stops the masking of frames.
   ///
   /// <https://datatracker.ietf.org/doc/draft-damjanovic-websockets-nomasking/>
   #[inline]
   #[must_use]
-  pub const fn set_no_masking(mut self, elem: bool) -> WebSocketAcceptor<C, R> {
+  pub const fn set_no_masking(mut self, elem: bool) -> WebSocketAcceptor<C, R, X> {
     self.no_masking = elem;
     self
   }
 
   /// Request callback.
   #[inline]
-  pub fn set_req<_E, _R>(self, elem: _R) -> WebSocketAcceptor<C, _R>
+  pub fn set_req<_E, _R>(self, elem: _R) -> WebSocketAcceptor<C, _R, X>
   where
     _R: FnOnce(&Request<'_, '_>) -> Result<bool, _E>,
   {
     WebSocketAcceptor {
       compression: self.compression,
+      extensions_cb: self.extensions_cb,
+      heartbeat_interval: self.heartbeat_interval,
+      heartbeat_timeout: self.heartbeat_timeout,
       no_masking: self.no_masking,
       req: elem,
+      subprotocols: self.subprotocols,
       wsb: self.wsb,
     }
   }
+
+  /// Supported subprotocols, ordered by preference.
+  ///
+  /// The first element that was also offered by the client is selected and made available
+  /// through [`crate::web_socket::WebSocket::subprotocol`]. If there isn't a match, then the
+  /// connection is established without a subprotocol. Defaults to an empty list.
+  ///
+  /// At most 8 subprotocols of up to 32 bytes are supported.
+  #[inline]
+  pub fn set_subprotocols<I>(mut self, elem: I) -> crate::Result<WebSocketAcceptor<C, R, X>>
+  where
+    I: IntoIterator,
+    I::Item: Lease<str>,
+  {
+    self.subprotocols.clear();
+    for subprotocol in elem {
+      self.subprotocols.push(subprotocol.lease().try_into()?)?;
+    }
+    Ok(self)
+  }
 }
 
-impl Default for WebSocketAcceptor<(), fn(&Request<'_, '_>) -> crate::Result<bool>> {
+impl Default
+  for WebSocketAcceptor<
+    (),
+    fn(&Request<'_, '_>) -> crate::Result<bool>,
+    fn(&Request<'_, '_>, &mut Vector<u8>) -> crate::Result<()>,
+  >
+{
   #[inline]
   fn default() -> Self {
+    #[expect(clippy::unnecessary_wraps, reason = "false-positive")]
+    #[inline]
+    const fn extensions_cb(_: &Request<'_, '_>, _: &mut Vector<u8>) -> crate::Result<()> {
+      Ok(())
+    }
     #[expect(clippy::unnecessary_wraps, reason = "false-positive")]
     #[inline]
     const fn req(_: &Request<'_, '_>) -> crate::Result<bool> {
       Ok(true)
     }
-    Self { compression: (), no_masking: true, req, wsb: WebSocketBuffer::default() }
+    Self {
+      compression: (),
+      extensions_cb,
+      heartbeat_interval: None,
+      heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT,
+      no_masking: true,
+      req,
+      subprotocols: Subprotocols::new(),
+      wsb: WebSocketBuffer::default(),
+    }
   }
 }
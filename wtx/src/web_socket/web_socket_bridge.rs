@@ -1,13 +1,29 @@
 use crate::{
+  calendar::Instant,
   collections::ArrayVectorCopy,
-  sync::{Arc, AtomicCell, AtomicWaker},
+  futures::Sleep,
+  http::ShutdownSignal,
+  sync::{Arc, AtomicBool, AtomicCell, AtomicWaker},
   tls::{TlsStreamBridge, TlsStreamBridgeData},
-  web_socket::{FrameControlArray, MAX_CONTROL_PAYLOAD_LEN, OpCode},
+  web_socket::{
+    CloseCode, FrameControlArray, MAX_CONTROL_PAYLOAD_LEN, OpCode,
+    heartbeat::{Heartbeat, HeartbeatAction},
+  },
+};
+use core::{
+  future::{pending, poll_fn},
+  pin::pin,
+  sync::atomic::Ordering,
+  task::Poll,
+  time::Duration,
 };
-use core::{future::poll_fn, pin::pin, task::Poll};
 
-type WsTy =
-  (AtomicCell<Option<(OpCode, ArrayVectorCopy<u8, MAX_CONTROL_PAYLOAD_LEN>)>>, AtomicWaker);
+type WsTy = (
+  AtomicCell<Option<(OpCode, ArrayVectorCopy<u8, MAX_CONTROL_PAYLOAD_LEN>)>>,
+  AtomicWaker,
+  AtomicCell<Heartbeat>,
+  AtomicBool,
+);
 
 /// The RFC requires all parties (Client or Server) to send back some types of frames.
 ///
@@ -21,39 +37,87 @@ type WsTy =
 /// #### Noteworthy
 ///
 /// Reply frames sent without the usage of [`WebSocketBridge`] in concurrent scenarios are not
-/// RFC-6455 compliant. Moreover, TLS data is also handled by this structure.
+/// RFC-6455 compliant. Moreover, TLS data and heartbeats are also handled by this structure.
 #[derive(Clone, Debug)]
 pub struct WebSocketBridge<const IS_CLIENT: bool> {
+  shutdown_signal: Option<ShutdownSignal>,
   tls: TlsStreamBridge<IS_CLIENT>,
   ws: Arc<WsTy>,
 }
 
 impl<const IS_CLIENT: bool> WebSocketBridge<IS_CLIENT> {
   pub(crate) fn new(tls: TlsStreamBridge<IS_CLIENT>) -> Self {
-    Self { tls, ws: Arc::new((AtomicCell::new(None), AtomicWaker::new())) }
+    Self {
+      shutdown_signal: None,
+      tls,
+      ws: Arc::new((
+        AtomicCell::new(None),
+        AtomicWaker::new(),
+        AtomicCell::new(Heartbeat::default()),
+        AtomicBool::new(false),
+      )),
+    }
   }
 
   /// Awaits special frames sent by the concurrent reader part. It should probably be
   /// called within a loop.
   ///
+  /// If heartbeats are enabled, also returns PING frames or a close frame when the peer is
+  /// unresponsive. A close frame is also returned once when the shutdown signal of the original
+  /// [`WebSocket`](crate::web_socket::WebSocket) is triggered.
+  ///
   /// The future returned by this method is cancel-safe in the sense that it does not owns
   /// temporary internal data.
+  ///
+  /// Errors are only returned when the timers of the heartbeats can't be created or awaited.
   #[inline]
-  pub async fn listen(&self) -> WebSocketBridgeData {
+  pub async fn listen(&self) -> crate::Result<WebSocketBridgeData> {
     let mut tls_fut = pin!(self.tls.listen());
     let mut ws_fut = pin!(self.do_listen());
+    let mut heartbeat_fut = pin!(self.heartbeat());
     poll_fn(|cx| {
-      let data = match (tls_fut.as_mut().poll(cx), ws_fut.as_mut().poll(cx)) {
+      let mut ws_poll = ws_fut.as_mut().poll(cx);
+      if ws_poll.is_pending() {
+        match heartbeat_fut.as_mut().poll(cx) {
+          Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+          Poll::Ready(Ok(elem)) => ws_poll = Poll::Ready(elem),
+          Poll::Pending => {}
+        }
+      }
+      let data = match (tls_fut.as_mut().poll(cx), ws_poll) {
         (Poll::Ready(tls), Poll::Ready(ws)) => WebSocketBridgeData { tls: Some(tls), ws: Some(ws) },
         (Poll::Ready(tls), Poll::Pending) => WebSocketBridgeData { tls: Some(tls), ws: None },
         (Poll::Pending, Poll::Ready(ws)) => WebSocketBridgeData { tls: None, ws: Some(ws) },
         (Poll::Pending, Poll::Pending) => return Poll::Pending,
       };
-      Poll::Ready(data)
+      Poll::Ready(Ok(data))
     })
     .await
   }
 
+  /// Round-trip time measured by the last answered heartbeat PING frame.
+  #[inline]
+  pub fn rtt(&self) -> Option<Duration> {
+    self.ws.2.load().rtt()
+  }
+
+  /// Should be called for every frame received by the reader part.
+  pub(crate) fn recv_heartbeat(&self, op_code: OpCode, payload: &[u8]) {
+    let now = Instant::new();
+    let _prev = self.ws.2.update(|mut heartbeat| {
+      heartbeat.recv(now, op_code, payload);
+      heartbeat
+    });
+  }
+
+  pub(crate) fn set_heartbeat(&self, heartbeat: Heartbeat) {
+    self.ws.2.store(heartbeat);
+  }
+
+  pub(crate) fn set_shutdown_signal(&mut self, shutdown_signal: Option<ShutdownSignal>) {
+    self.shutdown_signal = shutdown_signal;
+  }
+
   pub(crate) fn update(&self, data: (OpCode, ArrayVectorCopy<u8, MAX_CONTROL_PAYLOAD_LEN>)) {
     let _ = self.ws.0.update(|_prev| Some(data));
     self.ws.1.wake();
@@ -71,6 +135,54 @@ impl<const IS_CLIENT: bool> WebSocketBridge<IS_CLIENT> {
     })
     .await
   }
+
+  async fn heartbeat(&self) -> crate::Result<FrameControlArray> {
+    loop {
+      if self.shutdown_signal.as_ref().is_some_and(ShutdownSignal::is_triggered) {
+        if self.ws.3.swap(true, Ordering::AcqRel) {
+          return pending().await;
+        }
+        let payload = ArrayVectorCopy::from_array(CloseCode::Away.bytes());
+        return Ok(FrameControlArray::new(true, OpCode::Close, payload, 0));
+      }
+      let mut action = HeartbeatAction::Disabled;
+      let now = Instant::new();
+      let _prev = self.ws.2.update(|mut heartbeat| {
+        action = heartbeat.next_action(now);
+        heartbeat
+      });
+      let (op_code, payload) = match action {
+        HeartbeatAction::Close => {
+          (OpCode::Close, ArrayVectorCopy::from_array(CloseCode::Away.bytes()))
+        }
+        HeartbeatAction::Disabled => {
+          self.triggered().await;
+          continue;
+        }
+        HeartbeatAction::Ping(elem) => (OpCode::Ping, ArrayVectorCopy::from_array(elem)),
+        HeartbeatAction::Wait(duration) => {
+          let mut sleep = pin!(Sleep::new(duration)?);
+          let mut triggered = pin!(self.triggered());
+          poll_fn(|cx| {
+            if triggered.as_mut().poll(cx).is_ready() {
+              return Poll::Ready(Ok(()));
+            }
+            sleep.as_mut().poll(cx)
+          })
+          .await?;
+          continue;
+        }
+      };
+      return Ok(FrameControlArray::new(true, op_code, payload, 0));
+    }
+  }
+
+  async fn triggered(&self) {
+    match &self.shutdown_signal {
+      Some(elem) => elem.triggered().await,
+      None => pending().await,
+    }
+  }
 }
 
 /// Data returned by the [`WebSocketBridge::listen`] method. Should be handed to the writer part.
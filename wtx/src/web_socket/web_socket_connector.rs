@@ -1,14 +1,20 @@
-use crate::web_socket::WebSocketBuffer;
-use core::array::IntoIter;
+use crate::{
+  misc::Lease,
+  web_socket::{Subprotocols, WebSocketBuffer, heartbeat::DEFAULT_HEARTBEAT_TIMEOUT},
+};
+use core::{array::IntoIter, time::Duration};
 use httparse::Response;
 
 /// WebSocket connector
 #[derive(Debug)]
 pub struct WebSocketConnector<C, H, R> {
   pub(crate) compression: C,
+  pub(crate) heartbeat_interval: Option<Duration>,
+  pub(crate) heartbeat_timeout: Duration,
   pub(crate) headers: H,
   pub(crate) no_masking: bool,
   pub(crate) res_cb: R,
+  pub(crate) subprotocols: Subprotocols,
   pub(crate) wsb: WebSocketBuffer,
 }
 
@@ -18,21 +24,60 @@ impl<C, H, R> WebSocketConnector<C, H, R> {
   pub fn set_compression<_C>(self, elem: _C) -> WebSocketConnector<_C, H, R> {
     WebSocketConnector {
       compression: elem,
+      heartbeat_interval: self.heartbeat_interval,
+      heartbeat_timeout: self.heartbeat_timeout,
       headers: self.headers,
       no_masking: self.no_masking,
       res_cb: self.res_cb,
+      subprotocols: self.subprotocols,
       wsb: self.wsb,
     }
   }
 
+  /// Heartbeat interval
+  ///
+  /// Periodically sends PING frames while frames are awaited to detect dead connections and to
+  /// prevent intermediaries from discarding quiet connections. Round-trip times are measured
+  /// with the received PONG frames. Defaults to `None`.
+  ///
+  /// Pending reads are interrupted by timers, as such, the underlying stream must not block the
+  /// current thread.
+  #[inline]
+  #[must_use]
+  pub const fn set_heartbeat_interval(
+    mut self,
+    elem: Option<Duration>,
+  ) -> WebSocketConnector<C, H, R> {
+    self.heartbeat_interval = elem;
+    self
+  }
+
+  /// Heartbeat timeout
+  ///
+  /// If no frame is received within the specified duration after a PING frame, then the
+  /// connection is closed with [`crate::web_socket::CloseCode::Away`]. Only used when
+  /// [`Self::set_heartbeat_interval`] is set. Defaults to 20 seconds.
+  #[inline]
+  #[must_use]
+  pub const fn set_heartbeat_timeout(mut self, elem: Duration) -> WebSocketConnector<C, H, R> {
+    self.heartbeat_timeout = elem;
+    self
+  }
+
   /// Additional header that must be sent in the request.
+  ///
+  /// Extensions that aren't handled internally can be offered through `Sec-WebSocket-Extensions`
+  /// headers and verified by [`Self::set_res_cb`].
   #[inline]
   pub fn set_headers<_H>(self, elem: _H) -> WebSocketConnector<C, _H, R> {
     WebSocketConnector {
       compression: self.compression,
+      heartbeat_interval: self.heartbeat_interval,
+      heartbeat_timeout: self.heartbeat_timeout,
       headers: elem,
       no_masking: self.no_masking,
       res_cb: self.res_cb,
+      subprotocols: self.subprotocols,
       wsb: self.wsb,
     }
   }
@@ -52,12 +97,35 @@ impl<C, H, R> WebSocketConnector<C, H, R> {
   pub fn set_res_cb<_R>(self, elem: _R) -> WebSocketConnector<C, H, _R> {
     WebSocketConnector {
       compression: self.compression,
+      heartbeat_interval: self.heartbeat_interval,
+      heartbeat_timeout: self.heartbeat_timeout,
       headers: self.headers,
       no_masking: self.no_masking,
       res_cb: elem,
+      subprotocols: self.subprotocols,
       wsb: self.wsb,
     }
   }
+
+  /// Subprotocols offered in the request, ordered by preference.
+  ///
+  /// The subprotocol selected by the server is available through
+  /// [`crate::web_socket::WebSocket::subprotocol`] and the handshake fails if the server selects
+  /// a subprotocol that wasn't offered. Defaults to an empty list.
+  ///
+  /// At most 8 subprotocols of up to 32 bytes are supported.
+  #[inline]
+  pub fn set_subprotocols<I>(mut self, elem: I) -> crate::Result<WebSocketConnector<C, H, R>>
+  where
+    I: IntoIterator,
+    I::Item: Lease<str>,
+  {
+    self.subprotocols.clear();
+    for subprotocol in elem {
+      self.subprotocols.push(subprotocol.lease().try_into()?)?;
+    }
+    Ok(self)
+  }
 }
 
 impl Default
@@ -77,8 +145,11 @@ impl Default
     Self {
       compression: (),
       headers: [].into_iter(),
+      heartbeat_interval: None,
+      heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT,
       no_masking: true,
       res_cb,
+      subprotocols: Subprotocols::new(),
       wsb: WebSocketBuffer::new(),
     }
   }
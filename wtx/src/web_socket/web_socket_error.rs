@@ -7,6 +7,8 @@ pub enum WebSocketError {
   ClosedHandshake,
   /// HTTP headers must be unique.
   DuplicatedHeader,
+  /// The peer didn't send any frame after a heartbeat PING frame within the specified timeout.
+  HeartbeatTimeout,
   /// Received close frame has invalid parameters.
   InvalidCloseFrame,
   /// It was not possible to create a close frame with the given parameters.
@@ -17,6 +19,8 @@ pub enum WebSocketError {
   InvalidCompressionHeaderParameter,
   /// The client sent an invalid mask bit.
   InvalidMaskBit,
+  /// The server selected a subprotocol that wasn't offered by the client.
+  InvalidSubprotocol,
   /// Server received a frame without a mask.
   MissingFrameMask,
   /// Handshake response should return a 101 code.
@@ -1,13 +1,18 @@
 use crate::{
+  calendar::Instant,
   collections::Vector,
+  http::ShutdownSignal,
   misc::LeaseMut,
   net::{BufStreamReader, ConnectionState, Stream},
   rng::Xorshift64,
   tls::{TlsCtx, TlsStream, TlsStreamBridge},
   web_socket::{
     Frame, FrameMut, WebSocketBridge, WebSocketPayloadOrigin,
-    is_in_continuation_frame::IsInContinuationFrame, read_frame::read_frame,
-    web_socket_compression::NegotiatedWsCompression, write_frame::write_frame,
+    heartbeat::{Heartbeat, close_unresponsive, manage_heartbeat, read_within_deadline},
+    is_in_continuation_frame::IsInContinuationFrame,
+    read_frame::{read_frame, read_msg_fragment},
+    web_socket_compression::NegotiatedWsCompression,
+    write_frame::write_frame,
   },
 };
 use core::marker::PhantomData;
@@ -25,12 +30,14 @@ pub struct WebSocketCommonMut<'instance, NC, S, TCX, const IS_CLIENT: bool> {
 /// to the same instance.
 #[derive(Debug)]
 pub struct WebSocketReaderMut<'instance, NC, S, TCX, const IS_CLIENT: bool> {
+  pub(crate) heartbeat: &'instance mut Heartbeat,
   pub(crate) is_in_continuation_frame: &'instance mut Option<IsInContinuationFrame>,
   pub(crate) max_payload_len: usize,
   pub(crate) network_buffer: &'instance mut BufStreamReader,
   pub(crate) no_masking: bool,
   pub(crate) phantom: PhantomData<(NC, S, TCX)>,
   pub(crate) reader_buffer: &'instance mut Vector<u8>,
+  pub(crate) shutdown_signal: &'instance mut Option<ShutdownSignal>,
 }
 
 impl<'instance, NC, S, TCX, const IS_CLIENT: bool>
@@ -55,7 +62,19 @@ where
     'buffer: 'frame,
     'this: 'frame,
   {
-    read_frame::<_, _, _, _, _, true, IS_CLIENT>(
+    manage_heartbeat::<_, _, IS_CLIENT>(
+      self.heartbeat,
+      common.stream.connection_state.is_open(),
+      self.network_buffer,
+      self.no_masking,
+      common.rng,
+      self.shutdown_signal,
+      common.stream,
+      |el, state| el.connection_state = state,
+    )
+    .await?;
+    let bridge = WebSocketBridge::new(TlsStreamBridge::new());
+    let read = read_frame::<_, _, _, _, _, true, IS_CLIENT>(
       self.is_in_continuation_frame,
       self.max_payload_len,
       common.nc,
@@ -66,13 +85,88 @@ where
       self.reader_buffer.lease_mut(),
       common.rng,
       common.stream,
-      &WebSocketBridge::new(TlsStreamBridge::new()),
+      &bridge,
       buffer,
       |el| el.connection_state = ConnectionState::ClosedGracefully,
       |local_stream| local_stream,
       |local_stream| local_stream,
+    );
+    let Some(frame) = read_within_deadline(self.heartbeat, read).await? else {
+      return close_unresponsive::<_, _, _, IS_CLIENT>(
+        self.no_masking,
+        common.rng,
+        common.stream,
+        |el, state| el.connection_state = state,
+      )
+      .await;
+    };
+    self.heartbeat.recv(Instant::new(), frame.op_code(), frame.payload());
+    Ok(frame)
+  }
+
+  /// Reads the next fragment of a message or a control frame.
+  ///
+  /// Contrary to [`Self::read_frame`], continuation frames are not concatenated, which allows the
+  /// processing of large messages as they arrive. The first fragment carries the
+  /// [`OpCode`](crate::web_socket::OpCode) of the message and the subsequent fragments are
+  /// [`OpCode::Continuation`](crate::web_socket::OpCode::Continuation) frames, the last one being
+  /// final. Control frames can be returned between fragments.
+  ///
+  /// Text fragments are validated as they arrive. A character split across frames is only
+  /// delivered with the next fragment, so every returned payload is valid UTF-8.
+  ///
+  /// Must not be mixed with [`Self::read_frame`] while a message is being received.
+  #[inline]
+  pub async fn read_msg_fragment<'buffer, 'frame, 'this>(
+    &'this mut self,
+    buffer: &'buffer mut Vector<u8>,
+    common: &mut WebSocketCommonMut<'instance, NC, S, TCX, IS_CLIENT>,
+    payload_origin: WebSocketPayloadOrigin,
+  ) -> crate::Result<FrameMut<'frame>>
+  where
+    'buffer: 'frame,
+    'this: 'frame,
+  {
+    manage_heartbeat::<_, _, IS_CLIENT>(
+      self.heartbeat,
+      common.stream.connection_state.is_open(),
+      self.network_buffer,
+      self.no_masking,
+      common.rng,
+      self.shutdown_signal,
+      common.stream,
+      |el, state| el.connection_state = state,
     )
-    .await
+    .await?;
+    let bridge = WebSocketBridge::new(TlsStreamBridge::new());
+    let read = read_msg_fragment::<_, _, _, _, _, true, IS_CLIENT>(
+      self.is_in_continuation_frame,
+      self.max_payload_len,
+      common.nc,
+      common.nc_rsv1,
+      self.network_buffer.lease_mut(),
+      self.no_masking,
+      payload_origin,
+      self.reader_buffer.lease_mut(),
+      common.rng,
+      common.stream,
+      &bridge,
+      buffer,
+      |el| el.connection_state = ConnectionState::ClosedGracefully,
+      |local_stream| local_stream,
+      |local_stream| local_stream,
+    );
+    let Some(frame) = read_within_deadline(self.heartbeat, read).await? else {
+      return close_unresponsive::<_, _, _, IS_CLIENT>(
+        self.no_masking,
+        common.rng,
+        common.stream,
+        |el, state| el.connection_state = state,
+      )
+      .await;
+    };
+    self.heartbeat.recv(Instant::new(), frame.op_code(), frame.payload());
+    Ok(frame)
   }
 }
 
@@ -7,7 +7,7 @@ use crate::{
   web_socket::{
     Frame, FrameMut, WebSocketPayloadOrigin,
     is_in_continuation_frame::IsInContinuationFrame,
-    read_frame::read_frame,
+    read_frame::{read_frame, read_msg_fragment},
     web_socket_bridge::{WebSocketBridge, WebSocketBridgeData},
     web_socket_compression::{WebSocketCompression, WebSocketDecompression},
     write_frame::write_frame,
@@ -51,7 +51,7 @@ where
     'buffer: 'frame,
     'this: 'frame,
   {
-    read_frame::<_, _, _, _, _, false, IS_CLIENT>(
+    let frame = read_frame::<_, _, _, _, _, false, IS_CLIENT>(
       &mut self.is_in_continuation_frame,
       self.max_payload_len,
       &mut self.nc,
@@ -70,7 +70,55 @@ where
       |local_stream| local_stream.0,
       |local_stream| local_stream.1,
     )
-    .await
+    .await?;
+    self.stream_bridge.recv_heartbeat(frame.op_code(), frame.payload());
+    Ok(frame)
+  }
+
+  /// Reads the next fragment of a message or a control frame.
+  ///
+  /// Contrary to [`Self::read_frame`], continuation frames are not concatenated, which allows the
+  /// processing of large messages as they arrive. The first fragment carries the
+  /// [`OpCode`](crate::web_socket::OpCode) of the message and the subsequent fragments are
+  /// [`OpCode::Continuation`](crate::web_socket::OpCode::Continuation) frames, the last one being
+  /// final. Control frames can be returned between fragments.
+  ///
+  /// Text fragments are validated as they arrive. A character split across frames is only
+  /// delivered with the next fragment, so every returned payload is valid UTF-8.
+  ///
+  /// Must not be mixed with [`Self::read_frame`] while a message is being received.
+  #[inline]
+  pub async fn read_msg_fragment<'buffer, 'frame, 'this>(
+    &'this mut self,
+    buffer: &'buffer mut Vector<u8>,
+    payload_origin: WebSocketPayloadOrigin,
+  ) -> crate::Result<FrameMut<'frame>>
+  where
+    'buffer: 'frame,
+    'this: 'frame,
+  {
+    let frame = read_msg_fragment::<_, _, _, _, _, false, IS_CLIENT>(
+      &mut self.is_in_continuation_frame,
+      self.max_payload_len,
+      &mut self.nc,
+      self.nc_rsv1,
+      &mut self.network_buffer,
+      self.no_masking,
+      payload_origin,
+      &mut self.reader_buffer,
+      &mut self.rng,
+      &mut (&mut self.stream_reader, &mut ()),
+      &self.stream_bridge,
+      buffer,
+      |el| {
+        el.0.common().connection_state.store(ConnectionState::ReadClosed.into(), Ordering::Relaxed);
+      },
+      |local_stream| local_stream.0,
+      |local_stream| local_stream.1,
+    )
+    .await?;
+    self.stream_bridge.recv_heartbeat(frame.op_code(), frame.payload());
+    Ok(frame)
   }
 }
 
@@ -7,7 +7,7 @@ use crate::{
   net::StreamWriter,
   rng::Rng,
   web_socket::{
-    Frame, FrameMut, mask_op::mask_op, misc::has_masked_frame,
+    Frame, FrameMut, OpCode, mask_op::mask_op, misc::has_masked_frame,
     web_socket_compression::WebSocketCompression,
   },
 };
@@ -85,11 +85,13 @@ where
     }
     payload_len = payload_len.saturating_sub(4);
   }
+  // Only the first frame of a message indicates that the message is compressed.
+  let rsv1 = if frame.op_code() == OpCode::Continuation { 0 } else { nc_rsv1 };
   Ok(FrameMut::new(
     frame.fin(),
     frame.op_code(),
     writer_buffer.get_mut(..payload_len).unwrap_or_default(),
-    nc_rsv1,
+    rsv1,
   ))
 }
 
@@ -103,9 +105,12 @@ where
   }
   let mut should_compress = false;
   if !frame.op_code().is_control() {
-    let [first, _] = frame.header_first_two_mut();
     should_compress = nc_rsv1 != 0;
-    *first |= nc_rsv1;
+    // Only the first frame of a message indicates that the message is compressed.
+    if frame.op_code() != OpCode::Continuation {
+      let [first, _] = frame.header_first_two_mut();
+      *first |= nc_rsv1;
+    }
   }
   should_compress
 }